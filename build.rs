@@ -0,0 +1,145 @@
+// Build script - collects build information for core::build_info
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+
+    let mut generated = String::new();
+    generated.push_str(&format!("pub const GIT_HASH: &str = {:?};\n", git_hash(&manifest_dir)));
+    generated.push_str(&format!("pub const BUILD_DATE: &str = {:?};\n", build_date()));
+    generated.push_str(&format!("pub const FEATURES: &[&str] = &{:?};\n", enabled_features()));
+
+    generated.push_str("pub const THIRD_PARTY_CRATES: &[ThirdPartyCrate] = &[\n");
+    for (name, version, license) in third_party_crates(&manifest_dir) {
+        generated.push_str(&format!(
+            "    ThirdPartyCrate {{ name: {:?}, version: {:?}, license: {:?} }},\n",
+            name, version, license
+        ));
+    }
+    generated.push_str("];\n");
+
+    fs::write(out_dir.join("build_info.rs"), generated).unwrap();
+
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=Cargo.lock");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+}
+
+// Short hash of the checked out commit, if the source is a git repository
+fn git_hash(manifest_dir: &Path) -> String {
+    let git_dir = manifest_dir.join(".git");
+    if let Ok(head) = fs::read_to_string(git_dir.join("HEAD")) {
+        println!("cargo:rerun-if-changed=.git/HEAD");
+        if let Some(reference) = head.trim().strip_prefix("ref: ") {
+            println!("cargo:rerun-if-changed=.git/{}", reference);
+        }
+    }
+
+    Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .current_dir(manifest_dir)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|hash| hash.trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+// Build date as YYYY-MM-DD, honoring SOURCE_DATE_EPOCH for reproducible builds
+fn build_date() -> String {
+    let seconds = env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .unwrap_or_else(|| SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0));
+
+    let (year, month, day) = civil_from_days((seconds / 86_400) as i64);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+// Convert days since the Unix epoch to a (year, month, day) calendar date
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+// Cargo exposes enabled features to build scripts as CARGO_FEATURE_<NAME> variables
+fn enabled_features() -> Vec<String> {
+    let mut features: Vec<String> = env::vars()
+        .filter_map(|(key, _)| key.strip_prefix("CARGO_FEATURE_").map(|name| name.to_lowercase().replace('_', "-")))
+        .collect();
+    features.sort();
+    features
+}
+
+// Every dependency in Cargo.lock, with the license declared in its registry manifest
+fn third_party_crates(manifest_dir: &Path) -> Vec<(String, String, String)> {
+    let own_name = env::var("CARGO_PKG_NAME").unwrap_or_default();
+    let Ok(lock) = fs::read_to_string(manifest_dir.join("Cargo.lock")) else {
+        return Vec::new();
+    };
+
+    let registry_dirs = registry_source_dirs();
+    let mut crates = Vec::new();
+    let mut name = None;
+
+    for line in lock.lines() {
+        if let Some(value) = toml_string_value(line, "name") {
+            name = Some(value);
+        } else if let Some(version) = toml_string_value(line, "version") {
+            if let Some(name) = name.take() {
+                if name != own_name {
+                    let license = find_license(&registry_dirs, &name, &version);
+                    crates.push((name, version, license));
+                }
+            }
+        }
+    }
+
+    crates
+}
+
+fn toml_string_value(line: &str, key: &str) -> Option<String> {
+    let value = line.strip_prefix(key)?.trim_start().strip_prefix('=')?.trim();
+    Some(value.trim_matches('"').to_string())
+}
+
+// Registry source directories in CARGO_HOME, where downloaded crates are unpacked
+fn registry_source_dirs() -> Vec<PathBuf> {
+    let cargo_home = env::var("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|_| env::var("HOME").map(|home| PathBuf::from(home).join(".cargo")));
+
+    let Ok(cargo_home) = cargo_home else {
+        return Vec::new();
+    };
+
+    fs::read_dir(cargo_home.join("registry").join("src"))
+        .map(|entries| entries.filter_map(|entry| entry.ok().map(|entry| entry.path())).collect())
+        .unwrap_or_default()
+}
+
+fn find_license(registry_dirs: &[PathBuf], name: &str, version: &str) -> String {
+    for dir in registry_dirs {
+        let manifest = dir.join(format!("{}-{}", name, version)).join("Cargo.toml");
+        if let Ok(contents) = fs::read_to_string(manifest) {
+            if let Some(license) = contents.lines().find_map(|line| toml_string_value(line, "license")) {
+                return license;
+            }
+        }
+    }
+    "unknown".to_string()
+}
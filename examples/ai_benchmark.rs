@@ -0,0 +1,42 @@
+// Headless benchmark comparing the average score of each bot difficulty
+// Run with: cargo run --release --example ai_benchmark [games] [max_ticks]
+use rust_stackattack::core::ai::{Bot, Difficulty};
+use rust_stackattack::core::game::GameState;
+use rust_stackattack::core::types::{GameConfig, GameUpdateResult};
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let games: usize = args.next().and_then(|arg| arg.parse().ok()).unwrap_or(10);
+    let max_ticks: usize = args.next().and_then(|arg| arg.parse().ok()).unwrap_or(500);
+
+    println!("{} games per difficulty, at most {} updates each", games, max_ticks);
+
+    for difficulty in Difficulty::ALL {
+        let bot = Bot::new(difficulty);
+        let mut total_score = 0;
+        let mut total_ticks = 0;
+
+        for _ in 0..games {
+            let mut game = GameState::new(GameConfig {
+                grid_width: 10,
+                grid_height: 10,
+                ..Default::default()
+            }).unwrap();
+
+            for _ in 0..max_ticks {
+                total_ticks += 1;
+                if let GameUpdateResult::GameOver = bot.play(&mut game) {
+                    break;
+                }
+            }
+            total_score += game.score;
+        }
+
+        println!(
+            "{:<6} average score {:>6.1}, average length {:>6.1} updates",
+            difficulty.name(),
+            total_score as f64 / games as f64,
+            total_ticks as f64 / games as f64,
+        );
+    }
+}
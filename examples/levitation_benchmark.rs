@@ -0,0 +1,62 @@
+// Headless benchmark of the levitating block check on a board full of blocks with the bottom row gone
+// Compares the worklist in GameState with rescanning every block until nothing changes
+// Run with: cargo run --release --example levitation_benchmark [size]
+use std::time::Instant;
+
+use rust_stackattack::core::block::Block;
+use rust_stackattack::core::game::GameState;
+use rust_stackattack::core::types::{GameConfig, GridPos};
+
+// Every cell above the bottom row holds a settled block, listed top row first
+fn hanging_board(size: usize) -> GameState {
+    let mut game = GameState::new(GameConfig {
+        grid_width: size,
+        grid_height: size,
+        ..Default::default()
+    }).unwrap();
+    game.blocks = (0..size - 1)
+        .flat_map(|y| (0..size).map(move |x| Block {
+            position: GridPos::new(x, y),
+            falling: false,
+            ..Default::default()
+        }))
+        .collect();
+    game.sync_occupancy();
+    game
+}
+
+// The previous approach: rescan all blocks after any of them starts falling
+fn rescan_until_stable(game: &mut GameState) {
+    loop {
+        let mut changed = false;
+        for i in 0..game.blocks.len() {
+            if !game.blocks[i].falling && !game.block_has_support(i) {
+                game.blocks[i].falling = true;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+}
+
+fn main() {
+    let size: usize = std::env::args().nth(1).and_then(|arg| arg.parse().ok()).unwrap_or(64);
+    println!("{0}x{0} board, {1} hanging blocks", size, size * (size - 1));
+
+    let mut game = hanging_board(size);
+    let start = Instant::now();
+    game.check_for_levitating_blocks();
+    let worklist = start.elapsed();
+    assert!(game.blocks.iter().all(|block| block.falling));
+
+    let mut game = hanging_board(size);
+    let start = Instant::now();
+    rescan_until_stable(&mut game);
+    let rescan = start.elapsed();
+    assert!(game.blocks.iter().all(|block| block.falling));
+
+    println!("worklist {:>10.3?}", worklist);
+    println!("rescan   {:>10.3?}", rescan);
+}
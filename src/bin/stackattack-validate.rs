@@ -0,0 +1,41 @@
+// Check level files and packs before sharing them
+// stackattack-validate LEVEL_OR_PACK... prints every problem as path:line, and exits with 1 if any
+// file would be refused by the game. Warnings alone don't fail
+use std::path::Path;
+use std::process::ExitCode;
+
+use rust_stackattack::core::level_check::{self, Severity};
+
+fn main() -> ExitCode {
+    let paths: Vec<String> = std::env::args().skip(1).collect();
+    if paths.is_empty() {
+        eprintln!("usage: stackattack-validate LEVEL_OR_PACK...");
+        return ExitCode::from(2);
+    }
+
+    let (mut errors, mut warnings) = (0, 0);
+    for path in &paths {
+        for (file, findings) in level_check::check_path(Path::new(path)) {
+            if findings.is_empty() {
+                println!("{}: ok", file.display());
+            }
+            for finding in findings {
+                match finding.severity {
+                    Severity::Error => errors += 1,
+                    Severity::Warning => warnings += 1,
+                }
+                match finding.line {
+                    Some(line) => println!("{}:{}: {}", file.display(), line, finding),
+                    None => println!("{}: {}", file.display(), finding),
+                }
+            }
+        }
+    }
+
+    println!("{} error(s), {} warning(s)", errors, warnings);
+    if errors > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
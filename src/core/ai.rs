@@ -0,0 +1,141 @@
+// Core AI player - platform-independent
+// The bot looks ahead with GameState::simulate, keeping only the most promising states at every
+// step (beam search) and averaging several runs (rollouts) since block spawns are random
+// Rollouts reseed their copies, so the bot can't see the spawns the game is about to make
+use crate::core::game::{random_seed, GameState};
+use crate::core::types::{GameUpdateResult, GridPos, InputAction};
+
+// Actions the bot considers at every update
+const ACTIONS: [InputAction; 4] = [InputAction::None, InputAction::Left, InputAction::Right, InputAction::Up];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "easy" => Some(Difficulty::Easy),
+            "medium" => Some(Difficulty::Medium),
+            "hard" => Some(Difficulty::Hard),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Medium => "Medium",
+            Difficulty::Hard => "Hard",
+        }
+    }
+
+    pub fn search_config(&self) -> SearchConfig {
+        match self {
+            Difficulty::Easy => SearchConfig { depth: 1, beam_width: 1, rollouts: 1 },
+            Difficulty::Medium => SearchConfig { depth: 3, beam_width: 2, rollouts: 2 },
+            Difficulty::Hard => SearchConfig { depth: 6, beam_width: 3, rollouts: 4 },
+        }
+    }
+}
+
+// How far and how wide the bot searches
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SearchConfig {
+    pub depth: usize,      // Updates to look ahead, including the first action
+    pub beam_width: usize, // States kept at every step of the look-ahead
+    pub rollouts: usize,   // Independent look-aheads averaged per first action
+}
+
+pub struct Bot {
+    pub config: SearchConfig,
+}
+
+impl Bot {
+    pub fn new(difficulty: Difficulty) -> Self {
+        Self {
+            config: difficulty.search_config(),
+        }
+    }
+
+    // Pick the first action with the best average outcome over all rollouts
+    pub fn choose_action(&self, game: &GameState) -> InputAction {
+        let mut best = (InputAction::None, f64::NEG_INFINITY);
+
+        for action in ACTIONS {
+            let rollouts = self.config.rollouts.max(1);
+            let total: f64 = (0..rollouts)
+                .map(|_| {
+                    // Each rollout guesses its own spawns instead of peeking at the game's seed
+                    let mut guess = game.clone();
+                    guess.event_log = None;
+                    guess.reseed(random_seed());
+                    self.search(guess.simulate(&[action], 1), self.config.depth.saturating_sub(1))
+                })
+                .sum();
+            let value = total / rollouts as f64;
+
+            if value > best.1 {
+                best = (action, value);
+            }
+        }
+        best.0
+    }
+
+    // Play one update: choose an action, apply it and advance the game
+    pub fn play(&self, game: &mut GameState) -> GameUpdateResult {
+        let action = self.choose_action(game);
+        if let GameUpdateResult::Restart = game.process_input(action) {
+            return GameUpdateResult::Restart;
+        }
+        game.tick();
+
+        if game.game_over {
+            GameUpdateResult::GameOver
+        } else {
+            GameUpdateResult::Continue
+        }
+    }
+
+    // Beam search from a state, returning the best evaluation reachable within the depth
+    fn search(&self, state: GameState, depth: usize) -> f64 {
+        let mut beam = vec![state];
+
+        for _ in 0..depth {
+            let mut candidates: Vec<(f64, GameState)> = beam.iter()
+                .flat_map(|state| ACTIONS.iter().map(move |&action| state.simulate(&[action], 1)))
+                .map(|next| (evaluate(&next), next))
+                .collect();
+
+            candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
+            candidates.truncate(self.config.beam_width.max(1));
+            beam = candidates.into_iter().map(|(_, state)| state).collect();
+        }
+
+        beam.iter().map(evaluate).fold(f64::NEG_INFINITY, f64::max)
+    }
+}
+
+// Heuristic value of a game state, higher is better
+pub fn evaluate(game: &GameState) -> f64 {
+    if game.game_over {
+        return -1000.0;
+    }
+
+    // Height of the settled stack, tall stacks are close to losing
+    let stack_height = game.stack_height() as f64;
+
+    // Falling blocks in the player's column are a threat
+    let GridPos { x: player_x, y: player_y } = game.player.position;
+    let threats = game.blocks.iter()
+        .filter(|block| block.falling && block.position.y < player_y)
+        .filter(|block| block.cells().any(|cell| cell.x == player_x))
+        .count() as f64;
+
+    game.score as f64 * 10.0 + game.lives as f64 * 50.0 - stack_height * 2.0 - threats * 20.0
+}
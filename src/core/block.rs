@@ -1,29 +1,196 @@
 // Core block implementation - platform-independent
 use rand::Rng;
-use crate::core::types::Position;
-use crate::core::types::Direction;
+use serde::{Deserialize, Serialize};
+use crate::core::types::{Direction, GridPos};
 
+// Shape of a block, as cell offsets from its top-left position
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Shape {
+    Single,     // One cell
+    Horizontal, // 2x1 lying flat
+    Vertical,   // 1x2 standing upright
+    L,          // Three cells: a vertical pair with a foot to the right
+}
+
+impl Shape {
+    pub const ALL: [Shape; 4] = [Shape::Single, Shape::Horizontal, Shape::Vertical, Shape::L];
+
+    // Cells across and down from the top-left one
+    pub fn offsets(&self) -> &'static [(usize, usize)] {
+        match self {
+            Shape::Single => &[(0, 0)],
+            Shape::Horizontal => &[(0, 0), (1, 0)],
+            Shape::Vertical => &[(0, 0), (0, 1)],
+            Shape::L => &[(0, 0), (0, 1), (1, 1)],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.offsets().iter().map(|&(dx, _)| dx + 1).max().unwrap_or(1)
+    }
+
+    pub fn height(&self) -> usize {
+        self.offsets().iter().map(|&(_, dy)| dy + 1).max().unwrap_or(1)
+    }
+
+    // Cells covered with the top-left one at a position
+    pub fn cells_at(self, position: GridPos) -> impl Iterator<Item = GridPos> {
+        let GridPos { x, y } = position;
+        self.offsets().iter().map(move |&(dx, dy)| GridPos::new(x + dx, y + dy))
+    }
+}
+
+// Material of a block, which decides how it reacts to the player and to row clears
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BlockKind {
+    Crate, // Plain block
+    Steel, // Too heavy to be pushed
+    Ice,   // Keeps sliding after a push until it hits something
+    Bomb,  // Explodes when part of a cleared row
+}
+
+impl BlockKind {
+    // Pick a random kind, plain crates being the most common
+    fn random(rng: &mut impl Rng) -> Self {
+        match rng.gen_range(0..10) {
+            0 => BlockKind::Steel,
+            1 => BlockKind::Ice,
+            2 => BlockKind::Bomb,
+            _ => BlockKind::Crate,
+        }
+    }
+}
+
+// Stable name of a block on the field, kept while the blocks vector is reordered and rows are cleared
+// Handed out by the game the block is added to, UNASSIGNED until then
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize)]
+pub struct BlockId(pub u64);
+
+impl BlockId {
+    pub const UNASSIGNED: BlockId = BlockId(0);
+
+    pub fn is_assigned(self) -> bool {
+        self != Self::UNASSIGNED
+    }
+}
+
+impl std::fmt::Display for BlockId {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+#[derive(Clone)]
 pub struct Block {
-    pub position: Position,
+    pub id: BlockId, // Same for the whole life of the block, including what is left of it after a row clear
+    pub position: GridPos, // Top-left cell of the shape
+    pub last_position: GridPos, // Where the block was before the latest update, for drawing it on its way
     pub falling: bool,
     pub carried: bool, // Track if block is being carried
-    pub carrying_direction: Option<Direction>, // Track direction of carrying (positive = right, negative = left)
+    pub carrying_direction: Option<Direction>, // Track direction of carrying
+    pub shape: Shape,
+    pub kind: BlockKind,
+    pub sliding: Option<Direction>, // Direction of an ice block still sliding after a push
+    pub idle_ticks: u64, // Updates spent at rest without being moved
+    pub touched: bool, // Pushed, carried or kicked by the player at some point, worth a bonus when cleared
+    pub fuse: Option<u64>, // Updates left before a bomb that has landed blows up, None while it isn't lit
 }
 
 impl Block {
-    pub fn new(position: Position) -> Self {
+    pub fn new(position: GridPos) -> Self {
+        Self::with_shape(position, Shape::Single)
+    }
+
+    pub fn with_shape(position: GridPos, shape: Shape) -> Self {
         Self {
+            id: BlockId::UNASSIGNED,
             position,
+            last_position: position,
             falling: true,
             carried: false,
             carrying_direction: None,
+            shape,
+            kind: BlockKind::Crate,
+            sliding: None,
+            idle_ticks: 0,
+            touched: false,
+            fuse: None,
         }
     }
+
+    pub fn with_kind(position: GridPos, kind: BlockKind) -> Self {
+        Self {
+            kind,
+            ..Self::new(position)
+        }
+    }
+
+    // All grid cells covered by this block
+    pub fn cells(&self) -> impl Iterator<Item = GridPos> + '_ {
+        self.cells_at(self.position)
+    }
+
+    // Cells of this block if it were moved to another top-left position
+    pub fn cells_at(&self, position: GridPos) -> impl Iterator<Item = GridPos> + '_ {
+        self.shape.cells_at(position)
+    }
+
+    pub fn occupies(&self, cell: GridPos) -> bool {
+        self.cells().any(|c| c == cell)
+    }
+
+    // Lowest row covered by this block
+    pub fn bottom(&self) -> usize {
+        self.position.y + self.shape.height() - 1
+    }
+
+    // Rightmost column covered by this block
+    pub fn right(&self) -> usize {
+        self.position.x + self.shape.width() - 1
+    }
+
+    // Remove the cells in a cleared row, returning what is left of the block (if anything)
+    pub fn without_row(self, row: usize) -> Option<Block> {
+        let GridPos { x, y } = self.position;
+        if row < y || row > self.bottom() {
+            return Some(self);
+        }
+
+        let remainder = match (self.shape, row - y) {
+            (Shape::Vertical, 0) => Some((GridPos::new(x, y + 1), Shape::Single)),
+            (Shape::Vertical, _) => Some((GridPos::new(x, y), Shape::Single)),
+            (Shape::L, 0) => Some((GridPos::new(x, y + 1), Shape::Horizontal)),
+            (Shape::L, _) => Some((GridPos::new(x, y), Shape::Single)),
+            _ => None,
+        };
+
+        remainder.map(|(position, shape)| Block { position, shape, ..self })
+    }
 }
 
-pub fn spawn_random_block(grid_size: usize) -> Block {
-    let mut rng = rand::thread_rng();
-    let x = rng.gen_range(0..grid_size);
-    
-    Block::new((x, 0))
+impl Default for Block {
+    fn default() -> Self {
+        Self::new(GridPos::default())
+    }
+}
+
+pub fn spawn_random_block(grid_width: usize, grid_height: usize, rng: &mut impl Rng) -> Block {
+    let block = random_block(grid_width, grid_height, rng);
+    let x = rng.gen_range(0..=grid_width - block.shape.width());
+    Block { position: GridPos::new(x, 0), ..block }
+}
+
+// Random shape and kind for the next block, left for the caller to place in a column
+pub fn random_block(grid_width: usize, grid_height: usize, rng: &mut impl Rng) -> Block {
+    // Only pick shapes that fit into the grid
+    let shapes: Vec<Shape> = Shape::ALL.iter()
+        .copied()
+        .filter(|shape| shape.width() <= grid_width && shape.height() <= grid_height)
+        .collect();
+    let shape = shapes[rng.gen_range(0..shapes.len())];
+
+    Block {
+        kind: BlockKind::random(rng),
+        ..Block::with_shape(GridPos::default(), shape)
+    }
 }
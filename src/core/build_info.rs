@@ -0,0 +1,27 @@
+// Build information generated by build.rs - shared by all frontends for the About screen
+
+// A dependency compiled into the game, with its declared license
+#[derive(Debug, Clone, Copy)]
+pub struct ThirdPartyCrate {
+    pub name: &'static str,
+    pub version: &'static str,
+    pub license: &'static str,
+}
+
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// GIT_HASH, BUILD_DATE, FEATURES and THIRD_PARTY_CRATES
+include!(concat!(env!("OUT_DIR"), "/build_info.rs"));
+
+// Count dependencies per license, most common first
+pub fn license_summary() -> Vec<(&'static str, usize)> {
+    let mut summary: Vec<(&'static str, usize)> = Vec::new();
+    for dependency in THIRD_PARTY_CRATES {
+        match summary.iter_mut().find(|(license, _)| *license == dependency.license) {
+            Some((_, count)) => *count += 1,
+            None => summary.push((dependency.license, 1)),
+        }
+    }
+    summary.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    summary
+}
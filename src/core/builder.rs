@@ -0,0 +1,92 @@
+// Builder for a game set up by hand, for tests and embedders that need a known board to start from
+// The board holds exactly the blocks placed here, without the block a new game drops in at the start
+use crate::core::block::{Block, BlockKind, Shape};
+use crate::core::game::GameState;
+use crate::core::types::{ConfigError, GameConfig, GameMode, GridPos};
+
+#[derive(Clone, Default)]
+pub struct GameStateBuilder {
+    config: GameConfig,
+    blocks: Vec<Block>,
+    player: Option<GridPos>,
+}
+
+impl GameStateBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Start from a whole config rather than the defaults, calls after this one still change it
+    pub fn config(mut self, config: GameConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn grid(mut self, width: usize, height: usize) -> Self {
+        self.config.grid_width = width;
+        self.config.grid_height = height;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.config.seed = Some(seed);
+        self
+    }
+
+    pub fn mode(mut self, mode: GameMode) -> Self {
+        self.config.mode = mode;
+        self
+    }
+
+    pub fn lives(mut self, lives: u32) -> Self {
+        self.config.lives = lives;
+        self
+    }
+
+    pub fn pit(mut self, x: usize) -> Self {
+        self.config.pits.push(x);
+        self
+    }
+
+    // A single crate at rest with its top-left cell at (x, y)
+    pub fn block(self, x: usize, y: usize) -> Self {
+        self.with_block(Block { falling: false, ..Block::new(GridPos::new(x, y)) })
+    }
+
+    // A single crate still falling from (x, y)
+    pub fn falling_block(self, x: usize, y: usize) -> Self {
+        self.with_block(Block::new(GridPos::new(x, y)))
+    }
+
+    pub fn shaped_block(self, x: usize, y: usize, shape: Shape) -> Self {
+        self.with_block(Block { falling: false, ..Block::with_shape(GridPos::new(x, y), shape) })
+    }
+
+    pub fn kind_block(self, x: usize, y: usize, kind: BlockKind) -> Self {
+        self.with_block(Block { falling: false, ..Block::with_kind(GridPos::new(x, y), kind) })
+    }
+
+    // Any block as given, for the fields the shortcuts above don't cover
+    pub fn with_block(mut self, block: Block) -> Self {
+        self.blocks.push(block);
+        self
+    }
+
+    // Where the player's head is, the body goes down from there
+    pub fn player_at(mut self, x: usize, y: usize) -> Self {
+        self.player = Some(GridPos::new(x, y));
+        self
+    }
+
+    // The game, or why its config can't be played, see `GameConfig::validate`
+    pub fn build(self) -> Result<GameState, ConfigError> {
+        let mut game = GameState::new(self.config)?;
+        game.blocks = self.blocks;
+        game.sync_occupancy();
+        if let Some(position) = self.player {
+            game.player.position = position;
+            game.player.previous_position = position;
+        }
+        Ok(game)
+    }
+}
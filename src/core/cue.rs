@@ -0,0 +1,84 @@
+// Core accessibility cues - platform-independent
+// Watches the game for situations a player who can't see the board needs to hear about.
+// Each cue fires once when its situation starts, frontends turn them into sounds
+use std::collections::HashSet;
+
+use crate::core::game::{GameState, SpawnWarning};
+use crate::core::types::GridPos;
+
+// Rows between the top of the stack and the danger line that count as a high stack
+pub const DANGER_MARGIN: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Cue {
+    SpawnInColumn,     // The next block is about to drop over the player
+    RowAlmostComplete, // Another row is one cell short of clearing
+    DangerHigh,        // The stack has grown close to the danger line
+}
+
+#[derive(Debug, Default)]
+pub struct CueTracker {
+    spawn_warning: Option<SpawnWarning>,
+    almost_complete_rows: usize,
+    danger_high: bool,
+}
+
+impl CueTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Compare the game with how it was last time, returning the cues that just started
+    pub fn update(&mut self, game: &GameState) -> Vec<Cue> {
+        let mut cues = Vec::new();
+
+        // Announce a spawn once, when its column is first telegraphed
+        if self.spawn_warning.is_none() {
+            if let (Some(warning), Some(block)) = (game.spawn_warning, game.next_blocks.front()) {
+                let x = game.player.position.x;
+                if (warning.column..warning.column + block.shape.width()).contains(&x) {
+                    cues.push(Cue::SpawnInColumn);
+                }
+            }
+        }
+        self.spawn_warning = game.spawn_warning;
+
+        let almost_complete_rows = almost_complete_rows(game);
+        if almost_complete_rows > self.almost_complete_rows {
+            cues.push(Cue::RowAlmostComplete);
+        }
+        self.almost_complete_rows = almost_complete_rows;
+
+        let danger_high = stack_top(game).is_some_and(|top| top <= game.danger_line + DANGER_MARGIN);
+        if danger_high && !self.danger_high {
+            cues.push(Cue::DangerHigh);
+        }
+        self.danger_high = danger_high;
+
+        cues
+    }
+}
+
+// Cells taken by settled blocks
+fn settled_cells(game: &GameState) -> HashSet<GridPos> {
+    game.blocks.iter().filter(|block| !block.falling).flat_map(|block| block.cells()).collect()
+}
+
+// Rows with every cell but one filled by settled blocks or terrain
+fn almost_complete_rows(game: &GameState) -> usize {
+    let settled = settled_cells(game);
+    (0..game.grid_height)
+        .filter(|&y| {
+            let filled = (0..game.grid_width)
+                .map(|x| GridPos::new(x, y))
+                .filter(|cell| game.terrain.is_solid(*cell) || settled.contains(cell))
+                .count();
+            filled + 1 == game.grid_width
+        })
+        .count()
+}
+
+// Highest row reached by a settled block
+fn stack_top(game: &GameState) -> Option<usize> {
+    game.blocks.iter().filter(|block| !block.falling).map(|block| block.position.y).min()
+}
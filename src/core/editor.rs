@@ -0,0 +1,206 @@
+// Core level editor - platform-independent
+// Holds the level being edited and the tool in hand, frontends turn clicks on the grid into
+// placing or erasing at a cell and save the result in the level file format
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::core::ai::Bot;
+use crate::core::block::{BlockKind, Shape};
+use crate::core::game::GameState;
+use crate::core::level::{LevelDefinition, LevelError, PlacedBlock, PlacedTile};
+use crate::core::terrain::Tile;
+use crate::core::types::{ConfigError, Direction, GameConfig, GridPos};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tool {
+    Block(Shape, BlockKind),
+    Wall,
+    Ramp(Direction), // Rising toward the given direction
+    Pit,
+    PlayerStart,
+    Erase,
+}
+
+impl Tool {
+    // Every tool, in the order the palette shows them
+    pub const PALETTE: [Tool; 13] = [
+        Tool::Block(Shape::Single, BlockKind::Crate),
+        Tool::Block(Shape::Horizontal, BlockKind::Crate),
+        Tool::Block(Shape::Vertical, BlockKind::Crate),
+        Tool::Block(Shape::L, BlockKind::Crate),
+        Tool::Block(Shape::Single, BlockKind::Steel),
+        Tool::Block(Shape::Single, BlockKind::Ice),
+        Tool::Block(Shape::Single, BlockKind::Bomb),
+        Tool::Wall,
+        Tool::Ramp(Direction::Left),
+        Tool::Ramp(Direction::Right),
+        Tool::Pit,
+        Tool::PlayerStart,
+        Tool::Erase,
+    ];
+
+    // Short enough for a palette button
+    pub fn label(&self) -> &'static str {
+        match self {
+            Tool::Block(Shape::Single, BlockKind::Crate) => "1x1",
+            Tool::Block(Shape::Horizontal, _) => "2x1",
+            Tool::Block(Shape::Vertical, _) => "1x2",
+            Tool::Block(Shape::L, _) => "L",
+            Tool::Block(_, BlockKind::Steel) => "Stl",
+            Tool::Block(_, BlockKind::Ice) => "Ice",
+            Tool::Block(_, BlockKind::Bomb) => "Bmb",
+            Tool::Wall => "Wall",
+            Tool::Ramp(Direction::Left) => "\\",
+            Tool::Ramp(Direction::Right) => "/",
+            Tool::Pit => "Pit",
+            Tool::PlayerStart => "You",
+            Tool::Erase => "Del",
+        }
+    }
+}
+
+pub struct Editor {
+    pub level: LevelDefinition,
+    pub tool: Tool,
+}
+
+impl Editor {
+    pub fn new(grid_width: usize, grid_height: usize) -> Self {
+        Self::from_level(LevelDefinition::empty(grid_width, grid_height))
+    }
+
+    pub fn from_level(level: LevelDefinition) -> Self {
+        Self {
+            level,
+            tool: Tool::PALETTE[0],
+        }
+    }
+
+    fn in_grid(&self, cell: GridPos) -> bool {
+        cell.within(self.level.grid_width, self.level.grid_height)
+    }
+
+    // Use the current tool on a cell, replacing whatever was there
+    // Blocks and tiles that wouldn't fit the grid are not placed
+    pub fn apply(&mut self, cell: GridPos) {
+        if !self.in_grid(cell) {
+            return;
+        }
+        match self.tool {
+            Tool::Block(shape, kind) => {
+                let placed = PlacedBlock { x: cell.x, y: cell.y, shape, kind };
+                let cells: Vec<GridPos> = placed.to_block().cells().collect();
+                if cells.iter().all(|&cell| self.in_grid(cell)) {
+                    cells.iter().for_each(|&cell| self.clear(cell));
+                    self.level.blocks.push(placed);
+                }
+            },
+            Tool::Wall => self.place_tile(cell, Tile::Wall),
+            Tool::Ramp(direction) => self.place_tile(cell, Tile::Ramp(direction)),
+            Tool::Pit => {
+                if !self.level.pits.contains(&cell.x) {
+                    self.level.pits.push(cell.x);
+                }
+            },
+            Tool::PlayerStart => {
+                // The player's body is two cells tall, clicking the bottom row starts it standing there
+                let start = GridPos::new(cell.x, cell.y.min(self.level.grid_height - 2));
+                self.clear(start);
+                self.clear(start.below());
+                self.level.player_start = Some(start);
+            },
+            Tool::Erase => self.erase(cell),
+        }
+    }
+
+    fn place_tile(&mut self, cell: GridPos, tile: Tile) {
+        self.clear(cell);
+        self.level.terrain.push(PlacedTile { x: cell.x, y: cell.y, tile });
+    }
+
+    // Remove the block or tile covering a cell
+    fn clear(&mut self, cell: GridPos) {
+        self.level.blocks.retain(|placed| !placed.to_block().occupies(cell));
+        self.level.terrain.retain(|placed| GridPos::new(placed.x, placed.y) != cell);
+    }
+
+    // Remove whatever is at a cell, a pit goes with a click on its column's bottom cell
+    pub fn erase(&mut self, cell: GridPos) {
+        self.clear(cell);
+        if cell.y + 1 == self.level.grid_height {
+            self.level.pits.retain(|&x| x != cell.x);
+        }
+    }
+
+    // Write the level to a file that campaigns can load, if it is playable
+    pub fn save(&self, path: &Path) -> Result<(), LevelError> {
+        let json = self.level.to_json()?;
+        fs::write(path, json).map_err(|err| LevelError::Io(path.to_path_buf(), err))
+    }
+}
+
+// Headless game of the level being edited, shown next to the editor so spawns and physics can be
+// watched while building. It starts over from the level whenever the level changes, and whenever
+// the game ends so the preview keeps running
+pub struct LivePreview {
+    pub game: GameState,
+    pub bot: Option<Bot>, // Plays the preview, the player just stands there without one
+    level: LevelDefinition, // What the running game was started from
+    config: GameConfig,
+}
+
+impl LivePreview {
+    pub fn new(level: &LevelDefinition, config: GameConfig) -> Result<Self, ConfigError> {
+        Ok(Self {
+            game: GameState::from_level(level, config.clone())?,
+            bot: None,
+            level: level.clone(),
+            config,
+        })
+    }
+
+    pub fn with_bot(mut self, bot: Bot) -> Self {
+        self.bot = Some(bot);
+        self
+    }
+
+    // Start over if the level was edited since the preview started, true when it did
+    pub fn sync(&mut self, level: &LevelDefinition) -> bool {
+        if *level == self.level {
+            return false;
+        }
+        self.level = level.clone();
+        self.restart();
+        true
+    }
+
+    // An edit that leaves the level unplayable keeps the game that was running
+    fn restart(&mut self) {
+        if let Ok(game) = GameState::from_level(&self.level, self.config.clone()) {
+            self.game = game;
+        }
+    }
+
+    // Advance by one update, the bot choosing an input first if there is one
+    pub fn tick(&mut self) {
+        if self.game.game_over {
+            self.restart();
+            return;
+        }
+        match &self.bot {
+            Some(bot) => {
+                bot.play(&mut self.game);
+            },
+            None => self.game.tick(),
+        }
+    }
+
+    // Advance once an update is due, on the game's own refresh rate
+    pub fn update(&mut self) {
+        if self.game.last_update.elapsed() >= Duration::from_millis(self.game.refresh_rate_milliseconds) {
+            self.tick();
+            self.game.last_update = Instant::now();
+        }
+    }
+}
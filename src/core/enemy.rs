@@ -0,0 +1,54 @@
+// Core enemy implementation - platform-independent
+// The Foreman walks back and forth on top of the stack, touching him costs the player a life
+use crate::core::types::{Direction, GridPos};
+
+// Updates between two steps of the Foreman, he walks slower than the player
+pub const ENEMY_STEP_INTERVAL: u8 = 2;
+// Updates before a crushed Foreman comes back
+pub const ENEMY_RESPAWN_TICKS: u64 = 50;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Enemy {
+    pub position: GridPos,
+    pub last_position: GridPos, // Where the Foreman was before the latest update
+    pub direction: Direction, // Walking direction
+    pub falling: bool,
+    step_counter: u8,
+}
+
+impl Enemy {
+    pub fn new(position: GridPos) -> Self {
+        Self {
+            position,
+            last_position: position,
+            direction: Direction::Right,
+            falling: false,
+            step_counter: 0,
+        }
+    }
+
+    // Advance by one update: fall when nothing is below, otherwise patrol
+    // The Foreman turns around at walls and at the edge of whatever he stands on, so he stays on the stack
+    pub fn update(&mut self, is_solid: impl Fn(GridPos) -> bool, grid_width: usize, grid_height: usize) {
+        let stands_at = |cell: GridPos| cell.y + 1 >= grid_height || is_solid(cell.below());
+
+        if !stands_at(self.position) {
+            self.falling = true;
+            self.position.y += 1;
+            return;
+        }
+        self.falling = false;
+
+        self.step_counter += 1;
+        if self.step_counter < ENEMY_STEP_INTERVAL {
+            return;
+        }
+        self.step_counter = 0;
+
+        let target = self.position.neighbor(self.direction).filter(|target| target.x < grid_width);
+        match target {
+            Some(target) if !is_solid(target) && stands_at(target) => self.position = target,
+            _ => self.direction = self.direction.opposite(),
+        }
+    }
+}
@@ -0,0 +1,34 @@
+// Core game events - platform-independent
+// GameState queues events as things happen, frontends drain them to drive HUD, audio and effects
+use crate::core::block::BlockId;
+use crate::core::objective::ObjectiveKind;
+use crate::core::types::GridPos;
+
+// How the points for a row clear were calculated
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScoreBreakdown {
+    pub rows: u32,        // Rows cleared in the same update
+    pub base_points: u32, // Points for the rows alone, growing with each extra row
+    pub chain: u32,       // Number of clears in a row, each one shortly after the previous
+    pub touch_bonus: u32, // Points for the cells of blocks the player moved into the cleared rows
+    pub points: u32,      // Total awarded: base points times chain, plus the touch bonus
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameEvent {
+    RowsCleared(ScoreBreakdown),
+    RowCleared(usize), // One of the rows of a clear, by index, queued ahead of the clear's RowsCleared
+    StormWarning, // A block storm starts soon
+    StormStarted,
+    StormEnded,
+    CloseCall(GridPos), // A block landed where the player stood a moment ago, at the cell it landed in
+    ObjectiveCompleted(ObjectiveKind),
+    CampaignLevelStarted(usize), // The board was replaced with the next campaign level, by index
+    RowAlmostComplete(usize), // A block settled leaving this row one cell short of clearing
+    BlockCaught(BlockId),     // A falling block landed on the player's head and is carried
+    BlockLanded(BlockId, GridPos), // A falling block came to rest, at its top-left cell
+    BlocksDespawned(Vec<GridPos>), // Idle blocks were cleared off full columns in Zen, at the cells they covered
+    PlayerHit,                // The player lost a life, queued ahead of the game ending if it was the last
+    Exploded(GridPos),        // A bomb went off, at the center of its 3x3 blast, once per bomb of a chain
+    CoinCollected(GridPos, u32), // The player picked up a coin in this cell, worth these points
+}
@@ -0,0 +1,150 @@
+// Core event log - platform-independent
+// With GameConfig::record_events set, GameState logs every input that changed it and every update.
+// Spawns come from the seeded generator, so folding the log over a fresh game with the same
+// config rebuilds the exact same state, or the state at any earlier update
+// Each logged input also gets a stamp with the device it came from and when it arrived. Stamps
+// sit beside the events rather than in them, so logs without them still load
+use serde::{Deserialize, Serialize};
+
+use crate::core::game::GameState;
+use crate::core::types::{ConfigError, GameConfig, InputAction, InputDevice};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum LoggedEvent {
+    // An input that was applied in full
+    Input(InputAction),
+    // A held direction arriving before the repeat delay, it doesn't move the player
+    Held(InputAction),
+    // One update of the game
+    Tick,
+}
+
+// Provenance of one logged input
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct InputStamp {
+    pub event: usize, // Index of the input in the events
+    pub device: InputDevice,
+    pub tick: u64, // Updates played before the input arrived
+    pub millis: u64, // Game time into the run, in milliseconds
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventLog {
+    pub config: GameConfig, // Config the game started with, including the seed it used
+    pub events: Vec<LoggedEvent>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stamps: Vec<InputStamp>,
+}
+
+impl EventLog {
+    pub fn new(config: GameConfig) -> Self {
+        Self {
+            config,
+            events: Vec::new(),
+            stamps: Vec::new(),
+        }
+    }
+
+    // Add an event, returns false if it was a repeat left out of the log
+    pub fn record(&mut self, event: LoggedEvent) -> bool {
+        // Repeating an idle or held input changes nothing, so keep the log readable
+        let repeat = matches!(event, LoggedEvent::Input(InputAction::None) | LoggedEvent::Held(_));
+        if repeat && self.events.last() == Some(&event) {
+            return false;
+        }
+        self.events.push(event);
+        true
+    }
+
+    // Add an input along with where and when it came from
+    pub fn record_input(&mut self, event: LoggedEvent, device: InputDevice, tick: u64, millis: u64) {
+        if self.record(event) {
+            self.stamps.push(InputStamp {
+                event: self.events.len() - 1,
+                device,
+                tick,
+                millis,
+            });
+        }
+    }
+
+    // Device behind the event at the given index, if it was stamped
+    pub fn device_of(&self, event: usize) -> Option<InputDevice> {
+        self.stamps
+            .binary_search_by_key(&event, |stamp| stamp.event)
+            .ok()
+            .map(|found| self.stamps[found].device)
+    }
+
+    // Updates where two devices asked for different actions, like keyboard left with gamepad right
+    pub fn conflicts(&self) -> Vec<u64> {
+        let mut conflicts = Vec::new();
+        for (index, stamp) in self.stamps.iter().enumerate() {
+            let Some(action) = self.stamped_action(stamp) else {
+                continue;
+            };
+            let clash = self.stamps[..index].iter().rev()
+                .take_while(|earlier| earlier.tick == stamp.tick)
+                .any(|earlier| earlier.device != stamp.device &&
+                    self.stamped_action(earlier).is_some_and(|other| other != action));
+            if clash && conflicts.last() != Some(&stamp.tick) {
+                conflicts.push(stamp.tick);
+            }
+        }
+        conflicts
+    }
+
+    // Action of a stamped input, idle inputs don't count
+    fn stamped_action(&self, stamp: &InputStamp) -> Option<InputAction> {
+        match self.events.get(stamp.event)? {
+            LoggedEvent::Input(action) | LoggedEvent::Held(action) if *action != InputAction::None => Some(*action),
+            _ => None,
+        }
+    }
+
+    // Number of updates in the log
+    pub fn ticks(&self) -> usize {
+        self.events.iter().filter(|event| **event == LoggedEvent::Tick).count()
+    }
+
+    // Replay the whole log on a fresh game
+    pub fn rebuild(&self) -> Result<GameState, ConfigError> {
+        self.rebuild_until_tick(usize::MAX)
+    }
+
+    // Replay the log up to and including the given update, inputs after it are left out
+    // A log read from elsewhere may carry a config no game could have been started with
+    pub fn rebuild_until_tick(&self, tick: usize) -> Result<GameState, ConfigError> {
+        let mut game = GameState::new(self.config.clone())?;
+        let mut ticks = 0;
+
+        for (index, event) in self.events.iter().enumerate() {
+            if ticks >= tick {
+                break;
+            }
+            // Carry the stamps over, so a rebuilt game logs the same devices
+            let device = self.device_of(index).unwrap_or_default();
+            match *event {
+                LoggedEvent::Input(action) => {
+                    game.apply_input(action, true, device);
+                },
+                LoggedEvent::Held(action) => {
+                    game.apply_input(action, false, device);
+                },
+                LoggedEvent::Tick => {
+                    game.tick();
+                    ticks += 1;
+                },
+            }
+        }
+        Ok(game)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("event log is always serializable")
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+}
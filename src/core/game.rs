@@ -1,258 +1,1953 @@
 // Core game implementation - platform-independent
+use std::collections::VecDeque;
 use std::time::{Duration, Instant};
 
-use crate::core::block::{Block, spawn_random_block};
+use rand::distributions::WeightedIndex;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::core::block::{Block, BlockId, BlockKind, Shape, random_block};
+use crate::core::builder::GameStateBuilder;
+use crate::core::enemy::{Enemy, ENEMY_RESPAWN_TICKS};
+use crate::core::event::{GameEvent, ScoreBreakdown};
+use crate::core::event_log::{EventLog, LoggedEvent};
+use crate::core::grid::OccupancyGrid;
+use crate::core::level::{Level, LevelDefinition};
+use crate::core::objective::Objective;
+use crate::core::pickup::{Pickup, MAX_PICKUPS, PICKUP_SPAWN_INTERVAL};
 use crate::core::player::Player;
-use crate::core::types::{InputAction, Direction, GameConfig, GameUpdateResult};
+use crate::core::powerup::{ActivePowerUp, PowerUpKind, POWERUP_SPAWN_CHANCE};
+use crate::core::stats::{MarkerKind, RunStats};
+use crate::core::storm::{Storm, StormChange};
+use crate::core::terrain::{Terrain, Tile};
+use crate::core::types::{ConfigError, InputAction, InputDevice, Direction, GameConfig, GameMode, GameOverReason, GameUpdateResult, PlayerTiming, GridPos, Soundtrack, Theme};
+
+// Number of updates the player can't be hurt after losing a life
+pub const INVINCIBILITY_TICKS: u64 = 10;
+// A clear within this many updates of the previous one continues the chain
+pub const CHAIN_WINDOW_TICKS: u64 = 10;
+// Number of upcoming blocks rolled ahead of time, so frontends can show what comes next
+pub const NEXT_BLOCKS: usize = 3;
+// Updates ahead of a spawn that its column is locked in and telegraphed
+pub const SPAWN_WARNING_TICKS: u64 = 3;
+// Points for each block dropped into a pit
+pub const PIT_BONUS: u32 = 5;
+// Points for a block landing where the player stood a moment ago
+pub const CLOSE_CALL_BONUS: u32 = 2;
+// Length of a Time Attack game
+pub const TIME_ATTACK_DURATION: Duration = Duration::from_secs(180);
+
+// Outcome of moving a falling block down by one cell
+enum FallStep {
+    Moved,
+    Stopped,
+    HitPlayer,
+    Caught,
+    Sunk,
+}
 
+// Column the next block will drop in and the updates left until it does
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpawnWarning {
+    pub column: usize,
+    pub ticks_left: u64,
+}
+
+#[derive(Clone)]
 pub struct GameState {
-    pub grid_size: usize,
+    pub grid_width: usize,
+    pub grid_height: usize,
     pub cell_size: f32,
     pub player: Player,
+    // Idle second character the player can switch control to
+    pub partner: Option<Player>,
+    pub two_characters: bool,
+    pub player_timing: PlayerTiming,
     pub last_update: Instant,
     pub refresh_rate_milliseconds: u64,
     pub blocks: Vec<Block>,
+    // Id for the next block added to the field, ids are never reused within a run
+    next_block_id: u64,
+    // Blocks that will spawn next, in order, each placed at the top of the column it is headed for
+    pub next_blocks: VecDeque<Block>,
+    // Set shortly before a spawn, once the front of the queue has committed to its column
+    pub spawn_warning: Option<SpawnWarning>,
+    // Index of the cells each block covers, kept in step wherever blocks are added, moved or removed
+    pub occupancy: OccupancyGrid,
+    // Row of the highest settled block cell per column, grid_height for an empty column
+    // Kept up to date as blocks land, start falling, get cleared or pushed
+    stack_tops: Vec<usize>,
+    // Blocks checked for lost support per pass, None checks all of them every time
+    // Lowered by frontends when updates run too slow, so the check is spread over several updates
+    pub levitation_chunk: Option<usize>,
+    levitation_cursor: usize,
+    // Block indices reused by the per-update passes, taken out and put back so a tick doesn't allocate
+    scratch_indices: Vec<usize>,
+    pub terrain: Terrain,
+    pub pickups: Vec<Pickup>,
+    pub pickup_spawn_counter: u64,
+    // The Foreman, when the config has him, None while he is waiting to come back after being crushed
+    pub enemy_enabled: bool,
+    pub enemy: Option<Enemy>,
+    pub enemy_respawn_counter: u64,
+    pub active_powerup: Option<ActivePowerUp>,
     pub block_fall_speed: usize,
     pub block_spawn_rate: u64,
     pub block_spawn_counter: u64,
+    pub storm: Option<Storm>,
+    pub level: Level,
+    pub mode: GameMode,
+    // Updates a block may sit untouched on a full column in Zen before it is cleared, never if not set
+    pub idle_despawn: Option<u64>,
+    // Updates a landed bomb burns before it blows up, None leaves bombs to cleared rows
+    pub bomb_fuse: Option<u64>,
+    // Points per cell of a block the player moved that ends up in a cleared row
+    pub touch_bonus: u32,
+    // A practice run, which takes input macros and stays off the high score list
+    pub practice: bool,
+    // Index of the campaign level being played, 0 outside campaigns
+    pub campaign_level: usize,
+    // Goal of the current level, replaced on every level up
+    pub objective: Objective,
+    // Configured speeds, the current ones above are derived from these and the level
+    pub base_fall_speed: usize,
+    pub base_spawn_rate: u64,
     pub game_over: bool,
+    pub game_over_reason: Option<GameOverReason>,
+    pub danger_line: usize,
+    pub theme: Theme,
+    pub soundtrack: Soundtrack,
+    // Theme the game started with, before any campaign level changed it
+    pub base_theme: Theme,
+    pub block_patterns: bool,
+    pub head_carrying: bool,
+    pub auto_climb: bool,
+    pub jump_height: u8,
+    pub audio_cues: bool,
+    pub favor_low_columns: bool,
     pub score: u32,
+    pub chain: u32,
+    pub ticks_since_clear: u64,
+    // Score and stack height over the run, for the timeline on the game over screen
+    pub stats: RunStats,
+    pub events: Vec<GameEvent>,
+    pub lives: u32,
+    pub starting_lives: u32,
+    pub invincible_ticks: u64,
     pub last_move_direction: Option<Direction>,
-    last_move_time: Instant,
+    last_move_tick: u64, // Update the last move happened in, a held direction repeats once per update
+    // Seed of the generator behind block and pickup spawns
+    pub seed: u64,
+    rng: StdRng,
+    // Inputs and updates so far, kept only when the config asks for it
+    pub event_log: Option<EventLog>,
 }
 
 impl GameState {
-    pub fn new(config: GameConfig) -> Self {
+    /// Start a game from its config. A fixed seed makes every spawn, and so the whole game, repeatable.
+    /// A config the game can't be played with is turned down, see `GameConfig::validate`
+    ///
+    /// ```
+    /// use rust_stackattack::prelude::*;
+    ///
+    /// let game = GameState::new(GameConfig { grid_width: 8, grid_height: 10, seed: Some(7), ..Default::default() }).unwrap();
+    /// assert_eq!((game.grid_width, game.grid_height), (8, 10));
+    /// assert_eq!(game.seed, 7);
+    /// assert!(!game.game_over);
+    ///
+    /// let flat = GameConfig { grid_height: 1, ..Default::default() };
+    /// assert_eq!(GameState::new(flat).err(), Some(ConfigError::GridTooSmall { width: 16, height: 1 }));
+    /// ```
+    pub fn new(config: GameConfig) -> Result<Self, ConfigError> {
+        config.validate()?;
+        let seed = config.seed.unwrap_or_else(random_seed);
+        let event_log = config.record_events.then(|| EventLog::new(GameConfig {
+            seed: Some(seed),
+            ..config.clone()
+        }));
         let mut game = Self {
-            grid_size: config.grid_size,
+            grid_width: config.grid_width,
+            grid_height: config.grid_height,
             cell_size: config.cell_size,
-            player: Player::new(config.grid_size),
+            player: Player::with_timing(config.grid_width, config.grid_height, config.player_timing)
+                .with_auto_climb(config.auto_climb)
+                .with_jump_height(config.jump_height),
+            partner: None,
+            two_characters: config.two_characters,
+            player_timing: config.player_timing,
             last_update: Instant::now(),
             refresh_rate_milliseconds: config.refresh_rate_milliseconds,
             blocks: Vec::new(),
+            next_blocks: VecDeque::new(),
+            spawn_warning: None,
+            occupancy: OccupancyGrid::new(config.grid_width, config.grid_height),
+            stack_tops: vec![config.grid_height; config.grid_width],
+            levitation_chunk: None,
+            levitation_cursor: 0,
+            scratch_indices: Vec::new(),
+            terrain: Terrain::new(config.grid_width, config.grid_height),
+            pickups: Vec::with_capacity(MAX_PICKUPS),
+            pickup_spawn_counter: 0,
+            enemy_enabled: config.enemy,
+            enemy: None,
+            enemy_respawn_counter: 0,
+            active_powerup: None,
             block_fall_speed: config.block_fall_speed,
             block_spawn_rate: config.block_spawn_rate,
             block_spawn_counter: 0,
+            storm: config.storm_pacing.map(Storm::new),
+            level: Level::new(),
+            mode: config.mode.clone(),
+            idle_despawn: config.idle_despawn,
+            bomb_fuse: config.bomb_fuse,
+            touch_bonus: config.touch_bonus,
+            practice: config.practice,
+            campaign_level: 0,
+            objective: Objective::new(Level::new().objective()),
+            base_fall_speed: config.block_fall_speed,
+            base_spawn_rate: config.block_spawn_rate,
             game_over: false,
+            game_over_reason: None,
+            danger_line: config.danger_line,
+            theme: config.theme.unwrap_or(config.mode.style().theme),
+            base_theme: config.theme.unwrap_or(config.mode.style().theme),
+            soundtrack: config.mode.style().soundtrack,
+            block_patterns: config.block_patterns,
+            head_carrying: config.head_carrying,
+            auto_climb: config.auto_climb,
+            jump_height: config.jump_height,
+            audio_cues: config.audio_cues,
+            favor_low_columns: config.favor_low_columns,
             score: 0,
+            chain: 0,
+            ticks_since_clear: 0,
+            stats: RunStats::new(),
+            events: Vec::new(),
+            lives: config.lives,
+            starting_lives: config.lives,
+            invincible_ticks: 0,
             last_move_direction: None,
-            last_move_time: Instant::now(),
+            last_move_tick: 0,
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+            event_log,
+            next_block_id: 1,
         };
         
-        // Spawn the first block
+        for &x in &config.pits {
+            game.terrain.set_pit(x, true);
+        }
+        game.sync_character_terrain();
+        
+        if game.two_characters {
+            game.add_partner();
+        }
+        game.start_campaign_level(0);
+        
+        // Roll the upcoming blocks, then spawn the first one
+        game.refill_next_blocks();
         game.spawn_block();
+        game.spawn_enemy();
         
-        game
+        Ok(game)
+    }
+
+    /// A game set up by hand: the grid, seed and mode, the blocks on the board and where the player stands
+    ///
+    /// ```
+    /// use rust_stackattack::prelude::*;
+    ///
+    /// let game = GameState::builder().grid(10, 12).block(3, 9).player_at(5, 10).build().unwrap();
+    /// assert_eq!(game.blocks.len(), 1);
+    /// assert_eq!(game.blocks[0].position, (3, 9));
+    /// assert_eq!(game.player.position, (5, 10));
+    /// ```
+    pub fn builder() -> GameStateBuilder {
+        GameStateBuilder::new()
     }
 
-    // Reset game state
+    // Start over with a new seed, drawn from the current one so a logged run replays the same way
     pub fn restart(&mut self) {
-        self.player = Player::new(self.grid_size);
+        let seed = self.rng.gen();
+        self.restart_with_seed(seed);
+    }
+
+    // Reset game state, spawning the same sequence as any other run with this seed
+    pub fn restart_with_seed(&mut self, seed: u64) {
+        self.reseed(seed);
+        self.player = self.new_player();
+        self.partner = None;
+        if self.two_characters {
+            self.add_partner();
+        }
+        self.sync_character_terrain();
         self.blocks.clear();
+        self.next_block_id = 1;
+        self.sync_occupancy();
+        self.pickups.clear();
+        self.pickup_spawn_counter = 0;
+        self.enemy = None;
+        self.enemy_respawn_counter = 0;
+        self.active_powerup = None;
         self.last_update = Instant::now();
         self.block_spawn_counter = 0;
+        self.storm = self.storm.map(|storm| Storm::new(storm.pacing));
+        self.level = Level::new();
+        self.objective = Objective::new(self.level.objective());
+        self.apply_level_speeds();
         self.game_over = false;
+        self.game_over_reason = None;
         self.score = 0;
+        self.chain = 0;
+        self.ticks_since_clear = 0;
+        self.stats = RunStats::new();
+        self.events.clear();
+        self.lives = self.starting_lives;
+        self.invincible_ticks = 0;
         self.last_move_direction = None;
-        self.last_move_time = Instant::now();
+        self.last_move_tick = 0;
+        self.theme = self.base_theme;
+        self.soundtrack = self.mode.style().soundtrack;
+        self.start_campaign_level(0);
         
         // Spawn the first block for the new game
+        self.refill_next_blocks();
         self.spawn_block();
+        self.spawn_enemy();
+    }
+
+    // A game of a single level, e.g. to try out one from the editor, won once its objective is done
+    pub fn from_level(level: &LevelDefinition, config: GameConfig) -> Result<Self, ConfigError> {
+        Self::new(GameConfig {
+            grid_width: level.grid_width,
+            grid_height: level.grid_height,
+            pits: Vec::new(),
+            mode: GameMode::Campaign(vec![level.clone()]),
+            ..config
+        })
+    }
+
+    // Replace the board with a campaign level, does nothing outside campaigns
+    // Spawning is left to the caller, like after any other reset
+    fn start_campaign_level(&mut self, index: usize) {
+        let GameMode::Campaign(levels) = &self.mode else {
+            return;
+        };
+        let level = levels[index].clone();
+        self.campaign_level = index;
+        self.apply_level(&level);
+        self.level = Level { number: index as u32 + 1, rows_cleared: 0 };
+    }
+
+    // Set up the board, pace and objective of a level, with the player at its start
+    fn apply_level(&mut self, level: &LevelDefinition) {
+        let (width, height) = (level.grid_width, level.grid_height);
+        self.grid_width = width;
+        self.grid_height = height;
+        self.occupancy = OccupancyGrid::new(width, height);
+        self.stack_tops = vec![height; width];
+        self.levitation_cursor = 0;
+        self.terrain = Terrain::new(width, height);
+        for placed in &level.terrain {
+            self.terrain.set_tile(GridPos::new(placed.x, placed.y), placed.tile);
+        }
+        for &x in &level.pits {
+            self.terrain.set_pit(x, true);
+        }
+        self.blocks = level.blocks.iter().map(|placed| placed.to_block()).collect();
+        self.sync_occupancy();
+        self.next_blocks.clear();
+        self.spawn_warning = None;
+        self.block_spawn_counter = 0;
+        self.pickups.clear();
+        self.pickup_spawn_counter = 0;
+        self.enemy = None;
+        self.enemy_respawn_counter = 0;
+        
+        // Campaign levels keep the speeds and objective from their file until they are done
+        self.base_spawn_rate = level.block_spawn_rate;
+        self.base_fall_speed = level.block_fall_speed;
+        self.block_spawn_rate = level.block_spawn_rate;
+        self.block_fall_speed = level.block_fall_speed;
+        self.objective = Objective::new(level.objective);
+        if let Some(theme) = level.theme {
+            self.theme = theme;
+        }
+        if let Some(soundtrack) = level.soundtrack {
+            self.soundtrack = soundtrack;
+        }
+        
+        self.player = self.new_player();
+        self.player.position = level.player_start();
+        self.player.previous_position = self.player.position;
+        self.partner = None;
+        if self.two_characters {
+            self.add_partner();
+        }
+        self.sync_character_terrain();
+        self.settle();
+    }
+
+    // Move on to the next campaign level once the objective is done, the last one wins the game
+    fn advance_campaign(&mut self) {
+        let GameMode::Campaign(levels) = &self.mode else {
+            return;
+        };
+        let next = self.campaign_level + 1;
+        if next >= levels.len() {
+            self.end_game(GameOverReason::CampaignComplete);
+            return;
+        }
+        
+        self.start_campaign_level(next);
+        self.refill_next_blocks();
+        self.spawn_block();
+        self.spawn_enemy();
+        self.events.push(GameEvent::CampaignLevelStarted(next));
+    }
+
+    // A fresh character with the configured movement
+    fn new_player(&self) -> Player {
+        Player::with_timing(self.grid_width, self.grid_height, self.player_timing)
+            .with_auto_climb(self.auto_climb)
+            .with_jump_height(self.jump_height)
+    }
+
+    // Restart the spawn generator from a new seed
+    pub fn reseed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    // Place a ramp tile rising toward the given direction
+    pub fn place_ramp(&mut self, x: usize, y: usize, rises_toward: Direction) {
+        self.terrain.set_tile(GridPos::new(x, y), Tile::Ramp(rises_toward));
+        self.sync_character_terrain();
+    }
+
+    // Open a hole in the floor under a column
+    pub fn place_pit(&mut self, x: usize) {
+        self.terrain.set_pit(x, true);
+        self.sync_character_terrain();
+    }
+
+    // Whether a block in a column would drop through the floor, which takes a pit under every cell of its
+    // bottom row, since floor under any of them holds the whole block
+    pub fn block_over_pit(&self, block: &Block, x: usize) -> bool {
+        let height = block.shape.height();
+        block.cells_at(GridPos::new(x, 0)).filter(|cell| cell.y + 1 == height).all(|cell| self.terrain.is_pit(cell.x))
+    }
+
+    // Place the second character two columns away from the player
+    pub fn add_partner(&mut self) {
+        let mut partner = self.new_player();
+        let x = self.player.position.x;
+        partner.position.x = if x >= 2 { x - 2 } else { (x + 2).min(self.grid_width - 1) };
+        self.partner = Some(partner);
+        self.sync_character_terrain();
+    }
+
+    pub fn partner_occupies(&self, cell: GridPos) -> bool {
+        self.partner.as_ref().is_some_and(|partner| partner.occupies(cell))
+    }
+
+    // Swap control between the two characters, the one left behind stands idle
+    pub fn switch_character(&mut self) {
+        let Some(partner) = self.partner.as_mut() else {
+            return;
+        };
+        
+        self.player.release_carried_blocks(&mut self.blocks, None);
+        std::mem::swap(&mut self.player, partner);
+        self.last_move_direction = None;
+        self.sync_character_terrain();
+    }
+
+    // Each character sees the other as a wall, so it can be stood on but not walked through
+    pub fn sync_character_terrain(&mut self) {
+        self.player.copy_terrain(&self.terrain);
+        let Some(partner) = self.partner.as_mut() else {
+            return;
+        };
+        
+        partner.copy_terrain(&self.terrain);
+        self.player.wall_off(partner);
+        partner.wall_off(&self.player);
+    }
+
+    // Follow the blocks the player pushed or carried along on the latest sideways move
+    fn reindex_moved_blocks(&mut self, direction: Direction) {
+        for k in 0..self.player.moved_blocks().len() {
+            let idx = self.player.moved_blocks()[k];
+            let block = &self.blocks[idx];
+            let from = block.position.neighbor(direction.opposite()).expect("moved blocks came from a cell on the grid");
+            self.reindex_block(idx, from, block.shape, block.falling);
+        }
+    }
+
+    // Find where a block resting on a ramp would slide to, if that cell is free
+    pub fn ramp_slide_target(&self, block_idx: usize) -> Option<GridPos> {
+        // Only single-cell blocks are small enough to slide down a ramp
+        if self.blocks[block_idx].shape != Shape::Single {
+            return None;
+        }
+        
+        let position = self.blocks[block_idx].position;
+        let rises_toward = self.terrain.ramp_at(position.below())?;
+        
+        // Blocks slide toward the low side of the ramp
+        let target = position.neighbor(rises_toward.opposite()).filter(|target| target.x < self.grid_width)?;
+        
+        if self.terrain.is_solid(target) || self.player.occupies(target) || self.partner_occupies(target) ||
+            self.occupancy.is_occupied(target) {
+            return None;
+        }
+        Some(target)
     }
 
+    // Drop the next queued block, moving it to another column if its own is no longer fair
+    // With no such column the board is full: the game ends, or without death the spawn is skipped
     pub fn spawn_block(&mut self) {
-        self.blocks.push(spawn_random_block(self.grid_size));
+        self.assert_indexed();
+        self.spawn_warning = None;
+        let mut block = match self.next_blocks.pop_front() {
+            Some(block) => block,
+            None => self.roll_block(),
+        };
+        let next = self.roll_block();
+        self.next_blocks.push_back(next);
+        
+        if !self.fit_spawn(&mut block) {
+            if !self.no_death() {
+                self.end_game(GameOverReason::StackTooHigh);
+            }
+            return;
+        }
+        
+        // Spawned blocks appear in their cell, not sliding in from wherever they were queued
+        block.last_position = block.position;
+        self.add_block(block);
+    }
+    
+    // Keep the block's column if it is still fair, otherwise move it to one that is
+    // A shape too big for the gaps left shrinks to a single cell, false if even that fits nowhere
+    fn fit_spawn(&mut self, block: &mut Block) -> bool {
+        if self.spawn_column_fair(block, block.position.x) {
+            return true;
+        }
+        let mut column = self.choose_spawn_column(block);
+        if column.is_none() && block.shape != Shape::Single {
+            block.shape = Shape::Single;
+            column = self.choose_spawn_column(block);
+        }
+        let Some(x) = column else {
+            return false;
+        };
+        block.position = GridPos::new(x, 0);
+        true
+    }
+    
+    // Commit the front of the queue to a column against the board as it is now
+    fn telegraph_next_block(&mut self) -> Option<usize> {
+        let mut block = self.next_blocks.front()?.clone();
+        if !self.fit_spawn(&mut block) {
+            return None;
+        }
+        let column = block.position.x;
+        self.next_blocks[0] = block;
+        Some(column)
+    }
+    
+    // Roll a random block and the column it will drop in, judged by the board as it is now
+    fn roll_block(&mut self) -> Block {
+        let block = random_block(self.grid_width, self.grid_height, &mut self.rng);
+        let x = match self.choose_spawn_column(&block) {
+            Some(x) => x,
+            None => self.rng.gen_range(0..=self.grid_width - block.shape.width()),
+        };
+        Block { position: GridPos::new(x, 0), ..block }
+    }
+    
+    // Fill the queue of upcoming blocks from scratch
+    fn refill_next_blocks(&mut self) {
+        self.next_blocks.clear();
+        for _ in 0..NEXT_BLOCKS {
+            let block = self.roll_block();
+            self.next_blocks.push_back(block);
+        }
+    }
+    
+    // Pick a fair column for a block, weighting each by its free depth so the low columns fill up first
+    fn choose_spawn_column(&mut self, block: &Block) -> Option<usize> {
+        let columns = self.spawn_columns(block);
+        let weights = columns.iter().map(|&x| {
+            if self.favor_low_columns {
+                block.cells_at(GridPos::new(x, 0)).map(|cell| self.column_depth(cell.x)).min().unwrap_or(1)
+            } else {
+                1
+            }
+        });
+        let choice = WeightedIndex::new(weights).ok()?;
+        Some(columns[self.rng.sample(choice)])
+    }
+    
+    fn spawn_columns(&self, block: &Block) -> Vec<usize> {
+        // Blocks only go into pits by being pushed there
+        (0..=self.grid_width - block.shape.width())
+            .filter(|&x| self.spawn_column_fair(block, x) && !self.block_over_pit(block, x))
+            .collect()
+    }
+    
+    // A spawn column must be free where the block appears and keep one row of warning above the player
+    pub fn spawn_column_fair(&self, block: &Block, x: usize) -> bool {
+        block.cells_at(GridPos::new(x, 0)).all(|cell| {
+            let is_free = !self.terrain.is_solid(cell) && !self.occupancy.is_occupied(cell) &&
+                !self.player.occupies(cell) && !self.partner_occupies(cell);
+            let over_player = cell.x == self.player.position.x && self.player.position.y <= cell.y + 1;
+            is_free && !over_player
+        })
+    }
+    
+    // Number of empty cells from the top of a column down to whatever it rests on
+    fn column_depth(&self, x: usize) -> usize {
+        self.column_rest_cell(x).map_or(0, |cell| cell.y + 1)
+    }
+
+    // Reindex all blocks, needed after editing the blocks vector directly, e.g. a level loaded or set up by hand
+    // Blocks added that way get their ids here. Everything the game does to blocks itself keeps the index in step
+    pub fn sync_occupancy(&mut self) {
+        for i in 0..self.blocks.len() {
+            if !self.blocks[i].id.is_assigned() {
+                self.blocks[i].id = BlockId(self.next_block_id);
+                self.next_block_id += 1;
+            }
+        }
+        self.occupancy.rebuild(&self.blocks);
+        self.refresh_stack_tops();
+    }
+
+    // Passes trust the index, so blocks edited directly without sync_occupancy are caught in debug builds
+    fn assert_indexed(&self) {
+        debug_assert!(self.occupancy.matches(&self.blocks), "occupancy out of date, call sync_occupancy after editing blocks");
+    }
+
+    fn assign_block_id(&mut self, block: &mut Block) {
+        block.id = BlockId(self.next_block_id);
+        self.next_block_id += 1;
+    }
+
+    // Put a block on the field, giving it an id if it has none yet
+    fn add_block(&mut self, mut block: Block) {
+        if !block.id.is_assigned() {
+            self.assign_block_id(&mut block);
+        }
+        self.occupancy.insert(self.blocks.len(), &block);
+        if !block.falling {
+            self.raise_stack_tops(block.shape, block.position);
+        }
+        self.blocks.push(block);
+    }
+
+    // Take a block off the field, the ones after it move down a place
+    fn remove_block(&mut self, idx: usize) -> Block {
+        self.occupancy.remove(idx, &self.blocks[idx]);
+        let block = self.blocks.remove(idx);
+        for j in idx..self.blocks.len() {
+            self.occupancy.renumber(j + 1, j, &self.blocks[j]);
+        }
+        if !block.falling {
+            self.recheck_stack_tops(block.shape, block.position);
+        }
+        block
+    }
+
+    // Change blocks in place, dropping the ones edit returns false for, and reindex only what changed
+    fn edit_blocks(&mut self, mut edit: impl FnMut(&mut Block) -> bool) {
+        let mut kept = 0;
+        for i in 0..self.blocks.len() {
+            let (position, shape, falling) = (self.blocks[i].position, self.blocks[i].shape, self.blocks[i].falling);
+            if !edit(&mut self.blocks[i]) {
+                self.occupancy.remove_cells(i, shape.cells_at(position));
+                self.occupancy.remove_id(i, self.blocks[i].id);
+                if !falling {
+                    self.recheck_stack_tops(shape, position);
+                }
+                continue;
+            }
+            let block = &self.blocks[i];
+            if (block.position, block.shape, block.falling) != (position, shape, falling) {
+                self.reindex_block(i, position, shape, falling);
+            }
+            if kept != i {
+                self.blocks.swap(kept, i);
+                self.occupancy.renumber(i, kept, &self.blocks[kept]);
+            }
+            kept += 1;
+        }
+        self.blocks.truncate(kept);
+    }
+
+    // Follow a block whose cells or falling state changed, from where it was and how it was
+    fn reindex_block(&mut self, idx: usize, position: GridPos, shape: Shape, falling: bool) {
+        self.occupancy.remove_cells(idx, shape.cells_at(position));
+        self.occupancy.insert_cells(idx, self.blocks[idx].cells());
+        if !falling {
+            self.recheck_stack_tops(shape, position);
+        }
+        if !self.blocks[idx].falling {
+            self.raise_stack_tops(self.blocks[idx].shape, self.blocks[idx].position);
+        }
+    }
+
+    // Where a block currently sits in `blocks`, only good until the next block is removed
+    pub fn block_index(&self, id: BlockId) -> Option<usize> {
+        self.occupancy.index_of(id)
+    }
+
+    // A block on the field or carried on a character's head, None once it is cleared or destroyed
+    pub fn block(&self, id: BlockId) -> Option<&Block> {
+        if let Some(idx) = self.block_index(id) {
+            return Some(&self.blocks[idx]);
+        }
+        self.player.carrying.iter()
+            .chain(self.partner.iter().flat_map(|partner| partner.carrying.iter()))
+            .find(|block| block.id == id)
+    }
+
+    pub fn block_mut(&mut self, id: BlockId) -> Option<&mut Block> {
+        if let Some(idx) = self.block_index(id) {
+            return Some(&mut self.blocks[idx]);
+        }
+        self.player.carrying.iter_mut()
+            .chain(self.partner.iter_mut().flat_map(|partner| partner.carrying.iter_mut()))
+            .find(|block| block.id == id)
+    }
+
+    // Row of the highest settled cell in one column, scanned from the blocks rather than the cache
+    fn scan_stack_top(&self, x: usize) -> usize {
+        self.blocks.iter()
+            .filter(|block| !block.falling)
+            .flat_map(|block| block.cells())
+            .filter(|cell| cell.x == x)
+            .map(|cell| cell.y)
+            .fold(self.grid_height, usize::min)
+    }
+
+    fn stack_tops_in_sync(&self) -> bool {
+        (0..self.grid_width).all(|x| self.stack_tops[x] == self.scan_stack_top(x))
+    }
+
+    // Rebuilt in place, only for blocks edited directly, the game updates the columns it changes
+    fn refresh_stack_tops(&mut self) {
+        self.stack_tops.clear();
+        self.stack_tops.resize(self.grid_width, self.grid_height);
+        for GridPos { x, y } in self.blocks.iter().filter(|block| !block.falling).flat_map(|block| block.cells()) {
+            if x < self.grid_width {
+                self.stack_tops[x] = self.stack_tops[x].min(y);
+            }
+        }
+    }
+
+    // A settled block's cells can only bring the tops of their columns up
+    fn raise_stack_tops(&mut self, shape: Shape, position: GridPos) {
+        for GridPos { x, y } in shape.cells_at(position) {
+            if let Some(top) = self.stack_tops.get_mut(x) {
+                *top = (*top).min(y);
+            }
+        }
+    }
+
+    // Settled cells that were vacated or started falling, a column whose top one of them was looks further down
+    // Reads the index, so it has to be up to date with the change already
+    fn recheck_stack_tops(&mut self, shape: Shape, position: GridPos) {
+        for GridPos { x, y } in shape.cells_at(position) {
+            if self.stack_tops.get(x) != Some(&y) {
+                continue;
+            }
+            self.stack_tops[x] = (y..self.grid_height)
+                .find(|&row| self.settled_block_at(GridPos::new(x, row), usize::MAX))
+                .unwrap_or(self.grid_height);
+        }
+    }
+
+    // Row of the highest settled block cell in a column, grid_height if it has none
+    pub fn stack_top(&self, x: usize) -> usize {
+        debug_assert!(self.stack_tops_in_sync(), "stack top cache out of date");
+        self.stack_tops.get(x).copied().unwrap_or(self.grid_height)
+    }
+
+    // Row of the highest settled block cell anywhere, grid_height on an empty field
+    pub fn highest_stack_top(&self) -> usize {
+        debug_assert!(self.stack_tops_in_sync(), "stack top cache out of date");
+        self.stack_tops.iter().copied().min().unwrap_or(self.grid_height)
+    }
+
+    // Stop a falling block and raise the stack tops under it
+    fn settle_block(&mut self, block_idx: usize) {
+        self.blocks[block_idx].falling = false;
+        self.raise_stack_tops(self.blocks[block_idx].shape, self.blocks[block_idx].position);
+        
+        let vacated = self.blocks[block_idx].cells().find(|&cell| self.player.vacated(cell));
+        if let Some(cell) = vacated {
+            self.close_call(cell);
+        }
+        
+        // A bomb is lit the first time it lands, landing again after a fall doesn't reset its fuse
+        let block = &mut self.blocks[block_idx];
+        if block.kind == BlockKind::Bomb && block.fuse.is_none() {
+            block.fuse = self.bomb_fuse;
+        }
+        let block = &self.blocks[block_idx];
+        self.events.push(GameEvent::BlockLanded(block.id, block.position));
+        for y in block.position.y..=block.bottom() {
+            if self.row_one_short(y) {
+                self.events.push(GameEvent::RowAlmostComplete(y));
+            }
+        }
+    }
+
+    // Whether all but one cell of a row hold a settled block or solid terrain
+    // The fill count rules out most rows without looking at their cells
+    fn row_one_short(&self, y: usize) -> bool {
+        if self.occupancy.row_fill(y) + self.terrain.solid_cells_in_row(y) + 1 < self.grid_width {
+            return false;
+        }
+        let filled = (0..self.grid_width)
+            .filter(|&x| self.terrain.is_solid(GridPos::new(x, y)) || self.settled_block_at(GridPos::new(x, y), usize::MAX))
+            .count();
+        filled + 1 == self.grid_width
+    }
+
+    // The player got out of the way just in time
+    fn close_call(&mut self, cell: GridPos) {
+        self.score += CLOSE_CALL_BONUS;
+        self.stats.close_calls += 1;
+        self.stats.mark(MarkerKind::CloseCall);
+        self.events.push(GameEvent::CloseCall(cell));
+    }
+
+    // Move a block and keep the occupancy grid and stack tops in step
+    fn move_block(&mut self, block_idx: usize, position: GridPos) {
+        let block = &mut self.blocks[block_idx];
+        let (from, shape, falling) = (block.position, block.shape, block.falling);
+        block.position = position;
+        block.idle_ticks = 0;
+        self.reindex_block(block_idx, from, shape, falling);
+    }
+
+    // Whether a settled block other than the given one covers a cell
+    fn settled_block_at(&self, cell: GridPos, except: usize) -> bool {
+        self.occupancy.blocks_at(cell).iter().any(|&j| j != except && !self.blocks[j].falling)
     }
 
     pub fn check_for_levitating_blocks(&mut self) {
-        let mut blocks_changed = false;
+        self.assert_indexed();
         
-        for i in 0..self.blocks.len() {
+        // Check every settled block once (or the next chunk of them), then only the blocks resting on one
+        // that started falling
+        let len = self.blocks.len();
+        let mut worklist = std::mem::take(&mut self.scratch_indices);
+        worklist.clear();
+        match self.levitation_chunk {
+            Some(chunk) if chunk < len => {
+                let start = self.levitation_cursor % len;
+                self.levitation_cursor = (start + chunk) % len;
+                worklist.extend((start..start + chunk).map(|i| i % len));
+            },
+            _ => worklist.extend(0..len),
+        }
+        
+        while let Some(i) = worklist.pop() {
             // Skip blocks that are already falling
-            if self.blocks[i].falling {
+            if self.blocks[i].falling || self.block_has_support(i) {
                 continue;
             }
             
-            let (x, y) = self.blocks[i].position;
+            // No support is found, make it start falling
+            self.blocks[i].falling = true;
+            self.recheck_stack_tops(self.blocks[i].shape, self.blocks[i].position);
             
-            // Skip blocks on the bottom row
-            if y >= self.grid_size - 1 {
+            // Whatever rested on this block may have lost its support
+            for GridPos { x, y } in self.blocks[i].cells() {
+                if y == 0 {
+                    continue;
+                }
+                for &j in self.occupancy.blocks_at(GridPos::new(x, y - 1)) {
+                    if j != i && !self.blocks[j].falling {
+                        worklist.push(j);
+                    }
+                }
+            }
+        }
+        self.scratch_indices = worklist;
+    }
+
+    // Drop every unsupported settled block straight down to where it comes to rest, all in one pass,
+    // for boards changed in many places at once like a fresh level or the aftermath of bombs
+    // Blocks are handled from the bottom up, so each one lands on blocks already at rest. Blocks that would
+    // come down on a character are left falling, for the next update to resolve like any other hit
+    pub fn settle(&mut self) {
+        let mut order: Vec<usize> = (0..self.blocks.len()).filter(|&i| !self.blocks[i].falling).collect();
+        order.sort_by_key(|&i| (std::cmp::Reverse(self.blocks[i].bottom()), self.blocks[i].position.x));
+        
+        // Cells that stop a block: terrain, falling blocks and blocks already at rest
+        let width = self.grid_width;
+        let mut taken = vec![false; width * self.grid_height];
+        for GridPos { x, y } in self.blocks.iter().filter(|block| block.falling).flat_map(|block| block.cells()) {
+            taken[y * width + x] = true;
+        }
+        
+        let mut sunk = Vec::new();
+        for i in order {
+            let mut position = self.blocks[i].position;
+            let height = self.blocks[i].shape.height();
+            let mut on_character = false;
+            while position.y + height < self.grid_height {
+                let below: Vec<GridPos> = self.blocks[i].cells_at(position.below()).collect();
+                if below.iter().any(|&cell| self.player.occupies(cell) || self.partner_occupies(cell)) {
+                    on_character = true;
+                    break;
+                }
+                if below.iter().any(|&cell| taken[cell.y * width + cell.x] || self.terrain.is_solid(cell)) {
+                    break;
+                }
+                position = position.below();
+            }
+            
+            if position.y + height == self.grid_height && self.block_over_pit(&self.blocks[i], position.x) {
+                sunk.push(i);
                 continue;
             }
+            let block = &mut self.blocks[i];
+            let (from, shape) = (block.position, block.shape);
+            if from != position {
+                block.idle_ticks = 0;
+            }
+            block.position = position;
+            block.falling = on_character;
+            if from != position || on_character {
+                self.reindex_block(i, from, shape, false);
+            }
+            for GridPos { x: cx, y: cy } in self.blocks[i].cells() {
+                taken[cy * width + cx] = true;
+            }
+        }
+        
+        self.score += PIT_BONUS * sunk.len() as u32;
+        sunk.sort_unstable();
+        for &i in sunk.iter().rev() {
+            self.remove_block(i);
+        }
+    }
+
+    // Check if a settled block rests on the ground, terrain or another settled block
+    // Multi-cell blocks are rigid, so support under any of their cells holds the whole block
+    pub fn block_has_support(&self, block_idx: usize) -> bool {
+        let block = &self.blocks[block_idx];
+        
+        // Blocks touching the bottom row rest on the ground, unless it has a pit under them
+        if block.bottom() >= self.grid_height - 1 {
+            return !self.block_over_pit(block, block.position.x);
+        }
+        
+        block.cells().any(|cell| {
+            let below = cell.below();
+            
+            // Cells resting on another cell of the same block don't count
+            if block.occupies(below) {
+                return false;
+            }
+            
+            let has_block_support = self.settled_block_at(below, block_idx);
             
-            // Check if there's a block or ground beneath this one
-            let has_support = self.blocks.iter().any(|b| 
-                !b.falling && 
-                b.position.0 == x && 
-                b.position.1 == y + 1
-            );
+            // Terrain supports blocks too, except ramps they can slide down
+            let has_terrain_support = self.terrain.is_solid(below) &&
+                self.ramp_slide_target(block_idx).is_none();
             
-            // If no support is found, make it start falling
-            if !has_support {
-                self.blocks[i].falling = true;
-                blocks_changed = true;
+            // The idle character holds blocks up like any other obstacle
+            has_block_support || has_terrain_support || self.partner_occupies(below)
+        })
+    }
+
+    pub fn check_full_rows(&mut self) {
+        self.assert_indexed();
+        
+        // Find every full row first, so all of them are cleared in the same pass
+        // The fill counts rule out most rows without looking at their cells
+        let full_rows: Vec<usize> = (0..self.grid_height)
+            .filter(|&row| self.occupancy.row_fill(row) + self.terrain.solid_cells_in_row(row) >= self.grid_width)
+            .filter(|&row| {
+                // A row is full when every cell holds a settled block or solid terrain
+                (0..self.grid_width).all(|x| {
+                    self.terrain.is_solid(GridPos::new(x, row)) || self.settled_block_at(GridPos::new(x, row), usize::MAX)
+                })
+            })
+            .collect();
+        
+        if full_rows.is_empty() {
+            return;
+        }
+        
+        // Blocks the player moved into place score for every cell they lose to the clear
+        let touched_cells = self.blocks.iter()
+            .filter(|block| block.touched && !block.falling)
+            .flat_map(|block| block.cells())
+            .filter(|cell| full_rows.contains(&cell.y))
+            .count() as u32;
+        
+        // Bombs caught in a cleared row go off once it is cleared, once each however many of their cells it takes
+        let bombs: Vec<(BlockId, GridPos)> = self.blocks.iter()
+            .filter(|block| block.kind == BlockKind::Bomb && block.cells().any(|cell| full_rows.contains(&cell.y)))
+            .map(|block| (block.id, block.position))
+            .collect();
+        
+        // Remove all block cells in these rows, keeping what is left of multi-cell blocks
+        // What is left of those bombs is taken off too, so their own blasts don't set it off a second time
+        self.edit_blocks(|block| {
+            if bombs.iter().any(|&(id, _)| id == block.id) {
+                return false;
+            }
+            if !block.cells().any(|cell| full_rows.contains(&cell.y)) {
+                return true;
+            }
+            match full_rows.iter().try_fold(block.clone(), |remnant, &row| remnant.without_row(row)) {
+                Some(remnant) => {
+                    *block = remnant;
+                    true
+                },
+                None => false,
             }
+        });
+        
+        // Explosions leave holes all over the board, whatever they left hanging drops at once
+        let exploded = !bombs.is_empty();
+        for (_, center) in bombs {
+            self.explode(center);
+        }
+        if exploded {
+            self.settle();
         }
         
-        // If blocks started falling, check again for chain reactions
-        if blocks_changed {
-            self.check_for_levitating_blocks();
+        for &row in &full_rows {
+            self.events.push(GameEvent::RowCleared(row));
         }
+        self.score_cleared_rows(full_rows.len() as u32, touched_cells);
+        
+        // Check for blocks that are now levitating after removing the rows
+        self.check_for_levitating_blocks();
     }
 
-    pub fn check_full_rows(&mut self) {
-        // Check each row from the bottom up
-        for row in (0..self.grid_size).rev() {
-            // Count non-falling blocks in this row
-            let blocks_in_row = self.blocks.iter()
-                .filter(|block| !block.falling && block.position.1 == row)
-                .count();
-            
-            // If the row is full
-            if blocks_in_row == self.grid_size {
-                // Remove all blocks in this row
-                self.blocks.retain(|block| block.position.1 != row);
-                
-                // Increment the score
-                self.score += 1;
-                
-                // Check for blocks that are now levitating after removing the row
-                self.check_for_levitating_blocks();
-                
-                // We'll check one row at a time to keep it simple
-                // The next full row (if any) will be caught in the next update
-                break;
+    // Award escalating points for clearing several rows at once and for chained clears, and a flat bonus
+    // for each cleared cell of a block the player moved there
+    fn score_cleared_rows(&mut self, rows: u32, touched_cells: u32) {
+        if self.chain > 0 && self.ticks_since_clear <= CHAIN_WINDOW_TICKS {
+            self.chain += 1;
+        } else {
+            self.chain = 1;
+        }
+        self.ticks_since_clear = 0;
+        
+        // 1, 3, 6, 10... points for 1, 2, 3, 4... rows
+        let base_points = rows * (rows + 1) / 2;
+        let touch_bonus = touched_cells * self.touch_bonus;
+        let breakdown = ScoreBreakdown {
+            rows,
+            base_points,
+            chain: self.chain,
+            touch_bonus,
+            points: base_points * self.chain + touch_bonus,
+        };
+        
+        self.score += breakdown.points;
+        self.add_cleared_rows(rows);
+        self.events.push(GameEvent::RowsCleared(breakdown));
+        self.stats.mark(MarkerKind::RowsCleared);
+    }
+
+    /// Hand all events queued since the last call to the frontend
+    ///
+    /// ```
+    /// use rust_stackattack::core::block::Block;
+    /// use rust_stackattack::prelude::*;
+    ///
+    /// let mut game = GameState::new(GameConfig { grid_width: 8, grid_height: 10, seed: Some(7), ..Default::default() }).unwrap();
+    /// game.blocks = vec![Block { position: GridPos::new(0, 0), ..Default::default() }];
+    /// game.sync_occupancy();
+    /// for _ in 0..10 {
+    ///     game.tick();
+    /// }
+    ///
+    /// let landed = GameEvent::BlockLanded(game.blocks[0].id, GridPos::new(0, 9));
+    /// assert!(game.drain_events().contains(&landed));
+    /// assert!(game.drain_events().is_empty());
+    /// ```
+    pub fn drain_events(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    // Track cleared rows and speed up the game when a new level is reached
+    pub fn add_cleared_rows(&mut self, rows: u32) {
+        if self.objective.record_rows(rows) {
+            self.objective_completed();
+        }
+        if matches!(self.mode, GameMode::Campaign(_)) {
+            return;
+        }
+        if self.level.add_cleared_rows(rows) {
+            self.apply_level_speeds();
+            self.objective = Objective::new(self.level.objective());
+        }
+    }
+
+    fn objective_completed(&mut self) {
+        self.events.push(GameEvent::ObjectiveCompleted(self.objective.kind));
+    }
+
+    fn apply_level_speeds(&mut self) {
+        self.block_spawn_rate = self.level.spawn_rate(self.base_spawn_rate);
+        self.block_fall_speed = self.level.fall_speed(self.base_fall_speed);
+    }
+
+    // Destroy every block touching the 3x3 area around a cell, except steel
+    // Other bombs caught in the blast go off in turn around their own position, not one right at the center,
+    // which is the one going off
+    // Either character caught in any of the blasts costs a life
+    pub fn explode(&mut self, center: GridPos) {
+        let mut centers = vec![center];
+        let mut player_caught = false;
+        while let Some(center) = centers.pop() {
+            let in_blast = |cell: GridPos| cell.x.abs_diff(center.x) <= 1 && cell.y.abs_diff(center.y) <= 1;
+            centers.extend(self.blocks.iter()
+                .filter(|block| block.kind == BlockKind::Bomb && !block.occupies(center) && block.cells().any(in_blast))
+                .map(|block| block.position));
+            self.edit_blocks(|block| block.kind == BlockKind::Steel || !block.cells().any(in_blast));
+            player_caught |= self.player.body_cells().any(in_blast) ||
+                self.partner.as_ref().is_some_and(|partner| partner.body_cells().any(in_blast));
+            self.events.push(GameEvent::Exploded(center));
+        }
+        if player_caught {
+            self.lose_life(GameOverReason::Blown);
+        }
+    }
+
+    // Count down the fuses of landed bombs and set off the ones that burn out
+    // What the blasts leave hanging drops at once, like after bombs in a cleared row
+    fn burn_fuses(&mut self) {
+        let mut centers = Vec::new();
+        for block in &mut self.blocks {
+            match block.fuse {
+                Some(0) => centers.push(block.position),
+                Some(fuse) => block.fuse = Some(fuse - 1),
+                None => {},
             }
         }
+        if centers.is_empty() {
+            return;
+        }
+        
+        // Burnt out bombs are taken off first, so their own blasts don't set them off a second time
+        self.edit_blocks(|block| block.fuse != Some(0));
+        for center in centers {
+            self.explode(center);
+        }
+        self.settle();
     }
 
     pub fn update_blocks(&mut self) {
-        self.update_falling_blocks();
+        // Slow motion lets blocks fall only on every other update
+        let slowed = self.active_powerup
+            .is_some_and(|p| p.kind == PowerUpKind::SlowMotion && p.remaining_ticks % 2 == 1);
+        if self.powerup_active(PowerUpKind::Magnet) {
+            self.pull_falling_blocks();
+        }
+        if !slowed {
+            self.update_falling_blocks();
+        }
+        self.update_sliding_blocks();
+        self.crush_pickups();
+        self.tick_storm();
         self.handle_block_spawning();
+        self.handle_pickup_spawning();
         self.check_for_levitating_blocks();
         self.check_full_rows();
+        self.burn_fuses();
+        self.check_stack_height();
+        self.despawn_idle_blocks();
+        self.tick_powerup();
+    }
+
+    // Endless Zen sessions would otherwise keep every block ever dropped. Blocks left alone for long
+    // enough on a column stacked into the upper half of the board are cleared, and the rest settle
+    fn despawn_idle_blocks(&mut self) {
+        for block in &mut self.blocks {
+            let moving = block.falling || block.carried || block.sliding.is_some();
+            block.idle_ticks = if moving { 0 } else { block.idle_ticks + 1 };
+        }
+        let Some(limit) = self.idle_despawn.filter(|_| self.no_death()) else {
+            return;
+        };
+        
+        let full_below = self.grid_height / 2;
+        let stack_tops = &self.stack_tops;
+        let idle = |block: &Block| {
+            !block.falling && block.idle_ticks >= limit && block.cells().any(|cell| stack_tops[cell.x] < full_below)
+        };
+        let cells: Vec<GridPos> = self.blocks.iter().filter(|block| idle(block)).flat_map(|block| block.cells()).collect();
+        if cells.is_empty() {
+            return;
+        }
+        
+        // Picked against the stack as it was, before taking any of them off lowers it
+        let ids: Vec<BlockId> = self.blocks.iter().filter(|block| idle(block)).map(|block| block.id).collect();
+        self.edit_blocks(|block| !ids.contains(&block.id));
+        self.settle();
+        self.events.push(GameEvent::BlocksDespawned(cells));
+    }
+
+    pub fn end_game(&mut self, reason: GameOverReason) {
+        self.game_over = true;
+        self.game_over_reason = Some(reason);
+        self.stats.finish(self.score, self.stack_height());
+    }
+
+    // Zen games can't be lost, crushing blocks break on the player and the stack is trimmed
+    pub fn no_death(&self) -> bool {
+        self.mode == GameMode::Zen
+    }
+
+    // Time left in a Time Attack game, counted in updates so replays end at the same moment
+    pub fn time_left(&self) -> Option<Duration> {
+        if self.mode != GameMode::TimeAttack {
+            return None;
+        }
+        Some(TIME_ATTACK_DURATION.saturating_sub(self.elapsed()))
+    }
+
+    // Game time played so far, by updates rather than the wall clock
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_millis(self.stats.ticks * self.refresh_rate_milliseconds)
+    }
+
+    // Rows covered by the settled stack
+    pub fn stack_height(&self) -> usize {
+        self.grid_height - self.highest_stack_top()
+    }
+
+    // Like classic stackers, the game is lost once settled blocks pile up to the danger line
+    pub fn check_stack_height(&mut self) {
+        let danger_line = self.danger_line;
+        if self.highest_stack_top() > danger_line {
+            return;
+        }
+        
+        // Without death the blocks over the line are swept away instead
+        if self.no_death() {
+            self.edit_blocks(|block| block.falling || block.position.y > danger_line);
+        } else {
+            self.end_game(GameOverReason::StackTooHigh);
+        }
+    }
+
+    pub fn powerup_active(&self, kind: PowerUpKind) -> bool {
+        self.active_powerup.is_some_and(|p| p.kind == kind)
+    }
+
+    // A newly collected power-up replaces the one in effect
+    pub fn activate_powerup(&mut self, kind: PowerUpKind) {
+        self.active_powerup = Some(ActivePowerUp::new(kind));
+    }
+
+    fn tick_powerup(&mut self) {
+        if let Some(powerup) = &mut self.active_powerup {
+            if !powerup.tick() {
+                self.active_powerup = None;
+            }
+        }
+    }
+
+    // With the hammer active, pushing into a block smashes it instead of moving it
+    pub fn use_hammer(&mut self, direction: Direction) -> bool {
+        if !self.powerup_active(PowerUpKind::Hammer) {
+            return false;
+        }
+        
+        let Some(target) = self.player.position.neighbor(direction).filter(|target| target.x < self.grid_width) else {
+            return false;
+        };
+        
+        let body_size = self.player.body_size;
+        let hit = self.blocks.iter().position(|b| {
+            !b.falling && (target.y..target.y + body_size).any(|row| b.occupies(GridPos::new(target.x, row)))
+        });
+        
+        match hit {
+            Some(idx) => {
+                self.remove_block(idx);
+                self.active_powerup = None;
+                true
+            },
+            None => false,
+        }
+    }
+
+    // Kick the settled block in front of the player, feet level first, so it slides until it hits something
+    // Steel is too heavy to kick
+    pub fn kick(&mut self) -> bool {
+        let direction = self.player.facing;
+        let Some(target) = self.player.position.neighbor(direction).filter(|target| target.x < self.grid_width) else {
+            return false;
+        };
+        
+        let body_size = self.player.body_size;
+        let hit = (target.y..target.y + body_size).rev().find_map(|row| {
+            self.blocks.iter().position(|b| !b.falling && b.occupies(GridPos::new(target.x, row)))
+        });
+        
+        match hit {
+            Some(idx) if self.blocks[idx].kind != BlockKind::Steel => {
+                self.blocks[idx].sliding = Some(direction);
+                self.blocks[idx].touched = true;
+                true
+            },
+            _ => false,
+        }
+    }
+
+    // Top-left cell a falling block comes to rest in if nothing else moves, for a ghost drawn there
+    // Looks down through the occupancy index from where the block is, settled blocks, terrain and either character stop it
+    // None for blocks at rest or carried, and for blocks headed into a pit
+    pub fn landing_position(&self, block_idx: usize) -> Option<GridPos> {
+        let block = self.blocks.get(block_idx).filter(|block| block.falling && !block.carried)?;
+        let height = block.shape.height();
+        let mut position = block.position;
+        loop {
+            let below = position.below();
+            if below.y + height > self.grid_height {
+                return (!self.block_over_pit(block, position.x)).then_some(position);
+            }
+            let stopped = block.cells_at(below).any(|cell| {
+                self.terrain.is_solid(cell) ||
+                    self.settled_block_at(cell, block_idx) ||
+                    self.player.occupies(cell) ||
+                    self.partner_occupies(cell)
+            });
+            if stopped {
+                return Some(position);
+            }
+            position = below;
+        }
+    }
+
+    // Find the empty cell resting on top of a column (ground, block or terrain below it)
+    pub fn column_rest_cell(&self, x: usize) -> Option<GridPos> {
+        let occupied = |y: usize| self.terrain.is_solid(GridPos::new(x, y)) || self.occupancy.is_occupied(GridPos::new(x, y));
+        
+        // Only the cells above the settled stack need a look
+        let stack_top = self.stack_top(x);
+        let top = (0..stack_top).find(|&y| occupied(y)).unwrap_or(stack_top);
+        if top == 0 {
+            return None; // Column is full
+        }
+        Some(GridPos::new(x, top - 1))
+    }
+
+    // Place a coin in an empty cell, unless something is already there
+    pub fn spawn_pickup_at(&mut self, position: GridPos) -> bool {
+        self.place_pickup(Pickup::coin(position))
+    }
+
+    // Place any pickup in an empty cell, unless something is already there
+    pub fn place_pickup(&mut self, pickup: Pickup) -> bool {
+        self.assert_indexed();
+        let position = pickup.position;
+        let is_taken = self.player.occupies(position) ||
+            self.partner_occupies(position) ||
+            self.terrain.is_solid(position) ||
+            self.occupancy.is_occupied(position) ||
+            self.pickups.iter().any(|p| p.position == position);
+        
+        if is_taken {
+            return false;
+        }
+        self.pickups.push(pickup);
+        true
+    }
+
+    // Occasionally drop a coin or a power-up on top of a random column
+    pub fn handle_pickup_spawning(&mut self) {
+        self.pickup_spawn_counter += 1;
+        if self.pickup_spawn_counter < PICKUP_SPAWN_INTERVAL {
+            return;
+        }
+        self.pickup_spawn_counter = 0;
+        
+        if self.pickups.len() >= MAX_PICKUPS {
+            return;
+        }
+        
+        let x = self.rng.gen_range(0..self.grid_width);
+        if let Some(cell) = self.column_rest_cell(x) {
+            let pickup = if self.rng.gen_bool(POWERUP_SPAWN_CHANCE) {
+                Pickup::powerup(cell, PowerUpKind::ALL[self.rng.gen_range(0..PowerUpKind::ALL.len())])
+            } else {
+                Pickup::coin(cell)
+            };
+            self.place_pickup(pickup);
+        }
+    }
+
+    // Drop the Foreman in at the top of the column farthest from the player
+    pub fn spawn_enemy(&mut self) {
+        if !self.enemy_enabled {
+            return;
+        }
+        let player_x = self.player.position.x;
+        let farthest = (0..self.grid_width)
+            .filter(|&x| !self.terrain.is_solid(GridPos::new(x, 0)) && !self.occupancy.is_occupied(GridPos::new(x, 0)))
+            .max_by_key(|&x| x.abs_diff(player_x));
+        if let Some(x) = farthest {
+            self.enemy = Some(Enemy::new(GridPos::new(x, 0)));
+        }
+    }
+
+    // Walk the Foreman, crush him under landed blocks and hurt the player he runs into
+    pub fn update_enemy(&mut self) {
+        if !self.enemy_enabled {
+            return;
+        }
+        self.assert_indexed();
+        
+        let Some(enemy) = &mut self.enemy else {
+            self.enemy_respawn_counter += 1;
+            if self.enemy_respawn_counter >= ENEMY_RESPAWN_TICKS {
+                self.enemy_respawn_counter = 0;
+                self.spawn_enemy();
+            }
+            return;
+        };
+        
+        // A block that came down on him squashes him
+        if self.occupancy.is_occupied(enemy.position) {
+            self.enemy = None;
+            return;
+        }
+        
+        // He walks on terrain, settled blocks and the idle character, and straight into the player
+        let (terrain, occupancy, blocks, partner) = (&self.terrain, &self.occupancy, &self.blocks, &self.partner);
+        enemy.update(|cell| {
+            terrain.is_solid(cell) ||
+                occupancy.blocks_at(cell).iter().any(|&j| !blocks[j].falling) ||
+                partner.as_ref().is_some_and(|partner| partner.occupies(cell))
+        }, self.grid_width, self.grid_height);
+        
+        self.check_enemy_contact();
+    }
+
+    // Running into the Foreman costs a life, he turns around afterwards
+    pub fn check_enemy_contact(&mut self) {
+        let Some(enemy) = &mut self.enemy else {
+            return;
+        };
+        if self.player.occupies(enemy.position) {
+            enemy.direction = enemy.direction.opposite();
+            self.hit_player();
+        }
+    }
+
+    // Collect every pickup the player's body is touching
+    pub fn collect_pickups(&mut self) {
+        let (player, events) = (&self.player, &mut self.events);
+        let mut collected = 0;
+        let mut coins = 0;
+        let mut powerup = None;
+        self.pickups.retain(|pickup| {
+            if player.occupies(pickup.position) {
+                collected += pickup.value;
+                coins += pickup.powerup.is_none() as u32;
+                powerup = pickup.powerup.or(powerup);
+                if pickup.powerup.is_none() {
+                    events.push(GameEvent::CoinCollected(pickup.position, pickup.value));
+                }
+                false
+            } else {
+                true
+            }
+        });
+        self.score += collected;
+        if self.objective.record_coins(coins) {
+            self.objective_completed();
+        }
+        
+        if let Some(kind) = powerup {
+            self.activate_powerup(kind);
+        }
+    }
+
+    // Blocks landing on a pickup destroy it
+    pub fn crush_pickups(&mut self) {
+        self.assert_indexed();
+        let occupancy = &self.occupancy;
+        self.pickups.retain(|pickup| !occupancy.is_occupied(pickup.position));
     }
 
     pub fn update_falling_blocks(&mut self) {
-        for i in 0..self.blocks.len() {
-            // Skip blocks that are currently being carried
-            if self.blocks[i].carried {
+        self.assert_indexed();
+        
+        // Move the lowest blocks first, so a block falling onto another one sees where it ended up
+        // Skip blocks that are currently being carried
+        let mut falling = std::mem::take(&mut self.scratch_indices);
+        falling.clear();
+        falling.extend((0..self.blocks.len()).filter(|&i| self.blocks[i].falling && !self.blocks[i].carried));
+        falling.sort_unstable_by_key(|&i| (std::cmp::Reverse(self.blocks[i].bottom()), i));
+        
+        for next in 0..falling.len() {
+            // Nothing moves any more once the game is lost
+            if self.game_over {
+                break;
+            }
+            let Some(removed) = self.fall_block(falling[next]) else {
                 continue;
+            };
+            // The blocks after a removed one move down a place
+            for j in &mut falling[next + 1..] {
+                if *j > removed {
+                    *j -= 1;
+                }
             }
-            
-            if !self.blocks[i].falling {
+        }
+        self.scratch_indices = falling;
+    }
+
+    // Move one falling block for this update, returning its index if it left the list of blocks
+    fn fall_block(&mut self, i: usize) -> Option<usize> {
+        // Fast blocks still fall one cell at a time, so they can't skip over the player or the stack
+        for _ in 0..self.block_fall_speed {
+            match self.fall_one_cell(i) {
+                FallStep::Moved => continue,
+                FallStep::Stopped => break,
+                FallStep::HitPlayer => {
+                    // The crushing block is destroyed when the player survives the hit, or in a game without deaths
+                    if self.game_over {
+                        return None;
+                    }
+                    self.remove_block(i);
+                    return Some(i);
+                },
+                FallStep::Caught => {
+                    // The block moves onto the player's head
+                    let block = self.remove_block(i);
+                    let id = block.id;
+                    self.player.catch(block);
+                    self.events.push(GameEvent::BlockCaught(id));
+                    return Some(i);
+                },
+                FallStep::Sunk => return Some(i), // Dropped through a pit
+            }
+        }
+        None
+    }
+
+    fn fall_one_cell(&mut self, i: usize) -> FallStep {
+        let GridPos { x, y } = self.blocks[i].position;
+        let new_y = y + 1;
+        
+        // A light block dropping onto the head is caught rather than crushing the player
+        if self.head_carrying && GridPos::new(x, new_y) == self.player.position && self.player.can_catch(&self.blocks[i]) {
+            return FallStep::Caught;
+        }
+        
+        // Every cell of the block can hit the player
+        // An invincible player holds the block up until they step out from under it
+        let offsets = self.blocks[i].shape.offsets();
+        if self.invincible_ticks > 0 && offsets.iter().any(|&(dx, dy)| self.player.occupies(GridPos::new(x + dx, new_y + dy))) {
+            return FallStep::Stopped;
+        }
+        if offsets.iter().any(|&(dx, dy)| self.check_block_player_collision(x + dx, new_y + dy)) {
+            return FallStep::HitPlayer;
+        }
+        
+        if let Some(step) = self.check_block_bottom_collision(i, new_y) {
+            return step;
+        }
+        
+        if self.check_block_terrain_collision(i, x, new_y) {
+            return FallStep::Stopped;
+        }
+        
+        // Blocks land on the idle character instead of crushing it
+        if self.blocks[i].cells_at(GridPos::new(x, new_y)).any(|cell| self.partner_occupies(cell)) {
+            self.settle_block(i);
+            return FallStep::Stopped;
+        }
+        
+        if self.check_block_block_collision(i, x, new_y) {
+            self.settle_block(i);
+            return FallStep::Stopped;
+        }
+        if self.check_falling_block_collision(i, x, new_y) {
+            return FallStep::Stopped;
+        }
+        self.move_block(i, GridPos::new(x, new_y));
+        FallStep::Moved
+    }
+
+    // A falling block that couldn't move this update is in the way, wait behind it without landing
+    pub fn check_falling_block_collision(&self, block_idx: usize, x: usize, new_y: usize) -> bool {
+        self.blocks[block_idx].cells_at(GridPos::new(x, new_y))
+            .any(|cell| self.occupancy.blocks_at(cell).iter().any(|&j| j != block_idx))
+    }
+
+    pub fn check_block_player_collision(&mut self, x: usize, new_y: usize) -> bool {
+        // A block hitting any cell of the player's body crushes the player
+        if self.player.occupies(GridPos::new(x, new_y)) {
+            self.hit_player();
+            return true;
+        }
+        false
+    }
+
+    // Lose a life and respawn, the game ends once no lives are left
+    pub fn hit_player(&mut self) {
+        self.lose_life(GameOverReason::Crushed);
+    }
+
+    fn lose_life(&mut self, reason: GameOverReason) {
+        // Blasts pass over an invincible player, who holds crushing blocks up before they get here
+        // Without deaths nothing hurts, and a crushing block just breaks
+        if self.invincible_ticks > 0 || self.no_death() {
+            return;
+        }
+        
+        self.lives = self.lives.saturating_sub(1);
+        self.events.push(GameEvent::PlayerHit);
+        if self.lives == 0 {
+            self.end_game(reason);
+            return;
+        }
+        
+        self.respawn_player();
+        self.invincible_ticks = INVINCIBILITY_TICKS;
+    }
+
+    // Dropping into a pit costs a life even while invincible, nobody can stand in one
+    fn check_player_in_pit(&mut self) {
+        let GridPos { x, y } = self.player.position;
+        if !self.player.is_falling || y + self.player.body_size < self.grid_height || !self.terrain.is_pit(x) {
+            return;
+        }
+        
+        if !self.no_death() {
+            self.lives = self.lives.saturating_sub(1);
+            self.events.push(GameEvent::PlayerHit);
+            if self.lives == 0 {
+                self.end_game(GameOverReason::Fell);
+                return;
+            }
+        }
+        
+        self.respawn_player();
+        self.invincible_ticks = INVINCIBILITY_TICKS;
+    }
+
+    // Move the player to the nearest column with room to stand and nothing falling into it
+    pub fn respawn_player(&mut self) {
+        let start_x = self.player.position.x;
+        // The player respawns standing, even if it was hit while crouching
+        let body_size = self.new_player().body_size;
+        
+        let mut columns: Vec<usize> = (0..self.grid_width).collect();
+        columns.sort_by_key(|&x| x.abs_diff(start_x));
+        
+        for x in columns {
+            let has_falling_block = self.blocks.iter().any(|b| b.falling && b.cells().any(|cell| cell.x == x));
+            if has_falling_block {
                 continue;
             }
             
-            let (x, y) = self.blocks[i].position;
-            let new_y = y + self.block_fall_speed;
-            
-            if self.check_block_player_collision(x, new_y) {
-                return; // Game over detected, exit early
+            if let Some(GridPos { y: floor_y, .. }) = self.column_rest_cell(x) {
+                let over_pit = floor_y + 1 == self.grid_height && self.terrain.is_pit(x);
+                if floor_y + 1 >= body_size && !over_pit {
+                    let mut player = self.new_player();
+                    player.position = GridPos::new(x, floor_y + 1 - body_size);
+                    self.player = player;
+                    self.sync_character_terrain();
+                    return;
+                }
             }
+        }
+    }
+
+    // Drift every falling block a column toward the player, each one that has room to
+    // Blocks stop a column short of the player, the magnet shouldn't drop them on the player's head
+    pub fn pull_falling_blocks(&mut self) {
+        self.assert_indexed();
+        
+        let player_x = self.player.position.x;
+        for i in 0..self.blocks.len() {
+            let block = &self.blocks[i];
+            if !block.falling || block.carried {
+                continue;
+            }
+            let direction = match block.position.x.cmp(&player_x) {
+                std::cmp::Ordering::Less => Direction::Right,
+                std::cmp::Ordering::Greater => Direction::Left,
+                std::cmp::Ordering::Equal => continue,
+            };
+            let Some(target) = block.position.neighbor(direction) else {
+                continue;
+            };
+            let is_blocked = block.cells_at(target).any(|cell| {
+                !cell.within(self.grid_width, self.grid_height) ||
+                cell.x == player_x ||
+                self.terrain.is_solid(cell) ||
+                self.partner_occupies(cell) ||
+                self.occupancy.blocks_at(cell).iter().any(|&j| j != i)
+            });
+            if !is_blocked {
+                self.move_block(i, target);
+            }
+        }
+    }
+
+    // Move pushed ice blocks one cell further until they hit something
+    pub fn update_sliding_blocks(&mut self) {
+        self.assert_indexed();
+        
+        for i in 0..self.blocks.len() {
+            let Some(direction) = self.blocks[i].sliding else {
+                continue;
+            };
             
-            if self.check_block_bottom_collision(i, new_y) {
+            // A block that lost its footing stops sliding and just falls
+            if self.blocks[i].falling {
+                self.blocks[i].sliding = None;
                 continue;
             }
             
-            if self.check_block_block_collision(i, x, new_y) {
-                self.blocks[i].falling = false;
+            let block = &self.blocks[i];
+            let target = match direction {
+                Direction::Left => block.position.neighbor(direction),
+                Direction::Right => block.position.neighbor(direction).filter(|_| block.right() + 1 < self.grid_width),
+            };
+            let Some(target) = target else {
+                self.blocks[i].sliding = None;
+                continue;
+            };
+            let is_blocked = block.cells_at(target).any(|cell| {
+                self.terrain.is_solid(cell) ||
+                self.player.occupies(cell) ||
+                self.partner_occupies(cell) ||
+                self.occupancy.blocks_at(cell).iter().any(|&j| j != i)
+            });
+            
+            if is_blocked {
+                self.blocks[i].sliding = None;
             } else {
-                self.blocks[i].position.1 = new_y;
+                self.move_block(i, target);
             }
         }
     }
 
-    pub fn check_block_player_collision(&mut self, x: usize, new_y: usize) -> bool {
-        let (player_x, player_y) = self.player.position;
-        if x == player_x && new_y == player_y {
-            self.game_over = true;
-            return true;
+    // Land a block on the floor, or drop it through a pit for bonus points
+    fn check_block_bottom_collision(&mut self, block_idx: usize, new_y: usize) -> Option<FallStep> {
+        let height = self.blocks[block_idx].shape.height();
+        if new_y + height <= self.grid_height {
+            return None;
         }
-        false
+        
+        let x = self.blocks[block_idx].position.x;
+        if self.block_over_pit(&self.blocks[block_idx], x) {
+            self.remove_block(block_idx);
+            self.score += PIT_BONUS;
+            return Some(FallStep::Sunk);
+        }
+        
+        self.move_block(block_idx, GridPos::new(x, self.grid_height - height));
+        self.settle_block(block_idx);
+        Some(FallStep::Stopped)
     }
 
-    pub fn check_block_bottom_collision(&mut self, block_idx: usize, new_y: usize) -> bool {
-        if new_y >= self.grid_size {
-            self.blocks[block_idx].position.1 = self.grid_size - 1;
-            self.blocks[block_idx].falling = false;
-            return true;
+    // Land a block on terrain, or slide it toward the low side of a ramp
+    pub fn check_block_terrain_collision(&mut self, block_idx: usize, x: usize, new_y: usize) -> bool {
+        let block = &self.blocks[block_idx];
+        if !block.cells_at(GridPos::new(x, new_y)).any(|cell| self.terrain.is_solid(cell)) {
+            return false;
         }
-        false
+        
+        match self.ramp_slide_target(block_idx) {
+            Some(target) => self.move_block(block_idx, target),
+            None => self.settle_block(block_idx),
+        }
+        true
     }
 
     pub fn check_block_block_collision(&self, block_idx: usize, x: usize, new_y: usize) -> bool {
-        for j in 0..self.blocks.len() {
-            if block_idx != j && !self.blocks[j].falling && 
-               self.blocks[j].position.0 == x && 
-               self.blocks[j].position.1 == new_y {
-                return true;
-            }
-        }
-        false
+        self.blocks[block_idx].cells_at(GridPos::new(x, new_y)).any(|cell| self.settled_block_at(cell, block_idx))
+    }
+
+    fn tick_storm(&mut self) {
+        let Some(storm) = &mut self.storm else {
+            return;
+        };
+        let event = match storm.tick() {
+            Some(StormChange::Warning) => GameEvent::StormWarning,
+            Some(StormChange::Started) => GameEvent::StormStarted,
+            Some(StormChange::Ended) => GameEvent::StormEnded,
+            None => return,
+        };
+        self.events.push(event);
     }
 
     pub fn handle_block_spawning(&mut self) {
+        // Nothing spawns while frozen
+        if self.powerup_active(PowerUpKind::Freeze) {
+            return;
+        }
+        
+        // Storms spawn blocks faster for a while
+        let spawn_rate = match &self.storm {
+            Some(storm) => storm.spawn_rate(self.block_spawn_rate),
+            None => self.block_spawn_rate,
+        };
+        
         self.block_spawn_counter += 1;
-        if self.block_spawn_counter >= self.block_spawn_rate {
+        if self.block_spawn_counter >= spawn_rate {
             self.spawn_block();
             self.block_spawn_counter = 0;
         }
+        
+        // Close to the next spawn its column is picked, then only counted down
+        let ticks_left = spawn_rate - self.block_spawn_counter;
+        if ticks_left <= SPAWN_WARNING_TICKS && !self.game_over {
+            let column = match self.spawn_warning {
+                Some(warning) => Some(warning.column),
+                None => self.telegraph_next_block(),
+            };
+            self.spawn_warning = column.map(|column| SpawnWarning { column, ticks_left });
+        }
     }
 
     pub fn update_player(&mut self) {
-        // Update jump counter first
-        self.player.update_jump();
-        
-        // Update fall delay counter
-        self.player.update_fall_delay();
+        self.invincible_ticks = self.invincible_ticks.saturating_sub(1);
         
-        // Check if player should start falling
-        self.player.update_falling_state(&self.blocks, self.grid_size);
+        update_character(&mut self.player, &self.blocks, self.grid_height);
         
-        // Apply gravity if player is falling
-        if self.player.is_falling {
-            self.player.apply_gravity();
+        // The idle character is still subject to gravity
+        if let Some(partner) = self.partner.as_mut() {
+            update_character(partner, &self.blocks, self.grid_height);
         }
+        self.check_player_in_pit();
+        self.sync_character_terrain();
         
-        // Check if player should land, passing blocks for collision detection
-        self.player.land(&self.blocks, self.grid_size);
+        // Falling or landing can carry the player through a pickup
+        self.collect_pickups();
     }
 
-    // Process an input action and update the game state
+    // Holding a direction repeats once per update, a new direction moves immediately
+    // Counted in updates rather than wall-clock time, so it follows tick() in tests and replays alike
+    fn can_move(&self, direction: Direction) -> bool {
+        self.last_move_direction != Some(direction) || self.stats.ticks > self.last_move_tick
+    }
+
+    /// Process an input action and update the game state
+    ///
+    /// ```
+    /// use rust_stackattack::prelude::*;
+    ///
+    /// let mut game = GameState::new(GameConfig { grid_width: 8, grid_height: 10, seed: Some(7), ..Default::default() }).unwrap();
+    /// let x = game.player.position.x;
+    ///
+    /// let result = game.process_input(InputAction::Left);
+    /// assert!(matches!(result, GameUpdateResult::Continue));
+    /// assert_eq!(game.player.position.x, x - 1);
+    /// ```
     pub fn process_input(&mut self, action: InputAction) -> GameUpdateResult {
+        self.process_input_from(action, InputDevice::default())
+    }
+
+    // Process an input, logging the device it came from
+    pub fn process_input_from(&mut self, action: InputAction, device: InputDevice) -> GameUpdateResult {
+        let can_move = match action {
+            InputAction::Left => self.can_move(Direction::Left),
+            InputAction::Right => self.can_move(Direction::Right),
+            _ => true,
+        };
+        self.apply_input(action, can_move, device)
+    }
+
+    fn apply_restart(&mut self, action: InputAction) {
+        if action == InputAction::RestartSameSeed {
+            self.restart_with_seed(self.seed);
+        } else {
+            self.restart();
+        }
+    }
+
+    // Apply an input, a held direction that isn't due to repeat yet doesn't move the player
+    pub(crate) fn apply_input(&mut self, action: InputAction, can_move: bool, device: InputDevice) -> GameUpdateResult {
         // Early exit if game is over
         if self.game_over {
-            if action == InputAction::Restart {
-                self.restart();
-                return GameUpdateResult::Restart;
-            }
-            return GameUpdateResult::GameOver;
+            return match action {
+                InputAction::Restart | InputAction::RestartSameSeed => {
+                    self.record_input(LoggedEvent::Input(action), device);
+                    self.apply_restart(action);
+                    GameUpdateResult::Restart
+                },
+                _ => GameUpdateResult::GameOver,
+            };
         }
 
+        self.record_input(if can_move { LoggedEvent::Input(action) } else { LoggedEvent::Held(action) }, device);
+        self.assert_indexed();
+
         // Process player movement
         match action {
             InputAction::Left => {
-                if self.last_move_time.elapsed() >= Duration::from_millis(self.refresh_rate_milliseconds) {
-                    self.last_move_direction = Some(-1);
-                    self.player.move_left(&mut self.blocks);
-                    self.last_move_time = Instant::now();
+                if can_move {
+                    self.last_move_direction = Some(Direction::Left);
+                    if !self.use_hammer(Direction::Left) {
+                        self.player.move_left(&mut self.blocks);
+                        self.reindex_moved_blocks(Direction::Left);
+                    }
+                    self.last_move_tick = self.stats.ticks;
                 }
             },
             InputAction::Right => {
-                if self.last_move_time.elapsed() >= Duration::from_millis(self.refresh_rate_milliseconds) {
-                    self.last_move_direction = Some(1);
-                    self.player.move_right(&mut self.blocks);
-                    self.last_move_time = Instant::now();
+                if can_move {
+                    self.last_move_direction = Some(Direction::Right);
+                    if !self.use_hammer(Direction::Right) {
+                        self.player.move_right(&mut self.blocks);
+                        self.reindex_moved_blocks(Direction::Right);
+                    }
+                    self.last_move_tick = self.stats.ticks;
                 }
             },
             InputAction::Up => {
-                self.player.jump();
+                self.player.jump(&self.blocks);
+            },
+            InputAction::JumpRelease => {
+                self.player.cut_jump();
+            },
+            InputAction::Down => {
+                self.player.toggle_crouch(&self.blocks);
+            },
+            InputAction::Drop => {
+                // The block put down goes on the end of the list, still falling
+                if self.player.drop_carried_block(&mut self.blocks) {
+                    let idx = self.blocks.len() - 1;
+                    self.occupancy.insert(idx, &self.blocks[idx]);
+                }
+            },
+            InputAction::Kick => {
+                self.kick();
             },
-            InputAction::Restart => {
-                self.restart();
+            InputAction::Restart | InputAction::RestartSameSeed => {
+                self.apply_restart(action);
                 return GameUpdateResult::Restart;
             },
+            InputAction::SwitchCharacter => {
+                self.switch_character();
+            },
             InputAction::None => {
                 // No directional input, release carried blocks
                 self.player.release_carried_blocks(&mut self.blocks, None);
@@ -261,8 +1956,18 @@ impl GameState {
         }
 
         // Release blocks if direction changed
+        // Carried blocks are still falling, letting go of them leaves the stack tops as they are
         self.player.release_carried_blocks(&mut self.blocks, self.last_move_direction);
         
+        // The idle character has to see where the player moved
+        self.sync_character_terrain();
+        
+        // Walking or jumping through a pickup collects it
+        self.collect_pickups();
+        
+        // Walking into the Foreman hurts
+        self.check_enemy_contact();
+        
         // Check for levitating blocks that might have been moved
         self.check_for_levitating_blocks();
 
@@ -278,11 +1983,7 @@ impl GameState {
 
         // Check if it's time to update based on refresh rate
         if self.last_update.elapsed() >= Duration::from_millis(self.refresh_rate_milliseconds) {
-            // Update player
-            self.update_player();
-            
-            // Update falling blocks
-            self.update_blocks();
+            self.tick();
 
             // Reset the timer
             self.last_update = Instant::now();
@@ -294,4 +1995,137 @@ impl GameState {
             GameUpdateResult::Continue
         }
     }
+
+    /// Advance the game by one update, regardless of how much time has passed
+    ///
+    /// Two games with the same seed stay in step tick for tick
+    ///
+    /// ```
+    /// use rust_stackattack::prelude::*;
+    ///
+    /// let config = GameConfig { grid_width: 8, grid_height: 10, block_spawn_rate: 3, seed: Some(7), ..Default::default() };
+    /// let mut first = GameState::new(config.clone()).unwrap();
+    /// let mut second = GameState::new(config).unwrap();
+    /// for _ in 0..50 {
+    ///     first.tick();
+    ///     second.tick();
+    /// }
+    ///
+    /// let positions = |game: &GameState| game.blocks.iter().map(|block| block.position).collect::<Vec<_>>();
+    /// assert!(!first.blocks.is_empty());
+    /// assert_eq!(positions(&first), positions(&second));
+    /// assert_eq!(first.score, second.score);
+    /// ```
+    pub fn tick(&mut self) {
+        self.record(LoggedEvent::Tick);
+        self.remember_positions();
+        self.ticks_since_clear += 1;
+        if self.objective.record_tick() {
+            self.objective_completed();
+        }
+        
+        // Update player
+        self.update_player();
+        
+        // Update falling blocks
+        self.update_blocks();
+        
+        self.update_enemy();
+        self.stats.record_tick(self.score, self.stack_height());
+        self.player.remember_position();
+        
+        if self.objective.complete() && !self.game_over {
+            self.advance_campaign();
+        }
+        if self.time_left() == Some(Duration::ZERO) && !self.game_over {
+            self.end_game(GameOverReason::TimeUp);
+        }
+    }
+
+    // Note where blocks and the Foreman start this update, so frontends can draw them moving between cells
+    fn remember_positions(&mut self) {
+        for block in &mut self.blocks {
+            block.last_position = block.position;
+        }
+        if let Some(enemy) = &mut self.enemy {
+            enemy.last_position = enemy.position;
+        }
+    }
+
+    /// Play actions on a copy of the game without touching the live one, one action per update
+    /// Ticks past the end of the actions get no input, and the copy stops advancing at game over
+    ///
+    /// ```
+    /// use rust_stackattack::prelude::*;
+    ///
+    /// let game = GameState::new(GameConfig { grid_width: 8, grid_height: 10, seed: Some(7), ..Default::default() }).unwrap();
+    /// let ahead = game.simulate(&[InputAction::Right, InputAction::Right], 2);
+    ///
+    /// assert_eq!(ahead.player.position.x, game.player.position.x + 2);
+    /// assert_eq!(ahead.stats.ticks, 2);
+    /// assert_eq!(game.stats.ticks, 0);
+    /// ```
+    pub fn simulate(&self, actions: &[InputAction], ticks: usize) -> GameState {
+        let mut copy = self.clone();
+        copy.event_log = None;
+        
+        for tick in 0..ticks {
+            if copy.game_over {
+                break;
+            }
+            
+            // Simulated updates are a full refresh apart, so held moves are never throttled
+            copy.apply_input(actions.get(tick).copied().unwrap_or(InputAction::None), true, InputDevice::Bot);
+            copy.tick();
+        }
+        copy
+    }
+
+    fn record(&mut self, event: LoggedEvent) {
+        if let Some(log) = &mut self.event_log {
+            log.record(event);
+        }
+    }
+
+    // Log an input with its device, timed by the updates played plus how far into the current one it came
+    fn record_input(&mut self, event: LoggedEvent, device: InputDevice) {
+        if let Some(log) = &mut self.event_log {
+            let refresh = self.refresh_rate_milliseconds;
+            let into_update = (self.last_update.elapsed().as_millis() as u64).min(refresh);
+            log.record_input(event, device, self.stats.ticks, self.stats.ticks * refresh + into_update);
+        }
+    }
+}
+
+// Seed for a game that wasn't given one
+#[cfg(feature = "thread-rng")]
+pub fn random_seed() -> u64 {
+    rand::random()
+}
+
+// Without an OS random source std's hash keys, which differ per process, are random enough for spawns
+#[cfg(not(feature = "thread-rng"))]
+pub fn random_seed() -> u64 {
+    use std::hash::{BuildHasher, Hasher};
+    std::collections::hash_map::RandomState::new().build_hasher().finish()
+}
+
+// Advance jumping, falling and landing of a character by one update
+fn update_character(player: &mut Player, blocks: &[Block], grid_height: usize) {
+    // Update jump counter first
+    player.update_jump(blocks);
+    
+    // Update fall delay counter
+    player.update_fall_delay();
+    
+    // Check if player should start falling
+    player.update_falling_state(blocks, grid_height);
+    
+    // Apply gravity if player is falling
+    if player.is_falling {
+        player.apply_gravity();
+    }
+    
+    // Check if player should land, passing blocks for collision detection
+    player.land(blocks, grid_height);
 }
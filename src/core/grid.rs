@@ -0,0 +1,154 @@
+// Core occupancy grid - platform-independent
+// Spatial index from grid cells to the blocks covering them, so collision and support checks
+// look up a cell instead of scanning every block
+use std::collections::HashMap;
+
+use crate::core::block::{Block, BlockId};
+use crate::core::types::GridPos;
+
+#[derive(Clone)]
+pub struct OccupancyGrid {
+    grid_width: usize,
+    grid_height: usize,
+    // Indices into GameState::blocks per cell, falling blocks can briefly share a cell
+    cells: Vec<Vec<usize>>,
+    // Number of covered cells per row, updated as blocks come and go
+    row_fill: Vec<usize>,
+    // Index of each block with an id, moved along with the block when the ones before it are removed
+    indices: HashMap<BlockId, usize>,
+}
+
+impl OccupancyGrid {
+    pub fn new(grid_width: usize, grid_height: usize) -> Self {
+        Self {
+            grid_width,
+            grid_height,
+            // Room for one block per cell up front, so blocks moving into new cells don't allocate
+            cells: (0..grid_width * grid_height).map(|_| Vec::with_capacity(1)).collect(),
+            row_fill: vec![0; grid_height],
+            indices: HashMap::new(),
+        }
+    }
+
+    fn index(&self, position: GridPos) -> Option<usize> {
+        position.within(self.grid_width, self.grid_height).then(|| position.y * self.grid_width + position.x)
+    }
+
+    // Index every block from scratch, block indices are their positions in the slice
+    pub fn rebuild(&mut self, blocks: &[Block]) {
+        for cell in &mut self.cells {
+            cell.clear();
+        }
+        self.row_fill.fill(0);
+        self.indices.clear();
+        for (idx, block) in blocks.iter().enumerate() {
+            self.insert(idx, block);
+        }
+    }
+
+    pub fn insert(&mut self, idx: usize, block: &Block) {
+        self.insert_cells(idx, block.cells());
+        if block.id.is_assigned() {
+            self.indices.insert(block.id, idx);
+        }
+    }
+
+    pub fn remove(&mut self, idx: usize, block: &Block) {
+        self.remove_cells(idx, block.cells());
+        self.remove_id(idx, block.id);
+    }
+
+    pub fn remove_id(&mut self, idx: usize, id: BlockId) {
+        if self.indices.get(&id) == Some(&idx) {
+            self.indices.remove(&id);
+        }
+    }
+
+    // Cover cells with a block, for a block that moved or changed shape without changing its index
+    pub fn insert_cells(&mut self, idx: usize, cells: impl Iterator<Item = GridPos>) {
+        for cell in cells {
+            if let Some(i) = self.index(cell) {
+                if self.cells[i].is_empty() {
+                    self.row_fill[cell.y] += 1;
+                }
+                self.cells[i].push(idx);
+            }
+        }
+    }
+
+    pub fn remove_cells(&mut self, idx: usize, cells: impl Iterator<Item = GridPos>) {
+        for cell in cells {
+            if let Some(i) = self.index(cell) {
+                let was_covered = !self.cells[i].is_empty();
+                self.cells[i].retain(|&j| j != idx);
+                if was_covered && self.cells[i].is_empty() {
+                    self.row_fill[cell.y] -= 1;
+                }
+            }
+        }
+    }
+
+    // A block moved from one index to another, after blocks before it were taken out
+    pub fn renumber(&mut self, old: usize, new: usize, block: &Block) {
+        for cell in block.cells() {
+            if let Some(i) = self.index(cell) {
+                for j in &mut self.cells[i] {
+                    if *j == old {
+                        *j = new;
+                    }
+                }
+            }
+        }
+        if block.id.is_assigned() {
+            self.indices.insert(block.id, new);
+        }
+    }
+
+    // Where the block with an id sits in the indexed blocks
+    pub fn index_of(&self, id: BlockId) -> Option<usize> {
+        self.indices.get(&id).copied()
+    }
+
+    // Whether the index describes exactly these blocks, for debug checks that nothing edited them behind its back
+    pub fn matches(&self, blocks: &[Block]) -> bool {
+        let mut covered = 0;
+        for (idx, block) in blocks.iter().enumerate() {
+            for cell in block.cells() {
+                let Some(i) = self.index(cell) else {
+                    continue;
+                };
+                if !self.cells[i].contains(&idx) {
+                    return false;
+                }
+                covered += 1;
+            }
+            if block.id.is_assigned() && self.index_of(block.id) != Some(idx) {
+                return false;
+            }
+        }
+        let indexed: usize = self.cells.iter().map(Vec::len).sum();
+        let ids = blocks.iter().filter(|block| block.id.is_assigned()).count();
+        let rows_match = (0..self.grid_height).all(|y| {
+            let filled = self.cells[y * self.grid_width..(y + 1) * self.grid_width].iter().filter(|cell| !cell.is_empty()).count();
+            filled == self.row_fill[y]
+        });
+        covered == indexed && ids == self.indices.len() && rows_match
+    }
+
+    // Blocks covering a cell, cells outside the grid are always empty
+    pub fn blocks_at(&self, position: GridPos) -> &[usize] {
+        match self.index(position) {
+            Some(i) => &self.cells[i],
+            None => &[],
+        }
+    }
+
+    pub fn is_occupied(&self, position: GridPos) -> bool {
+        !self.blocks_at(position).is_empty()
+    }
+
+    // Cells of a row covered by any block, falling or not
+    pub fn row_fill(&self, row: usize) -> usize {
+        self.row_fill.get(row).copied().unwrap_or(0)
+    }
+}
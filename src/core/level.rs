@@ -0,0 +1,307 @@
+// Core level progression - platform-independent
+// Every few cleared rows the level goes up, making blocks spawn more often and fall faster
+// Campaigns instead play a fixed sequence of levels loaded from JSON files, one level per file
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::block::{Block, BlockKind, Shape};
+use crate::core::objective::{Objective, ObjectiveKind};
+use crate::core::player::Player;
+use crate::core::terrain::Tile;
+use crate::core::types::{GridPos, PlayerTiming, Soundtrack, Theme};
+
+// Number of cleared rows needed to reach the next level
+pub const ROWS_PER_LEVEL: u32 = 5;
+// Spawn rate never drops below this many updates between blocks
+pub const MIN_SPAWN_RATE: u64 = 2;
+// Number of levels between fall speed increases
+pub const LEVELS_PER_FALL_SPEED: u32 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Level {
+    pub number: u32,
+    pub rows_cleared: u32,
+}
+
+impl Default for Level {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Level {
+    pub fn new() -> Self {
+        Self {
+            number: 1,
+            rows_cleared: 0,
+        }
+    }
+
+    // Count cleared rows, returns true if that reached a new level
+    pub fn add_cleared_rows(&mut self, rows: u32) -> bool {
+        self.rows_cleared += rows;
+        let number = self.rows_cleared / ROWS_PER_LEVEL + 1;
+        let leveled_up = number > self.number;
+        self.number = number;
+        leveled_up
+    }
+
+    pub fn rows_until_next(&self) -> u32 {
+        ROWS_PER_LEVEL - self.rows_cleared % ROWS_PER_LEVEL
+    }
+
+    // Each level spawns blocks one update sooner
+    pub fn spawn_rate(&self, base: u64) -> u64 {
+        base.saturating_sub((self.number - 1) as u64).max(MIN_SPAWN_RATE.min(base))
+    }
+
+    pub fn fall_speed(&self, base: usize) -> usize {
+        base + ((self.number - 1) / LEVELS_PER_FALL_SPEED) as usize
+    }
+
+    // Levels take turns between the kinds of objective, each round asking a bit more
+    pub fn objective(&self) -> ObjectiveKind {
+        let round = (self.number - 1) / 3;
+        match (self.number - 1) % 3 {
+            0 => ObjectiveKind::CollectCoins(2 + round),
+            1 => ObjectiveKind::SurviveTicks(150 + 50 * round as u64),
+            _ => ObjectiveKind::ClearRows(2 + round),
+        }
+    }
+}
+
+// A block placed on the board before a campaign level starts
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PlacedBlock {
+    pub x: usize,
+    pub y: usize,
+    #[serde(default = "default_shape")]
+    pub shape: Shape,
+    #[serde(default = "default_kind")]
+    pub kind: BlockKind,
+}
+
+fn default_shape() -> Shape {
+    Shape::Single
+}
+
+fn default_kind() -> BlockKind {
+    BlockKind::Crate
+}
+
+fn default_fall_speed() -> usize {
+    1
+}
+
+impl PlacedBlock {
+    pub fn to_block(self) -> Block {
+        Block {
+            falling: false,
+            kind: self.kind,
+            ..Block::with_shape(GridPos::new(self.x, self.y), self.shape)
+        }
+    }
+}
+
+// A terrain tile placed on the board of a level
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PlacedTile {
+    pub x: usize,
+    pub y: usize,
+    pub tile: Tile,
+}
+
+// One level of a campaign, as read from its file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LevelDefinition {
+    #[serde(default)]
+    pub name: String,
+    pub grid_width: usize,
+    pub grid_height: usize,
+    #[serde(default)]
+    pub blocks: Vec<PlacedBlock>,
+    #[serde(default)]
+    pub terrain: Vec<PlacedTile>,
+    #[serde(default)]
+    pub pits: Vec<usize>, // Columns with a hole in the floor
+    // Top cell of the player's body, bottom middle of the grid if not set
+    #[serde(default)]
+    pub player_start: Option<GridPos>,
+    pub block_spawn_rate: u64, // Updates between two spawns
+    #[serde(default = "default_fall_speed")]
+    pub block_fall_speed: usize,
+    pub objective: ObjectiveKind,
+    // Look and music from this level on, left as they were if not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<Theme>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub soundtrack: Option<Soundtrack>,
+}
+
+impl LevelDefinition {
+    // Empty board with a middling pace and objective, the starting point of a new level in the editor
+    pub fn empty(grid_width: usize, grid_height: usize) -> Self {
+        Self {
+            name: String::new(),
+            grid_width,
+            grid_height,
+            blocks: Vec::new(),
+            terrain: Vec::new(),
+            pits: Vec::new(),
+            player_start: None,
+            block_spawn_rate: 10,
+            block_fall_speed: 1,
+            objective: ObjectiveKind::ClearRows(2),
+            theme: None,
+            soundtrack: None,
+        }
+    }
+
+    // Where the player starts, the same spot as in endless games unless the level sets one
+    pub fn player_start(&self) -> GridPos {
+        self.player_start.unwrap_or_else(|| {
+            Player::with_timing(self.grid_width, self.grid_height, PlayerTiming::default()).position
+        })
+    }
+
+    pub fn to_json(&self) -> Result<String, LevelError> {
+        self.validate()?;
+        serde_json::to_string_pretty(self).map_err(|err| LevelError::Parse(err.to_string()))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, LevelError> {
+        let level: Self = serde_json::from_str(json).map_err(|err| LevelError::Parse(err.to_string()))?;
+        level.validate()?;
+        Ok(level)
+    }
+
+    // Check the level can be played: the board fits the player, the pre-placed blocks and terrain fit
+    // the board without overlapping each other, the spawn row or the player, and the objective can be finished
+    pub fn validate(&self) -> Result<(), LevelError> {
+        match self.problems().into_iter().next() {
+            Some(problem) => Err(LevelError::Invalid(problem.message)),
+            None => Ok(()),
+        }
+    }
+
+    // Everything that makes the level unplayable, in the order validate reports them
+    pub fn problems(&self) -> Vec<LevelProblem> {
+        let mut problems = Vec::new();
+        let mut invalid = |field: &'static str, cell: Option<GridPos>, message: String| {
+            problems.push(LevelProblem { field, cell, message });
+        };
+        if self.grid_width < 2 || self.grid_height < 3 {
+            invalid("grid_width", None, format!("grid must be at least 2x3, got {}x{}", self.grid_width, self.grid_height));
+            return problems;
+        }
+        if self.block_spawn_rate == 0 {
+            invalid("block_spawn_rate", None, "block_spawn_rate must be at least 1 update".to_string());
+        }
+        if self.block_fall_speed == 0 {
+            invalid("block_fall_speed", None, "block_fall_speed must be at least 1".to_string());
+        }
+        if Objective::new(self.objective).target() == 0 {
+            invalid("objective", None, format!("objective \"{}\" has nothing to do", self.objective));
+        }
+
+        for &x in self.pits.iter().filter(|&&x| x >= self.grid_width) {
+            invalid("pits", None, format!("pit in column {} is outside the grid", x));
+        }
+        let GridPos { x: start_x, y: start_y } = self.player_start();
+        if start_x >= self.grid_width || start_y + 1 >= self.grid_height {
+            invalid("player_start", None, format!("player start ({}, {}) is outside the grid", start_x, start_y));
+            return problems;
+        }
+
+        let mut covered = vec![false; self.grid_width * self.grid_height];
+        let block_cells = self.blocks.iter().flat_map(|placed| {
+            placed.to_block().cells().map(|cell| (cell, ("block", placed.x, placed.y))).collect::<Vec<_>>()
+        });
+        let tile_cells = self.terrain.iter()
+            .filter(|placed| placed.tile != Tile::Empty)
+            .map(|placed| (GridPos::new(placed.x, placed.y), ("tile", placed.x, placed.y)));
+        let mut reported = Vec::new();
+        for (GridPos { x, y }, (what, at_x, at_y)) in block_cells.chain(tile_cells) {
+            // One problem per block or tile, the first of its cells to have one
+            if reported.contains(&(what, at_x, at_y)) {
+                continue;
+            }
+            let field = if what == "block" { "blocks" } else { "terrain" };
+            let problem = if x >= self.grid_width || y >= self.grid_height {
+                Some("reaches outside the grid")
+            } else if y == 0 {
+                Some("covers the spawn row")
+            } else if x == start_x && (start_y..=start_y + 1).contains(&y) {
+                Some("covers the player's start")
+            } else if covered[y * self.grid_width + x] {
+                Some("overlaps another block or tile")
+            } else {
+                covered[y * self.grid_width + x] = true;
+                None
+            };
+            if let Some(problem) = problem {
+                invalid(field, Some(GridPos::new(at_x, at_y)), format!("{} at ({}, {}) {}", what, at_x, at_y, problem));
+                reported.push((what, at_x, at_y));
+            }
+        }
+        problems
+    }
+}
+
+// Something that makes a level unplayable, with the field of the file it is about
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevelProblem {
+    pub field: &'static str,
+    pub cell: Option<GridPos>, // Where the block or tile at fault is placed
+    pub message: String,
+}
+
+#[derive(Debug)]
+pub enum LevelError {
+    Io(PathBuf, io::Error),
+    Parse(String),   // Not a level file
+    Invalid(String), // A level file, but not a playable one
+    InFile(PathBuf, Box<LevelError>),
+    NoLevels(PathBuf),
+}
+
+impl fmt::Display for LevelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LevelError::Io(path, err) => write!(f, "can't read {}: {}", path.display(), err),
+            LevelError::Parse(message) => write!(f, "malformed level: {}", message),
+            LevelError::Invalid(message) => write!(f, "invalid level: {}", message),
+            LevelError::InFile(path, err) => write!(f, "{}: {}", path.display(), err),
+            LevelError::NoLevels(path) => write!(f, "no level files in {}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for LevelError {}
+
+pub fn load_level(path: &Path) -> Result<LevelDefinition, LevelError> {
+    let json = fs::read_to_string(path).map_err(|err| LevelError::Io(path.to_path_buf(), err))?;
+    LevelDefinition::from_json(&json).map_err(|err| LevelError::InFile(path.to_path_buf(), Box::new(err)))
+}
+
+// Load a campaign from a single level file or a directory of them, played in file name order
+pub fn load_campaign(path: &Path) -> Result<Vec<LevelDefinition>, LevelError> {
+    if !path.is_dir() {
+        return Ok(vec![load_level(path)?]);
+    }
+
+    let entries = fs::read_dir(path).map_err(|err| LevelError::Io(path.to_path_buf(), err))?;
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|file| file.extension().is_some_and(|extension| extension == "json"))
+        .collect();
+    files.sort();
+    if files.is_empty() {
+        return Err(LevelError::NoLevels(path.to_path_buf()));
+    }
+    files.iter().map(|file| load_level(file)).collect()
+}
@@ -0,0 +1,193 @@
+// Level file checks for the stackattack-validate tool - platform-independent
+// Reports every problem in a file with the line it is on, and warns about levels that load fine
+// but likely can't be played the way their author meant
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::core::level::{LevelDefinition, LevelProblem};
+use crate::core::objective::ObjectiveKind;
+use crate::core::terrain::Tile;
+use crate::core::types::GridPos;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,   // The game refuses to load the file
+    Warning, // The file loads, but something about the level looks wrong
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub severity: Severity,
+    pub line: Option<usize>, // Line of the file it is about, counted from 1
+    pub message: String,
+}
+
+impl Finding {
+    fn error(line: Option<usize>, message: String) -> Self {
+        Self { severity: Severity::Error, line, message }
+    }
+
+    fn warning(line: Option<usize>, message: String) -> Self {
+        Self { severity: Severity::Warning, line, message }
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        write!(f, "{}: {}", severity, self.message)
+    }
+}
+
+// Check the text of one level file
+pub fn check_level_json(json: &str) -> Vec<Finding> {
+    let level: LevelDefinition = match serde_json::from_str(json) {
+        Ok(level) => level,
+        Err(err) => {
+            // The line goes with the finding, not at the end of the message
+            let message = err.to_string();
+            let message = message.rfind(" at line ").map_or(message.as_str(), |end| &message[..end]);
+            return vec![Finding::error((err.line() > 0).then_some(err.line()), message.to_string())];
+        },
+    };
+
+    let problems = level.problems();
+    if !problems.is_empty() {
+        return problems.iter().map(|problem| Finding::error(locate(json, problem), problem.message.clone())).collect();
+    }
+    warnings(&level, json)
+}
+
+// Check a level file, or every level file of a pack directory in the order a campaign plays them
+pub fn check_path(path: &Path) -> Vec<(PathBuf, Vec<Finding>)> {
+    if !path.is_dir() {
+        let findings = match fs::read_to_string(path) {
+            Ok(json) => check_level_json(&json),
+            Err(err) => vec![Finding::error(None, format!("can't read the file: {}", err))],
+        };
+        return vec![(path.to_path_buf(), findings)];
+    }
+
+    let mut files: Vec<PathBuf> = match fs::read_dir(path) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|file| file.extension().is_some_and(|extension| extension == "json"))
+            .collect(),
+        Err(err) => return vec![(path.to_path_buf(), vec![Finding::error(None, format!("can't read the directory: {}", err))])],
+    };
+    files.sort();
+    if files.is_empty() {
+        return vec![(path.to_path_buf(), vec![Finding::error(None, "no level files in the pack".to_string())])];
+    }
+
+    // Levels sharing a name can't be told apart on the level start notice
+    let mut names: Vec<(String, PathBuf)> = Vec::new();
+    let mut results = Vec::new();
+    for file in files {
+        let mut findings = check_path(&file).remove(0).1;
+        if let Some(level) = fs::read_to_string(&file).ok().and_then(|json| serde_json::from_str::<LevelDefinition>(&json).ok()) {
+            let name = level.name.trim().to_string();
+            if let Some((_, first)) = names.iter().find(|(seen, _)| !name.is_empty() && *seen == name) {
+                let file_name = first.file_name().unwrap_or_default().to_string_lossy();
+                findings.push(Finding::warning(None, format!("level name \"{}\" is already used by {}", name, file_name)));
+            }
+            names.push((name, file.clone()));
+        }
+        results.push((file, findings));
+    }
+    results
+}
+
+// Heuristics for levels that are valid but probably not what was meant
+fn warnings(level: &LevelDefinition, json: &str) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let (width, height) = (level.grid_width, level.grid_height);
+    let mut filled = vec![false; width * height];
+    for GridPos { x, y } in level.blocks.iter().flat_map(|placed| placed.to_block().cells().collect::<Vec<_>>()) {
+        filled[y * width + x] = true;
+    }
+    for placed in level.terrain.iter().filter(|placed| placed.tile != Tile::Empty) {
+        filled[placed.y * width + placed.x] = true;
+    }
+    let solid_tile = |cell: GridPos| level.terrain.iter().any(|placed| GridPos::new(placed.x, placed.y) == cell && placed.tile != Tile::Empty);
+
+    for placed in &level.blocks {
+        let block = placed.to_block();
+        let own = |cell: GridPos| block.occupies(cell);
+        let supported = block.bottom() + 1 == height ||
+            block.cells().any(|cell| !own(cell.below()) && filled[(cell.y + 1) * width + cell.x]);
+        let line = line_of_cell(json, "blocks", GridPos::new(placed.x, placed.y));
+        if !supported {
+            findings.push(Finding::warning(line, format!("block at ({}, {}) has nothing under it and falls as the level starts", placed.x, placed.y)));
+        } else if block.bottom() + 1 == height && block.cells().all(|cell| level.pits.contains(&cell.x)) {
+            findings.push(Finding::warning(line, format!("block at ({}, {}) stands over a pit and sinks as the level starts", placed.x, placed.y)));
+        }
+    }
+
+    for y in 1..height {
+        if (0..width).all(|x| filled[y * width + x]) {
+            findings.push(Finding::warning(line_of_key(json, "blocks"), format!("row {} is full before the level starts and clears right away", y)));
+        }
+    }
+
+    match level.objective {
+        ObjectiveKind::ClearRows(_) => {
+            // Blocks dropped into a pit sink, so its column never fills unless terrain catches them
+            for &x in &level.pits {
+                if !(0..height).any(|y| solid_tile(GridPos::new(x, y))) {
+                    findings.push(Finding::warning(line_of_key(json, "pits"), format!("column {} is an open pit, no row can be completed to clear", x)));
+                }
+            }
+        },
+        ObjectiveKind::SurviveTicks(ticks) if ticks < level.block_spawn_rate => {
+            findings.push(Finding::warning(line_of_key(json, "objective"), format!("the objective is done after {} updates, before the first block spawns", ticks)));
+        },
+        _ => {},
+    }
+
+    // A player walled in on both sides with a ceiling can't do anything
+    let GridPos { x: start_x, y: start_y } = level.player_start();
+    let blocked = |x: Option<usize>| x.is_none_or(|x| x >= width || (start_y..start_y + 2).all(|y| solid_tile(GridPos::new(x, y))));
+    let ceiling = start_y == 0 || solid_tile(GridPos::new(start_x, start_y - 1));
+    if blocked(start_x.checked_sub(1)) && blocked(Some(start_x + 1)) && ceiling {
+        let line = line_of_key(json, "player_start").or_else(|| line_of_key(json, "terrain"));
+        findings.push(Finding::warning(line, "the player starts walled in with no way out".to_string()));
+    }
+    findings
+}
+
+// Line a validation problem is about: the block or tile at fault, or else its field
+fn locate(json: &str, problem: &LevelProblem) -> Option<usize> {
+    problem.cell
+        .and_then(|cell| line_of_cell(json, problem.field, cell))
+        .or_else(|| line_of_key(json, problem.field))
+}
+
+// Line of the first "key": in the file
+fn line_of_key(json: &str, key: &str) -> Option<usize> {
+    let quoted = format!("\"{}\"", key);
+    json.lines().position(|line| line.contains(&quoted)).map(|index| index + 1)
+}
+
+// Line of the entry placed at a cell, within the list under the given key
+fn line_of_cell(json: &str, key: &str, GridPos { x, y }: GridPos) -> Option<usize> {
+    let start = line_of_key(json, key)? - 1;
+    let lines: Vec<String> = json.lines().map(|line| line.split_whitespace().collect()).collect();
+    (start..lines.len()).find(|&index| {
+        // Pretty-printed entries spread x and y over a few lines
+        has_number(&lines[index], "x", x) &&
+            lines[index..lines.len().min(index + 4)].iter().any(|line| has_number(line, "y", y))
+    }).map(|index| index + 1)
+}
+
+// Whether a line with the spaces taken out has "key":number, and not just a number starting with it
+fn has_number(line: &str, key: &str, number: usize) -> bool {
+    let pattern = format!("\"{}\":{}", key, number);
+    line.match_indices(&pattern).any(|(at, _)| {
+        !line[at + pattern.len()..].starts_with(|c: char| c.is_ascii_digit())
+    })
+}
@@ -1,7 +1,26 @@
 // Core module - platform-independent game logic
 
 // Export core modules
+pub mod ai;
 pub mod block;
+pub mod builder;
+pub mod build_info;
+pub mod cue;
+pub mod editor;
+pub mod enemy;
+pub mod event;
+pub mod event_log;
 pub mod player;
 pub mod game;
+pub mod grid;
+pub mod level;
+pub mod level_check;
+pub mod objective;
+pub mod pickup;
+pub mod powerup;
+pub mod scores;
+pub mod stats;
+pub mod storm;
+pub mod terrain;
+pub mod tutorial;
 pub mod types;
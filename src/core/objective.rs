@@ -0,0 +1,73 @@
+// Core objectives - platform-independent
+// Every level comes with a small goal on the side, progress is tracked by GameState and completion is
+// announced with an event
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ObjectiveKind {
+    ClearRows(u32),
+    SurviveTicks(u64),
+    CollectCoins(u32),
+}
+
+impl fmt::Display for ObjectiveKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ObjectiveKind::ClearRows(rows) => write!(f, "Clear {} rows", rows),
+            ObjectiveKind::SurviveTicks(ticks) => write!(f, "Survive {} updates", ticks),
+            ObjectiveKind::CollectCoins(coins) => write!(f, "Collect {} coins", coins),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Objective {
+    pub kind: ObjectiveKind,
+    pub progress: u64,
+}
+
+impl Objective {
+    pub fn new(kind: ObjectiveKind) -> Self {
+        Self { kind, progress: 0 }
+    }
+
+    pub fn target(&self) -> u64 {
+        match self.kind {
+            ObjectiveKind::ClearRows(rows) => rows as u64,
+            ObjectiveKind::SurviveTicks(ticks) => ticks,
+            ObjectiveKind::CollectCoins(coins) => coins as u64,
+        }
+    }
+
+    pub fn complete(&self) -> bool {
+        self.progress >= self.target()
+    }
+
+    // Count progress toward the goal, returns true if this completed it
+    fn advance(&mut self, amount: u64) -> bool {
+        if self.complete() {
+            return false;
+        }
+        self.progress = (self.progress + amount).min(self.target());
+        self.complete()
+    }
+
+    pub fn record_rows(&mut self, rows: u32) -> bool {
+        matches!(self.kind, ObjectiveKind::ClearRows(_)) && self.advance(rows as u64)
+    }
+
+    pub fn record_tick(&mut self) -> bool {
+        matches!(self.kind, ObjectiveKind::SurviveTicks(_)) && self.advance(1)
+    }
+
+    pub fn record_coins(&mut self, coins: u32) -> bool {
+        matches!(self.kind, ObjectiveKind::CollectCoins(_)) && self.advance(coins as u64)
+    }
+
+    // Goal and progress for the HUD, e.g. "Clear 3 rows 1/3"
+    pub fn progress_text(&self) -> String {
+        format!("{} {}/{}", self.kind, self.progress, self.target())
+    }
+}
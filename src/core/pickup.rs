@@ -0,0 +1,37 @@
+// Core pickup implementation - platform-independent
+use crate::core::powerup::PowerUpKind;
+use crate::core::types::GridPos;
+
+// Number of updates between attempts to spawn a pickup
+pub const PICKUP_SPAWN_INTERVAL: u64 = 25;
+// Maximum number of pickups lying on the board at once
+pub const MAX_PICKUPS: usize = 3;
+// Bonus score for collecting a coin
+pub const COIN_VALUE: u32 = 1;
+
+// A collectible lying in an empty cell, picked up when the player walks through it
+#[derive(Clone)]
+pub struct Pickup {
+    pub position: GridPos,
+    pub value: u32,
+    pub powerup: Option<PowerUpKind>,
+}
+
+impl Pickup {
+    pub fn coin(position: GridPos) -> Self {
+        Self {
+            position,
+            value: COIN_VALUE,
+            powerup: None,
+        }
+    }
+
+    // Power-ups grant their effect instead of score
+    pub fn powerup(position: GridPos, kind: PowerUpKind) -> Self {
+        Self {
+            position,
+            value: 0,
+            powerup: Some(kind),
+        }
+    }
+}
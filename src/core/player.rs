@@ -1,95 +1,274 @@
 // Core player implementation - platform-independent
-use crate::core::block::Block;
-use crate::core::types::Position;
-use crate::core::types::Direction;
-
-// Add a constant for fall delay duration
-const FALL_DELAY: u8 = 3; // Number of update cycles to wait before falling
+use crate::core::block::{Block, BlockKind, Shape};
+use crate::core::terrain::{Terrain, Tile};
+use crate::core::types::{Direction, GridPos, PlayerTiming};
 
+#[derive(Clone)]
 pub struct Player {
-    pub position: Position,
+    pub position: GridPos,
+    pub previous_position: GridPos, // Where the player was at the end of the last update
     pub in_air: bool,  // Track jump state
     pub is_falling: bool, // Track if player is falling due to gravity
     jump_counter: u8,  // Track how long to stay in the air
+    rise_remaining: u8, // Cells the current jump can still rise
+    pub jump_height: u8, // Cells a full jump rises, one per update
     just_jumped: bool, // Flag to prevent immediate landing
     pub body_size: usize, // Store the player's vertical size
+    pub crouching: bool, // Ducked down to one cell, the feet stay where they were
     fall_delay_counter: u8, // Counter for delaying fall
-    grid_size: usize, // Store the grid size for consistent boundary checks
+    gravity_counter: u8, // Updates spent falling since the last cell
+    pub timing: PlayerTiming, // Fall delay, jump duration and gravity speed
+    pub carrying: Option<Block>, // Block riding on the player's head
+    pub facing: Direction, // Side the player last moved toward, carried blocks are put down there
+    pub auto_climb: bool, // Step up onto single blocks instead of pushing them
+    grid_width: usize, // Store the grid dimensions for consistent boundary checks
+    grid_height: usize,
+    terrain: Terrain, // Store the level geometry for ramp and support checks
+    pushed: Vec<usize>, // Reused by every push, so walking into blocks doesn't allocate
+    moved: Vec<usize>, // Blocks the last sideways move shifted a cell along, with room for a few up front
 }
 
 impl Player {
-    pub fn new(grid_size: usize) -> Self {
+    pub fn new(grid_width: usize, grid_height: usize) -> Self {
+        Self::with_timing(grid_width, grid_height, PlayerTiming::default())
+    }
+
+    pub fn with_timing(grid_width: usize, grid_height: usize, timing: PlayerTiming) -> Self {
         let body_height = 2; // Store body size as a variable
         
         // Calculate starting x position (middle of grid)
-        // If even grid size, place a bit to the left of center
-        let start_x = if grid_size % 2 == 0 {
-            grid_size / 2 - 1 // Even grid size, place left of center
+        // If even grid width, place a bit to the left of center
+        let start_x = if grid_width.is_multiple_of(2) {
+            grid_width / 2 - 1 // Even grid width, place left of center
         } else {
-            grid_size / 2     // Odd grid size, place at center
+            grid_width / 2     // Odd grid width, place at center
         };
         
         Self {
-            position: (start_x, grid_size - body_height), // Start at bottom middle
+            position: GridPos::new(start_x, grid_height - body_height), // Start at bottom middle
+            previous_position: GridPos::new(start_x, grid_height - body_height),
             in_air: false,
             is_falling: false,
             jump_counter: 0,
+            rise_remaining: 0,
+            jump_height: 1,
             just_jumped: false,
             body_size: body_height,
+            crouching: false,
             fall_delay_counter: 0,
-            grid_size,
+            gravity_counter: 0,
+            timing,
+            carrying: None,
+            facing: Direction::Right,
+            auto_climb: false,
+            grid_width,
+            grid_height,
+            terrain: Terrain::new(grid_width, grid_height),
+            pushed: Vec::new(),
+            moved: Vec::with_capacity(8),
+        }
+    }
+
+    pub fn with_auto_climb(mut self, auto_climb: bool) -> Self {
+        self.auto_climb = auto_climb;
+        self
+    }
+
+    pub fn with_jump_height(mut self, jump_height: u8) -> Self {
+        self.jump_height = jump_height.max(1);
+        self
+    }
+
+    // Updates left before the player drops off a ledge, moving is locked until then (0 = no fall pending)
+    pub fn fall_delay_remaining(&self) -> u8 {
+        self.fall_delay_counter
+    }
+
+    // Replace the level geometry the player walks on
+    pub fn set_terrain(&mut self, terrain: Terrain) {
+        self.terrain = terrain;
+    }
+
+    // Copy the level geometry into the terrain already held, without allocating a new one
+    pub fn copy_terrain(&mut self, terrain: &Terrain) {
+        self.terrain.clone_from(terrain);
+    }
+
+    // Treat another character's body, and the block it carries, as walls
+    pub fn wall_off(&mut self, other: &Player) {
+        let GridPos { x, y } = other.position;
+        for row in y..y + other.body_size {
+            self.terrain.set_tile(GridPos::new(x, row), Tile::Wall);
+        }
+        if let Some(cell) = other.carried_cell() {
+            self.terrain.set_tile(cell, Tile::Wall);
         }
     }
     
-    // Add jump method
-    pub fn jump(&mut self) {
-        if !self.in_air && !self.is_falling && self.position.1 > 0 {
-            self.position.1 -= 1;  // Move up one block
+    // Jump up one cell, unless a block or terrain right above the head (or the carried block) is in the way
+    pub fn jump(&mut self, blocks: &[Block]) {
+        if !self.in_air && !self.is_falling && self.has_jump_room(blocks) {
+            self.position.y -= 1;  // Move up one block
+            self.rise_remaining = self.jump_height - 1; // Higher jumps keep rising on the next updates
             self.in_air = true;
-            self.jump_counter = 1;  // Stay in air for 1 update cycle
+            self.jump_counter = self.timing.jump_duration;  // Stay in air for the configured updates
             self.just_jumped = true; // Set flag to prevent immediate landing
         }
     }
     
+    // Duck to one cell high on the ground, or stand back up once there is room above the head
+    pub fn toggle_crouch(&mut self, blocks: &[Block]) {
+        if self.crouching {
+            self.stand_up(blocks);
+        } else if !self.in_air && !self.is_falling {
+            self.position.y += self.body_size - 1;
+            self.body_size = 1;
+            self.crouching = true;
+        }
+    }
+    
+    // Get back to full height, false while something overhead leaves no room
+    pub fn stand_up(&mut self, blocks: &[Block]) -> bool {
+        if !self.crouching || !self.has_jump_room(blocks) {
+            return false;
+        }
+        self.position.y -= 1;
+        self.body_size = 2;
+        self.crouching = false;
+        true
+    }
+    
+    // Stop rising, e.g. when Up is let go early, the hang time at the top still follows
+    pub fn cut_jump(&mut self) {
+        self.rise_remaining = 0;
+    }
+    
     // Method to update jump counter
-    pub fn update_jump(&mut self) {
+    pub fn update_jump(&mut self, blocks: &[Block]) {
         if self.just_jumped {
             // Reset the just_jumped flag, but don't decrement counter yet
             self.just_jumped = false;
+        } else if self.in_air && self.rise_remaining > 0 {
+            // Rise one more cell, a block or terrain overhead ends the rise early
+            if self.has_jump_room(blocks) {
+                self.position.y -= 1;
+                self.rise_remaining -= 1;
+            } else {
+                self.rise_remaining = 0;
+            }
         } else if self.in_air && self.jump_counter > 0 {
             // Only decrement counter in subsequent updates
             self.jump_counter -= 1;
         }
     }
     
+    // Check if a cell is covered by the player's body or the block on its head
+    pub fn occupies(&self, cell: GridPos) -> bool {
+        let on_body = cell.x == self.position.x && cell.y >= self.position.y && cell.y < self.position.y + self.body_size;
+        on_body || self.carried_cell() == Some(cell)
+    }
+    
+    // Cells of the body from the head down, without the carried block
+    pub fn body_cells(&self) -> impl Iterator<Item = GridPos> {
+        let GridPos { x, y } = self.position;
+        (y..y + self.body_size).map(move |y| GridPos::new(x, y))
+    }
+    
+    // Remember the current position, called once at the end of every update
+    pub fn remember_position(&mut self) {
+        self.previous_position = self.position;
+    }
+
+    // A cell the body covered at the end of the last update but has since moved out of
+    pub fn vacated(&self, cell: GridPos) -> bool {
+        let GridPos { x, y } = self.previous_position;
+        cell.x == x && cell.y >= y && cell.y < y + self.body_size && !self.occupies(cell)
+    }
+    
+    // Cell just above the head taken by the carried block
+    pub fn carried_cell(&self) -> Option<GridPos> {
+        self.carrying.as_ref().and_then(|_| self.position.above())
+    }
+    
+    // Only single crates and ice are light enough to catch, and only with room above the head
+    pub fn can_catch(&self, block: &Block) -> bool {
+        self.carrying.is_none() && self.position.y > 0 && block.shape == Shape::Single &&
+            matches!(block.kind, BlockKind::Crate | BlockKind::Ice)
+    }
+    
+    // Take a block onto the head, it stays there until dropped or the player is hit
+    pub fn catch(&mut self, mut block: Block) {
+        block.position = self.position.above().expect("can_catch needs room above the head");
+        block.falling = false;
+        block.sliding = None;
+        block.carried = false;
+        block.carrying_direction = None;
+        block.touched = true;
+        self.carrying = Some(block);
+    }
+    
+    // Put the carried block down beside the head, facing side first, where it starts falling
+    pub fn drop_carried_block(&mut self, blocks: &mut Vec<Block>) -> bool {
+        let Some(cell) = self.carried_cell() else {
+            return false;
+        };
+        
+        for side in [self.facing, self.facing.opposite()] {
+            let Some(target) = cell.neighbor(side).filter(|target| target.x < self.grid_width) else {
+                continue;
+            };
+            if self.terrain.is_solid(target) || blocks.iter().any(|block| block.occupies(target)) {
+                continue;
+            }
+            
+            let mut block = self.carrying.take().expect("carried cell implies a carried block");
+            block.last_position = cell;
+            block.position = target;
+            block.falling = true;
+            blocks.push(block);
+            return true;
+        }
+        false
+    }
+    
+    // Jumping needs the cell above the head free, or above the carried block when there is one
+    pub fn has_jump_room(&self, blocks: &[Block]) -> bool {
+        let Some(above) = self.carried_cell().unwrap_or(self.position).above() else {
+            return false;
+        };
+        !self.terrain.is_solid(above) && !blocks.iter().any(|block| block.occupies(above))
+    }
+    
     // Check if there's ground or a block beneath the player
-    pub fn has_support(&self, blocks: &[Block], grid_size: usize) -> bool {
-        // Check if player is at the bottom of the grid
-        if self.position.1 >= grid_size - self.body_size {
+    pub fn has_support(&self, blocks: &[Block], grid_height: usize) -> bool {
+        // Check if player is at the bottom of the grid, where only a pit gives way
+        if self.position.y >= grid_height - self.body_size {
+            return !self.terrain.is_pit(self.position.x);
+        }
+        
+        // Check if there's solid terrain (e.g. a ramp) directly beneath the player
+        if self.terrain.is_solid(GridPos::new(self.position.x, self.position.y + self.body_size)) {
             return true;
         }
         
         // Check if there's a block directly beneath the player
-        blocks.iter().any(|block| {
-            !block.falling && 
-            block.position.0 == self.position.0 && 
-            block.position.1 == self.position.1 + self.body_size
-        })
+        let below_feet = GridPos::new(self.position.x, self.position.y + self.body_size);
+        blocks.iter().any(|block| !block.falling && block.occupies(below_feet))
     }
     
     // Update player's falling state
-    pub fn update_falling_state(&mut self, blocks: &[Block], grid_size: usize) {
+    pub fn update_falling_state(&mut self, blocks: &[Block], grid_height: usize) {
         // Don't check for falling during active jumps
         if self.in_air {
             return;
         }
         
         // Check if there's no support beneath the player
-        if !self.has_support(blocks, grid_size) {
+        if !self.has_support(blocks, grid_height) {
             // If we're not already falling and not already delaying a fall
             if !self.is_falling && self.fall_delay_counter == 0 {
                 // Start the fall delay
-                self.fall_delay_counter = FALL_DELAY;
+                self.fall_delay_counter = self.timing.fall_delay;
             }
             // Note: We don't set is_falling=true here anymore, that happens in update_fall_delay
         } else {
@@ -102,8 +281,13 @@ impl Player {
     // Apply gravity to make player fall
     pub fn apply_gravity(&mut self) {
         // Only apply gravity if player is falling AND not already at the bottom boundary
-        if self.is_falling && self.position.1 < self.grid_size - self.body_size {
-            self.position.1 += 1;  // Move down one block
+        if self.is_falling && self.position.y < self.grid_height - self.body_size {
+            // Slower gravity waits several updates per cell
+            self.gravity_counter += 1;
+            if self.gravity_counter >= self.timing.gravity_interval {
+                self.gravity_counter = 0;
+                self.position.y += 1;  // Move down one block
+            }
         }
     }
     
@@ -120,10 +304,10 @@ impl Player {
     }
     
     // Modify land method to check for blocks below
-    pub fn land(&mut self, blocks: &[Block], grid_size: usize) {
+    pub fn land(&mut self, blocks: &[Block], grid_height: usize) {
         // Handle landing after a jump
         if self.in_air && self.jump_counter == 0 && !self.just_jumped {
-            let has_support = self.has_support(blocks, grid_size);
+            let has_support = self.has_support(blocks, grid_height);
             
             if !has_support {
                 // If there's no support after jumping, start falling
@@ -137,59 +321,136 @@ impl Player {
         }
         
         // Handle landing after falling due to gravity
-        if self.is_falling && self.has_support(blocks, grid_size) {
+        if self.is_falling && self.has_support(blocks, grid_height) {
             self.is_falling = false;
         }
+        
+        if !self.is_falling {
+            self.gravity_counter = 0;
+        }
     }
     
     // Private helper method to handle horizontal movement - refactored for clarity
-    fn move_horizontal(&mut self, move_by: isize, grid_size: usize, blocks: &mut [Block]) {
+    fn move_horizontal(&mut self, direction: Direction, grid_width: usize, blocks: &mut [Block]) {
+        self.moved.clear();
+        
         // Don't allow movement if player is about to fall (fall delay is active)
         if self.fall_delay_counter > 0 {
             return;
         }
+        self.facing = direction;
 
         // Check if movement is possible based on grid boundaries
-        if !self.can_move_in_direction(move_by, grid_size) {
+        let Some(target_x) = self.position.neighbor(direction).map(|target| target.x).filter(|&x| x < grid_width) else {
+            return;
+        };
+        
+        // A carried block can't be pushed through whatever is beside it
+        if self.carrying.is_some() && !self.carried_block_fits(target_x, self.position.y, blocks) {
             return;
         }
         
-        let target_x = (self.position.0 as isize + move_by) as usize;
+        // Terrain can't be pushed, but ramps can be walked up from their low side
+        if self.body_hits_terrain(target_x, self.position.y) {
+            self.try_ascend_ramp(direction, target_x, blocks);
+            self.check_support_after_move(blocks);
+            return;
+        }
+        
+        // Walking into a single settled block steps up onto it
+        if self.auto_climb && self.try_climb(target_x, blocks) {
+            self.check_support_after_move(blocks);
+            return;
+        }
         
         // Check for collision with any part of the player's body
         if let Some(block_idx) = self.find_blocking_block(target_x, blocks) {
-            self.handle_block_collision(block_idx, move_by, target_x, grid_size, blocks);
+            self.handle_block_collision(block_idx, direction, target_x, grid_width, blocks);
+        } else if self.can_descend_ramp(direction, target_x, blocks) {
+            // Walking off the low side of a ramp steps down one row
+            self.position = GridPos::new(target_x, self.position.y + 1);
         } else {
             // No block, move freely
-            self.position.0 = target_x;
+            self.position.x = target_x;
         }
         
         // Check for support after moving horizontally
-        self.check_support_after_move(grid_size, blocks);
+        self.check_support_after_move(blocks);
     }
     
-    // New method to check support after horizontal movement
-    fn check_support_after_move(&mut self, grid_size: usize, blocks: &[Block]) {
-        if !self.in_air && !self.is_falling && !self.has_support(blocks, grid_size) {
-            // Start the fall delay instead of immediately falling
-            self.fall_delay_counter = FALL_DELAY;
+    // Check if any cell of the player's body would overlap solid terrain
+    fn body_hits_terrain(&self, x: usize, top_y: usize) -> bool {
+        (0..self.body_size).any(|body_part| self.terrain.is_solid(GridPos::new(x, top_y + body_part)))
+    }
+    
+    // Check if the player's body fits into a column without hitting blocks or terrain
+    fn can_occupy(&self, x: usize, top_y: usize, blocks: &[Block]) -> bool {
+        !self.body_hits_terrain(x, top_y) &&
+        !blocks.iter().any(|block| {
+            (top_y..top_y + self.body_size).any(|y| block.occupies(GridPos::new(x, y)))
+        }) &&
+        (self.carrying.is_none() || self.carried_block_fits(x, top_y, blocks))
+    }
+    
+    // Check if the carried block fits above a head at the given spot
+    fn carried_block_fits(&self, x: usize, top_y: usize, blocks: &[Block]) -> bool {
+        let Some(cell) = GridPos::new(x, top_y).above() else {
+            return false;
+        };
+        !self.terrain.is_solid(cell) && !blocks.iter().any(|block| block.occupies(cell))
+    }
+    
+    // Step onto a ramp that rises in the direction of movement
+    fn try_ascend_ramp(&mut self, direction: Direction, target_x: usize, blocks: &[Block]) {
+        let feet_y = self.position.y + self.body_size - 1;
+        
+        // Only the feet may touch the ramp, and only from its low side
+        if self.terrain.ramp_at(GridPos::new(target_x, feet_y)) != Some(direction) {
+            return;
+        }
+        
+        if self.position.y > 0 && self.can_occupy(target_x, self.position.y - 1, blocks) {
+            self.position = GridPos::new(target_x, self.position.y - 1);
         }
     }
     
-    // Check if movement in a direction is possible based on grid boundaries
-    fn can_move_in_direction(&self, move_by: isize, grid_size: usize) -> bool {
-        if move_by < 0 {
-            self.position.0 > 0
-        } else {
-            self.position.0 < grid_size - 1
+    // Step onto a settled block at feet level, if the body fits on top of it
+    // A block with another one on top is a wall two high and can't be climbed
+    fn try_climb(&mut self, target_x: usize, blocks: &[Block]) -> bool {
+        let feet_y = self.position.y + self.body_size - 1;
+        let is_step = blocks.iter().any(|block| !block.falling && block.occupies(GridPos::new(target_x, feet_y)));
+        
+        if !is_step || self.position.y == 0 || !self.can_occupy(target_x, self.position.y - 1, blocks) {
+            return false;
+        }
+        self.position = GridPos::new(target_x, self.position.y - 1);
+        true
+    }
+    
+    // Check if the player is standing on a ramp and walking toward its low side
+    fn can_descend_ramp(&self, direction: Direction, target_x: usize, blocks: &[Block]) -> bool {
+        let below_feet = self.position.y + self.body_size;
+        
+        if self.terrain.ramp_at(GridPos::new(self.position.x, below_feet)) != Some(direction.opposite()) {
+            return false;
+        }
+        
+        below_feet < self.grid_height && self.can_occupy(target_x, self.position.y + 1, blocks)
+    }
+    
+    // New method to check support after horizontal movement
+    fn check_support_after_move(&mut self, blocks: &[Block]) {
+        if !self.in_air && !self.is_falling && !self.has_support(blocks, self.grid_height) {
+            // Start the fall delay instead of immediately falling
+            self.fall_delay_counter = self.timing.fall_delay;
         }
     }
     
     // Find a block that is blocking the player's movement
     fn find_blocking_block(&self, target_x: usize, blocks: &[Block]) -> Option<usize> {
         for body_part in 0..self.body_size {
-            let target_pos = (target_x, self.position.1 + body_part);
-            if let Some(idx) = blocks.iter().position(|block| block.position == target_pos) {
+            let target_pos = GridPos::new(target_x, self.position.y + body_part);
+            if let Some(idx) = blocks.iter().position(|block| block.occupies(target_pos)) {
                 return Some(idx);
             }
         }
@@ -197,184 +458,175 @@ impl Player {
     }
     
     // Handle collision with a block
-    fn handle_block_collision(&mut self, block_idx: usize, move_by: isize, target_x: usize, 
-                             grid_size: usize, blocks: &mut [Block]) {
+    fn handle_block_collision(&mut self, block_idx: usize, direction: Direction, target_x: usize, 
+                             grid_width: usize, blocks: &mut [Block]) {
         let block = &blocks[block_idx];
         
-        // Check if the block can move in this direction
-        if !self.can_block_move_in_direction(block.position.0, move_by, grid_size) {
+        // Steel blocks are too heavy to move
+        if block.kind == BlockKind::Steel {
             return;
         }
         
-        let block_target_x = (block.position.0 as isize + move_by) as usize;
+        // Check if the block can move in this direction
+        if !self.can_block_move_in_direction(block, direction, grid_width) {
+            return;
+        }
         
         if block.falling {
-            self.handle_falling_block_movement(block_idx, block_target_x, target_x, blocks);
+            self.handle_falling_block_movement(block_idx, direction, target_x, blocks);
         } else {
-            self.handle_normal_block_movement(block.position.0, block_target_x, target_x, blocks);
+            self.handle_normal_block_movement(direction, target_x, blocks);
         }
     }
     
     // New method to check if a block can move in a direction
-    fn can_block_move_in_direction(&self, block_x: usize, move_by: isize, grid_size: usize) -> bool {
-        if move_by < 0 {
-            block_x > 0
-        } else {
-            block_x < grid_size - 1
+    fn can_block_move_in_direction(&self, block: &Block, direction: Direction, grid_width: usize) -> bool {
+        match direction {
+            Direction::Left => block.position.x > 0,
+            Direction::Right => block.right() < grid_width - 1,
         }
     }
     
+    // Check if any cell would overlap the player's body after moving to a column
+    fn overlaps_body_at(&self, x: usize, cell: GridPos) -> bool {
+        cell.x == x && cell.y >= self.position.y && cell.y < self.position.y + self.body_size
+    }
+    
     // Handle movement of a falling block
-    fn handle_falling_block_movement(&mut self, block_idx: usize, block_target_x: usize, 
+    fn handle_falling_block_movement(&mut self, block_idx: usize, direction: Direction, 
                                     player_target_x: usize, blocks: &mut [Block]) {
-        let target = (block_target_x, blocks[block_idx].position.1);
+        let block_target = blocks[block_idx].position.neighbor(direction)
+            .expect("can_block_move_in_direction keeps the block on the grid");
         
-        // Check if the carried block's target position is occupied
-        let is_block_blocked = blocks.iter().any(|b| b.position == target);
+        // Check if any cell of the block's target position is occupied
+        let is_block_blocked = blocks[block_idx].cells_at(block_target).any(|cell| {
+            self.terrain.is_solid(cell) ||
+            self.overlaps_body_at(player_target_x, cell) ||
+            blocks.iter().enumerate().any(|(i, b)| i != block_idx && b.occupies(cell))
+        });
         
-        // Check if any part of the player's body would be blocked
+        // Check if any part of the player's body would be blocked by another block
         let is_player_blocked = blocks.iter().enumerate()
             .filter(|(i, _)| *i != block_idx) // Ignore the block we're trying to move
-            .any(|(_, b)| {
-                // For each block, check all positions along the player's body
-                for body_part in 0..self.body_size {
-                    // Skip the head position if that's where we're carrying a block
-                    if body_part == 0 && b.position == (player_target_x, self.position.1) {
-                        // This is where the carried block would be - skip this check
-                        continue;
-                    }
-                    
-                    // Check if this part of the body would collide with any block
-                    if b.position == (player_target_x, self.position.1 + body_part) {
-                        return true;
-                    }
-                }
-                false
-            });
+            .any(|(_, b)| b.cells().any(|cell| self.overlaps_body_at(player_target_x, cell)));
         
         if !is_block_blocked && !is_player_blocked {
             // Check if the block is at the player's head level (top of the player's body)
-            let is_at_head_level = blocks[block_idx].position.1 == self.position.1;
+            let is_at_head_level = blocks[block_idx].cells().any(|cell| cell.y == self.position.y);
             
             if is_at_head_level {
-                // Calculate move direction based on target vs current position
-                let move_direction = (block_target_x as isize - blocks[block_idx].position.0 as isize).signum();
-                
                 // Mark the block as carried and store the direction
                 blocks[block_idx].carried = true;
-                blocks[block_idx].carrying_direction = Some(move_direction);
+                blocks[block_idx].touched = true;
+                blocks[block_idx].carrying_direction = Some(direction);
             }
             
             // Move the falling block
-            blocks[block_idx].position.0 = block_target_x;
+            blocks[block_idx].position = block_target;
+            self.moved.push(block_idx);
             // Then move the player
-            self.position.0 = player_target_x;
+            self.position.x = player_target_x;
         }
     }
     
     // Handle movement of normal (non-falling) blocks
-    fn handle_normal_block_movement(&mut self, block_x: usize, 
-                                   block_target_x: usize, player_target_x: usize, 
+    fn handle_normal_block_movement(&mut self, direction: Direction, player_target_x: usize, 
                                    blocks: &mut [Block]) {
-        let pushable_indices = self.find_pushable_blocks(block_x, blocks);
-        
-        if pushable_indices.is_empty() {
-            return;
-        }
+        let mut pushable_indices = std::mem::take(&mut self.pushed);
+        self.find_pushable_blocks(player_target_x, blocks, &mut pushable_indices);
         
         // Check if any pushable block would be blocked in its new position
-        if !self.is_path_clear_for_blocks(&pushable_indices, block_target_x, blocks) {
-            return;
+        if !pushable_indices.is_empty() &&
+            self.is_path_clear_for_blocks(&pushable_indices, direction, player_target_x, blocks) {
+            self.push_blocks(&pushable_indices, direction, player_target_x, blocks);
         }
-        
-        // Move all pushable blocks
-        for &idx in &pushable_indices {
-            blocks[idx].position.0 = block_target_x;
+        self.pushed = pushable_indices;
+    }
+    
+    // Move the pushed group and the player behind it
+    fn push_blocks(&mut self, pushable_indices: &[usize], direction: Direction, player_target_x: usize,
+                   blocks: &mut [Block]) {
+        // Move all pushable blocks as rigid units
+        for &idx in pushable_indices {
+            blocks[idx].position = blocks[idx].position.neighbor(direction)
+                .expect("is_path_clear_for_blocks keeps pushed blocks on the grid");
+            blocks[idx].idle_ticks = 0;
+            blocks[idx].touched = true;
+            
+            // Ice keeps sliding on its own after the push
+            if blocks[idx].kind == BlockKind::Ice {
+                blocks[idx].sliding = Some(direction);
+            }
         }
         
+        self.moved.extend_from_slice(pushable_indices);
+        
         // Then move the player
-        self.position.0 = player_target_x;
+        self.position.x = player_target_x;
     }
     
-    // Find which blocks are pushable in a column
-    fn find_pushable_blocks(&self, block_x: usize, blocks: &[Block]) -> Vec<usize> {
-        // Define the player's body range
-        let player_top = self.position.1;
-        let player_bottom = self.position.1 + self.body_size - 1;
-        
-        // Collect all non-falling blocks in this column
-        let mut column_blocks: Vec<(usize, usize)> = blocks.iter()
+    // Find which blocks get pushed when the player walks into a column
+    fn find_pushable_blocks(&self, target_x: usize, blocks: &[Block], pushable_indices: &mut Vec<usize>) {
+        // Blocks touching the player's body in the target column are pushed directly
+        pushable_indices.clear();
+        pushable_indices.extend(blocks.iter()
             .enumerate()
-            .filter_map(|(i, b)| {
-                if b.position.0 == block_x && !b.falling {
-                    Some((i, b.position.1))
-                } else {
-                    None
-                }
-            })
-            .collect();
-        
-        // Sort by y-coordinate (top to bottom)
-        column_blocks.sort_by_key(|&(_, y)| y);
-        
-        let mut pushable_indices = Vec::new();
-        let mut pushable_y_coords = Vec::new();
-        
-        // First, mark blocks at player's body level as pushable
-        for &(idx, y) in &column_blocks {
-            if y >= player_top && y <= player_bottom {
-                pushable_indices.push(idx);
-                pushable_y_coords.push(y);
-            }
-        }
+            .filter(|(_, b)| !b.falling && b.cells().any(|cell| self.overlaps_body_at(target_x, cell)))
+            .map(|(i, _)| i));
         
-        // If we found some blocks at the player's level
-        if !pushable_indices.is_empty() {
-            // Now check all blocks ABOVE to see if they form a connected column
-            let mut new_pushable_found = true;
-            while new_pushable_found {
-                new_pushable_found = false;
+        // Blocks resting on top of pushed blocks are carried along with them
+        let mut new_pushable_found = !pushable_indices.is_empty();
+        while new_pushable_found {
+            new_pushable_found = false;
+            
+            for (idx, block) in blocks.iter().enumerate() {
+                // Skip falling blocks and those already marked as pushable
+                if block.falling || pushable_indices.contains(&idx) {
+                    continue;
+                }
                 
-                for &(idx, y) in &column_blocks {
-                    // Skip if already marked as pushable
-                    if pushable_indices.contains(&idx) {
-                        continue;
-                    }
-                    
-                    // Only consider blocks ABOVE the player's level
-                    if y > player_bottom {
-                        continue;
-                    }
-                    
-                    // Check if this block is connected to a pushable block directly below
-                    if y > 0 && pushable_y_coords.contains(&(y + 1)) {
-                        pushable_indices.push(idx);
-                        pushable_y_coords.push(y);
-                        new_pushable_found = true;
-                    }
+                // Check if any cell of this block sits directly on a pushable block
+                let rests_on_pushable = block.cells().any(|cell| {
+                    pushable_indices.iter().any(|&p| blocks[p].occupies(cell.below()))
+                });
+                
+                if rests_on_pushable {
+                    pushable_indices.push(idx);
+                    new_pushable_found = true;
                 }
             }
         }
-        
-        pushable_indices
     }
     
     // Check if the path is clear for all blocks to move
-    fn is_path_clear_for_blocks(&self, pushable_indices: &[usize], target_x: usize, blocks: &[Block]) -> bool {
-        // Check if the target position is outside the grid boundary
-        if target_x >= self.grid_size {
-            return false;
-        }
-
+    fn is_path_clear_for_blocks(&self, pushable_indices: &[usize], direction: Direction, 
+                                player_target_x: usize, blocks: &[Block]) -> bool {
         for &idx in pushable_indices {
-            let (_, y) = blocks[idx].position;
-            let target = (target_x, y);
+            let block = &blocks[idx];
+            
+            // A steel block anywhere in the pushed group holds everything in place
+            if block.kind == BlockKind::Steel {
+                return false;
+            }
             
-            // Check if target position is occupied by a block not in our pushable set
-            for (i, b) in blocks.iter().enumerate() {
-                if b.position == target && !pushable_indices.contains(&i) {
+            // Check if the block would leave the grid
+            let target = match block.position.neighbor(direction) {
+                Some(target) if self.can_block_move_in_direction(block, direction, self.grid_width) => target,
+                _ => return false,
+            };
+            
+            for cell in block.cells_at(target) {
+                // Blocks can't be pushed into terrain or into the player
+                if self.terrain.is_solid(cell) || self.overlaps_body_at(player_target_x, cell) {
                     return false;
                 }
+                
+                // Check if target position is occupied by a block not in our pushable set
+                for (i, b) in blocks.iter().enumerate() {
+                    if b.occupies(cell) && !pushable_indices.contains(&i) {
+                        return false;
+                    }
+                }
             }
         }
         true
@@ -394,13 +646,18 @@ impl Player {
         }
     }
     
+    // Indices of the blocks the latest move_left or move_right shifted one cell in its direction
+    pub fn moved_blocks(&self) -> &[usize] {
+        &self.moved
+    }
+    
     pub fn move_left(&mut self, blocks: &mut [Block]) {
-        // Use the stored grid size from the Player struct
-        self.move_horizontal(-1, self.grid_size, blocks);
+        // Use the stored grid width from the Player struct
+        self.move_horizontal(Direction::Left, self.grid_width, blocks);
     }
     
     pub fn move_right(&mut self, blocks: &mut [Block]) {
-        // Use the stored grid size from the Player struct
-        self.move_horizontal(1, self.grid_size, blocks);
+        // Use the stored grid width from the Player struct
+        self.move_horizontal(Direction::Right, self.grid_width, blocks);
     }
 }
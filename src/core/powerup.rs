@@ -0,0 +1,58 @@
+// Core power-up implementation - platform-independent
+// Power-ups are timed effects that change how the GameState behaves while active
+
+// Chance that a spawned pickup is a power-up instead of a coin
+pub const POWERUP_SPAWN_CHANCE: f64 = 0.2;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PowerUpKind {
+    Hammer,     // The next push destroys the block instead of moving it
+    Freeze,     // No new blocks spawn
+    SlowMotion, // Falling blocks move at half speed
+    Magnet,     // Falling blocks drift a column toward the player every update
+}
+
+impl PowerUpKind {
+    pub const ALL: [PowerUpKind; 4] = [PowerUpKind::Hammer, PowerUpKind::Freeze, PowerUpKind::SlowMotion, PowerUpKind::Magnet];
+
+    // Number of updates the effect lasts
+    pub fn duration(&self) -> u64 {
+        match self {
+            PowerUpKind::Hammer => 100,
+            PowerUpKind::Freeze => 30,
+            PowerUpKind::SlowMotion => 40,
+            PowerUpKind::Magnet => 30,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PowerUpKind::Hammer => "Hammer",
+            PowerUpKind::Freeze => "Freeze",
+            PowerUpKind::SlowMotion => "Slow-mo",
+            PowerUpKind::Magnet => "Magnet",
+        }
+    }
+}
+
+// The power-up currently in effect and the updates it has left
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ActivePowerUp {
+    pub kind: PowerUpKind,
+    pub remaining_ticks: u64,
+}
+
+impl ActivePowerUp {
+    pub fn new(kind: PowerUpKind) -> Self {
+        Self {
+            kind,
+            remaining_ticks: kind.duration(),
+        }
+    }
+
+    // Count down one update, returns false once the effect has run out
+    pub fn tick(&mut self) -> bool {
+        self.remaining_ticks = self.remaining_ticks.saturating_sub(1);
+        self.remaining_ticks > 0
+    }
+}
@@ -0,0 +1,43 @@
+// Core high scores - platform-independent
+// The best runs so far, best first, with what is needed to tell them apart and to play the same run again.
+// Saving and loading the list is left to the platform
+use serde::{Deserialize, Serialize};
+
+// Runs kept on the list
+pub const MAX_HIGH_SCORES: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoreEntry {
+    pub score: u32,
+    pub date: String, // YYYY-MM-DD
+    pub mode: String, // Name of the game mode, as shown to the player
+    pub seed: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HighScores {
+    pub entries: Vec<ScoreEntry>,
+}
+
+impl HighScores {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Whether a score would make it onto the list
+    pub fn qualifies(&self, score: u32) -> bool {
+        score > 0 && (self.entries.len() < MAX_HIGH_SCORES || self.entries.iter().any(|entry| entry.score < score))
+    }
+
+    // Add a run, returning its place on the list (0 for the best) or None if it didn't make it
+    // A tie goes below the runs that got there first
+    pub fn insert(&mut self, entry: ScoreEntry) -> Option<usize> {
+        if !self.qualifies(entry.score) {
+            return None;
+        }
+        let rank = self.entries.iter().position(|other| other.score < entry.score).unwrap_or(self.entries.len());
+        self.entries.insert(rank, entry);
+        self.entries.truncate(MAX_HIGH_SCORES);
+        Some(rank)
+    }
+}
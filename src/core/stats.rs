@@ -0,0 +1,83 @@
+// Core run statistics - platform-independent
+// Samples of how a run evolved, drawn as a timeline on the game over screen
+// Long runs keep a bounded number of samples by dropping every other one and sampling half as often
+
+// Updates between two samples at the start of a run
+pub const SAMPLE_INTERVAL: u64 = 5;
+// Samples kept before they are thinned out
+pub const MAX_SAMPLES: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sample {
+    pub tick: u64,
+    pub score: u32,
+    pub stack_height: usize, // Rows covered by the settled stack, how close the run was to losing
+}
+
+// Moments worth pointing out on the timeline
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MarkerKind {
+    RowsCleared,
+    CloseCall,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Marker {
+    pub tick: u64,
+    pub kind: MarkerKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunStats {
+    pub ticks: u64,
+    pub samples: Vec<Sample>,
+    pub markers: Vec<Marker>,
+    pub close_calls: u32,
+    interval: u64,
+}
+
+impl Default for RunStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RunStats {
+    pub fn new() -> Self {
+        Self {
+            ticks: 0,
+            // Never more than one past the cap, so sampling doesn't allocate once the run is going
+            samples: Vec::with_capacity(MAX_SAMPLES + 1),
+            markers: Vec::new(),
+            close_calls: 0,
+            interval: SAMPLE_INTERVAL,
+        }
+    }
+
+    // Count an update, taking a sample every interval
+    pub fn record_tick(&mut self, score: u32, stack_height: usize) {
+        self.ticks += 1;
+        if !self.ticks.is_multiple_of(self.interval) {
+            return;
+        }
+        self.samples.push(Sample { tick: self.ticks, score, stack_height });
+
+        if self.samples.len() > MAX_SAMPLES {
+            self.interval *= 2;
+            let interval = self.interval;
+            self.samples.retain(|sample| sample.tick.is_multiple_of(interval));
+        }
+    }
+
+    // Sample the end of the run, so the timeline reaches the final score
+    pub fn finish(&mut self, score: u32, stack_height: usize) {
+        if self.samples.last().is_some_and(|sample| sample.tick == self.ticks) {
+            return;
+        }
+        self.samples.push(Sample { tick: self.ticks, score, stack_height });
+    }
+
+    pub fn mark(&mut self, kind: MarkerKind) {
+        self.markers.push(Marker { tick: self.ticks, kind });
+    }
+}
@@ -0,0 +1,101 @@
+// Core block storms - platform-independent
+// Storms are short bursts of faster spawning that come around periodically, sooner as the game goes on.
+// Each one is announced ahead of time so frontends can warn the player before it starts
+use serde::{Deserialize, Serialize};
+
+// When storms happen and how hard they hit, in updates
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StormPacing {
+    pub first_storm: u64,      // Updates before the first storm starts
+    pub interval: u64,         // Updates from the end of one storm to the start of the next
+    pub interval_shrink: u64,  // Each storm brings the next one this much sooner
+    pub min_interval: u64,     // The gap between storms never gets shorter than this
+    pub duration: u64,         // Updates a storm lasts
+    pub warning: u64,          // Updates the warning comes before the storm
+    pub spawn_rate_divisor: u64, // Spawn rate is divided by this during a storm (2 = twice as many blocks)
+}
+
+impl Default for StormPacing {
+    // With the default 200ms refresh rate: first storm after a minute, ten second storms, five seconds warning
+    fn default() -> Self {
+        Self {
+            first_storm: 300,
+            interval: 300,
+            interval_shrink: 25,
+            min_interval: 100,
+            duration: 50,
+            warning: 25,
+            spawn_rate_divisor: 2,
+        }
+    }
+}
+
+// What changed in the storm cycle during an update
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StormChange {
+    Warning,
+    Started,
+    Ended,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Storm {
+    pub pacing: StormPacing,
+    pub ticks_until_start: u64, // Counting down to the next storm, or 0 while one is raging
+    pub remaining_ticks: u64,   // Updates left in the current storm
+    pub storms: u32,            // Storms started so far
+}
+
+impl Storm {
+    // A storm never comes sooner than its warning, so the warning is always given
+    pub fn new(pacing: StormPacing) -> Self {
+        Self {
+            pacing,
+            ticks_until_start: pacing.first_storm.max(pacing.warning + 1),
+            remaining_ticks: 0,
+            storms: 0,
+        }
+    }
+
+    pub fn active(&self) -> bool {
+        self.remaining_ticks > 0
+    }
+
+    // The storm has been announced but hasn't started yet
+    pub fn warning(&self) -> bool {
+        !self.active() && self.ticks_until_start <= self.pacing.warning
+    }
+
+    pub fn spawn_rate(&self, rate: u64) -> u64 {
+        if self.active() {
+            (rate / self.pacing.spawn_rate_divisor.max(1)).max(1)
+        } else {
+            rate
+        }
+    }
+
+    // Advance the cycle by one update
+    pub fn tick(&mut self) -> Option<StormChange> {
+        if self.active() {
+            self.remaining_ticks -= 1;
+            if self.remaining_ticks == 0 {
+                let shrink = self.pacing.interval_shrink * self.storms as u64;
+                let interval = self.pacing.interval.saturating_sub(shrink).max(self.pacing.min_interval);
+                self.ticks_until_start = interval.max(self.pacing.warning + 1);
+                return Some(StormChange::Ended);
+            }
+            return None;
+        }
+
+        self.ticks_until_start -= 1;
+        if self.ticks_until_start == 0 {
+            self.storms += 1;
+            self.remaining_ticks = self.pacing.duration.max(1);
+            return Some(StormChange::Started);
+        }
+        if self.ticks_until_start == self.pacing.warning {
+            return Some(StormChange::Warning);
+        }
+        None
+    }
+}
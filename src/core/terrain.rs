@@ -0,0 +1,128 @@
+// Core terrain implementation - platform-independent
+// Terrain is static level geometry that lives alongside the blocks but never moves
+use serde::{Deserialize, Serialize};
+
+use crate::core::types::{Direction, GridPos};
+
+// A single cell of level geometry
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Tile {
+    Empty,
+    Ramp(Direction), // Slope rising toward the given direction
+    Wall,            // Fully solid cell
+}
+
+pub struct Terrain {
+    grid_width: usize,
+    grid_height: usize,
+    tiles: Vec<Tile>,
+    solid_per_row: Vec<usize>, // Kept up to date by set_tile, so full row checks don't scan the tiles
+    pits: Vec<bool>, // Columns with a hole in the floor below the bottom row
+}
+
+// Written by hand so clone_from reuses the buffers, the characters copy the level terrain every update
+impl Clone for Terrain {
+    fn clone(&self) -> Self {
+        Self {
+            grid_width: self.grid_width,
+            grid_height: self.grid_height,
+            tiles: self.tiles.clone(),
+            solid_per_row: self.solid_per_row.clone(),
+            pits: self.pits.clone(),
+        }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.grid_width = source.grid_width;
+        self.grid_height = source.grid_height;
+        self.tiles.clone_from(&source.tiles);
+        self.solid_per_row.clone_from(&source.solid_per_row);
+        self.pits.clone_from(&source.pits);
+    }
+}
+
+impl Terrain {
+    pub fn new(grid_width: usize, grid_height: usize) -> Self {
+        Self {
+            grid_width,
+            grid_height,
+            tiles: vec![Tile::Empty; grid_width * grid_height],
+            solid_per_row: vec![0; grid_height],
+            pits: vec![false; grid_width],
+        }
+    }
+
+    // Get the tile at a position, cells outside the grid are treated as empty
+    pub fn tile(&self, position: GridPos) -> Tile {
+        if !position.within(self.grid_width, self.grid_height) {
+            return Tile::Empty;
+        }
+        self.tiles[position.y * self.grid_width + position.x]
+    }
+
+    pub fn set_tile(&mut self, position: GridPos, tile: Tile) {
+        let GridPos { x, y } = position;
+        if position.within(self.grid_width, self.grid_height) {
+            let old = std::mem::replace(&mut self.tiles[y * self.grid_width + x], tile);
+            match (old != Tile::Empty, tile != Tile::Empty) {
+                (false, true) => self.solid_per_row[y] += 1,
+                (true, false) => self.solid_per_row[y] -= 1,
+                _ => {},
+            }
+        }
+    }
+
+    // Solid tiles block movement and support whatever rests on them
+    pub fn is_solid(&self, position: GridPos) -> bool {
+        self.tile(position) != Tile::Empty
+    }
+
+    // Get the rising direction of the ramp at a position, if there is one
+    pub fn ramp_at(&self, position: GridPos) -> Option<Direction> {
+        match self.tile(position) {
+            Tile::Ramp(direction) => Some(direction),
+            _ => None,
+        }
+    }
+
+    // Iterate over all ramps with their positions (used for rendering)
+    pub fn ramps(&self) -> impl Iterator<Item = (GridPos, Direction)> + '_ {
+        self.tiles.iter().enumerate().filter_map(move |(i, tile)| match tile {
+            Tile::Ramp(direction) => Some((self.cell(i), *direction)),
+            _ => None,
+        })
+    }
+
+    // Iterate over all walls (used for rendering)
+    pub fn walls(&self) -> impl Iterator<Item = GridPos> + '_ {
+        self.tiles.iter().enumerate()
+            .filter(|(_, tile)| **tile == Tile::Wall)
+            .map(move |(i, _)| self.cell(i))
+    }
+
+    fn cell(&self, index: usize) -> GridPos {
+        GridPos::new(index % self.grid_width, index / self.grid_width)
+    }
+
+    // Open or close the floor under a column
+    pub fn set_pit(&mut self, x: usize, open: bool) {
+        if let Some(pit) = self.pits.get_mut(x) {
+            *pit = open;
+        }
+    }
+
+    // Nothing stands on the floor over a pit, columns outside the grid have no pit
+    pub fn is_pit(&self, x: usize) -> bool {
+        self.pits.get(x).copied().unwrap_or(false)
+    }
+
+    // Iterate over the columns with a pit (used for rendering)
+    pub fn pits(&self) -> impl Iterator<Item = usize> + '_ {
+        self.pits.iter().enumerate().filter_map(|(x, &open)| open.then_some(x))
+    }
+
+    // Count solid cells in a row (they fill the row just like settled blocks)
+    pub fn solid_cells_in_row(&self, row: usize) -> usize {
+        self.solid_per_row.get(row).copied().unwrap_or(0)
+    }
+}
@@ -0,0 +1,60 @@
+// Core tutorial hints - platform-independent
+// During a player's first few games, game events that show off a mechanic come with a short hint.
+// Each hint is given once, frontends decide when the tutorial is over and how hints are shown
+use std::collections::HashSet;
+
+use crate::core::event::GameEvent;
+
+// Games a new player gets hints in
+pub const TUTORIAL_GAMES: u32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Hint {
+    RowAlmostComplete,
+    Carrying,
+    CloseCall,
+    Storm,
+}
+
+impl Hint {
+    // The hint explaining what just happened, if the event has one
+    pub fn for_event(event: &GameEvent) -> Option<Hint> {
+        match event {
+            GameEvent::RowAlmostComplete(_) => Some(Hint::RowAlmostComplete),
+            GameEvent::BlockCaught(_) => Some(Hint::Carrying),
+            GameEvent::CloseCall(_) => Some(Hint::CloseCall),
+            GameEvent::StormWarning => Some(Hint::Storm),
+            _ => None,
+        }
+    }
+
+    pub fn message(&self) -> &'static str {
+        match self {
+            Hint::RowAlmostComplete => "Row almost full - push a block into the gap!",
+            Hint::Carrying => "Caught it! Press X to put the block down",
+            Hint::CloseCall => "Close call! Dodging just in time scores a bonus",
+            Hint::Storm => "A storm is coming - blocks will drop faster",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Tutorial {
+    shown: HashSet<Hint>,
+}
+
+impl Tutorial {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Whether a player who finished this many games still gets hints
+    pub fn applies_to(games_played: u32) -> bool {
+        games_played < TUTORIAL_GAMES
+    }
+
+    // The hint for an event, unless it was already given
+    pub fn hint(&mut self, event: &GameEvent) -> Option<Hint> {
+        Hint::for_event(event).filter(|&hint| self.shown.insert(hint))
+    }
+}
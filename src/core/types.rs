@@ -1,22 +1,163 @@
 // Core types used across the game
 // These types are platform-independent
+use std::fmt;
 
-// Position in the game grid
-pub type Position = (usize, usize);
+use serde::{Deserialize, Serialize};
 
-// Direction of movement
-pub type Direction = isize;
+use crate::core::level::LevelDefinition;
+use crate::core::storm::StormPacing;
+
+// Cell in the game grid, x across from the left and y down from the top
+// Saved as an [x, y] pair, like the tuples positions were before
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[serde(from = "(usize, usize)", into = "(usize, usize)")]
+pub struct GridPos {
+    pub x: usize,
+    pub y: usize,
+}
+
+// Older name of GridPos, kept so the prelude doesn't change
+pub type Position = GridPos;
+
+impl GridPos {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    // The cell moved by whole cells, None past the top or left edge
+    pub fn offset(self, dx: isize, dy: isize) -> Option<Self> {
+        Some(Self::new(self.x.checked_add_signed(dx)?, self.y.checked_add_signed(dy)?))
+    }
+
+    // The cell next to this one on the given side, None past the left edge
+    pub fn neighbor(self, direction: Direction) -> Option<Self> {
+        self.offset(direction.offset(), 0)
+    }
+
+    // None in the top row
+    pub fn above(self) -> Option<Self> {
+        self.offset(0, -1)
+    }
+
+    pub fn below(self) -> Self {
+        Self::new(self.x, self.y + 1)
+    }
+
+    pub fn within(self, width: usize, height: usize) -> bool {
+        self.x < width && self.y < height
+    }
+}
+
+impl From<(usize, usize)> for GridPos {
+    fn from((x, y): (usize, usize)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl From<GridPos> for (usize, usize) {
+    fn from(position: GridPos) -> Self {
+        (position.x, position.y)
+    }
+}
+
+impl PartialEq<(usize, usize)> for GridPos {
+    fn eq(&self, other: &(usize, usize)) -> bool {
+        (self.x, self.y) == *other
+    }
+}
+
+impl fmt::Display for GridPos {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+// Side something moves, faces or slopes toward
+// Saved as -1 for left and 1 for right, the offsets it used to be written as
+// Things start out facing right
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(try_from = "isize", into = "isize")]
+pub enum Direction {
+    Left,
+    #[default]
+    Right,
+}
+
+impl Direction {
+    pub const BOTH: [Direction; 2] = [Direction::Left, Direction::Right];
+
+    // Cells moved along x by one step
+    pub fn offset(self) -> isize {
+        match self {
+            Direction::Left => -1,
+            Direction::Right => 1,
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+
+    // Side of a signed step along x, None for no step
+    pub fn from_offset(dx: isize) -> Option<Self> {
+        match dx.signum() {
+            -1 => Some(Direction::Left),
+            1 => Some(Direction::Right),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<isize> for Direction {
+    type Error = String;
+
+    // Only a single step, a saved direction of any other size is a mistake rather than a side
+    fn try_from(dx: isize) -> Result<Self, Self::Error> {
+        match dx {
+            -1 => Ok(Direction::Left),
+            1 => Ok(Direction::Right),
+            _ => Err(format!("direction must be -1 or 1, got {}", dx)),
+        }
+    }
+}
+
+impl From<Direction> for isize {
+    fn from(direction: Direction) -> Self {
+        direction.offset()
+    }
+}
 
 // The platform-independent InputAction enum
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum InputAction {
     Left,
     Right,
     Up,
-    Restart,
+    JumpRelease, // Up was let go, a jump still rising stops there
+    Down, // Crouch to one cell high, or stand back up
+    Restart, // Start over with a new seed
+    RestartSameSeed, // Start over with the seed of the run just played, for practicing its spawns
+    SwitchCharacter, // Hand control to the other character, if there is one
+    Drop, // Put down the block carried on the head
+    Kick, // Send the block in front of the player sliding away
     None,
 }
 
+// Where an input came from, so logs can tell simultaneous sources apart
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum InputDevice {
+    #[default]
+    Keyboard,
+    Mouse,
+    Gamepad,
+    Touch,
+    Network,
+    Bot, // The autoplay bot or a scripted source
+}
+
 // Game state update result
 pub enum GameUpdateResult {
     Continue,
@@ -24,8 +165,32 @@ pub enum GameUpdateResult {
     Restart,
 }
 
+// Why the game ended, so frontends can explain it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameOverReason {
+    Crushed,      // A block fell on the player with no lives left
+    StackTooHigh, // A settled block reached the danger line
+    Fell,         // The player dropped into a pit with no lives left
+    CampaignComplete, // The objective of the last campaign level was reached
+    TimeUp,       // The clock of a Time Attack game ran out
+    Blown,        // The player was caught in a blast with no lives left
+}
+
+impl GameOverReason {
+    pub fn message(&self) -> &'static str {
+        match self {
+            GameOverReason::Crushed => "You were crushed",
+            GameOverReason::StackTooHigh => "The stack reached the top",
+            GameOverReason::Fell => "You fell into a pit",
+            GameOverReason::CampaignComplete => "Campaign complete",
+            GameOverReason::TimeUp => "Time's up",
+            GameOverReason::Blown => "You were caught in a blast",
+        }
+    }
+}
+
 // Rendering color - platform-independent representation
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Color {
     pub r: f32,
     pub g: f32,
@@ -39,13 +204,294 @@ impl Color {
     pub const RED: Color = Color { r: 1.0, g: 0.0, b: 0.0, a: 1.0 };
     pub const GREEN: Color = Color { r: 0.0, g: 1.0, b: 0.0, a: 1.0 };
     pub const BLUE: Color = Color { r: 0.0, g: 0.0, b: 1.0, a: 1.0 };
+
+    pub const fn from_rgb(r: u8, g: u8, b: u8) -> Self {
+        Color { r: r as f32 / 255.0, g: g as f32 / 255.0, b: b as f32 / 255.0, a: 1.0 }
+    }
+}
+
+// Palette the frontends draw the game with
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Theme {
+    #[default]
+    Classic,
+    Soft, // Muted pastel version of every color, for calm play
+}
+
+impl Theme {
+    pub fn apply(&self, color: Color) -> Color {
+        match self {
+            Theme::Classic => color,
+            // Blend halfway toward a warm off-white
+            Theme::Soft => Color {
+                r: color.r * 0.55 + 0.96 * 0.45,
+                g: color.g * 0.55 + 0.92 * 0.45,
+                b: color.b * 0.55 + 0.86 * 0.45,
+                a: color.a,
+            },
+        }
+    }
+
+    pub fn background(&self) -> Color {
+        self.apply(Color::WHITE)
+    }
+}
+
+// How long the player's moves take, in updates
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PlayerTiming {
+    pub fall_delay: u8,       // Updates the player hangs over a gap before falling
+    pub jump_duration: u8,    // Updates the player stays up after a jump
+    pub gravity_interval: u8, // Updates per cell while falling
+}
+
+impl Default for PlayerTiming {
+    fn default() -> Self {
+        Self {
+            fall_delay: 3,
+            jump_duration: 1,
+            gravity_interval: 1,
+        }
+    }
+}
+
+impl PlayerTiming {
+    // Every step has to take at least one update, a zero would stall the player
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.fall_delay == 0 {
+            return Err(ConfigError::FallDelay);
+        }
+        if self.jump_duration == 0 {
+            return Err(ConfigError::JumpDuration);
+        }
+        if self.gravity_interval == 0 {
+            return Err(ConfigError::GravityInterval);
+        }
+        Ok(())
+    }
+}
+
+// Background music track, the frontend decides how each one sounds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Track {
+    Calm,
+    Tense,
+    Danger,
+}
+
+// Music a mode or level plays
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Soundtrack {
+    // Calm while the stack is low, tenser as it nears the danger line
+    #[default]
+    Adaptive,
+    // The same track throughout
+    Fixed(Track),
+}
+
+// What a mode looks and sounds like, applied when it starts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModeStyle {
+    pub theme: Theme,
+    pub soundtrack: Soundtrack,
+}
+
+// How levels follow each other
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub enum GameMode {
+    // Levels go up with cleared rows, until the game is lost
+    #[default]
+    Endless,
+    // Endless rules against the clock, the score when time runs out is what counts
+    TimeAttack,
+    // Nothing ends the game: crushing blocks break on the player and the stack is trimmed instead
+    Zen,
+    // A fixed sequence of levels, each one starting once the objective of the previous is done
+    Campaign(Vec<LevelDefinition>),
+}
+
+impl GameMode {
+    // Modes that can be picked by name at startup, campaigns come with their levels instead
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "endless" => Some(GameMode::Endless),
+            "time-attack" => Some(GameMode::TimeAttack),
+            "zen" => Some(GameMode::Zen),
+            _ => None,
+        }
+    }
+
+    // Look and music of each mode, campaign levels can change both in their files
+    pub fn style(&self) -> ModeStyle {
+        let (theme, soundtrack) = match self {
+            GameMode::Endless | GameMode::Campaign(_) => (Theme::Classic, Soundtrack::Adaptive),
+            GameMode::TimeAttack => (Theme::Classic, Soundtrack::Fixed(Track::Tense)),
+            GameMode::Zen => (Theme::Soft, Soundtrack::Fixed(Track::Calm)),
+        };
+        ModeStyle { theme, soundtrack }
+    }
 }
 
 // Core game configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GameConfig {
-    pub grid_size: usize,
+    pub grid_width: usize,
+    pub grid_height: usize,
     pub cell_size: f32,
     pub refresh_rate_milliseconds: u64,
     pub block_fall_speed: usize,
     pub block_spawn_rate: u64,
+    pub lives: u32,
+    pub two_characters: bool, // Put an idle second character on the board to switch to
+    pub danger_line: usize,   // Settled blocks reaching this row or above end the game (0 = spawn row)
+    pub seed: Option<u64>,    // Seed for block and pickup spawns, random if not set
+    pub record_events: bool,  // Keep an event log the game can be rebuilt from
+    pub player_timing: PlayerTiming,
+    pub storm_pacing: Option<StormPacing>, // Periodic bursts of faster spawning, off if not set
+    pub theme: Option<Theme>, // Palette to draw with, the mode's own if not set
+    pub favor_low_columns: bool, // Spawn blocks more often over the emptier columns
+    pub block_patterns: bool, // Draw a thick outline and a pattern per block kind, not just a color
+    pub head_carrying: bool, // Light blocks landing on the player's head are caught and carried instead of crushing
+    pub audio_cues: bool, // Play distinct sounds for spawns over the player, nearly full rows and a high stack
+    pub auto_climb: bool, // Walking into a single block steps up onto it instead of pushing it
+    pub jump_height: u8, // Cells a jump rises while Up is held, at least 1
+    pub enemy: bool, // The Foreman patrols the top of the stack
+    pub pits: Vec<usize>, // Columns with a hole in the floor, blocks dropping in are destroyed and the player loses a life
+    pub mode: GameMode,
+    pub idle_despawn: Option<u64>, // In Zen, blocks untouched this many updates are cleared off full columns
+    // Points per cleared cell of a block the player pushed, carried or kicked, 0 for none
+    // Logs recorded before the bonus existed replay without it
+    #[serde(default)]
+    pub touch_bonus: u32,
+    // Drills rather than games: input macros are allowed and the score isn't kept among the best
+    #[serde(default)]
+    pub practice: bool,
+    // Updates a bomb burns after landing before it blows up, if not set bombs only go off in cleared rows
+    #[serde(default)]
+    pub bomb_fuse: Option<u64>,
 }
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            grid_width: 16,
+            grid_height: 16,
+            cell_size: 30.0,
+            refresh_rate_milliseconds: 200,
+            block_fall_speed: 1,
+            block_spawn_rate: 10,
+            lives: 3,
+            two_characters: false,
+            danger_line: 0,
+            seed: None,
+            record_events: false,
+            player_timing: PlayerTiming::default(),
+            storm_pacing: None,
+            theme: None,
+            favor_low_columns: false,
+            block_patterns: false,
+            head_carrying: false,
+            audio_cues: false,
+            auto_climb: false,
+            jump_height: 1,
+            enemy: false,
+            pits: Vec::new(),
+            mode: GameMode::Endless,
+            idle_despawn: None,
+            touch_bonus: 1,
+            practice: false,
+            bomb_fuse: None,
+        }
+    }
+}
+
+impl GameConfig {
+    // Calm play: blocks come slowly onto the low columns and nothing can end the game
+    // The mode brings muted colors and calm music with it
+    pub fn zen() -> Self {
+        Self {
+            block_spawn_rate: 20,
+            mode: GameMode::Zen,
+            favor_low_columns: true,
+            ..Self::default()
+        }
+    }
+
+    // The first thing that would make a game with this config panic, stall or end before it starts
+    // Campaign levels bring their own grid and pace, they are checked when they are loaded
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.grid_width < 2 || self.grid_height < 3 {
+            return Err(ConfigError::GridTooSmall { width: self.grid_width, height: self.grid_height });
+        }
+        if !(self.cell_size > 0.0 && self.cell_size.is_finite()) {
+            return Err(ConfigError::CellSize(self.cell_size));
+        }
+        if self.refresh_rate_milliseconds == 0 {
+            return Err(ConfigError::RefreshRate);
+        }
+        if self.block_fall_speed == 0 || self.block_fall_speed > self.grid_height {
+            return Err(ConfigError::FallSpeed { speed: self.block_fall_speed, grid_height: self.grid_height });
+        }
+        if self.block_spawn_rate == 0 {
+            return Err(ConfigError::SpawnRate);
+        }
+        if self.lives == 0 {
+            return Err(ConfigError::NoLives);
+        }
+        // Blocks settling on the floor row already reach a line that low
+        if self.danger_line + 1 >= self.grid_height {
+            return Err(ConfigError::DangerLine { line: self.danger_line, grid_height: self.grid_height });
+        }
+        self.player_timing.validate()?;
+        if self.jump_height == 0 {
+            return Err(ConfigError::JumpHeight);
+        }
+        if self.mode == GameMode::Campaign(Vec::new()) {
+            return Err(ConfigError::EmptyCampaign);
+        }
+        Ok(())
+    }
+}
+
+// What makes a GameConfig unplayable
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigError {
+    GridTooSmall { width: usize, height: usize }, // The player is two cells tall and needs room to move
+    CellSize(f32),                                // Not a positive number of pixels
+    RefreshRate,                                  // No time at all between updates
+    FallSpeed { speed: usize, grid_height: usize }, // Blocks that don't fall, or fall past the floor in one update
+    SpawnRate,                                    // No updates between spawns
+    NoLives,
+    DangerLine { line: usize, grid_height: usize }, // So low that the first block to land ends the game
+    // A move of the player that takes no updates
+    FallDelay,
+    JumpDuration,
+    GravityInterval,
+    JumpHeight,                                   // A jump that doesn't rise
+    EmptyCampaign,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::GridTooSmall { width, height } => write!(f, "grid must be at least 2x3, got {}x{}", width, height),
+            ConfigError::CellSize(size) => write!(f, "cell size must be a positive number of pixels, got {}", size),
+            ConfigError::RefreshRate => write!(f, "refresh rate must be at least 1 millisecond"),
+            ConfigError::FallSpeed { speed, grid_height } => {
+                write!(f, "block fall speed must be from 1 to the grid height {}, got {}", grid_height, speed)
+            },
+            ConfigError::SpawnRate => write!(f, "block spawn rate must be at least 1 update"),
+            ConfigError::NoLives => write!(f, "lives must be at least 1"),
+            ConfigError::DangerLine { line, grid_height } => {
+                write!(f, "danger line must be above the floor row {}, got {}", grid_height - 1, line)
+            },
+            ConfigError::FallDelay => write!(f, "fall delay must be at least 1 update"),
+            ConfigError::JumpDuration => write!(f, "jump duration must be at least 1 update"),
+            ConfigError::GravityInterval => write!(f, "gravity interval must be at least 1 update"),
+            ConfigError::JumpHeight => write!(f, "jump height must be at least 1"),
+            ConfigError::EmptyCampaign => write!(f, "campaign has no levels"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
@@ -1,16 +1,25 @@
 // Export our core modules
 pub mod core;
 pub mod platform;
+pub mod prelude;
 
-// Legacy exports to maintain backward compatibility during transition
+// Test doubles for frontends, only built for tests and embedders that ask for them
+#[cfg(feature = "test-util")]
+pub mod testing;
+
+// Legacy paths from before everything moved into `core`, kept as re-exports so older code still
+// builds, with a warning pointing at the new path. Deprecated as of 0.1.0 and removed in 0.2.0
+#[deprecated(since = "0.1.0", note = "use `rust_stackattack::core::block` instead")]
 pub mod block {
     pub use crate::core::block::*;
 }
 
+#[deprecated(since = "0.1.0", note = "use `rust_stackattack::core::player` instead")]
 pub mod player {
     pub use crate::core::player::*;
 }
 
+#[deprecated(since = "0.1.0", note = "use `rust_stackattack::core::game` instead")]
 pub mod game {
     pub use crate::core::game::*;
 }
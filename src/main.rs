@@ -1,36 +1,195 @@
 // Main entry point for the application
+use std::fs;
+use std::io;
+use std::path::Path;
+
 use ggez::event;
 use ggez::GameResult;
 
-// Import our platform-specific adapter
-mod core;
-mod platform;
-
-use platform::ggez::GameAdapter;
+// Import our platform-specific adapter from the library crate
+use rust_stackattack::core::ai::Bot;
+use rust_stackattack::core::editor::Editor;
+use rust_stackattack::core::level;
+use rust_stackattack::core::types::{GameConfig, GameMode};
+use rust_stackattack::platform::ggez::GameAdapter;
+use rust_stackattack::platform::input::KeyBindings;
+use rust_stackattack::platform::launch::{LaunchConfig, LaunchError, USAGE};
+use rust_stackattack::platform::settings::Settings;
+use rust_stackattack::platform::scores;
 
 fn main() -> GameResult {
-    // Game configuration
-    let grid_size = 16;
-    let cell_size = 30.0;
-    let refresh_rate = 200;
-    let block_fall_speed = 1;
-    let block_spawn_rate = 10;
-    
-    // Create the game adapter with our configuration
-    let game = GameAdapter::new(grid_size, cell_size, refresh_rate, block_fall_speed, block_spawn_rate);
-    
-    // Calculate window dimensions
-    let grid_pixel_size = grid_size as f32 * cell_size;
-    let window_width = grid_pixel_size;
-    let window_height = grid_pixel_size + cell_size; // Grid size plus score bar height
-
-    // Create a game context and event loop
+    let args: Vec<String> = std::env::args().collect();
+    // Saved in the config directory, changed on the F3 settings screen
+    let settings = Settings::load().unwrap_or_else(|error| {
+        eprintln!("Couldn't read settings: {}", error);
+        std::process::exit(2);
+    });
+    
+    // The arena size and theme picked on the settings screen, then config.toml in the config directory
+    // or the file given with --config, then the flags, --help lists them
+    // Calm play with slow spawns, no game over and muted colors with --zen or --mode zen
+    // Three minutes to score as much as possible with --mode time-attack
+    // Every GameConfig field has a flag, e.g. --lives 5, --seed 42 or --player-timing.fall-delay 6, among them:
+    //   --two-characters    an idle second character, switched to with Tab
+    //   --block-patterns    outlines and patterns per block kind, for players who can't tell the colors apart (or --patterns)
+    //   --head-carrying     catch light blocks on the head and put them down with X (or --carry)
+    //   --audio-cues        sounds for what happens on the board, to play partly by ear
+    //   --auto-climb        step up onto single blocks instead of pushing them
+    //   --enemy             the Foreman patrols the stack (or --foreman)
+    //   --jump-height 3     cells a held jump rises
+    //   --pits 3,12         holes in the floor, columns outside the grid are ignored
+    //   --idle-despawn 3000 in Zen, clear blocks left alone this many updates off full columns (or --despawn-idle)
+    //   --touch-bonus 0     points per cleared cell of a block the player moved there, 0 to score rows only
+    //   --practice          drill with input macros, F5 records one and F6 plays it back
+    //   --fall-delay 6 --gravity-interval 2   movement timing in updates, for a floatier feel
+    let (grid_width, grid_height) = settings.grid_size();
+    let defaults = GameConfig { grid_width, grid_height, theme: settings.theme, ..GameConfig::default() };
+    let read_file = |named: Option<&Path>| match named {
+        // Only a file asked for by name has to be there
+        Some(path) => fs::read_to_string(path)
+            .map(Some)
+            .map_err(|error| io::Error::new(error.kind(), format!("{}: {}", path.display(), error))),
+        None => Ok(LaunchConfig::path().and_then(|path| fs::read_to_string(path).ok())),
+    };
+    let launch = LaunchConfig::layered(defaults, read_file, &args[1..]).unwrap_or_else(|error| match error {
+        LaunchError::Help => {
+            print!("{}", USAGE);
+            std::process::exit(0);
+        },
+        error => {
+            eprintln!("Invalid configuration: {}", error);
+            std::process::exit(2);
+        },
+    });
+    let (mut config, frontend) = (launch.game, launch.frontend);
+    
+    // A sequence of levels from JSON files, e.g. --campaign levels/ plays every file in the folder by name
+    if let Some(folder) = &frontend.campaign {
+        match level::load_campaign(folder) {
+            Ok(levels) => config.mode = GameMode::Campaign(levels),
+            Err(err) => {
+                eprintln!("Invalid campaign: {}", err);
+                std::process::exit(2);
+            },
+        }
+    }
+    
+    let (grid_width, grid_height) = (config.grid_width, config.grid_height);
+    
+    // Create the game adapter with our configuration, reopening the window where it was last time
+    let game = GameAdapter::new(config).unwrap_or_else(|error| {
+        eprintln!("Invalid configuration: {}", error);
+        std::process::exit(2);
+    });
+    let mut game = game.with_settings(settings);
+    
+    // Slide the player between cells for this many milliseconds, e.g. --animation-ms 80, 0 for instant
+    // Remembered in the settings for the next session
+    if let Some(millis) = frontend.animation_millis {
+        game = game.with_animation_millis(millis);
+    }
+    
+    // Background music volume from 0 to 1, e.g. --music-volume 0 to turn it off
+    // Remembered in the settings for the next session
+    if let Some(volume) = frontend.music_volume {
+        game = game.with_music_volume(volume);
+    }
+    
+    // How far row clears, close calls and the end of the game shake the board, in cells, e.g. --shake 0 to keep it still
+    // Remembered in the settings for the next session
+    if let Some(intensity) = frontend.shake_intensity {
+        game = game.with_shake_intensity(intensity);
+    }
+    // How fast a shake settles, per second, e.g. --shake-damping 4 for a longer rumble
+    if let Some(damping) = frontend.shake_damping {
+        game = game.with_shake_damping(damping);
+    }
+    
+    // Draw the board as one scaled image for very weak graphics, --render low or --render full
+    // Remembered in the settings for the next session
+    if let Some(low_spec) = launch.window.low_spec {
+        game = game.with_low_spec(low_spec);
+    }
+    
+    // Start fullscreen or in a window, --fullscreen on or --fullscreen off, F11 switches while playing
+    // Remembered in the settings for the next session
+    if let Some(fullscreen) = launch.window.fullscreen {
+        game = game.with_fullscreen(fullscreen);
+    }
+    
+    // Best runs are kept in the platform's data directory
+    if let Some(path) = scores::path() {
+        game = game.with_high_scores(path);
+    }
+    
+    // Keys are looked up in a bindings file in the config directory, F2 rebinds them in the game
+    if let Some(path) = KeyBindings::path() {
+        game = game.with_key_bindings(path).unwrap_or_else(|error| {
+            eprintln!("Couldn't read key bindings: {}", error);
+            std::process::exit(2);
+        });
+    }
+    
+    // Hints during the first few games, --hints off to skip them or --hints on to undo that
+    // Remembered in the settings for the next session
+    if let Some(hints) = frontend.hints {
+        game = game.with_hints(hints);
+    }
+    
+    // Edit a level file instead of playing, e.g. --edit levels/01.json, a new file gets a --grid sized board
+    if let Some(path) = frontend.edit {
+        let editor = if path.exists() {
+            match level::load_level(&path) {
+                Ok(level) => Editor::from_level(level),
+                Err(err) => {
+                    eprintln!("Invalid level: {}", err);
+                    std::process::exit(2);
+                },
+            }
+        } else {
+            Editor::new(grid_width, grid_height)
+        };
+        game = game.with_editor(editor, path).unwrap_or_else(|error| {
+            eprintln!("Invalid level: {}", error);
+            std::process::exit(2);
+        });
+        
+        // Let the computer play the live preview beside the editor, e.g. --preview-bot medium
+        if let Some(difficulty) = frontend.preview_bot {
+            game = game.with_preview_bot(Bot::new(difficulty));
+        }
+    }
+    
+    // Drag the player sideways to push and click above it to jump, with --mouse
+    if frontend.mouse {
+        game = game.with_mouse_controls();
+    }
+    
+    // On-screen buttons and swipes for touchscreens, with --touch
+    #[cfg(feature = "touch")]
+    if frontend.touch {
+        game = game.with_touch_controls();
+    }
+    
+    // Left and Right held together move toward the latest one, or nowhere with --opposing neutral
+    if let Some(policy) = frontend.opposing {
+        game = game.with_conflict_policy(policy);
+    }
+    
+    // Watch the computer play with --bot easy|medium|hard
+    if let Some(difficulty) = frontend.bot {
+        game = game.with_bot(Bot::new(difficulty));
+    }
+    
+    // Create a game context and event loop, the window fits at least the grid plus the score bar
     let cb = ggez::ContextBuilder::new("stackattack_rust", "stepanhampl")
         .window_setup(ggez::conf::WindowSetup::default().title("Stackattack"))
-        .window_mode(ggez::conf::WindowMode::default().dimensions(window_width, window_height));
+        .window_mode(game.window_mode());
 
     let (ctx, event_loop) = cb.build()?;
+    game.restore_window_position(&ctx)?;
 
     // Run the main event loop
     event::run(ctx, event_loop, game)
 }
+
@@ -0,0 +1,194 @@
+// Platform-independent animation - poses for the player and a squash for blocks that just landed
+// Both are picked from how the game state changed and run on their own frame timer, so they stay
+// smooth however long a game update is
+use std::time::{Duration, Instant};
+
+use crate::core::game::GameState;
+use crate::core::types::{Direction, GridPos};
+use crate::platform::clock::Clock;
+
+pub const FRAME_DURATION: Duration = Duration::from_millis(90);
+pub const SQUASH_DURATION: Duration = Duration::from_millis(180);
+// A walk or push keeps playing this long after the last step, so held moves don't flicker to idle
+const MOVE_HOLD: Duration = Duration::from_millis(300);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlayerAction {
+    #[default]
+    Idle,
+    Walk,
+    Jump,
+    Fall,
+    Push,
+}
+
+impl PlayerAction {
+    fn frames(self) -> u32 {
+        match self {
+            PlayerAction::Idle | PlayerAction::Jump => 1,
+            PlayerAction::Fall | PlayerAction::Push => 2,
+            PlayerAction::Walk => 4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Pose {
+    pub action: PlayerAction,
+    pub frame: u32,
+    pub facing: Direction,
+}
+
+impl Pose {
+    // How the body is drawn in this pose: shift (x, y) and scale (width, height), relative to the cell
+    // Scaling keeps the feet where they are
+    pub fn body_transform(&self) -> (f32, f32, f32, f32) {
+        let facing = self.facing.offset() as f32;
+        match (self.action, self.frame) {
+            (PlayerAction::Idle, _) => (0.0, 0.0, 1.0, 1.0),
+            // Bob up on every other step, leaning into the walk
+            (PlayerAction::Walk, frame) => (0.04 * facing, if frame % 2 == 1 { -0.06 } else { 0.0 }, 1.0, 1.0),
+            // Stretched thin going up
+            (PlayerAction::Jump, _) => (0.0, 0.0, 0.85, 1.1),
+            // Flattened coming down, flapping a little
+            (PlayerAction::Fall, frame) => (0.0, 0.0, if frame == 0 { 1.1 } else { 1.15 }, 0.92),
+            // Leaning hard into the block, heaving between two frames
+            (PlayerAction::Push, frame) => (if frame == 0 { 0.12 } else { 0.08 } * facing, 0.0, 0.95, 0.95),
+        }
+    }
+}
+
+// What to draw this frame on top of the game state
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AnimationFrame {
+    pub player_offset: (f32, f32), // Slide into the player's cell, in cells
+    pub pose: Pose,
+    pub squashes: Vec<(GridPos, f32)>, // Blocks by position, with how squashed they are from 1 down to 0
+    pub tick_remaining: f32, // Share of the latest update's block and Foreman moves still to be drawn, 0 once in their cells
+}
+
+pub struct Animator {
+    action: PlayerAction,
+    facing: Direction,
+    started: Instant,
+    last_position: GridPos,
+    last_move: Instant,
+    landings: Vec<(GridPos, Instant)>,
+    ticks: u64,
+    ticked: Option<Instant>, // When the latest update was seen, None before the first one
+    refresh: Duration,
+}
+
+impl Animator {
+    pub fn new(position: GridPos, clock: &dyn Clock) -> Self {
+        let now = clock.now();
+        Self {
+            action: PlayerAction::Idle,
+            facing: Direction::Right,
+            started: now,
+            last_position: position,
+            last_move: now,
+            landings: Vec::new(),
+            ticks: 0,
+            ticked: None,
+            refresh: Duration::ZERO,
+        }
+    }
+
+    // Pick the player's action from how it moved since the last look
+    pub fn observe(&mut self, game: &GameState, clock: &dyn Clock) {
+        let now = clock.now();
+        if game.stats.ticks != self.ticks {
+            self.ticks = game.stats.ticks;
+            self.ticked = Some(now);
+        }
+        self.refresh = Duration::from_millis(game.refresh_rate_milliseconds);
+
+        let position = game.player.position;
+        let GridPos { x: last_x, y: last_y } = self.last_position;
+        if position != self.last_position {
+            self.last_move = now;
+        }
+
+        let action = if position.y < last_y {
+            PlayerAction::Jump
+        } else if position.y > last_y {
+            PlayerAction::Fall
+        } else if game.player.in_air {
+            // Hanging at the top of a jump or over a gap keeps the pose it had
+            match self.action {
+                PlayerAction::Fall => PlayerAction::Fall,
+                _ => PlayerAction::Jump,
+            }
+        } else if position.x != last_x {
+            self.facing = if position.x > last_x { Direction::Right } else { Direction::Left };
+            if Self::block_ahead(game, self.facing) { PlayerAction::Push } else { PlayerAction::Walk }
+        } else if matches!(self.action, PlayerAction::Walk | PlayerAction::Push) && now.duration_since(self.last_move) < MOVE_HOLD {
+            self.action
+        } else {
+            PlayerAction::Idle
+        };
+
+        if action != self.action {
+            self.action = action;
+            self.started = now;
+        }
+        self.last_position = position;
+        self.landings.retain(|&(_, landed)| now.duration_since(landed) < SQUASH_DURATION);
+    }
+
+    // A settled block right in front of the player, which it just pushed along
+    fn block_ahead(game: &GameState, facing: Direction) -> bool {
+        let Some(ahead) = game.player.position.neighbor(facing) else {
+            return false;
+        };
+        game.blocks.iter().any(|block| {
+            !block.falling && (0..game.player.body_size).any(|row| block.occupies(GridPos::new(ahead.x, ahead.y + row)))
+        })
+    }
+
+    // Start the squash of a block that just came to rest
+    pub fn block_landed(&mut self, position: GridPos, clock: &dyn Clock) {
+        self.landings.retain(|&(landed, _)| landed != position);
+        self.landings.push((position, clock.now()));
+    }
+
+    pub fn pose(&self, clock: &dyn Clock) -> Pose {
+        let elapsed = clock.now().duration_since(self.started);
+        let frame = (elapsed.as_millis() / FRAME_DURATION.as_millis()) as u32 % self.action.frames();
+        Pose {
+            action: self.action,
+            frame,
+            facing: self.facing,
+        }
+    }
+
+    // Blocks still squashed from landing, 1 right at the landing easing back to 0
+    pub fn squashes(&self, clock: &dyn Clock) -> Vec<(GridPos, f32)> {
+        let now = clock.now();
+        self.landings.iter()
+            .filter_map(|&(position, landed)| {
+                let progress = now.duration_since(landed).as_secs_f32() / SQUASH_DURATION.as_secs_f32();
+                (progress < 1.0).then_some((position, 1.0 - progress))
+            })
+            .collect()
+    }
+
+    // Blocks and the Foreman move a cell per update, drawn sliding there over the update that follows
+    pub fn tick_remaining(&self, clock: &dyn Clock) -> f32 {
+        let Some(ticked) = self.ticked.filter(|_| !self.refresh.is_zero()) else {
+            return 0.0;
+        };
+        let elapsed = clock.now().duration_since(ticked).as_secs_f32();
+        (1.0 - elapsed / self.refresh.as_secs_f32()).max(0.0)
+    }
+
+    pub fn frame(&self, player_offset: (f32, f32), clock: &dyn Clock) -> AnimationFrame {
+        AnimationFrame {
+            player_offset,
+            pose: self.pose(clock),
+            squashes: self.squashes(clock),
+            tick_remaining: self.tick_remaining(clock),
+        }
+    }
+}
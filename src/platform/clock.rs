@@ -0,0 +1,62 @@
+// Platform-independent time source - adapters ask the clock instead of calling Instant::now
+// so their timers can be driven by a mock clock in tests
+use std::time::{Duration, Instant};
+
+pub trait Clock {
+    fn now(&self) -> Instant;
+
+    // Hint that the adapter has nothing to do for a while
+    fn sleep(&self, duration: Duration);
+}
+
+// The real wall clock
+#[derive(Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+}
+
+// A value that is only shown for a limited time, like the row clear banner
+pub struct TimedValue<T> {
+    value: Option<(T, Instant)>,
+    duration: Duration,
+}
+
+impl<T> TimedValue<T> {
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            value: None,
+            duration,
+        }
+    }
+
+    pub fn set(&mut self, value: T, clock: &dyn Clock) {
+        self.value = Some((value, clock.now()));
+    }
+
+    // The value, if it was set recently enough
+    pub fn get(&self, clock: &dyn Clock) -> Option<&T> {
+        match &self.value {
+            Some((value, set_at)) if clock.now().duration_since(*set_at) < self.duration => Some(value),
+            _ => None,
+        }
+    }
+
+    // Share of the duration still to go, from 1 right after set down to 0, for fading things out
+    pub fn fraction_left(&self, clock: &dyn Clock) -> Option<f32> {
+        let (_, set_at) = self.value.as_ref()?;
+        let elapsed = clock.now().duration_since(*set_at);
+        (elapsed < self.duration).then(|| 1.0 - elapsed.as_secs_f32() / self.duration.as_secs_f32())
+    }
+
+    pub fn clear(&mut self) {
+        self.value = None;
+    }
+}
@@ -0,0 +1,86 @@
+// Sounds for accessibility cues and effects, synthesized as WAV data so no audio files have to ship with the game
+// Each cue has its own rhythm and pitch, so they can be told apart without looking
+use crate::core::cue::Cue;
+
+pub const SAMPLE_RATE: u32 = 22_050;
+// Fade at both ends of a note, so tones start and stop without clicks
+const FADE_SAMPLES: usize = 200;
+const VOLUME: f32 = 0.4;
+const WHOOSH_MILLIS: u32 = 180;
+
+// Notes of a cue as (frequency in Hz, length in milliseconds), a frequency of 0 is a rest
+pub fn notes(cue: Cue) -> &'static [(f32, u32)] {
+    match cue {
+        // Two short high beeps
+        Cue::SpawnInColumn => &[(880.0, 80), (0.0, 60), (880.0, 80)],
+        // A rising chord, almost there
+        Cue::RowAlmostComplete => &[(523.0, 90), (659.0, 90), (784.0, 120)],
+        // Three low pulses
+        Cue::DangerHigh => &[(220.0, 120), (0.0, 80), (220.0, 120), (0.0, 80), (220.0, 120)],
+    }
+}
+
+// Mono 16-bit samples of a cue
+pub fn cue_samples(cue: Cue) -> Vec<i16> {
+    tune_samples(notes(cue), VOLUME)
+}
+
+// Samples of a sequence of (frequency, milliseconds) notes, each faded in and out
+pub(crate) fn tune_samples(notes: &[(f32, u32)], volume: f32) -> Vec<i16> {
+    let mut samples = Vec::new();
+    for &(frequency, millis) in notes {
+        let length = (SAMPLE_RATE * millis / 1000) as usize;
+        for i in 0..length {
+            let fade = (i.min(length - 1 - i) as f32 / FADE_SAMPLES as f32).min(1.0);
+            let phase = 2.0 * std::f32::consts::PI * frequency * i as f32 / SAMPLE_RATE as f32;
+            samples.push((phase.sin() * fade * volume * i16::MAX as f32) as i16);
+        }
+    }
+    samples
+}
+
+// A cue as a complete WAV file in memory
+pub fn cue_wav(cue: Cue) -> Vec<u8> {
+    wav(&cue_samples(cue))
+}
+
+// Quick falling sweep for a close call
+pub fn whoosh_samples() -> Vec<i16> {
+    let length = (SAMPLE_RATE * WHOOSH_MILLIS / 1000) as usize;
+    let mut phase = 0.0f32;
+    (0..length).map(|i| {
+        let progress = i as f32 / length as f32;
+        let frequency = 1200.0 - 1000.0 * progress;
+        phase += 2.0 * std::f32::consts::PI * frequency / SAMPLE_RATE as f32;
+        let fade = (i.min(length - 1 - i) as f32 / FADE_SAMPLES as f32).min(1.0);
+        (phase.sin() * fade * VOLUME * i16::MAX as f32) as i16
+    }).collect()
+}
+
+pub fn whoosh_wav() -> Vec<u8> {
+    wav(&whoosh_samples())
+}
+
+// Wrap mono 16-bit samples in a WAV header
+pub(crate) fn wav(samples: &[i16]) -> Vec<u8> {
+    let data_size = samples.len() as u32 * 2;
+
+    let mut wav = Vec::with_capacity(44 + data_size as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_size).to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+    wav.extend_from_slice(b"fmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes()); // Format chunk size
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // Mono
+    wav.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    wav.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes()); // Bytes per second
+    wav.extend_from_slice(&2u16.to_le_bytes()); // Bytes per sample frame
+    wav.extend_from_slice(&16u16.to_le_bytes()); // Bits per sample
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_size.to_le_bytes());
+    for sample in samples {
+        wav.extend_from_slice(&sample.to_le_bytes());
+    }
+    wav
+}
@@ -0,0 +1,103 @@
+// Locale-aware formatting of numbers, dates and times of day, shared by all frontends
+// Only the handful of conventions the game shows are covered, anything unknown falls back to ISO style
+use std::env;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DateOrder {
+    YearMonthDay,
+    DayMonthYear,
+    MonthDayYear,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Locale {
+    pub thousands_separator: Option<char>,
+    pub date_order: DateOrder,
+    pub date_separator: char,
+    pub clock_24h: bool,
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self::ISO
+    }
+}
+
+impl Locale {
+    // Plain digits, YYYY-MM-DD and a 24 hour clock
+    pub const ISO: Locale = Locale {
+        thousands_separator: None,
+        date_order: DateOrder::YearMonthDay,
+        date_separator: '-',
+        clock_24h: true,
+    };
+
+    // Conventions for a POSIX locale name like en_US.UTF-8 or cs_CZ
+    pub fn from_tag(tag: &str) -> Self {
+        let tag = tag.split(['.', '@']).next().unwrap_or_default();
+        let (language, region) = tag.split_once(['_', '-']).unwrap_or((tag, ""));
+        let locale = |separator, date_order, date_separator, clock_24h| Locale {
+            thousands_separator: Some(separator),
+            date_order,
+            date_separator,
+            clock_24h,
+        };
+        match (language, region) {
+            ("en", "US") => locale(',', DateOrder::MonthDayYear, '/', false),
+            ("en", _) => locale(',', DateOrder::DayMonthYear, '/', true),
+            ("de" | "cs" | "sk" | "pl" | "ru", _) => locale(' ', DateOrder::DayMonthYear, '.', true),
+            ("fr" | "es" | "it" | "pt", _) => locale(' ', DateOrder::DayMonthYear, '/', true),
+            _ => Self::ISO,
+        }
+    }
+
+    // The user's locale as the environment gives it, the same variables and order as the C library reads them
+    pub fn from_env() -> Self {
+        ["LC_ALL", "LC_NUMERIC", "LANG"]
+            .iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .map_or(Self::ISO, |tag| Self::from_tag(&tag))
+    }
+
+    pub fn number(&self, n: u64) -> String {
+        let digits = n.to_string();
+        let Some(separator) = self.thousands_separator else {
+            return digits;
+        };
+        let mut formatted = String::new();
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                formatted.push(separator);
+            }
+            formatted.push(digit);
+        }
+        formatted
+    }
+
+    // Reorder a YYYY-MM-DD date, anything else is shown as it is
+    pub fn date(&self, iso: &str) -> String {
+        let parts: Vec<&str> = iso.split('-').collect();
+        let [year, month, day] = parts[..] else {
+            return iso.to_string();
+        };
+        let ordered = match self.date_order {
+            DateOrder::YearMonthDay => [year, month, day],
+            DateOrder::DayMonthYear => [day, month, year],
+            DateOrder::MonthDayYear => [month, day, year],
+        };
+        ordered.join(&self.date_separator.to_string())
+    }
+
+    pub fn time_of_day(&self, hour: u32, minute: u32) -> String {
+        if self.clock_24h {
+            return format!("{:02}:{:02}", hour, minute);
+        }
+        let suffix = if hour < 12 { "AM" } else { "PM" };
+        let hour = match hour % 12 {
+            0 => 12,
+            hour => hour,
+        };
+        format!("{}:{:02} {}", hour, minute, suffix)
+    }
+}
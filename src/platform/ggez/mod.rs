@@ -1,290 +1,1205 @@
 // Platform-specific implementation for ggez
-use std::collections::{HashSet, VecDeque};
+use std::collections::HashSet;
+use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime};
 
+use ggez::audio::{SoundData, SoundSource, Source};
+use ggez::conf::{FullscreenType, WindowMode};
 use ggez::event::EventHandler;
-use ggez::graphics::{self, Canvas, Color, DrawParam, Mesh, Rect, Text};
-use ggez::input::keyboard::{KeyCode, KeyInput};
+use ggez::graphics::{self, Canvas, DrawParam, Image, ImageFormat, InstanceArray, Mesh, MeshBuilder, Sampler, Text};
+use ggez::input::keyboard::{KeyCode, KeyInput, KeyMods};
 use ggez::input::mouse::MouseButton;
-use ggez::{Context, GameResult};
+use ggez::winit::dpi::PhysicalPosition;
+#[cfg(feature = "touch")]
+use ggez::winit::event::TouchPhase;
+use ggez::{Context, GameError, GameResult};
 
+use crate::core::ai::Bot;
+use crate::core::cue::CueTracker;
+use crate::core::editor::{Editor, LivePreview};
+use crate::core::event::{GameEvent, ScoreBreakdown};
 use crate::core::game::GameState;
-use crate::core::types::{GameConfig, InputAction};
+use crate::core::scores::{HighScores, ScoreEntry};
+use crate::core::tutorial::{Hint, Tutorial};
+use crate::core::types::{Color, ConfigError, GameConfig, GridPos, InputAction, InputDevice};
+use crate::platform::clock::{Clock, SystemClock, TimedValue};
+use crate::platform::cue_sound;
+use crate::platform::music::{self, AudioManager, Music, DEFAULT_MUSIC_VOLUME};
+use crate::platform::format::Locale;
+use crate::platform::input::{self, ConflictPolicy, Control, FrameInput, InputSource, KeyBindings, RebindMenu};
+use crate::platform::input_macro::InputMacro;
+use crate::platform::animation::Animator;
+use crate::platform::motion::PlayerMotion;
+use crate::platform::particles::Particles;
+use crate::platform::popups::Popups;
+use crate::platform::pixels::CellPixels;
+use crate::platform::mouse::MouseInput;
+#[cfg(feature = "touch")]
+use crate::platform::touch::TouchControls;
+use crate::platform::render::{self, EditorButton, Layout, Letterbox, Rect, Renderer, TextAnchor};
+use crate::platform::scores;
+use crate::platform::settings::{MonitorArea, Settings, SettingsItem, SettingsMenu, WindowGeometry};
+use crate::platform::shake::{Impact, ScreenShake, DEFAULT_SHAKE_INTENSITY, HIT_FLASH_DURATION};
+use crate::platform::watchdog::TickWatchdog;
+use crate::platform::share;
+
+// How long the score breakdown of a row clear stays on screen
+const CLEAR_BANNER_DURATION: Duration = Duration::from_millis(1500);
+// How long a notice, like the saved summary card's path, stays on screen
+const NOTICE_DURATION: Duration = Duration::from_secs(5);
+// How long a tutorial hint stays on screen
+const HINT_DURATION: Duration = Duration::from_secs(4);
+// How long the popup over a close call stays on screen
+const CLOSE_CALL_DURATION: Duration = Duration::from_millis(800);
+// How long idle blocks cleared in Zen take to fade out
+const DESPAWN_FADE_DURATION: Duration = Duration::from_millis(600);
+// Blocks checked for lost support per update once the watchdog is at its highest level
+const DEGRADED_LEVITATION_CHUNK: usize = 64;
+
+// Convert from core representation to ggez representation
+fn to_ggez_color(color: Color) -> graphics::Color {
+    graphics::Color::new(color.r, color.g, color.b, color.a)
+}
+
+// A line as drawn, compared to find a batch of lines that was already built into a mesh
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Line {
+    from: (f32, f32),
+    to: (f32, f32),
+    width: f32,
+    color: Color,
+}
+
+// Meshes kept from one frame to the next, so drawing doesn't upload the same shapes over and over
+#[derive(Default)]
+struct MeshCache {
+    // White unit square, every rectangle is this square moved, scaled and tinted
+    quad: Option<Mesh>,
+    // Rectangles drawn one after another, sent together as instances of the square
+    rects: Option<InstanceArray>,
+    // Batches of lines built last frame, the grid among them, built again only when they change
+    // e.g. after a resize
+    lines: Vec<(Vec<Line>, Mesh)>,
+    // Batches used this frame, kept for the next one
+    lines_used: Vec<(Vec<Line>, Mesh)>,
+}
+
+// Renderer drawing onto a ggez canvas
+// Rectangles and lines drawn in a row are batched and drawn together once something else comes in
+// between, which keeps everything in the order it was drawn
+// Mesh errors can't be returned from the Renderer methods, so the first one is kept for later
+struct GgezRenderer<'a> {
+    ctx: &'a mut Context,
+    canvas: &'a mut Canvas,
+    cache: &'a mut MeshCache,
+    rects_pending: bool,
+    lines_pending: Vec<Line>,
+    error: Option<GameError>,
+}
+
+impl<'a> GgezRenderer<'a> {
+    fn new(ctx: &'a mut Context, canvas: &'a mut Canvas, cache: &'a mut MeshCache) -> Self {
+        Self { ctx, canvas, cache, rects_pending: false, lines_pending: Vec::new(), error: None }
+    }
+
+    // Build the square and its instances on the first rectangle, a frame of text only needs no mesh
+    fn prepare_rects(&mut self) {
+        if self.cache.quad.is_none() {
+            let square = graphics::Rect::new(0.0, 0.0, 1.0, 1.0);
+            match Mesh::new_rectangle(self.ctx, graphics::DrawMode::fill(), square, graphics::Color::WHITE) {
+                Ok(quad) => self.cache.quad = Some(quad),
+                Err(error) => {
+                    self.error.get_or_insert(error);
+                },
+            }
+        }
+        if self.cache.rects.is_none() {
+            self.cache.rects = Some(InstanceArray::new(self.ctx, None));
+        }
+    }
+
+    fn draw_mesh(&mut self, mesh: GameResult<Mesh>) {
+        match mesh {
+            Ok(mesh) => self.canvas.draw(&mesh, DrawParam::default()),
+            Err(error) => {
+                self.error.get_or_insert(error);
+            }
+        }
+    }
+
+    // Draw the batch collected so far, before anything drawn after it
+    fn flush(&mut self) {
+        if self.rects_pending {
+            self.rects_pending = false;
+            if let (Some(quad), Some(rects)) = (&self.cache.quad, &mut self.cache.rects) {
+                self.canvas.draw_instanced_mesh(quad.clone(), rects, DrawParam::default());
+                rects.clear();
+            }
+        }
+        if self.lines_pending.is_empty() {
+            return;
+        }
+        let lines = std::mem::take(&mut self.lines_pending);
+        let mesh = match self.cache.lines.iter().position(|(cached, _)| *cached == lines) {
+            Some(index) => Ok(self.cache.lines.swap_remove(index).1),
+            None => self.build_lines(&lines),
+        };
+        match mesh {
+            Ok(mesh) => {
+                self.canvas.draw(&mesh, DrawParam::default());
+                self.cache.lines_used.push((lines, mesh));
+            },
+            Err(error) => {
+                self.error.get_or_insert(error);
+            },
+        }
+    }
+
+    fn build_lines(&mut self, lines: &[Line]) -> GameResult<Mesh> {
+        let mut builder = MeshBuilder::new();
+        for line in lines {
+            let points = [ggez::glam::Vec2::new(line.from.0, line.from.1), ggez::glam::Vec2::new(line.to.0, line.to.1)];
+            builder.line(&points, line.width, to_ggez_color(line.color))?;
+        }
+        Ok(Mesh::from_data(self.ctx, builder.build()))
+    }
+
+    fn finish(mut self) -> GameResult {
+        self.flush();
+        // Batches not drawn this frame aren't needed anymore
+        self.cache.lines = std::mem::take(&mut self.cache.lines_used);
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Renderer for GgezRenderer<'_> {
+    fn fill_rect(&mut self, rect: Rect, color: Color) {
+        if !self.lines_pending.is_empty() {
+            self.flush();
+        }
+        self.prepare_rects();
+        if let Some(rects) = self.cache.rects.as_mut().filter(|_| self.cache.quad.is_some()) {
+            rects.push(DrawParam::default().dest([rect.x, rect.y]).scale([rect.w, rect.h]).color(to_ggez_color(color)));
+            self.rects_pending = true;
+        }
+    }
+
+    fn fill_polygon(&mut self, points: &[(f32, f32)], color: Color) {
+        self.flush();
+        let points: Vec<ggez::glam::Vec2> = points.iter().map(|&(x, y)| ggez::glam::Vec2::new(x, y)).collect();
+        let mesh = Mesh::new_polygon(self.ctx, graphics::DrawMode::fill(), &points, to_ggez_color(color));
+        self.draw_mesh(mesh);
+    }
+
+    fn draw_line(&mut self, from: (f32, f32), to: (f32, f32), width: f32, color: Color) {
+        if self.rects_pending {
+            self.flush();
+        }
+        self.lines_pending.push(Line { from, to, width, color });
+    }
+
+    fn draw_text(&mut self, text: &str, position: (f32, f32), scale: f32, anchor: TextAnchor, color: Color) {
+        self.flush();
+        let offset = match anchor {
+            TextAnchor::Left => [0.0, 0.5],
+            TextAnchor::Center => [0.5, 0.5],
+        };
+
+        self.canvas.draw(
+            &Text::new(text),
+            DrawParam::default()
+                .dest([position.0, position.1])
+                .color(to_ggez_color(color))
+                .scale([scale, scale])
+                .offset(offset),
+        );
+    }
+}
+
+// Keyboard state turned into one InputAction per frame, through the player's key bindings
+#[derive(Default)]
+pub struct KeyboardInput {
+    bindings: KeyBindings,
+    held_keys: HashSet<KeyCode>,
+    // Left and Right while held, oldest first
+    held_directions: Vec<InputAction>,
+    frame: FrameInput,
+    policy: ConflictPolicy,
+}
+
+// Name a key is bound by in the bindings file, e.g. "Left", "A" or "Space"
+pub fn key_name(keycode: KeyCode) -> String {
+    format!("{:?}", keycode)
+}
+
+impl KeyboardInput {
+    pub fn with_policy(policy: ConflictPolicy) -> Self {
+        Self {
+            policy,
+            ..Default::default()
+        }
+    }
+
+    pub fn policy(&self) -> ConflictPolicy {
+        self.policy
+    }
+
+    pub fn bindings(&self) -> &KeyBindings {
+        &self.bindings
+    }
+
+    // New bindings take effect for keys pressed from now on
+    pub fn set_bindings(&mut self, bindings: KeyBindings) {
+        self.bindings = bindings;
+        self.release_all();
+    }
+
+    // Forget every key held and pressed, e.g. when the game stops listening to them for a while
+    pub fn release_all(&mut self) {
+        self.held_keys.clear();
+        self.held_directions.clear();
+        self.frame = FrameInput::default();
+    }
+
+    fn control(&self, keycode: KeyCode) -> Option<Control> {
+        self.bindings.control(&key_name(keycode))
+    }
+
+    // Some key bound to the control is down
+    fn held(&self, control: Control) -> bool {
+        self.held_keys.iter().any(|&keycode| self.control(keycode) == Some(control))
+    }
+
+    pub fn key_down(&mut self, keycode: KeyCode) {
+        let Some(control) = self.control(keycode) else {
+            return;
+        };
+        match control {
+            Control::Left | Control::Right => {
+                let direction = if control == Control::Left { InputAction::Left } else { InputAction::Right };
+                self.held_keys.insert(keycode);
+                self.held_directions.retain(|&held| held != direction);
+                self.held_directions.push(direction);
+                self.frame.press_direction(direction);
+            },
+            Control::Jump => {
+                self.held_keys.insert(keycode);
+                self.frame.jump = true;
+            },
+            Control::Switch => self.frame.switch = true,
+            Control::Crouch => self.frame.crouch = true,
+            Control::Drop => self.frame.drop = true,
+            Control::Kick => self.frame.kick = true,
+            Control::Preview => {
+                self.held_keys.insert(keycode);
+            },
+        }
+    }
+
+    pub fn key_up(&mut self, keycode: KeyCode) {
+        // Remove from held keys when released
+        self.held_keys.remove(&keycode);
+        // Another key bound to the same direction keeps it held
+        match self.control(keycode) {
+            Some(Control::Left) if !self.held(Control::Left) => self.held_directions.retain(|&held| held != InputAction::Left),
+            Some(Control::Right) if !self.held(Control::Right) => self.held_directions.retain(|&held| held != InputAction::Right),
+            Some(Control::Jump) => self.frame.jump_released = true,
+            _ => {},
+        }
+    }
+
+    // Holding the preview key shows a preview of the current plan
+    pub fn preview_held(&self) -> bool {
+        self.held(Control::Preview)
+    }
+
+    // The direction the player is holding, without consuming any presses
+    pub fn planned_action(&self) -> InputAction {
+        self.policy.resolve_directions(&self.held_directions)
+    }
+}
+
+impl InputSource for KeyboardInput {
+    fn poll(&mut self) -> InputAction {
+        // Held direction keys keep moving the player, behind anything pressed this frame
+        for &direction in self.held_directions.iter().rev() {
+            self.frame.hold_direction(direction);
+        }
+
+        let action = self.frame.resolve(self.policy);
+        self.frame = FrameInput::default();
+        action
+    }
+}
 
 // Game adapter that wraps the core game state and handles ggez-specific functionality
 pub struct GameAdapter {
     game_state: GameState,
-    held_keys: HashSet<KeyCode>,
-    keys_pressed_since_update: Vec<KeyCode>,
-    direction_press_order: VecDeque<KeyCode>,
-    restart_button: Rect,
-    score_bar_height: f32,
+    keyboard: KeyboardInput,
+    layout: Layout,
+    // The About screen pauses the game while it is open
+    show_about: bool,
+    // The controls screen, open over the paused game to rebind keys
+    rebinding: Option<RebindMenu>,
+    // The settings screen, open over the paused game, changes apply as they are made
+    settings_menu: Option<SettingsMenu>,
+    // Where rebound keys are saved, they last only for the session without one
+    key_bindings_path: Option<PathBuf>,
+    // Latest row clear, shown briefly as a banner
+    clear_banner: TimedValue<ScoreBreakdown>,
+    // Where the last summary card was saved or why it failed, or a completed objective
+    notice: TimedValue<String>,
+    // Cell of the latest close call, marked with a short popup
+    close_call: TimedValue<GridPos>,
+    despawned: TimedValue<Vec<GridPos>>,
+    // Red over the board right after the player lost a life
+    hit_flash: TimedValue<()>,
+    // Hints for a new player, None once the first few games are over or hints are turned off
+    tutorial: Option<Tutorial>,
+    hint: TimedValue<Hint>,
+    // Whether the current game over was already counted in the settings
+    game_counted: bool,
+    clock: Box<dyn Clock>,
+    // Computer player that replaces the keyboard when set
+    bot: Option<Bot>,
+    // Watches for situations to play accessibility cues for, when they are turned on
+    cues: Option<CueTracker>,
+    music: AudioManager,
+    // Sources of the music in the current mix, a track changing speed gets a new source
+    music_sources: Vec<(Music, Source)>,
+    // Saved again on quit with the window's last geometry
+    settings: Settings,
+    // Turns extras off while updates take too long
+    watchdog: TickWatchdog,
+    // Drag-to-push and click-to-jump, alongside the keyboard when turned on
+    mouse: Option<MouseInput>,
+    // On-screen buttons and swipes, for touchscreens without a keyboard
+    #[cfg(feature = "touch")]
+    touch: Option<TouchControls>,
+    // Recorded on F5 and played back on F6, in practice runs only
+    input_macro: InputMacro,
+    // Where the player is drawn while it slides between cells
+    motion: PlayerMotion,
+    // Player poses and block landings, on a frame timer of their own
+    animator: Animator,
+    // Debris from landings and sparkles from clears, left out while the watchdog turns extras off
+    particles: Particles,
+    // Points rising from row clears and coins
+    popups: Popups,
+    // Window size in pixels as of the latest resize, the layout is scaled to fit it
+    window_pixels: (f32, f32),
+    // Shapes kept between frames for the renderer
+    meshes: MeshCache,
+    // Draw the board as one scaled image of a pixel per cell, for weak graphics or once meshes fail
+    low_spec: bool,
+    pixels: CellPixels,
+    // Jolts the board on row clears, close calls and the end of the game
+    shake: ScreenShake,
+    // Level editor and the file it saves to, when the game was started to edit a level
+    editor: Option<(Editor, PathBuf)>,
+    // Showing the editor rather than trying out its level
+    editing: bool,
+    // Running game of the level being edited, shown beside the editor and started over after every edit
+    preview: Option<LivePreview>,
+    // Best runs so far and the file they are kept in, nothing is kept without one
+    high_scores: HighScores,
+    high_scores_path: Option<PathBuf>,
+    // Place of the game just finished on the list, if it made it
+    new_high_score: Option<usize>,
+    // The high score screen is open over the game over screen
+    show_scores: bool,
 }
 
 impl GameAdapter {
-    pub fn new(grid_size: usize, cell_size: f32, refresh_rate: u64, block_fall_speed: usize, block_spawn_rate: u64) -> Self {
-        let config = GameConfig {
-            grid_size,
-            cell_size,
-            refresh_rate_milliseconds: refresh_rate,
-            block_fall_speed,
-            block_spawn_rate,
-        };
+    // Fails on a config no game can be played with
+    pub fn new(config: GameConfig) -> Result<Self, ConfigError> {
+        let game_state = GameState::new(config)?;
+        let layout = Layout::new(&game_state).with_locale(Locale::from_env());
+        let window_pixels = (layout.window_width(), layout.window_height());
+        let cues = game_state.audio_cues.then(CueTracker::new);
+        // An update may use half of the time until the next one
+        let budget = Duration::from_millis(game_state.refresh_rate_milliseconds) / 2;
+        let clock: Box<dyn Clock> = Box::new(SystemClock);
+        let motion = PlayerMotion::new(Duration::ZERO, game_state.player.position, clock.as_ref());
+        let animator = Animator::new(game_state.player.position, clock.as_ref());
+        let particles = Particles::new(clock.as_ref());
 
-        Self {
-            game_state: GameState::new(config),
-            held_keys: HashSet::new(),
-            keys_pressed_since_update: Vec::new(),
-            direction_press_order: VecDeque::new(),
-            restart_button: Rect::new(0.0, 0.0, 0.0, 0.0),
-            score_bar_height: cell_size,
-        }
+        Ok(Self {
+            game_state,
+            keyboard: KeyboardInput::default(),
+            layout,
+            show_about: false,
+            rebinding: None,
+            settings_menu: None,
+            key_bindings_path: None,
+            clear_banner: TimedValue::new(CLEAR_BANNER_DURATION),
+            notice: TimedValue::new(NOTICE_DURATION),
+            close_call: TimedValue::new(CLOSE_CALL_DURATION),
+            despawned: TimedValue::new(DESPAWN_FADE_DURATION),
+            hit_flash: TimedValue::new(HIT_FLASH_DURATION),
+            tutorial: Some(Tutorial::new()),
+            hint: TimedValue::new(HINT_DURATION),
+            game_counted: false,
+            clock,
+            bot: None,
+            cues,
+            music: AudioManager::new(DEFAULT_MUSIC_VOLUME),
+            music_sources: Vec::new(),
+            settings: Settings::default(),
+            watchdog: TickWatchdog::new(budget),
+            mouse: None,
+            #[cfg(feature = "touch")]
+            touch: None,
+            input_macro: InputMacro::default(),
+            motion,
+            animator,
+            particles,
+            popups: Popups::new(),
+            window_pixels,
+            meshes: MeshCache::default(),
+            low_spec: false,
+            pixels: CellPixels::new(),
+            shake: ScreenShake::new(),
+            editor: None,
+            editing: false,
+            preview: None,
+            high_scores: HighScores::new(),
+            high_scores_path: None,
+            new_high_score: None,
+            show_scores: false,
+        })
+    }
+
+    // Window size needed to show the whole grid and the score bar
+    pub fn window_size(&self) -> (f32, f32) {
+        (self.layout.window_width(), self.layout.window_height())
     }
 
-    // Convert from platform-specific representation to core representation
-    fn determine_movement(&mut self) -> InputAction {
-        // If no keys were pressed, return None
-        if self.keys_pressed_since_update.is_empty() {
-            return InputAction::None;
+    // Switch between a window and borderless fullscreen on the current monitor, remembered for next time
+    fn toggle_fullscreen(&mut self, ctx: &mut Context) -> GameResult {
+        let geometry = self.window_geometry(ctx);
+        let fullscreen = !geometry.fullscreen;
+        self.settings.window = Some(WindowGeometry { fullscreen, ..geometry });
+        if fullscreen {
+            ctx.gfx.set_fullscreen(FullscreenType::Desktop)?;
+            // The game is fitted to the monitor right away, before the resize comes in
+            if let Some(monitor) = ctx.gfx.window().current_monitor() {
+                self.window_pixels = (monitor.size().width as f32, monitor.size().height as f32);
+            }
+        } else {
+            ctx.gfx.set_fullscreen(FullscreenType::Windowed)?;
+            ctx.gfx.set_drawable_size(geometry.width, geometry.height)?;
+            self.window_pixels = (geometry.width, geometry.height);
         }
-        
-        // Check if "Up" was pressed, prioritize jump
-        if self.keys_pressed_since_update.contains(&KeyCode::Up) {
-            return InputAction::Up;
+        Ok(())
+    }
+
+    // Open fullscreen or in a window, remembered for next time
+    pub fn with_fullscreen(mut self, fullscreen: bool) -> Self {
+        let needed = self.window_size();
+        self.settings.set_fullscreen(fullscreen, needed);
+        self
+    }
+
+    // Where the layout sits in the window as it is now
+    fn letterbox(&self) -> Letterbox {
+        Letterbox::fit(&self.layout, self.window_pixels)
+    }
+
+    // Save a summary card of the finished run next to the game and tell the player its full path
+    fn share(&mut self) {
+        let notice = match std::env::current_dir().and_then(|dir| share::save_summary_card(&self.game_state, &dir)) {
+            Ok(path) => format!("Saved {}", path.display()),
+            Err(error) => format!("Couldn't save card: {}", error),
+        };
+        self.notice.set(notice, self.clock.as_ref());
+    }
+
+    // Campaign levels can change the grid size, the window follows the board
+    fn fit_layout(&mut self, ctx: &mut Context) -> GameResult {
+        let game = &self.game_state;
+        if (self.layout.grid_width, self.layout.grid_height) == (game.grid_width, game.grid_height) {
+            return Ok(());
         }
-        
-        // If we have direction keys in the order queue, return the last one
-        if !self.direction_press_order.is_empty() {
-            let last = self.direction_press_order.back().cloned();
-            return match last {
-                Some(KeyCode::Left) => InputAction::Left,
-                Some(KeyCode::Right) => InputAction::Right,
-                _ => InputAction::None,
-            };
+        self.layout = Layout::new(game).with_locale(self.layout.locale).with_grid_lines(self.layout.grid_lines);
+        let (width, height) = self.window_size();
+        ctx.gfx.set_drawable_size(width, height)
+    }
+
+    // Time an update that advanced the game, and change what is turned off when the watchdog says so
+    fn watch_tick(&mut self, started: Instant, previous_update: Instant) {
+        if self.game_state.last_update == previous_update {
+            return;
         }
-        
-        InputAction::None
+        let Some(level) = self.watchdog.record(self.clock.now().duration_since(started)) else {
+            return;
+        };
+        self.game_state.levitation_chunk = (level >= 2).then_some(DEGRADED_LEVITATION_CHUNK);
     }
 
-    // Determine the current movement direction based on held keys
-    fn get_current_movement(&self) -> InputAction {
-        if self.held_keys.contains(&KeyCode::Left) {
-            InputAction::Left
-        } else if self.held_keys.contains(&KeyCode::Right) {
-            InputAction::Right
+    // Start or stop recording the macro, or play it back, and say what happened
+    fn use_macro(&mut self, record: bool) {
+        let result = if record {
+            self.input_macro.toggle_recording(&self.game_state).map(|started| match started {
+                true => "Recording macro, F5 to stop".to_string(),
+                false => format!("Macro saved: {} actions", self.input_macro.actions.len()),
+            })
         } else {
-            InputAction::None
-        }
-    }
-
-    // Draw methods
-    fn draw_grid(&self, ctx: &mut Context, canvas: &mut Canvas, y_offset: f32) -> GameResult {
-        // Draw the grid lines
-        for i in 0..=self.game_state.grid_size {
-            let position = i as f32 * self.game_state.cell_size;
-
-            // Horizontal line
-            let h_line = Mesh::new_line(
-                ctx,
-                &[
-                    ggez::glam::Vec2::new(0.0, position + y_offset),
-                    ggez::glam::Vec2::new(self.game_state.cell_size * self.game_state.grid_size as f32, position + y_offset),
-                ],
-                1.0,
-                Color::BLACK,
-            )?;
-
-            // Vertical line
-            let v_line = Mesh::new_line(
-                ctx,
-                &[
-                    ggez::glam::Vec2::new(position, y_offset),
-                    ggez::glam::Vec2::new(position, self.game_state.cell_size * self.game_state.grid_size as f32 + y_offset),
-                ],
-                1.0,
-                Color::BLACK,
-            )?;
-
-            canvas.draw(&h_line, DrawParam::default());
-            canvas.draw(&v_line, DrawParam::default());
+            self.input_macro.play(&self.game_state).map(|_| "Playing macro".to_string())
+        };
+        let notice = result.unwrap_or_else(|error| error.to_string());
+        self.notice.set(notice, self.clock.as_ref());
+    }
+
+    // Everything in a frame, drawn through any renderer
+    fn draw_frame(&self, renderer: &mut impl Renderer) {
+        if let Some((editor, _)) = self.editor.as_ref().filter(|_| self.editing) {
+            render::draw_editor(editor, &self.layout, renderer);
+            if let Some(preview) = &self.preview {
+                render::draw_live_preview(&preview.game, &self.layout, renderer);
+            }
+            if let Some(notice) = self.notice.get(self.clock.as_ref()) {
+                render::draw_notice(notice, &self.layout, renderer);
+            }
+            return;
+        }
+        let animation = self.animator.frame(self.motion.offset(self.clock.as_ref()), self.clock.as_ref());
+        // The board shakes under the banners and screens drawn over it, which stay readable
+        let (dx, dy) = self.shake.offset(self.clock.as_ref());
+        let mut camera = render::Camera { offset: (dx * self.layout.cell_size, dy * self.layout.cell_size), inner: &mut *renderer };
+        render::draw_game_animated(&self.game_state, &self.layout, &animation, &mut camera);
+        render::draw_landing_ghosts(&self.game_state, &self.layout, &mut camera);
+        render::draw_particles(self.particles.visible(self.clock.as_ref()), &self.layout, &mut camera);
+        render::draw_popups(self.popups.visible(self.clock.as_ref()), &self.layout, &mut camera);
+        if let Some(fraction) = self.hit_flash.fraction_left(self.clock.as_ref()) {
+            render::draw_hit_flash(fraction, &self.layout, &mut camera);
+        }
+        if let Some(breakdown) = self.clear_banner.get(self.clock.as_ref()) {
+            render::draw_clear_banner(breakdown, &self.layout, renderer);
+        }
+        if let Some(notice) = self.notice.get(self.clock.as_ref()) {
+            render::draw_notice(notice, &self.layout, renderer);
+        }
+        if let Some(hint) = self.hint.get(self.clock.as_ref()) {
+            render::draw_hint(hint.message(), &self.layout, renderer);
+        }
+        if let (Some(cells), Some(fraction)) = (self.despawned.get(self.clock.as_ref()), self.despawned.fraction_left(self.clock.as_ref())) {
+            render::draw_despawned(cells, fraction, &self.layout, renderer);
+        }
+        if let Some(&cell) = self.close_call.get(self.clock.as_ref()) {
+            render::draw_close_call(cell, &self.layout, renderer);
+        }
+        render::draw_degradation(self.watchdog.level, &self.layout, renderer);
+        if let Some(recorded) = self.input_macro.recorded() {
+            render::draw_macro_recording(recorded, &self.layout, renderer);
+        }
+        if let Some(mouse) = &self.mouse {
+            render::draw_mouse_affordances(mouse, &self.game_state, &self.layout, renderer);
         }
+        #[cfg(feature = "touch")]
+        if let Some(touch) = self.touch.as_ref().filter(|_| !self.game_state.game_over) {
+            render::draw_touch_controls(touch, &self.layout, renderer);
+        }
+        // The preview is the first extra to go when updates run slow
+        if self.keyboard.preview_held() && !self.game_state.game_over && self.watchdog.level == 0 {
+            // Ghost of the next second if the held direction is kept
+            let ticks = (1000 / self.game_state.refresh_rate_milliseconds.max(1)).max(1) as usize;
+            let plan = vec![self.keyboard.planned_action(); ticks];
+            let preview = self.game_state.simulate(&plan, ticks);
+            render::draw_preview(&preview, &self.layout, renderer);
+        }
+        if self.show_scores {
+            render::draw_high_scores(&self.high_scores, self.new_high_score, &self.layout, renderer);
+        }
+        if let Some(menu) = &self.rebinding {
+            render::draw_key_bindings(self.keyboard.bindings(), menu, &self.layout, renderer);
+        }
+        if let Some(menu) = &self.settings_menu {
+            render::draw_settings(&self.settings, menu, &self.layout, renderer);
+        }
+        if self.show_about {
+            render::draw_about(&self.layout, renderer);
+        }
+    }
 
-        Ok(())
+    // The board as one image of a pixel per cell, scaled up without smoothing
+    fn draw_cell_image(&mut self, ctx: &mut Context, canvas: &mut Canvas) {
+        self.pixels.paint(&self.game_state);
+        let (width, height) = (self.pixels.width as u32, self.pixels.height as u32);
+        let image = Image::from_pixels(ctx, &self.pixels.rgba, ImageFormat::Rgba8UnormSrgb, width, height);
+        canvas.set_sampler(Sampler::nearest_clamp());
+        let scale = [self.layout.cell_size, self.layout.cell_size];
+        canvas.draw(&image, DrawParam::default().dest([self.layout.origin_x, 0.0]).scale(scale));
+        canvas.set_default_sampler();
     }
 
-    fn draw_score_bar(&self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult {
-        let score_bar = Mesh::new_rectangle(
-            ctx,
-            graphics::DrawMode::fill(),
-            Rect::new(
-                0.0,
-                0.0,
-                self.game_state.grid_size as f32 * self.game_state.cell_size,
-                self.score_bar_height,
-            ),
-            Color::BLUE,
-        )?;
-        canvas.draw(&score_bar, DrawParam::default());
-        
-        let score_text = Text::new(format!("Score: {}", self.game_state.score));
-        let text_x = 10.0; // Left padding
-        let text_y = self.score_bar_height / 2.0;
-        
-        canvas.draw(
-            &score_text, 
-            DrawParam::default()
-                .dest([text_x, text_y])
-                .color(Color::WHITE)
-                .offset([0.0, 0.5]) // Center vertically
-        );
-        
+    // Play the sound of every cue that just started
+    fn play_cues(&mut self, ctx: &mut Context) -> GameResult {
+        let Some(tracker) = &mut self.cues else {
+            return Ok(());
+        };
+        for cue in tracker.update(&self.game_state) {
+            let data = SoundData::from_bytes(&cue_sound::cue_wav(cue));
+            Source::from_data(ctx, data)?.play_detached(ctx)?;
+        }
         Ok(())
     }
 
-    fn draw_restart_button(&mut self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult {
-        let button_width = 80.0;
-        let button_height = self.score_bar_height * 0.8;
-        let button_x = self.game_state.grid_size as f32 * self.game_state.cell_size - button_width - 10.0;
-        let button_y = (self.score_bar_height - button_height) / 2.0;
-        
-        self.restart_button = Rect::new(button_x, button_y, button_width, button_height);
-        
-        let restart_button_mesh = Mesh::new_rectangle(
-            ctx,
-            graphics::DrawMode::fill(),
-            self.restart_button,
-            Color::GREEN,
-        )?;
-        canvas.draw(&restart_button_mesh, DrawParam::default());
-        
-        let restart_text = Text::new("Restart");
-        let restart_text_x = button_x + button_width / 2.0;
-        let restart_text_y = button_y + button_height / 2.0;
-        
-        canvas.draw(
-            &restart_text, 
-            DrawParam::default()
-                .dest([restart_text_x, restart_text_y])
-                .color(Color::BLACK)
-                .offset([0.5, 0.5])
-        );
-        
+    // Crossfade the background music toward what the game calls for
+    fn play_music(&mut self, ctx: &mut Context) -> GameResult {
+        if self.music.volume == 0.0 {
+            self.music_sources.clear();
+            return Ok(());
+        }
+        let mix = self.music.update(&self.game_state, self.clock.as_ref());
+        self.music_sources.retain(|(music, _)| mix.iter().any(|(playing, _)| playing == music));
+        for (music, volume) in mix {
+            if !self.music_sources.iter().any(|(playing, _)| *playing == music) {
+                let mut source = Source::from_data(ctx, SoundData::from_bytes(&music::track_wav(music.track)))?;
+                source.set_repeat(true);
+                source.set_pitch(music.speed());
+                source.set_volume(0.0);
+                source.play(ctx)?;
+                self.music_sources.push((music, source));
+            }
+            if let Some((_, source)) = self.music_sources.iter_mut().find(|(playing, _)| *playing == music) {
+                source.set_volume(volume);
+            }
+        }
         Ok(())
     }
 
-    fn draw_player(&self, ctx: &mut Context, canvas: &mut Canvas, y_offset: f32) -> GameResult {
-        let player_pos = self.game_state.player.position;
-        let player_mesh = Mesh::new_rectangle(
-            ctx,
-            graphics::DrawMode::fill(),
-            Rect::new(
-                player_pos.0 as f32 * self.game_state.cell_size,
-                player_pos.1 as f32 * self.game_state.cell_size,
-                self.game_state.cell_size,
-                self.game_state.cell_size * self.game_state.player.body_size as f32,
-            ),
-            Color::RED,
-        )?;
-        canvas.draw(&player_mesh, DrawParam::default().dest([0.0, y_offset]));
-        
-        Ok(())
+    // Replace the time source, e.g. with a mock clock in tests
+    pub fn with_clock(mut self, clock: Box<dyn Clock>) -> Self {
+        self.motion = PlayerMotion::new(self.motion.duration, self.game_state.player.position, clock.as_ref());
+        self.animator = Animator::new(self.game_state.player.position, clock.as_ref());
+        self.particles = Particles::new(clock.as_ref());
+        self.popups.clear();
+        self.shake.clear();
+        self.clock = clock;
+        self
     }
 
-    fn draw_blocks(&self, ctx: &mut Context, canvas: &mut Canvas, y_offset: f32) -> GameResult {
-        for block in &self.game_state.blocks {
-            let (x, y) = block.position;
-            let block_mesh = Mesh::new_rectangle(
-                ctx,
-                graphics::DrawMode::fill(),
-                Rect::new(
-                    x as f32 * self.game_state.cell_size,
-                    y as f32 * self.game_state.cell_size,
-                    self.game_state.cell_size,
-                    self.game_state.cell_size,
-                ),
-                Color::BLACK,
-            )?;
-            canvas.draw(&block_mesh, DrawParam::default().dest([0.0, y_offset]));
+    // Settings loaded at startup, written back when the game closes
+    pub fn with_settings(mut self, settings: Settings) -> Self {
+        self.motion.duration = Duration::from_millis(settings.animation_millis);
+        self.music.volume = settings.music_volume.unwrap_or(DEFAULT_MUSIC_VOLUME);
+        self.shake.intensity = settings.shake_intensity.unwrap_or(DEFAULT_SHAKE_INTENSITY);
+        self.input_macro = InputMacro::new(settings.input_macro.clone());
+        self.low_spec = settings.low_spec;
+        self.layout.grid_lines = !settings.hide_grid;
+        // The window opens at its saved size, until the first resize says otherwise
+        if let Some(geometry) = settings.window {
+            self.window_pixels = geometry.dimensions(self.window_size());
         }
-        
-        Ok(())
+        self.settings = settings;
+        self.update_tutorial();
+        self
     }
 
-    fn draw_game_over(&self, canvas: &mut Canvas) -> GameResult {
-        if !self.game_state.game_over {
+    // Keep the best runs in a file, loading the ones already there
+    pub fn with_high_scores(mut self, path: PathBuf) -> Self {
+        self.high_scores = scores::load_from(&path);
+        self.high_scores_path = Some(path);
+        self
+    }
+
+    // Put a finished game on the high score list if it made it there
+    fn record_high_score(&mut self) {
+        let Some(path) = self.high_scores_path.as_ref().filter(|_| !self.game_state.practice) else {
+            return;
+        };
+        let entry = ScoreEntry {
+            score: self.game_state.score,
+            date: share::utc_date(SystemTime::now()),
+            mode: render::mode_name(&self.game_state).to_string(),
+            seed: self.game_state.seed,
+        };
+        self.new_high_score = self.high_scores.insert(entry);
+        if let Some(rank) = self.new_high_score {
+            if let Err(error) = scores::save_to(&self.high_scores, path) {
+                eprintln!("Couldn't save high scores: {}", error);
+            }
+            self.notice.set(format!("New high score: #{}", rank + 1), self.clock.as_ref());
+        }
+    }
+
+    // Open the level editor instead of a game, saving the level to a file
+    // Fails on a level too small to play on
+    pub fn with_editor(mut self, editor: Editor, path: PathBuf) -> Result<Self, ConfigError> {
+        self.game_state = GameState::from_level(&editor.level, self.editor_config())?;
+        self.layout = Layout::new(&self.game_state)
+            .with_locale(self.layout.locale)
+            .with_grid_lines(self.layout.grid_lines)
+            .with_split_view(true);
+        self.preview = Some(LivePreview::new(&editor.level, self.editor_config())?);
+        self.editor = Some((editor, path));
+        self.editing = true;
+        Ok(self)
+    }
+
+    // Try out the edited level, or say why it can't be played
+    fn play_edited_level(&mut self, ctx: &mut Context) -> GameResult {
+        let Some((editor, _)) = &self.editor else {
             return Ok(());
+        };
+        let game = editor.level.validate()
+            .map_err(|err| err.to_string())
+            .and_then(|()| GameState::from_level(&editor.level, self.editor_config()).map_err(|err| err.to_string()));
+        match game {
+            Ok(game) => self.game_state = game,
+            Err(message) => {
+                self.notice.set(message, self.clock.as_ref());
+                return Ok(());
+            },
+        }
+        self.editing = false;
+        self.notice.set("Press E to go back to the editor".to_string(), self.clock.as_ref());
+        self.fit_layout(ctx)?;
+        self.set_split_view(ctx, false)
+    }
+
+    // Let a bot play the editor's live preview instead of leaving the player standing
+    pub fn with_preview_bot(mut self, bot: Bot) -> Self {
+        if let Some(preview) = &mut self.preview {
+            preview.bot = Some(bot);
+        }
+        self
+    }
+
+    // The editor shows its live preview beside the grid, trying the level out uses the grid alone
+    fn set_split_view(&mut self, ctx: &mut Context, split_view: bool) -> GameResult {
+        self.layout.split_view = split_view && self.preview.is_some();
+        let (width, height) = self.window_size();
+        ctx.gfx.set_drawable_size(width, height)
+    }
+
+    // Keep the live preview running, starting it over whenever the level was edited
+    fn update_preview(&mut self) {
+        let (Some((editor, _)), Some(preview)) = (&self.editor, &mut self.preview) else {
+            return;
+        };
+        preview.sync(&editor.level);
+        preview.update();
+    }
+
+    fn save_edited_level(&mut self) {
+        let Some((editor, path)) = &self.editor else {
+            return;
+        };
+        let notice = match editor.save(path) {
+            Ok(()) => format!("Saved to {}", path.display()),
+            Err(err) => format!("Couldn't save: {}", err),
+        };
+        self.notice.set(notice, self.clock.as_ref());
+    }
+
+    // Left click picks from the palette or uses the tool on a cell, right click erases a cell
+    fn editor_click(&mut self, ctx: &mut Context, button: MouseButton, x: f32, y: f32) -> GameResult {
+        match (button, self.layout.editor_button_at(x, y)) {
+            (MouseButton::Left, Some(EditorButton::Save)) => self.save_edited_level(),
+            (MouseButton::Left, Some(EditorButton::Play)) => return self.play_edited_level(ctx),
+            (MouseButton::Left, Some(EditorButton::Tool(tool))) => {
+                if let Some((editor, _)) = &mut self.editor {
+                    editor.tool = tool;
+                }
+            },
+            _ => {
+                let (Some(cell), Some((editor, _))) = (self.layout.cell_at(x, y), &mut self.editor) else {
+                    return Ok(());
+                };
+                match button {
+                    MouseButton::Left => editor.apply(cell),
+                    MouseButton::Right => editor.erase(cell),
+                    _ => {},
+                }
+            },
         }
-        
-        let window_width = self.game_state.grid_size as f32 * self.game_state.cell_size;
-        let window_height = window_width + self.game_state.cell_size;
-        let game_over_text = Text::new("Game Over");
-        
-        let text_x = window_width / 2.0;
-        let text_y = window_height / 2.0;
-        
-        canvas.draw(
-            &game_over_text, 
-            DrawParam::default()
-                .dest([text_x, text_y])
-                .color(Color::RED)
-                .scale([2.0, 2.0])
-                .offset([0.5, 0.5])
-        );
-        
         Ok(())
     }
-}
 
-// Implement ggez EventHandler for the GameAdapter
-impl EventHandler for GameAdapter {
-    fn update(&mut self, _ctx: &mut Context) -> GameResult {
-        // Skip updates if the game is over
-        if self.game_state.game_over {
+    fn editor_config(&self) -> GameConfig {
+        GameConfig {
+            cell_size: self.layout.cell_size,
+            refresh_rate_milliseconds: self.game_state.refresh_rate_milliseconds,
+            ..GameConfig::default()
+        }
+    }
+
+    // Turn tutorial hints on or off, kept in the settings for next time
+    pub fn with_hints(mut self, enabled: bool) -> Self {
+        self.settings.hide_hints = !enabled;
+        self.update_tutorial();
+        self
+    }
+
+    fn update_tutorial(&mut self) {
+        let wanted = !self.settings.hide_hints && Tutorial::applies_to(self.settings.games_played);
+        match (wanted, self.tutorial.is_some()) {
+            (true, false) => self.tutorial = Some(Tutorial::new()),
+            (false, true) => self.tutorial = None,
+            _ => {},
+        }
+    }
+
+    // Background music volume from 0 to 1, remembered in the settings
+    pub fn with_music_volume(mut self, volume: f32) -> Self {
+        let volume = volume.clamp(0.0, 1.0);
+        self.settings.music_volume = Some(volume);
+        self.music.volume = volume;
+        self
+    }
+
+    // How far heavy impacts shake the board, in cells, 0 to keep it still
+    pub fn with_shake_intensity(mut self, intensity: f32) -> Self {
+        self.shake = self.shake.with_intensity(intensity);
+        self.settings.shake_intensity = Some(self.shake.intensity);
+        self
+    }
+
+    // Draw the board as one scaled image instead of a shape per block
+    pub fn with_low_spec(mut self, low_spec: bool) -> Self {
+        self.low_spec = low_spec;
+        self.settings.low_spec = low_spec;
+        self
+    }
+
+    // How fast a shake settles, per second
+    pub fn with_shake_damping(mut self, damping: f32) -> Self {
+        self.shake = self.shake.with_damping(damping);
+        self
+    }
+
+    // Change how long the player slides between cells, kept in the settings for next time
+    pub fn with_animation_millis(mut self, millis: u64) -> Self {
+        self.settings.animation_millis = millis;
+        self.motion.duration = Duration::from_millis(millis);
+        self
+    }
+
+    // Window setup for the next session: the saved geometry if there is one, fitted around the grid
+    pub fn window_mode(&self) -> WindowMode {
+        let needed = self.window_size();
+        let Some(geometry) = self.settings.window else {
+            return WindowMode::default().dimensions(needed.0, needed.1).resizable(true);
+        };
+        let (width, height) = geometry.dimensions(needed);
+        let fullscreen = if geometry.fullscreen { FullscreenType::Desktop } else { FullscreenType::Windowed };
+        WindowMode::default().dimensions(width, height).fullscreen_type(fullscreen).resizable(true)
+    }
+
+    // Move the new window to where it was last time, kept on one of the monitors still connected
+    pub fn restore_window_position(&self, ctx: &Context) -> GameResult {
+        let Some(geometry) = self.settings.window.filter(|geometry| !geometry.fullscreen) else {
             return Ok(());
+        };
+        let monitors: Vec<MonitorArea> = ctx.gfx.window().available_monitors()
+            .map(|monitor| MonitorArea {
+                x: monitor.position().x,
+                y: monitor.position().y,
+                width: monitor.size().width,
+                height: monitor.size().height,
+            })
+            .collect();
+        match geometry.position_on(&monitors) {
+            Some((x, y)) => ctx.gfx.set_window_position(PhysicalPosition::new(x, y)),
+            None => Ok(()),
+        }
+    }
+
+    // The window's size, position and fullscreen state
+    // A fullscreen window keeps the windowed geometry it had before, so leaving fullscreen restores it
+    fn window_geometry(&self, ctx: &Context) -> WindowGeometry {
+        let fullscreen = ctx.gfx.window().fullscreen().is_some();
+        match (fullscreen, self.settings.window) {
+            (true, Some(previous)) => WindowGeometry { fullscreen, ..previous },
+            _ => {
+                let (width, height) = ctx.gfx.drawable_size();
+                let position = ctx.gfx.window_position().unwrap_or_default();
+                WindowGeometry { width, height, x: position.x, y: position.y, fullscreen }
+            },
+        }
+    }
+
+    fn save_window_geometry(&mut self, ctx: &Context) {
+        self.settings.window = Some(self.window_geometry(ctx));
+        if let Err(error) = self.settings.save() {
+            eprintln!("Couldn't save settings: {}", error);
+        }
+    }
+
+    // Also control the player with the mouse
+    pub fn with_mouse_controls(mut self) -> Self {
+        self.mouse = Some(MouseInput::new(self.layout.cell_size));
+        self
+    }
+
+    // Feed a frame of touch input to the game if a touch has something to do, returns whether it did
+    #[cfg(feature = "touch")]
+    fn step_touch(&mut self) -> bool {
+        match self.touch.as_mut().filter(|touch| touch.has_action()) {
+            Some(touch) => {
+                input::step(&mut self.game_state, &mut self.input_macro.recorder(touch));
+                true
+            },
+            None => false,
         }
+    }
+
+    #[cfg(not(feature = "touch"))]
+    fn step_touch(&mut self) -> bool {
+        false
+    }
+
+    // Also control the player with on-screen buttons and swipes
+    #[cfg(feature = "touch")]
+    pub fn with_touch_controls(mut self) -> Self {
+        self.touch = Some(TouchControls::new());
+        self
+    }
 
-        // Add held direction keys to the keys_pressed_since_update for continuous movement
-        if self.held_keys.contains(&KeyCode::Left) {
-            self.keys_pressed_since_update.push(KeyCode::Left);
-            if !self.direction_press_order.contains(&KeyCode::Left) {
-                self.direction_press_order.push_back(KeyCode::Left);
+    // How the keyboard settles Left and Right pressed together
+    pub fn with_conflict_policy(mut self, policy: ConflictPolicy) -> Self {
+        self.keyboard.policy = policy;
+        self
+    }
+
+    // Map keys through a bindings file, writing the defaults there first so there is a file to edit
+    // Fails on a file that can't be read or doesn't parse
+    pub fn with_key_bindings(mut self, path: PathBuf) -> io::Result<Self> {
+        if !path.exists() {
+            if let Err(error) = KeyBindings::default().save_to(&path) {
+                eprintln!("Couldn't write key bindings: {}", error);
             }
         }
-        if self.held_keys.contains(&KeyCode::Right) {
-            self.keys_pressed_since_update.push(KeyCode::Right);
-            if !self.direction_press_order.contains(&KeyCode::Right) {
-                self.direction_press_order.push_back(KeyCode::Right);
+        self.keyboard.set_bindings(KeyBindings::load_from(&path)?);
+        self.key_bindings_path = Some(path);
+        Ok(self)
+    }
+
+    // A key pressed on the controls screen: moves the pick, starts a rebind or is the key bound
+    fn rebind_key(&mut self, keycode: KeyCode) {
+        let Some(menu) = &mut self.rebinding else {
+            return;
+        };
+        if !menu.waiting {
+            match keycode {
+                KeyCode::Up => menu.move_selection(-1),
+                KeyCode::Down => menu.move_selection(1),
+                KeyCode::Return | KeyCode::NumpadEnter => menu.waiting = true,
+                KeyCode::Escape | KeyCode::F2 => self.rebinding = None,
+                _ => {},
             }
+            return;
         }
-        
-        // Process the key presses according to priority rules
-        let action = self.determine_movement();
-        
-        // Process the input in the game state
-        self.game_state.process_input(action);
-        
-        // Clear keys pressed since update and direction order
-        self.keys_pressed_since_update.clear();
-        self.direction_press_order.clear();
+        // Escape gives up on the rebind, it can't be bound itself
+        if keycode == KeyCode::Escape {
+            menu.waiting = false;
+            return;
+        }
+        let mut bindings = self.keyboard.bindings().clone();
+        if !menu.key_pressed(&key_name(keycode), &mut bindings) {
+            return;
+        }
+        self.keyboard.set_bindings(bindings);
+        if let Some(path) = &self.key_bindings_path {
+            if let Err(error) = self.keyboard.bindings().save_to(path) {
+                eprintln!("Couldn't save key bindings: {}", error);
+            }
+        }
+    }
 
-        // Update game state
-        self.game_state.update();
+    // A key pressed on the settings screen: moves the pick or changes the picked setting, saved when the screen closes
+    fn settings_key(&mut self, ctx: &Context, keycode: KeyCode) {
+        let Some(menu) = &mut self.settings_menu else {
+            return;
+        };
+        let step = match keycode {
+            KeyCode::Up => return menu.move_selection(-1),
+            KeyCode::Down => return menu.move_selection(1),
+            KeyCode::Left => -1,
+            KeyCode::Right => 1,
+            // The key bindings have a screen of their own
+            KeyCode::Return | KeyCode::NumpadEnter if menu.selected_item() == SettingsItem::KeyBindings => {
+                self.rebinding = Some(RebindMenu::default());
+                self.settings_menu = None;
+                return self.save_window_geometry(ctx);
+            },
+            KeyCode::Return | KeyCode::NumpadEnter => 1,
+            KeyCode::Escape | KeyCode::F3 => {
+                self.settings_menu = None;
+                return self.save_window_geometry(ctx);
+            },
+            _ => return,
+        };
+        if menu.adjust(&mut self.settings, step) {
+            self.apply_settings();
+        }
+    }
 
-        Ok(())
+    // Take up changed settings, all but the grid size which waits for the next start
+    fn apply_settings(&mut self) {
+        self.music.volume = self.settings.music_volume.unwrap_or(DEFAULT_MUSIC_VOLUME);
+        self.layout.grid_lines = !self.settings.hide_grid;
+        let theme = self.settings.theme.unwrap_or(self.game_state.mode.style().theme);
+        self.game_state.base_theme = theme;
+        self.game_state.theme = theme;
     }
 
-    fn draw(&mut self, ctx: &mut Context) -> GameResult {
-        let mut canvas = graphics::Canvas::from_frame(ctx, Color::WHITE);
+    // Let a bot play instead of the keyboard
+    pub fn with_bot(mut self, bot: Bot) -> Self {
+        self.bot = Some(bot);
+        self
+    }
+}
 
-        self.draw_score_bar(ctx, &mut canvas)?;
-        self.draw_restart_button(ctx, &mut canvas)?;
+// Implement ggez EventHandler for the GameAdapter
+impl EventHandler for GameAdapter {
+    fn update(&mut self, ctx: &mut Context) -> GameResult {
+        if self.editing && !self.show_about {
+            self.update_preview();
+        }
+        if self.show_about || self.editing || self.rebinding.is_some() || self.settings_menu.is_some() {
+            return Ok(());
+        }
+        let (started, previous_update) = (self.clock.now(), self.game_state.last_update);
+        match &self.bot {
+            // A macro being played back takes over from the player, one action per update like the bot
+            _ if self.input_macro.is_playing() => {
+                let refresh = Duration::from_millis(self.game_state.refresh_rate_milliseconds);
+                if !self.game_state.game_over && self.game_state.last_update.elapsed() >= refresh {
+                    if let Some(action) = self.input_macro.next_action() {
+                        self.game_state.process_input_from(action, InputDevice::Bot);
+                    }
+                }
+                self.game_state.update();
+            },
+            Some(bot) => {
+                // The bot decides once per game update
+                let refresh = Duration::from_millis(self.game_state.refresh_rate_milliseconds);
+                if !self.game_state.game_over && self.game_state.last_update.elapsed() >= refresh {
+                    let action = bot.choose_action(&self.game_state);
+                    self.game_state.process_input_from(action, InputDevice::Bot);
+                }
+                self.game_state.update();
+            },
+            // A touch or queued mouse action takes the frame, otherwise the keyboard has it
+            None => {
+                if !self.step_touch() {
+                    match self.mouse.as_mut().filter(|mouse| mouse.has_action()) {
+                        Some(mouse) => {
+                            input::step(&mut self.game_state, &mut self.input_macro.recorder(mouse));
+                        },
+                        None => {
+                            input::step(&mut self.game_state, &mut self.input_macro.recorder(&mut self.keyboard));
+                        },
+                    }
+                }
+            },
+        }
+        self.watch_tick(started, previous_update);
+        if self.game_state.last_update != previous_update {
+            self.input_macro.end_update();
+        }
+        self.fit_layout(ctx)?;
+        self.motion.follow(self.game_state.player.position, self.clock.as_ref());
+        self.animator.observe(&self.game_state, self.clock.as_ref());
 
-        // Define the offset for all game elements
-        let y_offset = self.score_bar_height;
+        for event in self.game_state.drain_events() {
+            if let Some(hint) = self.tutorial.as_mut().and_then(|tutorial| tutorial.hint(&event)) {
+                self.hint.set(hint, self.clock.as_ref());
+            }
+            self.popups.observe(&event, self.game_state.grid_width, self.clock.as_ref());
+            match event {
+                GameEvent::RowsCleared(breakdown) => {
+                    self.shake.kick(Impact::RowsCleared(breakdown.rows), self.clock.as_ref());
+                    self.clear_banner.set(breakdown, self.clock.as_ref());
+                },
+                // The storm banner follows the game state, there is no sound to start yet
+                GameEvent::StormWarning | GameEvent::StormStarted | GameEvent::StormEnded => {},
+                GameEvent::ObjectiveCompleted(kind) => {
+                    self.notice.set(format!("Objective done: {}", kind), self.clock.as_ref());
+                },
+                // Only shown as tutorial hints
+                GameEvent::RowAlmostComplete(_) | GameEvent::BlockCaught(_) => {},
+                // Only shown as a popup
+                GameEvent::CoinCollected(..) => {},
+                GameEvent::BlockLanded(_, position) => {
+                    self.animator.block_landed(position, self.clock.as_ref());
+                    if self.watchdog.level == 0 {
+                        self.particles.block_landed(position, self.clock.as_ref());
+                    }
+                },
+                GameEvent::RowCleared(row) => {
+                    if self.watchdog.level == 0 {
+                        self.particles.row_cleared(row, self.game_state.grid_width, self.clock.as_ref());
+                    }
+                },
+                GameEvent::CampaignLevelStarted(index) => {
+                    self.notice.set(format!("Level {}", index + 1), self.clock.as_ref());
+                },
+                GameEvent::BlocksDespawned(cells) => self.despawned.set(cells, self.clock.as_ref()),
+                GameEvent::PlayerHit => self.hit_flash.set((), self.clock.as_ref()),
+                GameEvent::Exploded(center) => {
+                    self.shake.kick(Impact::Explosion, self.clock.as_ref());
+                    if self.watchdog.level == 0 {
+                        self.particles.explosion(center, self.clock.as_ref());
+                    }
+                },
+                GameEvent::CloseCall(cell) => {
+                    self.close_call.set(cell, self.clock.as_ref());
+                    self.shake.kick(Impact::CloseCall, self.clock.as_ref());
+                    let data = SoundData::from_bytes(&cue_sound::whoosh_wav());
+                    Source::from_data(ctx, data)?.play_detached(ctx)?;
+                },
+            }
+        }
+        
+        // Each finished game counts toward the end of the tutorial
+        if self.game_state.game_over && !self.game_counted {
+            self.shake.kick(Impact::GameOver, self.clock.as_ref());
+            self.input_macro.stop();
+            self.settings.games_played += 1;
+            self.update_tutorial();
+            self.record_high_score();
+        }
+        if !self.game_state.game_over {
+            self.new_high_score = None;
+            self.show_scores = false;
+        }
+        self.game_counted = self.game_state.game_over;
+        self.play_music(ctx)?;
+        self.play_cues(ctx)
+    }
 
-        self.draw_grid(ctx, &mut canvas, y_offset)?;
-        self.draw_player(ctx, &mut canvas, y_offset)?;
-        self.draw_blocks(ctx, &mut canvas, y_offset)?;
-        self.draw_game_over(&mut canvas)?;
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        let mut canvas = graphics::Canvas::from_frame(ctx, to_ggez_color(self.game_state.theme.background()));
+        let area = self.letterbox().visible_area(self.window_pixels);
+        canvas.set_screen_coordinates(graphics::Rect::new(area.x, area.y, area.w, area.h));
+
+        // The editor always draws in full, it has no cell image
+        let low_spec = self.low_spec && !self.editing;
+        if low_spec {
+            self.draw_cell_image(ctx, &mut canvas);
+        }
+        let mut meshes = std::mem::take(&mut self.meshes);
+        let mut renderer = GgezRenderer::new(ctx, &mut canvas, &mut meshes);
+        if low_spec {
+            self.draw_frame(&mut render::TextOnly { inner: &mut renderer });
+        } else {
+            self.draw_frame(&mut renderer);
+        }
+        let result = renderer.finish();
+        self.meshes = meshes;
+        // Graphics that can't build meshes still get a board to play on
+        if let Err(error) = result {
+            eprintln!("Couldn't draw with meshes ({}), switching to low-spec rendering", error);
+            self.low_spec = true;
+        }
 
         canvas.finish(ctx)?;
         Ok(())
@@ -292,56 +1207,194 @@ impl EventHandler for GameAdapter {
 
     fn key_down_event(
         &mut self,
-        _ctx: &mut Context,
+        ctx: &mut Context,
         key_input: KeyInput,
         _repeat: bool,
     ) -> GameResult {
-        // Ignore input if game is over
-        if self.game_state.game_over {
+        if key_input.keycode == Some(KeyCode::F1) {
+            self.show_about = !self.show_about;
             return Ok(());
         }
 
-        if let Some(keycode) = key_input.keycode {
-            match keycode {
-                KeyCode::Left | KeyCode::Right | KeyCode::Up => {
-                    // Add to held keys
-                    self.held_keys.insert(keycode);
-                    
-                    // Add to keys pressed since update
-                    self.keys_pressed_since_update.push(keycode);
-                    
-                    // Update direction order for left/right keys
-                    if keycode == KeyCode::Left || keycode == KeyCode::Right {
-                        // Remove the key if it's already in the queue (to update its position)
-                        if let Some(pos) = self.direction_press_order.iter().position(|&k| k == keycode) {
-                            self.direction_press_order.remove(pos);
-                        }
-                        // Add it to the back (most recent)
-                        self.direction_press_order.push_back(keycode);
-                    }
-                },
-                _ => {}
+        // While the controls screen is open every key goes to it
+        if self.rebinding.is_some() {
+            if let Some(keycode) = key_input.keycode {
+                self.rebind_key(keycode);
             }
+            return Ok(());
+        }
+
+        // While the settings screen is open every key goes to it
+        if self.settings_menu.is_some() {
+            if let Some(keycode) = key_input.keycode {
+                self.settings_key(ctx, keycode);
+            }
+            return Ok(());
+        }
+
+        // F3 opens the settings screen, pausing the game
+        if key_input.keycode == Some(KeyCode::F3) && !self.editing && !self.show_about {
+            self.settings_menu = Some(SettingsMenu::default());
+            self.keyboard.release_all();
+            return Ok(());
+        }
+
+        // F2 opens the controls screen, pausing the game
+        if key_input.keycode == Some(KeyCode::F2) && !self.editing && !self.show_about {
+            self.rebinding = Some(RebindMenu::default());
+            self.keyboard.release_all();
+            return Ok(());
+        }
+
+        // F11 switches between a window and fullscreen, also while editing or on the game over screen
+        if key_input.keycode == Some(KeyCode::F11) {
+            return self.toggle_fullscreen(ctx);
+        }
+
+        // E leaves a level being tried out for the editor again
+        if key_input.keycode == Some(KeyCode::E) && self.editor.is_some() && !self.editing {
+            self.editing = true;
+            self.keyboard.release_all();
+            return self.set_split_view(ctx, true);
+        }
+
+        // F5 starts and stops recording a macro, F6 plays it back, both refused outside practice
+        if matches!(key_input.keycode, Some(KeyCode::F5 | KeyCode::F6)) && !self.game_state.game_over && !self.show_about {
+            self.use_macro(key_input.keycode == Some(KeyCode::F5));
+            return Ok(());
+        }
+
+        // Ctrl+R plays the same run again, even from the game over screen
+        if key_input.keycode == Some(KeyCode::R) && key_input.mods.contains(KeyMods::CTRL) && !self.show_about {
+            self.game_state.process_input(InputAction::RestartSameSeed);
+            return Ok(());
+        }
+
+        // Ignore input if game is over or the About screen is open
+        if self.game_state.game_over || self.show_about {
+            return Ok(());
+        }
+
+        if let Some(keycode) = key_input.keycode {
+            self.keyboard.key_down(keycode);
         }
         Ok(())
     }
-    
+
     fn mouse_button_down_event(
         &mut self,
-        _ctx: &mut Context,
+        ctx: &mut Context,
         button: MouseButton,
         x: f32,
         y: f32,
     ) -> GameResult {
+        // Buttons and cells are hit-tested where the letterboxed layout put them
+        let (x, y) = self.letterbox().to_layout((x, y));
+        if self.editing {
+            return self.editor_click(ctx, button, x, y);
+        }
         if button == MouseButton::Left {
+            // Any click closes the high scores again
+            if self.show_scores {
+                self.show_scores = false;
+            } else if self.game_state.game_over && self.layout.scores_button().contains(x, y) {
+                self.show_scores = true;
             // Check if click was inside the restart button
-            if self.restart_button.contains([x, y]) {
+            } else if self.layout.restart_button().contains(x, y) {
                 self.game_state.restart();
+            } else if self.game_state.game_over && self.layout.share_button().contains(x, y) {
+                self.share();
+            } else if let Some(mouse) = self.mouse.as_mut().filter(|_| !self.game_state.game_over && !self.show_about) {
+                mouse.press((x, y), self.layout.player_rect(&self.game_state));
             }
         }
         Ok(())
     }
 
+    fn mouse_button_up_event(
+        &mut self,
+        _ctx: &mut Context,
+        button: MouseButton,
+        _x: f32,
+        _y: f32,
+    ) -> GameResult {
+        if let (MouseButton::Left, Some(mouse)) = (button, self.mouse.as_mut()) {
+            mouse.release();
+        }
+        Ok(())
+    }
+
+    fn mouse_motion_event(
+        &mut self,
+        ctx: &mut Context,
+        x: f32,
+        y: f32,
+        _dx: f32,
+        _dy: f32,
+    ) -> GameResult {
+        let (x, y) = self.letterbox().to_layout((x, y));
+        let Some(mouse) = self.mouse.as_mut() else {
+            return Ok(());
+        };
+        // The release can get lost, e.g. when it happens over another window, so the button state has the last word
+        if mouse.drag.is_some() && !ctx.mouse.button_pressed(MouseButton::Left) {
+            mouse.cancel();
+        }
+        mouse.motion((x, y), self.layout.player_rect(&self.game_state));
+        Ok(())
+    }
+
+    // Resizing or maximizing scales the game to the new size, keeping its proportions
+    fn resize_event(&mut self, _ctx: &mut Context, width: f32, height: f32) -> GameResult {
+        self.window_pixels = (width, height);
+        Ok(())
+    }
+
+    // A drag doesn't survive switching to another window
+    fn focus_event(&mut self, _ctx: &mut Context, gained: bool) -> GameResult {
+        if let (false, Some(mouse)) = (gained, self.mouse.as_mut()) {
+            mouse.cancel();
+        }
+        #[cfg(feature = "touch")]
+        if let (false, Some(touch)) = (gained, self.touch.as_mut()) {
+            touch.cancel();
+        }
+        Ok(())
+    }
+
+    // Touches on the grid go to the touch controls when they are on, anything else is handled like the mouse
+    #[cfg(feature = "touch")]
+    fn touch_event(&mut self, ctx: &mut Context, phase: TouchPhase, x: f64, y: f64) -> GameResult {
+        let (x, y) = (x as f32, y as f32);
+        ctx.mouse.handle_move(x, y);
+        let position = self.letterbox().to_layout((x, y));
+        let playing = !self.game_state.game_over && !self.show_about && !self.editing;
+        match (phase, self.touch.as_mut()) {
+            (TouchPhase::Started, touch) => {
+                if !(playing && touch.is_some_and(|touch| touch.start(position, &self.layout))) {
+                    self.mouse_button_down_event(ctx, MouseButton::Left, x, y)?;
+                }
+            },
+            (TouchPhase::Moved, Some(touch)) => touch.moved(position, &self.layout),
+            // A touch doesn't press the mouse button, so mouse drags are followed without checking it
+            (TouchPhase::Moved, None) => {
+                if let Some(mouse) = self.mouse.as_mut() {
+                    mouse.motion(position, self.layout.player_rect(&self.game_state));
+                }
+            },
+            (TouchPhase::Ended | TouchPhase::Cancelled, Some(touch)) => touch.end(),
+            (TouchPhase::Ended | TouchPhase::Cancelled, None) => self.mouse_button_up_event(ctx, MouseButton::Left, x, y)?,
+        }
+        Ok(())
+    }
+
+    fn quit_event(&mut self, ctx: &mut Context) -> GameResult<bool> {
+        // The last macro recorded is kept for the next session
+        self.settings.input_macro = self.input_macro.actions.clone();
+        self.save_window_geometry(ctx);
+        Ok(false)
+    }
+
     // Add key up event handler to clear direction when keys are released
     fn key_up_event(
         &mut self,
@@ -349,26 +1402,7 @@ impl EventHandler for GameAdapter {
         key_input: KeyInput,
     ) -> GameResult {
         if let Some(keycode) = key_input.keycode {
-            // Remove from held keys when released
-            self.held_keys.remove(&keycode);
-            
-            // If up arrow is released and a direction key is still held,
-            // add that direction key to keys_pressed_since_update to continue movement
-            if keycode == KeyCode::Up {
-                if self.held_keys.contains(&KeyCode::Left) {
-                    self.keys_pressed_since_update.push(KeyCode::Left);
-                    // Make sure it's also in the direction queue
-                    if !self.direction_press_order.contains(&KeyCode::Left) {
-                        self.direction_press_order.push_back(KeyCode::Left);
-                    }
-                } else if self.held_keys.contains(&KeyCode::Right) {
-                    self.keys_pressed_since_update.push(KeyCode::Right);
-                    // Make sure it's also in the direction queue
-                    if !self.direction_press_order.contains(&KeyCode::Right) {
-                        self.direction_press_order.push_back(KeyCode::Right);
-                    }
-                }
-            }
+            self.keyboard.key_up(keycode);
         }
         Ok(())
     }
@@ -0,0 +1,125 @@
+// Platform-independent heads-up display - score, lives, level, time, power-up and chain across the top bar
+// Items share the bar left of the restart button. On narrow boards their text shrinks, and when even that
+// isn't enough they wrap onto more rows within the bar, every item is always shown
+use crate::core::game::{GameState, CHAIN_WINDOW_TICKS};
+use crate::core::types::Color;
+use crate::platform::render::{Layout, Rect, COUNTDOWN_WARNING_SECONDS, POWERUP_TEXT_COLOR, WARNING_COLOR};
+
+// Rough width of a character at text scale 1, in pixels, for fitting items into the bar
+pub const HUD_CHAR_WIDTH: f32 = 9.0;
+// Smallest text scale before items wrap onto another row instead
+pub const MIN_HUD_SCALE: f32 = 0.6;
+// Space before the first item and at least between two items, in pixels
+const HUD_PADDING: f32 = 10.0;
+const HUD_GAP: f32 = 12.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HudItem {
+    Score,
+    Lives,
+    Level,
+    Timer,   // Time left in Time Attack, time played otherwise
+    PowerUp, // The active power-up and its updates left
+    Chain,   // Multiplier of the next clear while the chain window is open
+}
+
+impl HudItem {
+    // In order from left to right, then top to bottom
+    pub const ALL: [HudItem; 6] = [HudItem::Score, HudItem::Lives, HudItem::Level, HudItem::Timer, HudItem::PowerUp, HudItem::Chain];
+}
+
+// An item with its text, color and meter, before it is placed
+type Item = (HudItem, String, Color, Option<f32>);
+
+// One item as it is drawn, in window pixels
+#[derive(Debug, Clone, PartialEq)]
+pub struct HudEntry {
+    pub item: HudItem,
+    pub text: String,
+    pub color: Color,
+    pub meter: Option<f32>, // Share of a bar under the text still full, from 1 down to 0
+    pub area: Rect,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hud {
+    pub scale: f32, // Text scale all items are drawn at
+    pub entries: Vec<HudEntry>,
+}
+
+impl Hud {
+    pub fn new(game: &GameState, layout: &Layout) -> Self {
+        let items: Vec<Item> = HudItem::ALL.iter()
+            .filter_map(|&item| content(item, game, layout).map(|(text, color, meter)| (item, text, color, meter)))
+            .collect();
+        let available = layout.restart_button().x - HUD_GAP - HUD_PADDING;
+        let needed = |items: &[Item], scale: f32| {
+            items.iter().map(|(_, text, _, _)| text_width(text, scale)).sum::<f32>() + HUD_GAP * items.len().saturating_sub(1) as f32
+        };
+        let scale = (available / needed(&items, 1.0)).clamp(MIN_HUD_SCALE, 1.0);
+
+        // Each row takes as many items as fit, and at least one
+        let mut rows: Vec<&[Item]> = Vec::new();
+        let mut rest = &items[..];
+        while !rest.is_empty() {
+            let fit = (1..=rest.len()).rev().find(|&n| needed(&rest[..n], scale) <= available).unwrap_or(1);
+            rows.push(&rest[..fit]);
+            rest = &rest[fit..];
+        }
+
+        let row_height = layout.score_bar_height / rows.len().max(1) as f32;
+        let mut entries = Vec::with_capacity(items.len());
+        for (row, row_items) in rows.iter().enumerate() {
+            // Room to spare spreads the items of a row out evenly
+            let spare = (available - needed(row_items, scale)).max(0.0) / row_items.len() as f32;
+            let mut x = HUD_PADDING;
+            for (item, text, color, meter) in row_items.iter().cloned() {
+                let width = text_width(&text, scale);
+                let area = Rect::new(x, row as f32 * row_height, width, row_height);
+                x += width + HUD_GAP + spare;
+                entries.push(HudEntry { item, text, color, meter, area });
+            }
+        }
+        Self { scale, entries }
+    }
+
+    pub fn entry(&self, item: HudItem) -> Option<&HudEntry> {
+        self.entries.iter().find(|entry| entry.item == item)
+    }
+}
+
+fn text_width(text: &str, scale: f32) -> f32 {
+    text.chars().count() as f32 * HUD_CHAR_WIDTH * scale
+}
+
+// Text, color and meter of an item, None when it has nothing to show right now
+fn content(item: HudItem, game: &GameState, layout: &Layout) -> Option<(String, Color, Option<f32>)> {
+    match item {
+        HudItem::Score => Some((format!("Score: {}", layout.locale.number(game.score as u64)), Color::WHITE, None)),
+        // Lives don't matter when nothing can end the game
+        HudItem::Lives if game.no_death() => Some(("Zen".to_string(), Color::WHITE, None)),
+        HudItem::Lives => Some((format!("Lives: {}", game.lives), Color::WHITE, None)),
+        HudItem::Level => Some((format!("Level {}", game.level.number), Color::WHITE, None)),
+        HudItem::Timer => {
+            let (time, warning) = match game.time_left() {
+                Some(left) => {
+                    let seconds = left.as_secs_f32().ceil() as u64;
+                    (seconds, seconds <= COUNTDOWN_WARNING_SECONDS)
+                },
+                None => (game.elapsed().as_secs(), false),
+            };
+            let color = if warning { WARNING_COLOR } else { Color::WHITE };
+            Some((format!("{}:{:02}", time / 60, time % 60), color, None))
+        },
+        HudItem::PowerUp => game.active_powerup.map(|powerup| {
+            let left = powerup.remaining_ticks as f32 / powerup.kind.duration() as f32;
+            (format!("{} {}", powerup.kind.name(), powerup.remaining_ticks), POWERUP_TEXT_COLOR, Some(left))
+        }),
+        // The next clear in the window counts one more
+        HudItem::Chain if game.chain > 0 && game.ticks_since_clear <= CHAIN_WINDOW_TICKS => {
+            let left = 1.0 - game.ticks_since_clear as f32 / CHAIN_WINDOW_TICKS as f32;
+            Some((format!("Chain x{}", game.chain + 1), POWERUP_TEXT_COLOR, Some(left)))
+        },
+        HudItem::Chain => None,
+    }
+}
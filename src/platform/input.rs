@@ -0,0 +1,274 @@
+// Platform-independent input - anything that can produce InputActions for the game
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::game::GameState;
+use crate::core::types::{GameUpdateResult, InputAction, InputDevice};
+use crate::platform::storage;
+
+// A source of player input, polled once per frame
+pub trait InputSource {
+    // Return the action for this frame and reset any per-frame state
+    fn poll(&mut self) -> InputAction;
+
+    // Device the actions come from, recorded alongside them in event logs
+    fn device(&self) -> InputDevice {
+        InputDevice::Keyboard
+    }
+}
+
+// What to do when Left and Right are both asked for in the same frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConflictPolicy {
+    #[default]
+    LatestWins, // The direction pressed last
+    Neutral, // Neither, the player stands still until one of them is let go
+}
+
+impl ConflictPolicy {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "latest" => Some(ConflictPolicy::LatestWins),
+            "neutral" => Some(ConflictPolicy::Neutral),
+            _ => None,
+        }
+    }
+
+    // Pick one direction out of Left and Right presses, oldest first
+    pub fn resolve_directions(self, directions: &[InputAction]) -> InputAction {
+        let left = directions.contains(&InputAction::Left);
+        let right = directions.contains(&InputAction::Right);
+        match (left, right, self) {
+            (true, true, ConflictPolicy::Neutral) => InputAction::None,
+            (true, true, ConflictPolicy::LatestWins) => directions.last().copied().unwrap_or(InputAction::None),
+            (true, false, _) => InputAction::Left,
+            (false, true, _) => InputAction::Right,
+            (false, false, _) => InputAction::None,
+        }
+    }
+}
+
+// Everything asked for during one frame, filled in by a device and turned into a single action
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FrameInput {
+    pub switch: bool,
+    pub drop: bool,
+    pub kick: bool,
+    pub crouch: bool,
+    pub jump: bool,
+    pub jump_released: bool,
+    pub directions: Vec<InputAction>, // Left and Right, oldest first
+}
+
+impl FrameInput {
+    // Note a direction, moving it to the back if it was already there
+    pub fn press_direction(&mut self, direction: InputAction) {
+        self.directions.retain(|&earlier| earlier != direction);
+        self.directions.push(direction);
+    }
+
+    // Note a direction only if it isn't there yet, so it stays older than anything pressed this frame
+    pub fn hold_direction(&mut self, direction: InputAction) {
+        if !self.directions.contains(&direction) {
+            self.directions.insert(0, direction);
+        }
+    }
+
+    // One action for the frame. Switching, dropping, kicking and crouching take the whole frame, in
+    // that order. A jump comes before any direction, a direction still held moves on the next frame
+    pub fn resolve(&self, policy: ConflictPolicy) -> InputAction {
+        if self.switch {
+            InputAction::SwitchCharacter
+        } else if self.drop {
+            InputAction::Drop
+        } else if self.kick {
+            InputAction::Kick
+        } else if self.crouch {
+            InputAction::Down
+        } else if self.jump {
+            // Pressing Up again after letting go jumps rather than cutting the jump short
+            InputAction::Up
+        } else if self.jump_released {
+            InputAction::JumpRelease
+        } else {
+            policy.resolve_directions(&self.directions)
+        }
+    }
+}
+
+// Run one frame: feed the polled input to the game, then advance it
+pub fn step(game: &mut GameState, input: &mut impl InputSource) -> GameUpdateResult {
+    // Skip updates if the game is over
+    if game.game_over {
+        return GameUpdateResult::GameOver;
+    }
+
+    let action = input.poll();
+    if let GameUpdateResult::Restart = game.process_input_from(action, input.device()) {
+        return GameUpdateResult::Restart;
+    }
+    game.update()
+}
+
+// What a key can be bound to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Control {
+    Left,
+    Right,
+    Jump,
+    Crouch,
+    Switch,
+    Drop,
+    Kick,
+    Preview, // Held to see where the held direction leads
+}
+
+impl Control {
+    pub const ALL: [Control; 8] = [
+        Control::Left,
+        Control::Right,
+        Control::Jump,
+        Control::Crouch,
+        Control::Switch,
+        Control::Drop,
+        Control::Kick,
+        Control::Preview,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Control::Left => "Left",
+            Control::Right => "Right",
+            Control::Jump => "Jump",
+            Control::Crouch => "Crouch",
+            Control::Switch => "Switch character",
+            Control::Drop => "Drop",
+            Control::Kick => "Kick",
+            Control::Preview => "Preview",
+        }
+    }
+}
+
+// Keys bound to each control, by the frontend's names for its keys, e.g. "Left", "A" or "Space"
+// Kept in a TOML file the player can edit by hand, a control left out of it keeps its default keys
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub left: Vec<String>,
+    pub right: Vec<String>,
+    pub jump: Vec<String>,
+    pub crouch: Vec<String>,
+    pub switch: Vec<String>,
+    pub drop: Vec<String>,
+    pub kick: Vec<String>,
+    pub preview: Vec<String>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let keys = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+        Self {
+            left: keys(&["Left"]),
+            right: keys(&["Right"]),
+            jump: keys(&["Up"]),
+            crouch: keys(&["Down"]),
+            switch: keys(&["Tab"]),
+            drop: keys(&["X"]),
+            kick: keys(&["Space"]),
+            preview: keys(&["LShift", "RShift"]),
+        }
+    }
+}
+
+impl KeyBindings {
+    // Bindings file in the platform's config directory, None if there is no home to put it in
+    pub fn path() -> Option<PathBuf> {
+        storage::config_path("keys.toml")
+    }
+
+    // Bindings from a file, the defaults if there is none yet
+    // The file is edited by hand, one that doesn't parse is an error saying what's wrong with it
+    pub fn load_from(path: &Path) -> io::Result<Self> {
+        match storage::read_plain(path)? {
+            Some(text) => toml::from_str(&text).map_err(|error| storage::invalid(path, error)),
+            None => Ok(Self::default()),
+        }
+    }
+
+    // Written as plain TOML without a checksum, so it stays easy to edit
+    pub fn save_to(&self, path: &Path) -> io::Result<()> {
+        let text = toml::to_string_pretty(self).map_err(io::Error::other)?;
+        storage::write_atomic(path, text.as_bytes())
+    }
+
+    pub fn keys(&self, control: Control) -> &[String] {
+        match control {
+            Control::Left => &self.left,
+            Control::Right => &self.right,
+            Control::Jump => &self.jump,
+            Control::Crouch => &self.crouch,
+            Control::Switch => &self.switch,
+            Control::Drop => &self.drop,
+            Control::Kick => &self.kick,
+            Control::Preview => &self.preview,
+        }
+    }
+
+    fn keys_mut(&mut self, control: Control) -> &mut Vec<String> {
+        match control {
+            Control::Left => &mut self.left,
+            Control::Right => &mut self.right,
+            Control::Jump => &mut self.jump,
+            Control::Crouch => &mut self.crouch,
+            Control::Switch => &mut self.switch,
+            Control::Drop => &mut self.drop,
+            Control::Kick => &mut self.kick,
+            Control::Preview => &mut self.preview,
+        }
+    }
+
+    // Control a key is bound to, if any
+    pub fn control(&self, key: &str) -> Option<Control> {
+        Control::ALL.into_iter().find(|&control| self.keys(control).iter().any(|bound| bound == key))
+    }
+
+    // Bind a key to a control in place of its keys so far, taking it off any other control
+    pub fn rebind(&mut self, control: Control, key: &str) {
+        for other in Control::ALL {
+            self.keys_mut(other).retain(|bound| bound != key);
+        }
+        *self.keys_mut(control) = vec![key.to_string()];
+    }
+}
+
+// The rebinding screen: a control is picked, then the next key pressed is bound to it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RebindMenu {
+    pub selected: usize, // Index into Control::ALL
+    pub waiting: bool,   // The next key pressed goes to the picked control
+}
+
+impl RebindMenu {
+    pub fn selected_control(&self) -> Control {
+        Control::ALL[self.selected % Control::ALL.len()]
+    }
+
+    // Move the pick up or down the list, wrapping around at either end
+    pub fn move_selection(&mut self, delta: isize) {
+        let count = Control::ALL.len() as isize;
+        self.selected = (self.selected as isize + delta).rem_euclid(count) as usize;
+    }
+
+    // A key pressed on the screen, bound to the picked control while one is awaited
+    // Returns whether the bindings changed
+    pub fn key_pressed(&mut self, key: &str, bindings: &mut KeyBindings) -> bool {
+        if !self.waiting {
+            return false;
+        }
+        self.waiting = false;
+        bindings.rebind(self.selected_control(), key);
+        true
+    }
+}
@@ -0,0 +1,150 @@
+// Platform-independent input macros - a short run of actions recorded in practice and played back on
+// a hotkey, for drilling a sequence until it comes naturally. Only practice runs take them, a macro
+// would be an unfair advantage in a game that counts
+// Recording keeps one action per game update, the first the player sent during it, so a held key
+// records every cell it walks and playback moves exactly as the player did
+use std::fmt;
+
+use crate::core::game::GameState;
+use crate::core::types::{InputAction, InputDevice};
+use crate::platform::input::InputSource;
+
+// Longest macro, recording stops by itself once it is full
+pub const MAX_MACRO_ACTIONS: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MacroError {
+    NotPractice, // Macros are refused outside practice runs
+    Empty,       // Nothing recorded yet to play
+}
+
+impl fmt::Display for MacroError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MacroError::NotPractice => write!(f, "Macros only work in practice"),
+            MacroError::Empty => write!(f, "No macro recorded yet"),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct InputMacro {
+    pub actions: Vec<InputAction>, // The macro last recorded
+    recording: Option<Vec<InputAction>>,
+    pending: Option<InputAction>, // First action of the game update under way while recording
+    playing: Option<usize>,       // Index of the next action to play back
+}
+
+impl InputMacro {
+    pub fn new(mut actions: Vec<InputAction>) -> Self {
+        actions.truncate(MAX_MACRO_ACTIONS);
+        Self { actions, ..Self::default() }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    // Actions in the recording under way, None while not recording
+    pub fn recorded(&self) -> Option<usize> {
+        self.recording.as_ref().map(Vec::len)
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing.is_some()
+    }
+
+    // Start recording over the old macro, or finish the recording under way
+    // Ok(true) when a recording started, Ok(false) when one finished
+    pub fn toggle_recording(&mut self, game: &GameState) -> Result<bool, MacroError> {
+        if self.is_recording() {
+            self.finish_recording();
+            return Ok(false);
+        }
+        if !game.practice {
+            return Err(MacroError::NotPractice);
+        }
+        self.playing = None;
+        self.pending = None;
+        self.recording = Some(Vec::new());
+        Ok(true)
+    }
+
+    fn finish_recording(&mut self) {
+        if let Some(mut recorded) = self.recording.take() {
+            recorded.extend(self.pending.take());
+            recorded.truncate(MAX_MACRO_ACTIONS);
+            self.actions = recorded;
+        }
+    }
+
+    // Note an action the player sent while recording, only the first of each game update counts
+    // Restarts aren't part of a drill, they only start one over
+    pub fn observe(&mut self, action: InputAction) {
+        let skipped = matches!(action, InputAction::None | InputAction::Restart | InputAction::RestartSameSeed);
+        if self.is_recording() && self.pending.is_none() && !skipped {
+            self.pending = Some(action);
+        }
+    }
+
+    // The game advanced, the action held back for this update goes into the recording
+    pub fn end_update(&mut self) {
+        let Some(recorded) = &mut self.recording else {
+            return;
+        };
+        recorded.extend(self.pending.take());
+        if recorded.len() >= MAX_MACRO_ACTIONS {
+            self.finish_recording();
+        }
+    }
+
+    // Play the macro from the start, one action per game update
+    pub fn play(&mut self, game: &GameState) -> Result<(), MacroError> {
+        if !game.practice {
+            return Err(MacroError::NotPractice);
+        }
+        self.finish_recording();
+        if self.actions.is_empty() {
+            return Err(MacroError::Empty);
+        }
+        self.playing = Some(0);
+        Ok(())
+    }
+
+    // Next action of the playback under way, None once it has played through
+    pub fn next_action(&mut self) -> Option<InputAction> {
+        let index = self.playing?;
+        let action = self.actions.get(index).copied();
+        self.playing = action.map(|_| index + 1);
+        action
+    }
+
+    // Drop a playback or a recording under way, e.g. when the game ends
+    pub fn stop(&mut self) {
+        self.playing = None;
+        self.recording = None;
+        self.pending = None;
+    }
+
+    // Input source passing the player's actions on, noting them down while recording
+    pub fn recorder<'a, S: InputSource>(&'a mut self, source: &'a mut S) -> Recorder<'a, S> {
+        Recorder { input_macro: self, source }
+    }
+}
+
+pub struct Recorder<'a, S: InputSource> {
+    input_macro: &'a mut InputMacro,
+    source: &'a mut S,
+}
+
+impl<S: InputSource> InputSource for Recorder<'_, S> {
+    fn poll(&mut self) -> InputAction {
+        let action = self.source.poll();
+        self.input_macro.observe(action);
+        action
+    }
+
+    fn device(&self) -> InputDevice {
+        self.source.device()
+    }
+}
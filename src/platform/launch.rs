@@ -0,0 +1,389 @@
+// Launch configuration, layered: built-in defaults, then a config file, then command-line flags
+// Every GameConfig field is set in the file by its name, e.g. block_spawn_rate = 5, and with a flag of the same
+// name in kebab case, e.g. --block-spawn-rate 5, nested fields joined by a dot, e.g. --player-timing.fall-delay 6
+// The window's own options go in the file's [window] table, options of the frontend only have flags
+// Every argument is read here, anything that isn't a flag the game knows is an error
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use toml::value::{Table, Value};
+
+use crate::core::ai::Difficulty;
+use crate::core::storm::StormPacing;
+use crate::core::types::{GameConfig, GameMode, Theme};
+use crate::platform::input::ConflictPolicy;
+use crate::platform::storage;
+
+// Shorter names kept for flags from before every field had one
+const ALIASES: &[(&str, &str)] = &[
+    ("carry", "head_carrying"),
+    ("patterns", "block_patterns"),
+    ("foreman", "enemy"),
+    ("despawn-idle", "idle_despawn"),
+    ("fall-delay", "player_timing.fall_delay"),
+    ("jump-duration", "player_timing.jump_duration"),
+    ("gravity-interval", "player_timing.gravity_interval"),
+];
+// Fields with flags of their own that take friendlier values, e.g. --mode time-attack
+const OWN_FLAGS: &[&str] = &["mode"];
+// Flags that are on when given and take no value
+const SWITCHES: &[&str] = &["help", "zen", "storms", "mouse", "touch"];
+// Flags that aren't settings and take a value
+const OPTIONS: &[&str] = &[
+    "config", "mode", "grid", "campaign", "fullscreen", "render", "animation-ms", "music-volume", "shake", "shake-damping",
+    "hints", "opposing", "bot", "edit", "preview-bot",
+];
+
+// What --help prints
+pub const USAGE: &str = "\
+Usage: rust_stackattack [FLAGS]
+
+Game:
+  --mode endless|time-attack|zen   what ends the game and how it is scored
+  --zen                            calm play, the same as --mode zen
+  --campaign DIR                   play the levels in DIR by name
+  --grid WIDTHxHEIGHT              arena size, at least 2x3
+  --storms                         periodic block storms
+  --config FILE                    settings file to use instead of config.toml
+  --SETTING VALUE                  any setting of the config file in kebab case, e.g. --lives 5,
+                                   --pits 3,12 or --player-timing.fall-delay 6, switches need no value
+
+Window:
+  --fullscreen on|off              start fullscreen or in a window
+  --render low|full                draw the board as one scaled image or with shapes
+  --animation-ms MILLIS            slide the player between cells, 0 for instant
+  --music-volume VOLUME            background music from 0 to 1
+  --shake CELLS                    how far heavy impacts shake the board, 0 to keep it still
+  --shake-damping RATE             how fast a shake settles, per second
+  --hints on|off                   hints during the first few games
+
+Controls:
+  --mouse                          drag to push and click to jump
+  --touch                          on-screen buttons and swipes
+  --opposing latest|neutral        where Left and Right held together move
+  --bot easy|medium|hard           watch the computer play
+  --edit FILE                      edit a level file instead of playing
+  --preview-bot easy|medium|hard   the computer plays the editor's preview
+
+  --help                           show this and quit
+";
+
+#[derive(Debug)]
+pub enum LaunchError {
+    Help,              // --help was given, print USAGE instead of starting
+    Flag(String),      // An argument that isn't a flag the game has
+    Read(io::Error),   // The config file couldn't be read
+    File(String),      // The config file isn't valid TOML
+    Unknown(String),   // A setting the game doesn't have, in the config file
+    Missing(String),   // A flag given without its value
+    Invalid(String),   // A setting with a value of the wrong kind
+}
+
+impl fmt::Display for LaunchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LaunchError::Help => write!(f, "{}", USAGE),
+            LaunchError::Flag(arg) => write!(f, "unknown argument `{}`, see --help", arg),
+            LaunchError::Read(error) => write!(f, "couldn't read config file: {}", error),
+            LaunchError::File(message) => write!(f, "config file isn't valid TOML: {}", message),
+            LaunchError::Unknown(key) => write!(f, "unknown setting `{}`", key),
+            LaunchError::Missing(flag) => write!(f, "--{} needs a value", flag),
+            LaunchError::Invalid(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+// io::Error can't be compared, a read error equals another of the same kind
+impl PartialEq for LaunchError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (LaunchError::Help, LaunchError::Help) => true,
+            (LaunchError::Flag(a), LaunchError::Flag(b)) => a == b,
+            (LaunchError::Read(a), LaunchError::Read(b)) => a.kind() == b.kind(),
+            (LaunchError::File(a), LaunchError::File(b)) => a == b,
+            (LaunchError::Unknown(a), LaunchError::Unknown(b)) => a == b,
+            (LaunchError::Missing(a), LaunchError::Missing(b)) => a == b,
+            (LaunchError::Invalid(a), LaunchError::Invalid(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+// Options of the window rather than the game, unset ones keep what the settings remember
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WindowOptions {
+    pub fullscreen: Option<bool>,
+    pub low_spec: Option<bool>, // Draw the board as one scaled image, for very weak graphics
+}
+
+// Options of the frontend that only come as flags, unset ones keep what the settings remember
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FrontendOptions {
+    pub campaign: Option<PathBuf>, // Folder of level files, played in place of the mode
+    pub animation_millis: Option<u64>,
+    pub music_volume: Option<f32>,
+    pub shake_intensity: Option<f32>,
+    pub shake_damping: Option<f32>,
+    pub hints: Option<bool>,
+    pub mouse: bool,
+    pub touch: bool,
+    pub opposing: Option<ConflictPolicy>,
+    pub bot: Option<Difficulty>,
+    pub edit: Option<PathBuf>, // Level file to edit, a new one if it isn't there
+    pub preview_bot: Option<Difficulty>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaunchConfig {
+    pub game: GameConfig,
+    pub window: WindowOptions,
+    pub frontend: FrontendOptions,
+}
+
+// Everything the arguments asked for, before it is layered over the defaults and the file
+#[derive(Default)]
+struct Flags {
+    config: Option<PathBuf>,
+    mode: Option<GameMode>,
+    grid: Option<(usize, usize)>,
+    storms: bool,
+    settings: Vec<(String, Value)>,
+    window: WindowOptions,
+    frontend: FrontendOptions,
+}
+
+impl LaunchConfig {
+    // Config file in the platform's config directory, None if there is no home to put it in
+    pub fn path() -> Option<PathBuf> {
+        storage::config_path("config.toml")
+    }
+
+    // The defaults, Zen's pace over them when the file or the flags choose Zen, then a config file's settings and then the flags
+    // read_file gets the file named with --config, or None to read the usual one, and gives back None if there is none
+    pub fn layered<F>(defaults: GameConfig, read_file: F, args: &[String]) -> Result<Self, LaunchError>
+    where
+        F: FnOnce(Option<&Path>) -> io::Result<Option<String>>,
+    {
+        let known = known_fields();
+        let flags = parse_flags(args, &known)?;
+        let mut file = Table::new();
+        let mut window = WindowOptions::default();
+        if let Some(text) = read_file(flags.config.as_deref()).map_err(LaunchError::Read)? {
+            file = toml::from_str(&text).map_err(|error| LaunchError::File(error.to_string()))?;
+            if let Some(options) = file.remove("window") {
+                window = options.try_into().map_err(|error| LaunchError::Invalid(format!("[window]: {}", error)))?;
+            }
+        }
+
+        // Zen set by either the file or the flags brings its pace, a mode the file gets wrong is reported below
+        let file_mode = file.get("mode").and_then(|mode| mode.clone().try_into::<GameMode>().ok());
+        let defaults = match flags.mode.as_ref().or(file_mode.as_ref()) {
+            Some(GameMode::Zen) => zen_pace(defaults),
+            _ => defaults,
+        };
+        let mut game = to_table(&defaults);
+        for (key, value) in flatten("", file) {
+            set(&mut game, &known, &key, value)?;
+        }
+
+        for (key, value) in flags.settings {
+            set(&mut game, &known, &key, value)?;
+        }
+        let mut game: GameConfig = Value::Table(game).try_into().map_err(|error: toml::de::Error| LaunchError::Invalid(error.to_string()))?;
+        if let Some(mode) = flags.mode {
+            game.mode = mode;
+        }
+        if let Some((width, height)) = flags.grid {
+            (game.grid_width, game.grid_height) = (width, height);
+        }
+        // Paced as set in the config if it was
+        if flags.storms {
+            game.storm_pacing.get_or_insert_with(StormPacing::default);
+        }
+
+        let window = WindowOptions {
+            fullscreen: flags.window.fullscreen.or(window.fullscreen),
+            low_spec: flags.window.low_spec.or(window.low_spec),
+        };
+        Ok(Self { game, window, frontend: flags.frontend })
+    }
+}
+
+// Read every argument into the flags it sets, settings as values of their kind in the order given
+fn parse_flags(args: &[String], known: &Table) -> Result<Flags, LaunchError> {
+    let mut flags = Flags::default();
+    let mut args = args.iter().peekable();
+    while let Some(arg) = args.next() {
+        let Some(flag) = arg.strip_prefix("--") else {
+            return Err(LaunchError::Flag(arg.clone()));
+        };
+        if SWITCHES.contains(&flag) {
+            match flag {
+                "help" => return Err(LaunchError::Help),
+                "zen" => flags.mode = Some(GameMode::Zen),
+                "storms" => flags.storms = true,
+                "mouse" => flags.frontend.mouse = true,
+                // Listed in USAGE either way, a build without the controls says so rather than ignoring it
+                _ if cfg!(feature = "touch") => flags.frontend.touch = true,
+                _ => return Err(LaunchError::Invalid("--touch needs a build with the touch feature".to_string())),
+            }
+            continue;
+        }
+
+        let value = args.next_if(|next| !next.starts_with("--")).map(|next| next.as_str());
+        if let Some(key) = setting_key(flag, known) {
+            let kind = lookup(known, &key).expect("setting_key only returns known settings");
+            let value = match (value, kind) {
+                (Some(raw), kind) => parse_value(raw, kind),
+                // A bare switch turns it on, e.g. --two-characters
+                (None, Value::Boolean(_)) => Value::Boolean(true),
+                (None, _) => return Err(LaunchError::Missing(flag.to_string())),
+            };
+            flags.settings.push((key, value));
+            continue;
+        }
+
+        if !OPTIONS.contains(&flag) {
+            return Err(LaunchError::Flag(arg.clone()));
+        }
+        let value = value.ok_or_else(|| LaunchError::Missing(flag.to_string()))?;
+        let invalid = |expected: &str| LaunchError::Invalid(format!("--{} expects {}, not {}", flag, expected, value));
+        match flag {
+            "config" => flags.config = Some(PathBuf::from(value)),
+            "mode" => {
+                let mode = GameMode::from_name(value).ok_or_else(|| invalid("endless, time-attack or zen"))?;
+                flags.mode = Some(mode);
+            },
+            "grid" => flags.grid = Some(parse_grid(value).ok_or_else(|| invalid("WIDTHxHEIGHT, at least 2x3"))?),
+            "campaign" => flags.frontend.campaign = Some(PathBuf::from(value)),
+            "fullscreen" => flags.window.fullscreen = Some(switch(value).ok_or_else(|| invalid("on or off"))?),
+            "render" => match value {
+                "low" => flags.window.low_spec = Some(true),
+                "full" => flags.window.low_spec = Some(false),
+                _ => return Err(invalid("low or full")),
+            },
+            "animation-ms" => flags.frontend.animation_millis = Some(value.parse().map_err(|_| invalid("milliseconds"))?),
+            "music-volume" => flags.frontend.music_volume = Some(value.parse().map_err(|_| invalid("a volume from 0 to 1"))?),
+            "shake" => flags.frontend.shake_intensity = Some(value.parse().map_err(|_| invalid("a number of cells"))?),
+            "shake-damping" => flags.frontend.shake_damping = Some(value.parse().map_err(|_| invalid("a rate per second"))?),
+            "hints" => flags.frontend.hints = Some(switch(value).ok_or_else(|| invalid("on or off"))?),
+            "opposing" => flags.frontend.opposing = Some(ConflictPolicy::from_name(value).ok_or_else(|| invalid("latest or neutral"))?),
+            "bot" => flags.frontend.bot = Some(Difficulty::from_name(value).ok_or_else(|| invalid("easy, medium or hard"))?),
+            "edit" => flags.frontend.edit = Some(PathBuf::from(value)),
+            _ => flags.frontend.preview_bot = Some(Difficulty::from_name(value).ok_or_else(|| invalid("easy, medium or hard"))?),
+        }
+    }
+    Ok(flags)
+}
+
+// Zen's slower spawns and friendlier columns, keeping the arena and theme of the defaults given
+fn zen_pace(defaults: GameConfig) -> GameConfig {
+    let zen = GameConfig::zen();
+    GameConfig {
+        block_spawn_rate: zen.block_spawn_rate,
+        favor_low_columns: zen.favor_low_columns,
+        mode: zen.mode,
+        ..defaults
+    }
+}
+
+// A grid size given as WIDTHxHEIGHT, the player is two cells tall and needs room to move
+fn parse_grid(value: &str) -> Option<(usize, usize)> {
+    let (width, height) = value.split_once('x')?;
+    let (width, height) = (width.parse().ok()?, height.parse().ok()?);
+    (width >= 2 && height >= 3).then_some((width, height))
+}
+
+fn to_table(config: &GameConfig) -> Table {
+    match Value::try_from(config) {
+        Ok(Value::Table(table)) => table,
+        _ => unreachable!("a GameConfig is always a table"),
+    }
+}
+
+// Every setting the game has with a value of its kind: the defaults, with the optional ones filled in
+fn known_fields() -> Table {
+    to_table(&GameConfig {
+        seed: Some(0),
+        storm_pacing: Some(StormPacing::default()),
+        theme: Some(Theme::default()),
+        idle_despawn: Some(0),
+        bomb_fuse: Some(0),
+        ..GameConfig::default()
+    })
+}
+
+// The setting a flag sets, by alias or by name, None if it isn't one
+fn setting_key(flag: &str, known: &Table) -> Option<String> {
+    let key = ALIASES.iter()
+        .find(|(alias, _)| *alias == flag)
+        .map(|(_, key)| key.to_string())
+        .unwrap_or_else(|| flag.replace('-', "_"));
+    let own_flag = OWN_FLAGS.contains(&key.as_str());
+    (!own_flag && lookup(known, &key).is_some()).then_some(key)
+}
+
+fn lookup<'a>(table: &'a Table, key: &str) -> Option<&'a Value> {
+    let (first, rest) = key.split_once('.').map_or((key, None), |(first, rest)| (first, Some(rest)));
+    match (table.get(first)?, rest) {
+        (value, None) => Some(value),
+        (Value::Table(inner), Some(rest)) => lookup(inner, rest),
+        _ => None,
+    }
+}
+
+// A flag's text as a value of the setting's kind, lists without their brackets, e.g. --pits 3,12
+// Text that isn't a value of any kind stays text, for the error to say what was expected instead
+fn parse_value(raw: &str, kind: &Value) -> Value {
+    let text = match kind {
+        Value::String(_) => return Value::String(raw.to_string()),
+        Value::Array(_) if !raw.starts_with('[') => format!("[{}]", raw),
+        _ => raw.to_string(),
+    };
+    toml::from_str::<Table>(&format!("value = {}", text)).ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| Value::String(raw.to_string()))
+}
+
+fn switch(value: &str) -> Option<bool> {
+    match value {
+        "on" => Some(true),
+        "off" => Some(false),
+        _ => None,
+    }
+}
+
+// Tables of a config file as dotted keys, e.g. [player_timing] fall_delay = 6 as player_timing.fall_delay
+fn flatten(prefix: &str, table: Table) -> Vec<(String, Value)> {
+    table.into_iter()
+        .flat_map(|(key, value)| {
+            let key = if prefix.is_empty() { key } else { format!("{}.{}", prefix, key) };
+            match value {
+                Value::Table(inner) => flatten(&key, inner),
+                value => vec![(key, value)],
+            }
+        })
+        .collect()
+}
+
+// Put a value at a dotted key, an unset optional table starts out from its defaults
+fn set(game: &mut Table, known: &Table, key: &str, value: Value) -> Result<(), LaunchError> {
+    fn set_in(table: &mut Table, known: &Table, path: &str, key: &str, value: Value) -> Result<(), LaunchError> {
+        let unknown = || LaunchError::Unknown(key.to_string());
+        let (first, rest) = path.split_once('.').map_or((path, None), |(first, rest)| (first, Some(rest)));
+        let known_value = known.get(first).ok_or_else(unknown)?;
+        let Some(rest) = rest else {
+            table.insert(first.to_string(), value);
+            return Ok(());
+        };
+        let entry = table.entry(first.to_string()).or_insert_with(|| known_value.clone());
+        match (entry, known_value) {
+            (Value::Table(inner), Value::Table(known_inner)) => set_in(inner, known_inner, rest, key, value),
+            _ => Err(unknown()),
+        }
+    }
+    set_in(game, known, key, key, value)
+}
@@ -1,4 +1,33 @@
 // Platform module - contains platform-specific implementations
 
+// Platform-independent interfaces shared by all frontends
+pub mod animation;
+pub mod clock;
+pub mod cue_sound;
+pub mod format;
+pub mod hud;
+pub mod input;
+pub mod input_macro;
+pub mod launch;
+pub mod motion;
+pub mod mouse;
+pub mod music;
+pub mod particles;
+pub mod pixels;
+pub mod popups;
+#[cfg(feature = "summary-card")]
+pub mod raster;
+pub mod render;
+pub mod scores;
+pub mod settings;
+pub mod shake;
+#[cfg(feature = "summary-card")]
+pub mod share;
+pub mod storage;
+#[cfg(feature = "touch")]
+pub mod touch;
+pub mod watchdog;
+
 // Export platform-specific modules
+#[cfg(feature = "ggez-frontend")]
 pub mod ggez;
@@ -0,0 +1,66 @@
+// Platform-independent player motion - slides the drawn player between cells
+// The slide takes a set time of its own, so it can be made snappy or smooth whatever the update rate is
+use std::time::{Duration, Instant};
+
+use crate::core::types::GridPos;
+use crate::platform::clock::Clock;
+
+// Moves longer than this many cells (respawns, restarts) jump straight to the new cell
+const MAX_SLIDE_CELLS: f32 = 2.0;
+
+pub struct PlayerMotion {
+    pub duration: Duration, // Zero draws the player in its cell right away
+    from: (f32, f32),       // Drawn position when the current slide started, in cells
+    to: GridPos,
+    started: Instant,
+}
+
+impl PlayerMotion {
+    pub fn new(duration: Duration, position: GridPos, clock: &dyn Clock) -> Self {
+        Self {
+            duration,
+            from: (position.x as f32, position.y as f32),
+            to: position,
+            started: clock.now(),
+        }
+    }
+
+    // Slide toward the player's cell if it changed, from wherever the player is drawn now
+    pub fn follow(&mut self, position: GridPos, clock: &dyn Clock) {
+        if position == self.to {
+            return;
+        }
+        let drawn = self.drawn(clock);
+        let distance = (drawn.0 - position.x as f32).abs().max((drawn.1 - position.y as f32).abs());
+        self.from = if distance > MAX_SLIDE_CELLS { (position.x as f32, position.y as f32) } else { drawn };
+        self.to = position;
+        self.started = clock.now();
+    }
+
+    // Where to draw the player, in cells
+    pub fn drawn(&self, clock: &dyn Clock) -> (f32, f32) {
+        let progress = if self.duration.is_zero() {
+            1.0
+        } else {
+            (clock.now().duration_since(self.started).as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+        };
+        let (to_x, to_y) = (self.to.x as f32, self.to.y as f32);
+        (self.from.0 + (to_x - self.from.0) * progress, self.from.1 + (to_y - self.from.1) * progress)
+    }
+
+    // How far the drawn player is from its cell, in cells
+    pub fn offset(&self, clock: &dyn Clock) -> (f32, f32) {
+        let drawn = self.drawn(clock);
+        (drawn.0 - self.to.x as f32, drawn.1 - self.to.y as f32)
+    }
+}
+
+// How far something that moved from one cell to another in the latest update is drawn from its new cell,
+// in cells, with the share of the move still to be shown. Long jumps are drawn in place right away
+pub fn slide_offset(from: GridPos, to: GridPos, remaining: f32) -> (f32, f32) {
+    let (dx, dy) = (from.x as f32 - to.x as f32, from.y as f32 - to.y as f32);
+    if dx.abs().max(dy.abs()) > MAX_SLIDE_CELLS {
+        return (0.0, 0.0);
+    }
+    (dx * remaining, dy * remaining)
+}
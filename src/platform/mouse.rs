@@ -0,0 +1,118 @@
+// Platform-independent mouse controls
+// Dragging sideways from the player pushes that way one cell per cell dragged, clicking above the player jumps
+// Frontends feed in window coordinates and the player's body rect, and poll it like any other InputSource
+use std::collections::VecDeque;
+
+use crate::core::types::{InputAction, InputDevice};
+use crate::platform::input::InputSource;
+use crate::platform::render::Rect;
+
+// Fraction of a cell past the last step the pointer must travel before a drag moves the player again
+// Above half a cell, so a pointer resting between two steps doesn't move the player back and forth
+pub const DRAG_THRESHOLD: f32 = 0.6;
+
+// A drag in progress, started by pressing on the player
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Drag {
+    pub start: (f32, f32),
+    pub current: (f32, f32),
+    pub steps: i32, // Cells the drag has moved the player so far, negative to the left
+}
+
+impl Drag {
+    // Direction of the next step if the pointer is far enough for one
+    pub fn next_step(&self, cell_size: f32) -> Option<i32> {
+        let dragged = (self.current.0 - self.start.0) / cell_size - self.steps as f32;
+        if dragged >= DRAG_THRESHOLD {
+            Some(1)
+        } else if dragged <= -DRAG_THRESHOLD {
+            Some(-1)
+        } else {
+            None
+        }
+    }
+}
+
+pub struct MouseInput {
+    cell_size: f32,
+    pub drag: Option<Drag>,
+    jumping: bool, // A click above the player is held, releasing it cuts the jump short
+    pub hover_jump: bool, // The pointer is over the area that jumps when clicked
+    actions: VecDeque<InputAction>,
+}
+
+impl MouseInput {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            drag: None,
+            jumping: false,
+            hover_jump: false,
+            actions: VecDeque::new(),
+        }
+    }
+
+    // The cells above the player and half a cell to either side
+    fn jump_area(&self, player: Rect) -> Rect {
+        let margin = self.cell_size / 2.0;
+        Rect::new(player.x - margin, 0.0, player.w + 2.0 * margin, player.y)
+    }
+
+    // Start a drag on the player or jump from above it, returns whether the press was used
+    pub fn press(&mut self, position: (f32, f32), player: Rect) -> bool {
+        if player.contains(position.0, position.1) {
+            self.drag = Some(Drag { start: position, current: position, steps: 0 });
+            return true;
+        }
+        if self.jump_area(player).contains(position.0, position.1) {
+            self.actions.push_back(InputAction::Up);
+            self.jumping = true;
+            return true;
+        }
+        false
+    }
+
+    // Follow the pointer, queueing a step each time a drag crosses the threshold
+    pub fn motion(&mut self, position: (f32, f32), player: Rect) {
+        self.hover_jump = self.drag.is_none() && self.jump_area(player).contains(position.0, position.1);
+        let cell_size = self.cell_size;
+        let Some(drag) = &mut self.drag else {
+            return;
+        };
+
+        drag.current = position;
+        // A fast drag can cross several cells between two motion events
+        while let Some(direction) = drag.next_step(cell_size) {
+            drag.steps += direction;
+            self.actions.push_back(if direction > 0 { InputAction::Right } else { InputAction::Left });
+        }
+    }
+
+    pub fn release(&mut self) {
+        if self.jumping {
+            self.actions.push_back(InputAction::JumpRelease);
+        }
+        self.cancel();
+    }
+
+    // Forget the drag without a release, e.g. when the window loses focus or a release was missed
+    pub fn cancel(&mut self) {
+        self.drag = None;
+        self.jumping = false;
+    }
+
+    pub fn has_action(&self) -> bool {
+        !self.actions.is_empty()
+    }
+}
+
+impl InputSource for MouseInput {
+    // One queued action per frame, so a long drag walks the player over several updates
+    fn poll(&mut self) -> InputAction {
+        self.actions.pop_front().unwrap_or(InputAction::None)
+    }
+
+    fn device(&self) -> InputDevice {
+        InputDevice::Mouse
+    }
+}
@@ -0,0 +1,152 @@
+// Background music, synthesized like the cues so no audio files have to ship with the game
+// The audio manager follows the game: the track its mode or level asks for, or with the adaptive
+// soundtrack a calm one while the stack is low and tenser ones as it nears the danger line. Music
+// gets a little faster with every level, changes crossfade and the frontend plays the mix
+use std::time::{Duration, Instant};
+
+use crate::core::game::GameState;
+use crate::core::types::{Soundtrack, Track};
+use crate::platform::clock::Clock;
+use crate::platform::cue_sound;
+
+pub const DEFAULT_MUSIC_VOLUME: f32 = 0.5;
+pub const CROSSFADE: Duration = Duration::from_secs(2);
+// Each level up to this many plays the music a step faster
+const MAX_TEMPO_STEPS: u32 = 5;
+const TEMPO_STEP: f32 = 0.08;
+// Share of the rows up to the danger line the stack covers when the music turns tense, then urgent
+const TENSE_FILL: f32 = 0.5;
+const DANGER_FILL: f32 = 0.75;
+// Quieter than the cues, so those still stand out
+const TRACK_VOLUME: f32 = 0.25;
+
+// Track for how close the stack is to the danger line
+pub fn adaptive_track(game: &GameState) -> Track {
+    let rows = game.grid_height.saturating_sub(game.danger_line).max(1);
+    let fill = game.stack_height() as f32 / rows as f32;
+    if fill >= DANGER_FILL {
+        Track::Danger
+    } else if fill >= TENSE_FILL {
+        Track::Tense
+    } else {
+        Track::Calm
+    }
+}
+
+// One loop of a track as (frequency in Hz, length in milliseconds), a frequency of 0 is a rest
+pub fn notes(track: Track) -> &'static [(f32, u32)] {
+    match track {
+        // Slow major arpeggio
+        Track::Calm => &[
+            (262.0, 400), (330.0, 400), (392.0, 400), (523.0, 400),
+            (392.0, 400), (330.0, 400), (294.0, 400), (0.0, 400),
+            (220.0, 400), (262.0, 400), (330.0, 400), (440.0, 400),
+            (392.0, 400), (330.0, 400), (262.0, 800),
+        ],
+        // Minor line in steady eighths
+        Track::Tense => &[
+            (220.0, 250), (262.0, 250), (330.0, 250), (262.0, 250),
+            (247.0, 250), (294.0, 250), (330.0, 250), (294.0, 250),
+            (220.0, 250), (262.0, 250), (349.0, 250), (330.0, 250),
+            (294.0, 250), (262.0, 250), (247.0, 500),
+        ],
+        // Low pulses climbing by semitones
+        Track::Danger => &[
+            (110.0, 150), (0.0, 50), (110.0, 150), (0.0, 50),
+            (117.0, 150), (0.0, 50), (117.0, 150), (0.0, 50),
+            (123.0, 150), (0.0, 50), (123.0, 150), (0.0, 50),
+            (131.0, 150), (0.0, 50), (147.0, 350), (0.0, 50),
+        ],
+    }
+}
+
+// Mono 16-bit samples of one loop
+pub fn track_samples(track: Track) -> Vec<i16> {
+    cue_sound::tune_samples(notes(track), TRACK_VOLUME)
+}
+
+// One loop as a complete WAV file, played on repeat
+pub fn track_wav(track: Track) -> Vec<u8> {
+    cue_sound::wav(&track_samples(track))
+}
+
+// A track at a speed, what one playing source sounds like
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Music {
+    pub track: Track,
+    pub tempo_step: u32,
+}
+
+impl Music {
+    // What fits the game right now, nothing once it's over
+    pub fn for_game(game: &GameState) -> Option<Self> {
+        (!game.game_over).then(|| Music {
+            track: match game.soundtrack {
+                Soundtrack::Adaptive => adaptive_track(game),
+                Soundtrack::Fixed(track) => track,
+            },
+            tempo_step: game.level.number.saturating_sub(1).min(MAX_TEMPO_STEPS),
+        })
+    }
+
+    // Playback speed, 1 being the track as written
+    pub fn speed(&self) -> f32 {
+        1.0 + TEMPO_STEP * self.tempo_step as f32
+    }
+}
+
+pub struct AudioManager {
+    pub volume: f32,
+    current: Option<Music>,
+    // Music fading out, with the volume it had when the fade began
+    previous: Option<(Music, f32)>,
+    fade_started: Option<Instant>,
+}
+
+impl AudioManager {
+    pub fn new(volume: f32) -> Self {
+        Self {
+            volume: volume.clamp(0.0, 1.0),
+            current: None,
+            previous: None,
+            fade_started: None,
+        }
+    }
+
+    // Follow the game, returning every music that should be playing with its volume
+    pub fn update(&mut self, game: &GameState, clock: &dyn Clock) -> Vec<(Music, f32)> {
+        let now = clock.now();
+        let wanted = Music::for_game(game);
+        if wanted != self.current {
+            // A fade cut short hands over from wherever it got to
+            let level = self.current_volume(now);
+            self.previous = self.current.map(|music| (music, level));
+            self.current = wanted;
+            self.fade_started = Some(now);
+        }
+
+        let progress = self.progress(now);
+        if progress >= 1.0 {
+            self.previous = None;
+        }
+        let mut mix = Vec::new();
+        if let Some((music, level)) = self.previous {
+            mix.push((music, level * (1.0 - progress)));
+        }
+        if let Some(music) = self.current {
+            mix.push((music, self.volume * progress));
+        }
+        mix
+    }
+
+    fn progress(&self, now: Instant) -> f32 {
+        match self.fade_started {
+            Some(started) => (now.duration_since(started).as_secs_f32() / CROSSFADE.as_secs_f32()).min(1.0),
+            None => 1.0,
+        }
+    }
+
+    fn current_volume(&self, now: Instant) -> f32 {
+        self.volume * self.progress(now)
+    }
+}
@@ -0,0 +1,149 @@
+// Platform-independent particles - debris kicked up by landing blocks, sparkles along cleared rows and
+// flames bursting out of bombs
+// Every particle lives in a fixed pool that is written over in turn, so effects never allocate while
+// the game runs. A particle's path follows from when and where it started, there is nothing to step
+use std::time::{Duration, Instant};
+
+use crate::core::types::GridPos;
+use crate::platform::clock::Clock;
+
+// Particles alive at once, the oldest is replaced when a new one needs the room
+pub const MAX_PARTICLES: usize = 256;
+pub const PARTICLE_LIFETIME: Duration = Duration::from_millis(600);
+// Debris thrown up by a landing block
+pub const DEBRIS_PER_LANDING: usize = 6;
+// Sparkles per cell of a cleared row
+pub const SPARKLES_PER_CELL: usize = 2;
+// Flames and debris thrown out by a bomb going off
+pub const PARTICLES_PER_BLAST: usize = 16;
+// Pull on debris, in cells per second squared
+const GRAVITY: f32 = 30.0;
+// Turn between the directions of particles spawned one after another, spreads a burst evenly without a generator
+const GOLDEN_ANGLE: f32 = 2.399_963;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParticleKind {
+    Debris,  // Falls back down under gravity
+    Sparkle, // Drifts up and out, unaffected by gravity
+    Flame,   // Bursts out fast, unaffected by gravity
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Particle {
+    kind: ParticleKind,
+    origin: (f32, f32),   // In cells, from the grid's top-left corner
+    velocity: (f32, f32), // In cells per second
+    born: Instant,
+    alive: bool,
+}
+
+// A live particle as it should be drawn right now
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParticleView {
+    pub kind: ParticleKind,
+    pub position: (f32, f32), // In cells
+    pub life_left: f32,       // From 1 when spawned down to 0 when it disappears
+}
+
+pub struct Particles {
+    pool: Vec<Particle>,
+    next: usize,    // Slot the next particle goes into
+    spawned: usize, // Particles spawned so far, picks the direction of the next one
+}
+
+impl Particles {
+    pub fn new(clock: &dyn Clock) -> Self {
+        let dead = Particle {
+            kind: ParticleKind::Debris,
+            origin: (0.0, 0.0),
+            velocity: (0.0, 0.0),
+            born: clock.now(),
+            alive: false,
+        };
+        Self {
+            pool: vec![dead; MAX_PARTICLES],
+            next: 0,
+            spawned: 0,
+        }
+    }
+
+    // Debris flying up and out from under a block that came to rest, at its top-left cell
+    pub fn block_landed(&mut self, cell: GridPos, clock: &dyn Clock) {
+        let bottom = (cell.x as f32 + 0.5, cell.y as f32 + 1.0);
+        for _ in 0..DEBRIS_PER_LANDING {
+            let (dx, dy) = self.direction();
+            // Mostly sideways and up, off the surface the block landed on
+            self.spawn(ParticleKind::Debris, bottom, (dx * 4.0, -dy.abs() * 5.0 - 2.0), clock);
+        }
+    }
+
+    // Sparkles across every cell of a cleared row
+    pub fn row_cleared(&mut self, row: usize, grid_width: usize, clock: &dyn Clock) {
+        for x in 0..grid_width {
+            let center = (x as f32 + 0.5, row as f32 + 0.5);
+            for _ in 0..SPARKLES_PER_CELL {
+                let (dx, dy) = self.direction();
+                self.spawn(ParticleKind::Sparkle, center, (dx * 1.5, dy * 1.5 - 1.0), clock);
+            }
+        }
+    }
+
+    // A ring of flames over a bomb's 3x3 blast, with debris from what it destroyed
+    pub fn explosion(&mut self, center: GridPos, clock: &dyn Clock) {
+        let middle = (center.x as f32 + 0.5, center.y as f32 + 0.5);
+        for i in 0..PARTICLES_PER_BLAST {
+            let (dx, dy) = self.direction();
+            match i % 2 {
+                0 => self.spawn(ParticleKind::Flame, middle, (dx * 3.0, dy * 3.0), clock),
+                _ => self.spawn(ParticleKind::Debris, middle, (dx * 5.0, dy * 5.0 - 4.0), clock),
+            }
+        }
+    }
+
+    // Unit vector turning by the golden angle with every particle
+    fn direction(&self) -> (f32, f32) {
+        let angle = self.spawned as f32 * GOLDEN_ANGLE;
+        (angle.cos(), angle.sin())
+    }
+
+    fn spawn(&mut self, kind: ParticleKind, origin: (f32, f32), velocity: (f32, f32), clock: &dyn Clock) {
+        self.pool[self.next] = Particle { kind, origin, velocity, born: clock.now(), alive: true };
+        self.next = (self.next + 1) % MAX_PARTICLES;
+        self.spawned += 1;
+    }
+
+    // Drop every particle, e.g. on a restart
+    pub fn clear(&mut self) {
+        for particle in &mut self.pool {
+            particle.alive = false;
+        }
+    }
+
+    // Particles still in the air, where they are now
+    pub fn visible<'a>(&'a self, clock: &dyn Clock) -> impl Iterator<Item = ParticleView> + 'a {
+        let now = clock.now();
+        self.pool.iter().filter(|particle| particle.alive).filter_map(move |particle| {
+            let age = now.duration_since(particle.born);
+            if age >= PARTICLE_LIFETIME {
+                return None;
+            }
+            let t = age.as_secs_f32();
+            let gravity = match particle.kind {
+                ParticleKind::Debris => GRAVITY,
+                ParticleKind::Sparkle | ParticleKind::Flame => 0.0,
+            };
+            Some(ParticleView {
+                kind: particle.kind,
+                position: (
+                    particle.origin.0 + particle.velocity.0 * t,
+                    particle.origin.1 + particle.velocity.1 * t + gravity * t * t / 2.0,
+                ),
+                life_left: 1.0 - t / PARTICLE_LIFETIME.as_secs_f32(),
+            })
+        })
+    }
+
+    pub fn live_count(&self, clock: &dyn Clock) -> usize {
+        self.visible(clock).count()
+    }
+}
@@ -0,0 +1,95 @@
+// Platform-independent low-spec frame - the board painted one pixel per cell into a CPU-side buffer
+// Frontends upload it as a single image and draw it scaled up, for graphics too weak to build a
+// shape for every block. The score bar is the top row, its text is drawn over it as usual
+use crate::core::game::GameState;
+use crate::core::types::{Color, GridPos};
+use crate::platform::render::{
+    block_color, lit_block_color, powerup_color, COIN_COLOR, ENEMY_COLOR, PARTNER_COLOR, PIT_COLOR, PLAYER_COLOR, TERRAIN_COLOR,
+};
+
+#[derive(Debug, Default)]
+pub struct CellPixels {
+    pub width: usize,
+    pub height: usize, // Rows of the grid and one more for the score bar
+    pub rgba: Vec<u8>, // Four bytes per pixel, row after row from the top
+}
+
+impl CellPixels {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Paint the board as it is now, reusing the buffer while the grid keeps its size
+    pub fn paint(&mut self, game: &GameState) {
+        self.width = game.grid_width;
+        self.height = game.grid_height + 1;
+        self.rgba.resize(self.width * self.height * 4, 0);
+
+        let background = game.theme.background();
+        for pixel in self.rgba.chunks_exact_mut(4) {
+            pixel.copy_from_slice(&to_rgba(background));
+        }
+        for x in 0..self.width {
+            self.set(game, (x, 0), Color::BLUE);
+        }
+
+        let cell = |position: GridPos| (position.x, position.y + 1);
+        for position in game.terrain.walls() {
+            self.set(game, cell(position), TERRAIN_COLOR);
+        }
+        for (position, _) in game.terrain.ramps() {
+            self.set(game, cell(position), TERRAIN_COLOR);
+        }
+        for x in game.terrain.pits() {
+            self.set(game, cell(GridPos::new(x, game.grid_height - 1)), PIT_COLOR);
+        }
+        for pickup in &game.pickups {
+            let color = pickup.powerup.map_or(COIN_COLOR, powerup_color);
+            self.set(game, cell(pickup.position), color);
+        }
+        for block in &game.blocks {
+            for position in block.cells() {
+                self.set(game, cell(position), lit_block_color(block));
+            }
+        }
+        if let Some(enemy) = &game.enemy {
+            self.set(game, cell(enemy.position), ENEMY_COLOR);
+        }
+        if let Some(partner) = &game.partner {
+            let GridPos { x, y } = partner.position;
+            for body in y..y + partner.body_size {
+                self.set(game, cell(GridPos::new(x, body)), PARTNER_COLOR);
+            }
+        }
+        let GridPos { x, y } = game.player.position;
+        for body in y..y + game.player.body_size {
+            self.set(game, cell(GridPos::new(x, body)), PLAYER_COLOR);
+        }
+        if let (Some(block), Some(position)) = (&game.player.carrying, game.player.carried_cell()) {
+            self.set(game, cell(position), block_color(block.kind));
+        }
+    }
+
+    // Color of a pixel, black outside the buffer
+    pub fn pixel(&self, x: usize, y: usize) -> [u8; 4] {
+        if x >= self.width || y >= self.height {
+            return [0, 0, 0, 255];
+        }
+        let start = (y * self.width + x) * 4;
+        [self.rgba[start], self.rgba[start + 1], self.rgba[start + 2], self.rgba[start + 3]]
+    }
+
+    // Paint one pixel in the game's theme, cells off the grid are left out
+    fn set(&mut self, game: &GameState, (x, y): (usize, usize), color: Color) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+        let start = (y * self.width + x) * 4;
+        self.rgba[start..start + 4].copy_from_slice(&to_rgba(game.theme.apply(color)));
+    }
+}
+
+fn to_rgba(color: Color) -> [u8; 4] {
+    let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    [channel(color.r), channel(color.g), channel(color.b), 255]
+}
@@ -0,0 +1,92 @@
+// Platform-independent floating text - "+6" popups that rise from where points were scored and fade out
+// Fed from the game's events, each popup lasts a fixed time and its place follows from when it started
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::core::event::GameEvent;
+use crate::core::types::GridPos;
+use crate::platform::clock::Clock;
+
+pub const POPUP_LIFETIME: Duration = Duration::from_millis(900);
+// Popups shown at once, the oldest goes first when a new one needs the room
+pub const MAX_POPUPS: usize = 8;
+// How far a popup rises over its lifetime, in cells
+pub const POPUP_RISE: f32 = 1.5;
+
+struct Popup {
+    text: String,
+    origin: (f32, f32), // In cells, from the grid's top-left corner
+    born: Instant,
+}
+
+// A live popup as it should be drawn right now
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PopupView<'a> {
+    pub text: &'a str,
+    pub position: (f32, f32), // In cells, the middle of the text
+    pub life_left: f32,       // From 1 when shown down to 0 when it disappears
+}
+
+#[derive(Default)]
+pub struct Popups {
+    popups: VecDeque<Popup>,
+    cleared_rows: Vec<usize>, // Rows of the clear being reported, the points follow in RowsCleared
+}
+
+impl Popups {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Show text rising from a point given in cells
+    pub fn show(&mut self, text: String, origin: (f32, f32), clock: &dyn Clock) {
+        let now = clock.now();
+        self.popups.retain(|popup| now.duration_since(popup.born) < POPUP_LIFETIME);
+        if self.popups.len() == MAX_POPUPS {
+            self.popups.pop_front();
+        }
+        self.popups.push_back(Popup { text, origin, born: now });
+    }
+
+    // Points from a game event, shown over the middle of the rows cleared or at the coin picked up
+    pub fn observe(&mut self, event: &GameEvent, grid_width: usize, clock: &dyn Clock) {
+        match event {
+            GameEvent::RowCleared(row) => self.cleared_rows.push(*row),
+            GameEvent::RowsCleared(breakdown) => {
+                let rows = std::mem::take(&mut self.cleared_rows);
+                let middle = match rows.len() {
+                    0 => return,
+                    count => rows.iter().sum::<usize>() as f32 / count as f32 + 0.5,
+                };
+                self.show(format!("+{}", breakdown.points), (grid_width as f32 / 2.0, middle), clock);
+            },
+            GameEvent::CoinCollected(GridPos { x, y }, points) if *points > 0 => {
+                self.show(format!("+{}", points), (*x as f32 + 0.5, *y as f32 + 0.5), clock);
+            },
+            _ => {},
+        }
+    }
+
+    // Drop every popup, e.g. on a restart
+    pub fn clear(&mut self) {
+        self.popups.clear();
+        self.cleared_rows.clear();
+    }
+
+    // Popups still showing, where they are now
+    pub fn visible<'a>(&'a self, clock: &dyn Clock) -> impl Iterator<Item = PopupView<'a>> + 'a {
+        let now = clock.now();
+        self.popups.iter().filter_map(move |popup| {
+            let age = now.duration_since(popup.born);
+            if age >= POPUP_LIFETIME {
+                return None;
+            }
+            let progress = age.as_secs_f32() / POPUP_LIFETIME.as_secs_f32();
+            Some(PopupView {
+                text: &popup.text,
+                position: (popup.origin.0, popup.origin.1 - POPUP_RISE * progress),
+                life_left: 1.0 - progress,
+            })
+        })
+    }
+}
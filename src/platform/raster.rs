@@ -0,0 +1,179 @@
+// Headless software renderer drawing into an in-memory RGBA image
+// Used to export images without a window, text uses a built-in 3x5 pixel font
+use std::path::Path;
+
+use image::{ImageResult, Rgba, RgbaImage};
+
+use crate::core::types::Color;
+use crate::platform::render::{Rect, Renderer, TextAnchor};
+
+// Font pixels per glyph column and row, glyphs are one column apart
+const GLYPH_WIDTH: f32 = 3.0;
+const GLYPH_HEIGHT: f32 = 5.0;
+const GLYPH_ADVANCE: f32 = 4.0;
+
+pub struct ImageRenderer {
+    pub image: RgbaImage,
+}
+
+impl ImageRenderer {
+    pub fn new(width: u32, height: u32, background: Color) -> Self {
+        Self {
+            image: RgbaImage::from_pixel(width, height, to_rgba(background)),
+        }
+    }
+
+    pub fn save_png(&self, path: &Path) -> ImageResult<()> {
+        self.image.save_with_format(path, image::ImageFormat::Png)
+    }
+
+    // Mix a color into one pixel according to its alpha, pixels outside the image are ignored
+    fn blend(&mut self, x: i64, y: i64, color: Color) {
+        if x < 0 || y < 0 || x >= self.image.width() as i64 || y >= self.image.height() as i64 {
+            return;
+        }
+        let pixel = self.image.get_pixel_mut(x as u32, y as u32);
+        let mix = |old: u8, new: f32| (old as f32 * (1.0 - color.a) + new * 255.0 * color.a).round() as u8;
+        *pixel = Rgba([mix(pixel[0], color.r), mix(pixel[1], color.g), mix(pixel[2], color.b), 255]);
+    }
+
+    // Fill every pixel whose center passes the test, within a bounding box
+    fn fill_where(&mut self, (x0, y0, x1, y1): (f32, f32, f32, f32), color: Color, inside: impl Fn(f32, f32) -> bool) {
+        for y in y0.floor() as i64..y1.ceil() as i64 {
+            for x in x0.floor() as i64..x1.ceil() as i64 {
+                if inside(x as f32 + 0.5, y as f32 + 0.5) {
+                    self.blend(x, y, color);
+                }
+            }
+        }
+    }
+}
+
+fn to_rgba(color: Color) -> Rgba<u8> {
+    let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    Rgba([channel(color.r), channel(color.g), channel(color.b), channel(color.a)])
+}
+
+impl Renderer for ImageRenderer {
+    fn fill_rect(&mut self, rect: Rect, color: Color) {
+        self.fill_where((rect.x, rect.y, rect.x + rect.w, rect.y + rect.h), color, |x, y| rect.contains(x, y));
+    }
+
+    fn fill_polygon(&mut self, points: &[(f32, f32)], color: Color) {
+        if points.len() < 3 {
+            return;
+        }
+        let bounds = points.iter().fold((f32::MAX, f32::MAX, f32::MIN, f32::MIN), |(x0, y0, x1, y1), &(x, y)| {
+            (x0.min(x), y0.min(y), x1.max(x), y1.max(y))
+        });
+
+        // Even-odd rule: a point is inside if a ray to the right crosses an odd number of edges
+        self.fill_where(bounds, color, |x, y| {
+            let mut inside = false;
+            for (i, &(ax, ay)) in points.iter().enumerate() {
+                let (bx, by) = points[(i + 1) % points.len()];
+                if (ay > y) != (by > y) && x < ax + (y - ay) / (by - ay) * (bx - ax) {
+                    inside = !inside;
+                }
+            }
+            inside
+        });
+    }
+
+    fn draw_line(&mut self, from: (f32, f32), to: (f32, f32), width: f32, color: Color) {
+        let half = (width / 2.0).max(0.5);
+        let bounds = (from.0.min(to.0) - half, from.1.min(to.1) - half, from.0.max(to.0) + half, from.1.max(to.1) + half);
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let length_squared = dx * dx + dy * dy;
+
+        // Pixels within half the width of the segment
+        self.fill_where(bounds, color, |x, y| {
+            let t = if length_squared > 0.0 {
+                (((x - from.0) * dx + (y - from.1) * dy) / length_squared).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let (px, py) = (from.0 + t * dx - x, from.1 + t * dy - y);
+            px * px + py * py <= half * half
+        });
+    }
+
+    // Scale 1 matches the height of the windowed frontends' default text
+    fn draw_text(&mut self, text: &str, position: (f32, f32), scale: f32, anchor: TextAnchor, color: Color) {
+        let unit = (3.0 * scale).round().max(1.0);
+        let width = text.chars().count() as f32 * GLYPH_ADVANCE * unit - unit;
+        let left = match anchor {
+            TextAnchor::Left => position.0,
+            TextAnchor::Center => position.0 - width / 2.0,
+        };
+        let top = position.1 - GLYPH_HEIGHT * unit / 2.0;
+
+        for (i, c) in text.chars().enumerate() {
+            let glyph_left = left + i as f32 * GLYPH_ADVANCE * unit;
+            for (row, bits) in glyph(c).iter().enumerate() {
+                for column in 0..GLYPH_WIDTH as usize {
+                    if bits & (0b100 >> column) != 0 {
+                        let x = glyph_left + column as f32 * unit;
+                        let y = top + row as f32 * unit;
+                        self.fill_rect(Rect::new(x, y, unit, unit), color);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Rows of a 3x5 glyph, top first, leftmost pixel in the highest bit
+// Lowercase letters are drawn as capitals, unknown characters as a question mark
+fn glyph(c: char) -> [u8; 5] {
+    match c.to_ascii_uppercase() {
+        ' ' => [0b000, 0b000, 0b000, 0b000, 0b000],
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b010, 0b010, 0b010],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+        'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'C' => [0b011, 0b100, 0b100, 0b100, 0b011],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b100, 0b110, 0b100, 0b111],
+        'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+        'G' => [0b011, 0b100, 0b101, 0b101, 0b011],
+        'H' => [0b101, 0b101, 0b111, 0b101, 0b101],
+        'I' => [0b111, 0b010, 0b010, 0b010, 0b111],
+        'J' => [0b001, 0b001, 0b001, 0b101, 0b010],
+        'K' => [0b101, 0b101, 0b110, 0b101, 0b101],
+        'L' => [0b100, 0b100, 0b100, 0b100, 0b111],
+        'M' => [0b101, 0b111, 0b111, 0b101, 0b101],
+        'N' => [0b110, 0b101, 0b101, 0b101, 0b101],
+        'O' => [0b010, 0b101, 0b101, 0b101, 0b010],
+        'P' => [0b110, 0b101, 0b110, 0b100, 0b100],
+        'Q' => [0b010, 0b101, 0b101, 0b110, 0b011],
+        'R' => [0b110, 0b101, 0b110, 0b101, 0b101],
+        'S' => [0b011, 0b100, 0b010, 0b001, 0b110],
+        'T' => [0b111, 0b010, 0b010, 0b010, 0b010],
+        'U' => [0b101, 0b101, 0b101, 0b101, 0b111],
+        'V' => [0b101, 0b101, 0b101, 0b101, 0b010],
+        'W' => [0b101, 0b101, 0b111, 0b111, 0b101],
+        'X' => [0b101, 0b101, 0b010, 0b101, 0b101],
+        'Y' => [0b101, 0b101, 0b010, 0b010, 0b010],
+        'Z' => [0b111, 0b001, 0b010, 0b100, 0b111],
+        ':' => [0b000, 0b010, 0b000, 0b010, 0b000],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        ',' => [0b000, 0b000, 0b000, 0b010, 0b100],
+        '/' => [0b001, 0b001, 0b010, 0b100, 0b100],
+        '+' => [0b000, 0b010, 0b111, 0b010, 0b000],
+        '!' => [0b010, 0b010, 0b010, 0b000, 0b010],
+        '(' => [0b001, 0b010, 0b010, 0b010, 0b001],
+        ')' => [0b100, 0b010, 0b010, 0b010, 0b100],
+        '#' => [0b101, 0b111, 0b101, 0b111, 0b101],
+        _ => [0b110, 0b001, 0b010, 0b000, 0b010],
+    }
+}
@@ -0,0 +1,1137 @@
+// Platform-independent rendering - describes what to draw for a GameState
+// Each platform implements the Renderer trait with its own drawing primitives
+use crate::core::block::{Block, BlockKind};
+use crate::core::event::ScoreBreakdown;
+use crate::core::build_info;
+use crate::core::editor::{Editor, Tool};
+use crate::core::game::GameState;
+use crate::core::powerup::PowerUpKind;
+use crate::core::scores::HighScores;
+use crate::core::stats::{MarkerKind, RunStats};
+use crate::core::types::{Color, Direction, GameConfig, GameMode, GridPos, Theme};
+use crate::platform::animation::{AnimationFrame, Pose};
+use crate::platform::format::Locale;
+use crate::platform::hud::Hud;
+use crate::platform::input::{Control, KeyBindings, RebindMenu};
+use crate::platform::input_macro::MAX_MACRO_ACTIONS;
+use crate::platform::motion::slide_offset;
+use crate::platform::particles::{ParticleKind, ParticleView};
+use crate::platform::popups::PopupView;
+use crate::platform::settings::{Settings, SettingsItem, SettingsMenu};
+use crate::platform::mouse::MouseInput;
+#[cfg(feature = "touch")]
+use crate::platform::touch::{TouchButton, TouchControls};
+
+// Horizontal anchoring of text relative to its position (always centered vertically)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TextAnchor {
+    Left,
+    Center,
+}
+
+// Axis-aligned rectangle in window pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub x: f32,
+    pub y: f32,
+    pub w: f32,
+    pub h: f32,
+}
+
+impl Rect {
+    pub fn new(x: f32, y: f32, w: f32, h: f32) -> Self {
+        Self { x, y, w, h }
+    }
+
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x <= self.x + self.w && y >= self.y && y <= self.y + self.h
+    }
+}
+
+// Drawing primitives every platform has to provide
+pub trait Renderer {
+    fn fill_rect(&mut self, rect: Rect, color: Color);
+    fn fill_polygon(&mut self, points: &[(f32, f32)], color: Color);
+    fn draw_line(&mut self, from: (f32, f32), to: (f32, f32), width: f32, color: Color);
+    fn draw_text(&mut self, text: &str, position: (f32, f32), scale: f32, anchor: TextAnchor, color: Color);
+}
+
+// Renderer passing every draw on with its color run through a theme
+pub struct Themed<'a, R: Renderer> {
+    pub theme: Theme,
+    pub inner: &'a mut R,
+}
+
+impl<R: Renderer> Renderer for Themed<'_, R> {
+    fn fill_rect(&mut self, rect: Rect, color: Color) {
+        self.inner.fill_rect(rect, self.theme.apply(color));
+    }
+
+    fn fill_polygon(&mut self, points: &[(f32, f32)], color: Color) {
+        self.inner.fill_polygon(points, self.theme.apply(color));
+    }
+
+    fn draw_line(&mut self, from: (f32, f32), to: (f32, f32), width: f32, color: Color) {
+        self.inner.draw_line(from, to, width, self.theme.apply(color));
+    }
+
+    fn draw_text(&mut self, text: &str, position: (f32, f32), scale: f32, anchor: TextAnchor, color: Color) {
+        self.inner.draw_text(text, position, scale, anchor, self.theme.apply(color));
+    }
+}
+
+// Renderer passing every draw on moved by an offset in pixels, for shaking the board
+pub struct Camera<'a, R: Renderer> {
+    pub offset: (f32, f32),
+    pub inner: &'a mut R,
+}
+
+impl<R: Renderer> Camera<'_, R> {
+    fn moved(&self, point: (f32, f32)) -> (f32, f32) {
+        (point.0 + self.offset.0, point.1 + self.offset.1)
+    }
+}
+
+impl<R: Renderer> Renderer for Camera<'_, R> {
+    fn fill_rect(&mut self, rect: Rect, color: Color) {
+        self.inner.fill_rect(Rect::new(rect.x + self.offset.0, rect.y + self.offset.1, rect.w, rect.h), color);
+    }
+
+    fn fill_polygon(&mut self, points: &[(f32, f32)], color: Color) {
+        // Nothing to copy while the board is at rest
+        if self.offset == (0.0, 0.0) {
+            return self.inner.fill_polygon(points, color);
+        }
+        let moved: Vec<(f32, f32)> = points.iter().map(|&point| self.moved(point)).collect();
+        self.inner.fill_polygon(&moved, color);
+    }
+
+    fn draw_line(&mut self, from: (f32, f32), to: (f32, f32), width: f32, color: Color) {
+        self.inner.draw_line(self.moved(from), self.moved(to), width, color);
+    }
+
+    fn draw_text(&mut self, text: &str, position: (f32, f32), scale: f32, anchor: TextAnchor, color: Color) {
+        self.inner.draw_text(text, self.moved(position), scale, anchor, color);
+    }
+}
+
+// How the layout sits in a window of another size: scaled evenly to fit and centered, with bars
+// along the sides it doesn't reach
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Letterbox {
+    pub scale: f32,
+    pub offset: (f32, f32), // Top left corner of the layout in the window, in window pixels
+}
+
+impl Letterbox {
+    pub fn fit(layout: &Layout, window: (f32, f32)) -> Self {
+        let (width, height) = (layout.window_width(), layout.window_height());
+        // A minimized window has no size to fit into
+        if window.0 <= 0.0 || window.1 <= 0.0 || width <= 0.0 || height <= 0.0 {
+            return Self { scale: 1.0, offset: (0.0, 0.0) };
+        }
+        let scale = (window.0 / width).min(window.1 / height);
+        Self { scale, offset: ((window.0 - width * scale) / 2.0, (window.1 - height * scale) / 2.0) }
+    }
+
+    // Cells as big as they end up on screen
+    pub fn cell_size(&self, layout: &Layout) -> f32 {
+        layout.cell_size * self.scale
+    }
+
+    // Layout point under a window point, e.g. for mouse clicks
+    pub fn to_layout(&self, (x, y): (f32, f32)) -> (f32, f32) {
+        ((x - self.offset.0) / self.scale, (y - self.offset.1) / self.scale)
+    }
+
+    // The whole window in layout coordinates, bars included
+    pub fn visible_area(&self, window: (f32, f32)) -> Rect {
+        let (x, y) = self.to_layout((0.0, 0.0));
+        Rect::new(x, y, window.0 / self.scale, window.1 / self.scale)
+    }
+}
+
+// Renderer passing only text on, for frontends that draw the shapes some other way
+pub struct TextOnly<'a, R: Renderer> {
+    pub inner: &'a mut R,
+}
+
+impl<R: Renderer> Renderer for TextOnly<'_, R> {
+    fn fill_rect(&mut self, _rect: Rect, _color: Color) {}
+
+    fn fill_polygon(&mut self, _points: &[(f32, f32)], _color: Color) {}
+
+    fn draw_line(&mut self, _from: (f32, f32), _to: (f32, f32), _width: f32, _color: Color) {}
+
+    fn draw_text(&mut self, text: &str, position: (f32, f32), scale: f32, anchor: TextAnchor, color: Color) {
+        self.inner.draw_text(text, position, scale, anchor, color);
+    }
+}
+
+// Screen layout derived from the game configuration, and how numbers and dates are written on it
+pub struct Layout {
+    pub grid_width: usize,
+    pub grid_height: usize,
+    pub cell_size: f32,
+    pub score_bar_height: f32,
+    pub locale: Locale,
+    pub origin_x: f32, // Left edge of the grid, past the editor for its live preview
+    pub split_view: bool, // The editor with its live preview to the right, one cell apart
+    pub grid_lines: bool, // Lines between the cells, can be turned off in the settings
+}
+
+impl Layout {
+    pub fn new(game: &GameState) -> Self {
+        Self {
+            grid_width: game.grid_width,
+            grid_height: game.grid_height,
+            cell_size: game.cell_size,
+            score_bar_height: game.cell_size,
+            locale: Locale::default(),
+            origin_x: 0.0,
+            split_view: false,
+            grid_lines: true,
+        }
+    }
+
+    pub fn with_locale(self, locale: Locale) -> Self {
+        Self { locale, ..self }
+    }
+
+    pub fn with_split_view(self, split_view: bool) -> Self {
+        Self { split_view, ..self }
+    }
+
+    pub fn with_grid_lines(self, grid_lines: bool) -> Self {
+        Self { grid_lines, ..self }
+    }
+
+    // Where the editor's live preview is drawn, to the right of the grid being edited
+    pub fn preview(&self) -> Layout {
+        Layout {
+            origin_x: self.origin_x + self.grid_pixel_width() + self.cell_size,
+            split_view: false,
+            ..*self
+        }
+    }
+
+    pub fn grid_pixel_width(&self) -> f32 {
+        self.grid_width as f32 * self.cell_size
+    }
+
+    pub fn grid_pixel_height(&self) -> f32 {
+        self.grid_height as f32 * self.cell_size
+    }
+
+    pub fn window_width(&self) -> f32 {
+        if self.split_view {
+            self.preview().origin_x + self.grid_pixel_width()
+        } else {
+            self.grid_pixel_width()
+        }
+    }
+
+    pub fn window_height(&self) -> f32 {
+        self.grid_pixel_height() + self.score_bar_height
+    }
+
+    // Rectangle of a grid cell, offset below the score bar
+    pub fn cell_rect(&self, x: usize, y: usize) -> Rect {
+        Rect::new(
+            x as f32 * self.cell_size + self.origin_x,
+            y as f32 * self.cell_size + self.score_bar_height,
+            self.cell_size,
+            self.cell_size,
+        )
+    }
+
+    // Grid cell under a window point, None over the score bar or outside the window
+    pub fn cell_at(&self, x: f32, y: f32) -> Option<GridPos> {
+        let (x, y) = (x - self.origin_x, y - self.score_bar_height);
+        if x < 0.0 || y < 0.0 {
+            return None;
+        }
+        let cell = GridPos::new((x / self.cell_size) as usize, (y / self.cell_size) as usize);
+        cell.within(self.grid_width, self.grid_height).then_some(cell)
+    }
+
+    // Palette, save and play buttons of the editor, side by side across the score bar above its grid
+    pub fn editor_button(&self, button: EditorButton) -> Rect {
+        let index = match button {
+            EditorButton::Tool(tool) => Tool::PALETTE.iter().position(|&t| t == tool).unwrap_or(0),
+            EditorButton::Save => Tool::PALETTE.len(),
+            EditorButton::Play => Tool::PALETTE.len() + 1,
+        };
+        let width = self.grid_pixel_width() / EditorButton::all().len() as f32;
+        Rect::new(self.origin_x + index as f32 * width + 1.0, 1.0, width - 2.0, self.score_bar_height - 2.0)
+    }
+
+    pub fn editor_button_at(&self, x: f32, y: f32) -> Option<EditorButton> {
+        EditorButton::all().into_iter().find(|&button| self.editor_button(button).contains(x, y))
+    }
+
+    // The cells of the player's whole body
+    pub fn player_rect(&self, game: &GameState) -> Rect {
+        let GridPos { x, y } = game.player.position;
+        let head = self.cell_rect(x, y);
+        Rect::new(head.x, head.y, head.w, head.h * game.player.body_size as f32)
+    }
+
+    pub fn restart_button(&self) -> Rect {
+        let button_width = 80.0;
+        let button_height = self.score_bar_height * 0.8;
+        let button_x = self.window_width() - button_width - 10.0;
+        let button_y = (self.score_bar_height - button_height) / 2.0;
+        Rect::new(button_x, button_y, button_width, button_height)
+    }
+
+    // Shown under the game over message
+    pub fn share_button(&self) -> Rect {
+        let button_width = 80.0;
+        let button_height = self.score_bar_height * 0.8;
+        let button_x = (self.window_width() - button_width) / 2.0;
+        let button_y = self.window_height() / 2.0 + 50.0;
+        Rect::new(button_x, button_y, button_width, button_height)
+    }
+
+    // Next to the share button, opens the high scores
+    pub fn scores_button(&self) -> Rect {
+        let share = self.share_button();
+        Rect::new(share.x + share.w + 10.0, share.y, share.w, share.h)
+    }
+
+    // Under the share button, as tall as the window leaves room for
+    pub fn timeline_rect(&self) -> Rect {
+        let share = self.share_button();
+        let top = share.y + share.h + 10.0;
+        let height = (self.window_height() - top - 10.0).clamp(0.0, 80.0);
+        Rect::new(10.0, top, self.window_width() - 20.0, height)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EditorButton {
+    Tool(Tool),
+    Save,
+    Play,
+}
+
+impl EditorButton {
+    pub fn all() -> Vec<EditorButton> {
+        Tool::PALETTE.iter().map(|&tool| EditorButton::Tool(tool))
+            .chain([EditorButton::Save, EditorButton::Play])
+            .collect()
+    }
+}
+
+// Seconds left of a Time Attack game from which the time in the HUD is drawn as a warning
+pub const COUNTDOWN_WARNING_SECONDS: u64 = 30;
+
+// Size of the run summary card in pixels
+pub const SUMMARY_CARD_WIDTH: u32 = 360;
+pub const SUMMARY_CARD_HEIGHT: u32 = 220;
+pub const SUMMARY_CARD_BACKGROUND: Color = Color::from_rgb(20, 24, 48);
+
+pub const TERRAIN_COLOR: Color = Color::from_rgb(120, 120, 120);
+pub const PIT_COLOR: Color = Color::from_rgb(10, 10, 10);
+pub const PLAYER_COLOR: Color = Color::RED;
+// The idle character is drawn darker than the one under control
+pub const PARTNER_COLOR: Color = Color::from_rgb(140, 30, 30);
+// Faded player color while invincible after losing a life
+pub const INVINCIBLE_PLAYER_COLOR: Color = Color { r: 1.0, g: 0.0, b: 0.0, a: 0.4 };
+pub const COIN_COLOR: Color = Color::from_rgb(240, 200, 40);
+pub const POWERUP_TEXT_COLOR: Color = Color::from_rgb(255, 255, 120);
+// The Foreman in his overalls, with a hard hat on top
+pub const ENEMY_COLOR: Color = Color::from_rgb(40, 90, 200);
+pub const ENEMY_HAT_COLOR: Color = Color::from_rgb(250, 210, 0);
+// Warnings of a pending fall or an incoming storm
+pub const WARNING_COLOR: Color = Color::from_rgb(255, 160, 40);
+pub const DRAG_COLOR: Color = Color { r: 1.0, g: 1.0, b: 1.0, a: 0.6 };
+pub const TIMELINE_BACKGROUND: Color = Color { r: 0.0, g: 0.0, b: 0.0, a: 0.6 };
+pub const TIMELINE_MARKER_COLOR: Color = Color { r: 1.0, g: 1.0, b: 1.0, a: 0.5 };
+pub const EDITOR_BUTTON_COLOR: Color = Color::from_rgb(40, 40, 70);
+pub const DESPAWN_COLOR: Color = Color { r: 0.8, g: 0.8, b: 0.8, a: 0.6 };
+pub const DEBRIS_COLOR: Color = Color::from_rgb(150, 110, 70);
+pub const SPARKLE_COLOR: Color = Color::from_rgb(255, 240, 160);
+pub const FLAME_COLOR: Color = Color::from_rgb(255, 90, 20);
+pub const POPUP_COLOR: Color = Color::from_rgb(255, 220, 60);
+// Lit bombs flash this on every other update of their fuse
+pub const FUSE_FLASH_COLOR: Color = Color::from_rgb(255, 230, 120);
+pub const HIT_FLASH_COLOR: Color = Color { r: 1.0, g: 0.0, b: 0.0, a: 0.4 };
+pub const HINT_BACKGROUND: Color = Color { r: 0.0, g: 0.0, b: 0.3, a: 0.75 };
+// Translucent ghost of a simulated future state
+pub const TOUCH_BUTTON_COLOR: Color = Color { r: 1.0, g: 1.0, b: 1.0, a: 0.2 };
+pub const PREVIEW_COLOR: Color = Color { r: 0.3, g: 0.3, b: 0.9, a: 0.35 };
+// Outline where a falling block is going to land
+pub const GHOST_COLOR: Color = Color { r: 1.0, g: 1.0, b: 1.0, a: 0.4 };
+pub const ABOUT_BACKGROUND: Color = Color { r: 0.0, g: 0.0, b: 0.0, a: 0.85 };
+
+pub fn block_color(kind: BlockKind) -> Color {
+    match kind {
+        BlockKind::Crate => Color::BLACK,
+        BlockKind::Steel => Color::from_rgb(110, 120, 130),
+        BlockKind::Ice => Color::from_rgb(120, 200, 240),
+        BlockKind::Bomb => Color::from_rgb(230, 120, 20),
+    }
+}
+
+// Color of a block on the board, a lit bomb blinking as its fuse burns
+pub fn lit_block_color(block: &Block) -> Color {
+    match block.fuse {
+        Some(fuse) if fuse % 2 == 1 => FUSE_FLASH_COLOR,
+        _ => block_color(block.kind),
+    }
+}
+
+// Outlines and patterns contrast with the block color underneath
+pub fn pattern_color(kind: BlockKind) -> Color {
+    match kind {
+        BlockKind::Crate | BlockKind::Steel => Color::WHITE,
+        BlockKind::Ice | BlockKind::Bomb => Color::BLACK,
+    }
+}
+
+pub fn powerup_color(kind: PowerUpKind) -> Color {
+    match kind {
+        PowerUpKind::Hammer => Color::from_rgb(160, 90, 40),
+        PowerUpKind::Freeze => Color::from_rgb(80, 220, 255),
+        PowerUpKind::SlowMotion => Color::from_rgb(180, 100, 230),
+        PowerUpKind::Magnet => Color::from_rgb(220, 40, 60),
+    }
+}
+
+// Draw a whole frame of the game
+pub fn draw_game(game: &GameState, layout: &Layout, renderer: &mut impl Renderer) {
+    draw_game_animated(game, layout, &AnimationFrame::default(), renderer);
+}
+
+// Draw the game mid-animation: the player sliding into its cell in its current pose, and blocks
+// that just landed squashed
+pub fn draw_game_animated(game: &GameState, layout: &Layout, animation: &AnimationFrame, renderer: &mut impl Renderer) {
+    let renderer = &mut Themed { theme: game.theme, inner: renderer };
+    draw_score_bar(game, layout, renderer);
+    draw_restart_button(layout, renderer);
+    draw_objective(game, layout, renderer);
+    draw_grid(layout, renderer);
+    draw_spawn_warning(game, layout, renderer);
+    draw_terrain(game, layout, renderer);
+    draw_player_posed(game, layout, animation.player_offset, animation.pose, renderer);
+    draw_enemy_moving(game, layout, animation.tick_remaining, renderer);
+    draw_blocks_animated(game, layout, animation, renderer);
+    draw_pickups(game, layout, renderer);
+    draw_next_blocks(game, layout, renderer);
+    draw_storm_banner(game, layout, renderer);
+    draw_game_over(game, layout, renderer);
+}
+
+// The level being edited, as it will look when the game starts, under the editor's buttons
+pub fn draw_editor(editor: &Editor, layout: &Layout, renderer: &mut impl Renderer) {
+    renderer.fill_rect(Rect::new(0.0, 0.0, layout.window_width(), layout.score_bar_height), Color::BLUE);
+    for button in EditorButton::all() {
+        let rect = layout.editor_button(button);
+        let (label, color) = match button {
+            EditorButton::Tool(tool) => (tool.label(), tool_color(tool)),
+            EditorButton::Save => ("Save", Color::GREEN),
+            EditorButton::Play => ("Play", Color::GREEN),
+        };
+        renderer.fill_rect(rect, color);
+        if button == EditorButton::Tool(editor.tool) {
+            draw_outline(rect, 2.0, Color::WHITE, renderer);
+        }
+        let center = (rect.x + rect.w / 2.0, rect.y + rect.h / 2.0);
+        renderer.draw_text(label, center, 0.6, TextAnchor::Center, Color::BLACK);
+    }
+
+    draw_grid(layout, renderer);
+    // A board too small to play on has nothing on it to show yet
+    let Ok(mut preview) = GameState::from_level(&editor.level, GameConfig {
+        cell_size: layout.cell_size,
+        seed: Some(0),
+        ..GameConfig::default()
+    }) else {
+        return;
+    };
+    preview.blocks.retain(|block| !block.falling);
+    preview.sync_occupancy();
+    draw_terrain(&preview, layout, renderer);
+    draw_player(&preview, layout, renderer);
+    draw_blocks(&preview, layout, renderer);
+}
+
+// The editor's live preview: the level being played, to the right of the grid being edited
+pub fn draw_live_preview(game: &GameState, layout: &Layout, renderer: &mut impl Renderer) {
+    let preview = layout.preview();
+    let title = (preview.origin_x + preview.grid_pixel_width() / 2.0, layout.score_bar_height / 2.0);
+    renderer.draw_text("Preview", title, 0.6, TextAnchor::Center, Color::WHITE);
+
+    draw_grid(&preview, renderer);
+    draw_terrain(game, &preview, renderer);
+    draw_player(game, &preview, renderer);
+    draw_enemy(game, &preview, renderer);
+    draw_blocks(game, &preview, renderer);
+    draw_pickups(game, &preview, renderer);
+}
+
+// Palette swatch of a tool, the color of what it places
+fn tool_color(tool: Tool) -> Color {
+    match tool {
+        Tool::Block(_, kind) => block_color(kind),
+        Tool::Wall | Tool::Ramp(_) => TERRAIN_COLOR,
+        Tool::Pit => WARNING_COLOR,
+        Tool::PlayerStart => PLAYER_COLOR,
+        Tool::Erase => EDITOR_BUTTON_COLOR,
+    }
+}
+
+fn draw_outline(rect: Rect, width: f32, color: Color, renderer: &mut impl Renderer) {
+    let (right, bottom) = (rect.x + rect.w, rect.y + rect.h);
+    renderer.draw_line((rect.x, rect.y), (right, rect.y), width, color);
+    renderer.draw_line((right, rect.y), (right, bottom), width, color);
+    renderer.draw_line((right, bottom), (rect.x, bottom), width, color);
+    renderer.draw_line((rect.x, bottom), (rect.x, rect.y), width, color);
+}
+
+pub fn draw_score_bar(game: &GameState, layout: &Layout, renderer: &mut impl Renderer) {
+    renderer.fill_rect(Rect::new(0.0, 0.0, layout.window_width(), layout.score_bar_height), Color::BLUE);
+
+    let hud = Hud::new(game, layout);
+    for entry in &hud.entries {
+        let area = entry.area;
+        renderer.draw_text(&entry.text, (area.x, area.y + area.h / 2.0), hud.scale, TextAnchor::Left, entry.color);
+        // Meters run along the bottom of the bar under their item
+        if let Some(meter) = entry.meter {
+            let height = (area.h / 10.0).max(2.0);
+            let bar = Rect::new(area.x, area.y + area.h - height - 1.0, area.w * meter.clamp(0.0, 1.0), height);
+            renderer.fill_rect(bar, entry.color);
+        }
+    }
+}
+
+// The level's objective and how far along it is, just under the score bar, green once it is done
+pub fn draw_objective(game: &GameState, layout: &Layout, renderer: &mut impl Renderer) {
+    let color = if game.objective.complete() { Color::GREEN } else { Color { a: 0.7, ..Color::WHITE } };
+    let position = (10.0, layout.score_bar_height + layout.cell_size / 2.0);
+    renderer.draw_text(&game.objective.progress_text(), position, 0.8, TextAnchor::Left, color);
+}
+
+pub fn draw_restart_button(layout: &Layout, renderer: &mut impl Renderer) {
+    let button = layout.restart_button();
+    renderer.fill_rect(button, Color::GREEN);
+
+    let center = (button.x + button.w / 2.0, button.y + button.h / 2.0);
+    renderer.draw_text("Restart", center, 1.0, TextAnchor::Center, Color::BLACK);
+}
+
+pub fn draw_grid(layout: &Layout, renderer: &mut impl Renderer) {
+    if !layout.grid_lines {
+        return;
+    }
+    let (left, top) = (layout.origin_x, layout.score_bar_height);
+    let width = layout.grid_pixel_width();
+    let height = layout.grid_pixel_height();
+
+    // Horizontal lines
+    for i in 0..=layout.grid_height {
+        let y = i as f32 * layout.cell_size + top;
+        renderer.draw_line((left, y), (left + width, y), 1.0, Color::BLACK);
+    }
+
+    // Vertical lines
+    for i in 0..=layout.grid_width {
+        let x = i as f32 * layout.cell_size + left;
+        renderer.draw_line((x, top), (x, height + top), 1.0, Color::BLACK);
+    }
+}
+
+pub fn draw_terrain(game: &GameState, layout: &Layout, renderer: &mut impl Renderer) {
+    for GridPos { x, y } in game.terrain.walls() {
+        renderer.fill_rect(layout.cell_rect(x, y), TERRAIN_COLOR);
+    }
+
+    for (GridPos { x, y }, rises_toward) in game.terrain.ramps() {
+        let cell = layout.cell_rect(x, y);
+
+        // Triangle with its tall side facing the rising direction
+        let high_x = match rises_toward {
+            Direction::Right => cell.x + cell.w,
+            Direction::Left => cell.x,
+        };
+        renderer.fill_polygon(
+            &[(cell.x, cell.y + cell.h), (cell.x + cell.w, cell.y + cell.h), (high_x, cell.y)],
+            TERRAIN_COLOR,
+        );
+    }
+
+    // Pits are a dark gap along the bottom edge of the grid
+    for x in game.terrain.pits() {
+        let cell = layout.cell_rect(x, game.grid_height - 1);
+        renderer.fill_rect(Rect::new(cell.x, cell.y + cell.h * 0.8, cell.w, cell.h * 0.2), PIT_COLOR);
+    }
+}
+
+pub fn draw_player(game: &GameState, layout: &Layout, renderer: &mut impl Renderer) {
+    draw_player_at(game, layout, (0.0, 0.0), renderer);
+}
+
+pub fn draw_player_at(game: &GameState, layout: &Layout, offset: (f32, f32), renderer: &mut impl Renderer) {
+    draw_player_posed(game, layout, offset, Pose::default(), renderer);
+}
+
+pub fn draw_player_posed(game: &GameState, layout: &Layout, offset: (f32, f32), pose: Pose, renderer: &mut impl Renderer) {
+    if let Some(partner) = &game.partner {
+        let head = layout.cell_rect(partner.position.x, partner.position.y);
+        renderer.fill_rect(Rect::new(head.x, head.y, head.w, head.h * partner.body_size as f32), PARTNER_COLOR);
+    }
+
+    let GridPos { x, y } = game.player.position;
+    let (shift_x, shift_y) = (offset.0 * layout.cell_size, offset.1 * layout.cell_size);
+    let head = layout.cell_rect(x, y);
+    let head = Rect::new(head.x + shift_x, head.y + shift_y, head.w, head.h);
+    let color = if game.invincible_ticks > 0 { INVINCIBLE_PLAYER_COLOR } else { PLAYER_COLOR };
+    
+    // Over a gap the player wobbles from side to side under a warning mark until the fall starts,
+    // so the movement lockout doesn't look like dropped input
+    let fall_delay = game.player.fall_delay_remaining();
+    let wobble = match fall_delay {
+        0 => 0.0,
+        n if n % 2 == 0 => head.w * 0.1,
+        _ => -head.w * 0.1,
+    };
+    let (pose_x, pose_y, scale_w, scale_h) = pose.body_transform();
+    let (body_w, body_h) = (head.w * scale_w, head.h * game.player.body_size as f32 * scale_h);
+    let feet = head.y + head.h * game.player.body_size as f32;
+    renderer.fill_rect(
+        Rect::new(
+            head.x + wobble + (head.w - body_w) / 2.0 + pose_x * head.w,
+            feet - body_h + pose_y * head.h,
+            body_w,
+            body_h,
+        ),
+        color,
+    );
+    // The carried block rides along with the wobble
+    if let (Some(block), Some(GridPos { x, y })) = (&game.player.carrying, game.player.carried_cell()) {
+        let cell = layout.cell_rect(x, y);
+        let cell = Rect::new(cell.x + wobble + shift_x, cell.y + shift_y, cell.w, cell.h);
+        renderer.fill_rect(cell, block_color(block.kind));
+        if game.block_patterns {
+            draw_block_pattern(block.kind, cell, renderer);
+        }
+    }
+    if fall_delay > 0 {
+        renderer.draw_text("!", (head.x + head.w / 2.0, head.y - head.h / 2.0), 1.0, TextAnchor::Center, WARNING_COLOR);
+    }
+}
+
+pub fn draw_enemy(game: &GameState, layout: &Layout, renderer: &mut impl Renderer) {
+    draw_enemy_moving(game, layout, 0.0, renderer);
+}
+
+// The Foreman on his way over from the cell he left in the latest update
+pub fn draw_enemy_moving(game: &GameState, layout: &Layout, tick_remaining: f32, renderer: &mut impl Renderer) {
+    let Some(enemy) = &game.enemy else {
+        return;
+    };
+    let offset = slide_offset(enemy.last_position, enemy.position, tick_remaining);
+    let cell = shifted(layout.cell_rect(enemy.position.x, enemy.position.y), offset, layout.cell_size);
+    let inset = cell.w * 0.15;
+    renderer.fill_rect(Rect::new(cell.x + inset, cell.y + cell.h * 0.3, cell.w - 2.0 * inset, cell.h * 0.7), ENEMY_COLOR);
+    renderer.fill_polygon(
+        &[(cell.x, cell.y + cell.h * 0.3), (cell.x + cell.w, cell.y + cell.h * 0.3), (cell.x + cell.w / 2.0, cell.y)],
+        ENEMY_HAT_COLOR,
+    );
+}
+
+pub fn draw_blocks(game: &GameState, layout: &Layout, renderer: &mut impl Renderer) {
+    draw_blocks_animated(game, layout, &AnimationFrame::default(), renderer);
+}
+
+// Blocks slide over from the cells they left in the latest update, and blocks that just landed are drawn
+// flattened onto what they landed on, easing back to full height
+pub fn draw_blocks_animated(game: &GameState, layout: &Layout, animation: &AnimationFrame, renderer: &mut impl Renderer) {
+    for block in &game.blocks {
+        let color = lit_block_color(block);
+        let squash = animation.squashes.iter()
+            .find(|(position, _)| *position == block.position)
+            .map_or(0.0, |&(_, amount)| amount);
+        let offset = slide_offset(block.last_position, block.position, animation.tick_remaining);
+        let bottom = shifted(layout.cell_rect(block.position.x, block.bottom()), offset, layout.cell_size);
+        let floor = bottom.y + bottom.h;
+        for GridPos { x, y } in block.cells() {
+            let cell = squashed(shifted(layout.cell_rect(x, y), offset, layout.cell_size), floor, squash);
+            renderer.fill_rect(cell, color);
+            if game.block_patterns {
+                draw_block_pattern(block.kind, cell, renderer);
+            }
+        }
+    }
+}
+
+// A rect moved by an offset given in cells
+fn shifted(rect: Rect, offset: (f32, f32), cell_size: f32) -> Rect {
+    Rect::new(rect.x + offset.0 * cell_size, rect.y + offset.1 * cell_size, rect.w, rect.h)
+}
+
+// A cell of a block flattened by up to a quarter of its height toward the block's bottom edge, and widened
+fn squashed(cell: Rect, floor: f32, amount: f32) -> Rect {
+    if amount <= 0.0 {
+        return cell;
+    }
+    let (scale_w, scale_h) = (1.0 + 0.1 * amount, 1.0 - 0.25 * amount);
+    let w = cell.w * scale_w;
+    Rect::new(cell.x - (w - cell.w) / 2.0, floor - (floor - cell.y) * scale_h, w, cell.h * scale_h)
+}
+
+// Thick outline plus a pattern telling the kinds apart without color:
+// crates are plain, steel has horizontal stripes, ice diagonal stripes and bombs dots
+pub fn draw_block_pattern(kind: BlockKind, cell: Rect, renderer: &mut impl Renderer) {
+    let color = pattern_color(kind);
+    let width = (cell.w / 10.0).max(2.0);
+    let inset = width / 2.0;
+    let (left, top, right, bottom) = (cell.x + inset, cell.y + inset, cell.x + cell.w - inset, cell.y + cell.h - inset);
+    for (from, to) in [((left, top), (right, top)), ((right, top), (right, bottom)), ((right, bottom), (left, bottom)), ((left, bottom), (left, top))] {
+        renderer.draw_line(from, to, width, color);
+    }
+
+    match kind {
+        BlockKind::Crate => {},
+        BlockKind::Steel => {
+            for i in 1..3 {
+                let y = cell.y + cell.h * i as f32 / 3.0;
+                renderer.draw_line((left, y), (right, y), width / 2.0, color);
+            }
+        },
+        BlockKind::Ice => {
+            renderer.draw_line((left, cell.y + cell.h / 2.0), (cell.x + cell.w / 2.0, top), width / 2.0, color);
+            renderer.draw_line((left, bottom), (right, top), width / 2.0, color);
+            renderer.draw_line((cell.x + cell.w / 2.0, bottom), (right, cell.y + cell.h / 2.0), width / 2.0, color);
+        },
+        BlockKind::Bomb => {
+            let dot = cell.w / 6.0;
+            for (fx, fy) in [(0.3, 0.3), (0.7, 0.3), (0.5, 0.5), (0.3, 0.7), (0.7, 0.7)] {
+                let (cx, cy) = (cell.x + cell.w * fx, cell.y + cell.h * fy);
+                renderer.fill_rect(Rect::new(cx - dot / 2.0, cy - dot / 2.0, dot, dot), color);
+            }
+        },
+    }
+}
+
+// A translucent outline of every falling block at the cells it will land in
+pub fn draw_landing_ghosts(game: &GameState, layout: &Layout, renderer: &mut impl Renderer) {
+    if game.game_over {
+        return;
+    }
+    for (i, block) in game.blocks.iter().enumerate() {
+        let Some(landing) = game.landing_position(i).filter(|&landing| landing != block.position) else {
+            continue;
+        };
+        for GridPos { x, y } in block.cells_at(landing) {
+            let cell = layout.cell_rect(x, y);
+            let inset = cell.w * 0.1;
+            let ghost = Rect::new(cell.x + inset, cell.y + inset, cell.w - 2.0 * inset, cell.h - 2.0 * inset);
+            draw_outline(ghost, (cell.w / 15.0).max(1.0), GHOST_COLOR, renderer);
+        }
+    }
+}
+
+pub fn draw_pickups(game: &GameState, layout: &Layout, renderer: &mut impl Renderer) {
+    for pickup in &game.pickups {
+        let cell = layout.cell_rect(pickup.position.x, pickup.position.y);
+        let (cx, cy) = (cell.x + cell.w / 2.0, cell.y + cell.h / 2.0);
+        let r = cell.w / 3.0;
+
+        match pickup.powerup {
+            // Power-ups are small squares colored by their effect
+            Some(kind) => renderer.fill_rect(Rect::new(cx - r, cy - r, 2.0 * r, 2.0 * r), powerup_color(kind)),
+            // Coins are drawn as small diamonds in the middle of their cell
+            None => renderer.fill_polygon(&[(cx, cy - r), (cx + r, cy), (cx, cy + r), (cx - r, cy)], COIN_COLOR),
+        }
+    }
+}
+
+// Small arrows along the top of the grid over the columns the next blocks will drop into,
+// colored by kind and fading the further off the spawn is
+pub fn draw_next_blocks(game: &GameState, layout: &Layout, renderer: &mut impl Renderer) {
+    for (i, block) in game.next_blocks.iter().enumerate().rev() {
+        let color = Color { a: 0.8 / (i + 1) as f32, ..block_color(block.kind) };
+        for GridPos { x, y } in block.cells().filter(|cell| cell.y == block.position.y) {
+            let cell = layout.cell_rect(x, y);
+            let (cx, quarter) = (cell.x + cell.w / 2.0, cell.w / 4.0);
+            renderer.fill_polygon(&[(cx - quarter, cell.y), (cx + quarter, cell.y), (cx, cell.y + quarter)], color);
+        }
+    }
+}
+
+// Flash the columns the next block is about to drop into, on every other update
+pub fn draw_spawn_warning(game: &GameState, layout: &Layout, renderer: &mut impl Renderer) {
+    let (Some(warning), Some(block)) = (game.spawn_warning, game.next_blocks.front()) else {
+        return;
+    };
+    if warning.ticks_left % 2 == 0 {
+        return;
+    }
+
+    let color = Color { a: 0.3, ..WARNING_COLOR };
+    for x in warning.column..warning.column + block.shape.width() {
+        let top = layout.cell_rect(x, 0);
+        renderer.fill_rect(Rect::new(top.x, top.y, top.w, layout.grid_pixel_height()), color);
+    }
+}
+
+// Mouse controls: a line along the drag, brighter once it is far enough for the next step,
+// and an arrow over the head while a click would jump
+pub fn draw_mouse_affordances(mouse: &MouseInput, game: &GameState, layout: &Layout, renderer: &mut impl Renderer) {
+    if let Some(drag) = &mouse.drag {
+        let color = if drag.next_step(layout.cell_size).is_some() { WARNING_COLOR } else { DRAG_COLOR };
+        renderer.draw_line(drag.start, drag.current, 3.0, color);
+        let size = layout.cell_size * 0.2;
+        renderer.fill_rect(Rect::new(drag.current.0 - size / 2.0, drag.current.1 - size / 2.0, size, size), color);
+    }
+
+    if mouse.hover_jump && mouse.drag.is_none() {
+        let above = layout.player_rect(game);
+        let (left, right, bottom) = (above.x + above.w * 0.2, above.x + above.w * 0.8, above.y - above.h * 0.05);
+        renderer.fill_polygon(&[(left, bottom), (right, bottom), (above.x + above.w / 2.0, bottom - above.w * 0.5)], DRAG_COLOR);
+    }
+}
+
+// Touch buttons over the bottom of the grid, the held one lit up, and the path of a swipe
+#[cfg(feature = "touch")]
+pub fn draw_touch_controls(touch: &TouchControls, layout: &Layout, renderer: &mut impl Renderer) {
+    for button in TouchButton::ALL {
+        let rect = button.rect(layout);
+        let color = if touch.held == Some(button) { DRAG_COLOR } else { TOUCH_BUTTON_COLOR };
+        renderer.fill_rect(rect, color);
+        let center = (rect.x + rect.w / 2.0, rect.y + rect.h / 2.0);
+        renderer.draw_text(button.label(), center, 1.0, TextAnchor::Center, Color::WHITE);
+    }
+    if let Some(swipe) = &touch.swipe {
+        renderer.draw_line(swipe.start, swipe.current, 3.0, DRAG_COLOR);
+    }
+}
+
+// Ghost of where the player and blocks will be in a simulated future state
+pub fn draw_preview(preview: &GameState, layout: &Layout, renderer: &mut impl Renderer) {
+    for block in &preview.blocks {
+        for GridPos { x, y } in block.cells() {
+            renderer.fill_rect(layout.cell_rect(x, y), PREVIEW_COLOR);
+        }
+    }
+
+    let GridPos { x, y } = preview.player.position;
+    let head = layout.cell_rect(x, y);
+    renderer.fill_rect(Rect::new(head.x, head.y, head.w, head.h * preview.player.body_size as f32), PREVIEW_COLOR);
+}
+
+// Text describing how a row clear was scored
+pub fn clear_banner_text(breakdown: &ScoreBreakdown) -> String {
+    let mut details = if breakdown.rows == 1 { "1 row".to_string() } else { format!("{} rows", breakdown.rows) };
+    if breakdown.chain > 1 {
+        details += &format!(", chain x{}", breakdown.chain);
+    }
+    if breakdown.touch_bonus > 0 {
+        details += &format!(", moved +{}", breakdown.touch_bonus);
+    }
+    format!("+{} ({})", breakdown.points, details)
+}
+
+// Score breakdown of the latest clear, shown near the top of the grid
+pub fn draw_clear_banner(breakdown: &ScoreBreakdown, layout: &Layout, renderer: &mut impl Renderer) {
+    let position = (layout.window_width() / 2.0, layout.score_bar_height + layout.cell_size);
+    renderer.draw_text(&clear_banner_text(breakdown), position, 1.5, TextAnchor::Center, POWERUP_TEXT_COLOR);
+}
+
+// Popup over the cell a block just landed in after the player dodged it
+pub fn draw_close_call(cell: GridPos, layout: &Layout, renderer: &mut impl Renderer) {
+    let rect = layout.cell_rect(cell.x, cell.y);
+    let position = (rect.x + rect.w / 2.0, rect.y - rect.h / 2.0);
+    renderer.draw_text("Close call!", position, 0.8, TextAnchor::Center, POWERUP_TEXT_COLOR);
+}
+
+// Ghosts of idle blocks cleared in Zen, fading as fraction_left drops to 0
+pub fn draw_despawned(cells: &[GridPos], fraction_left: f32, layout: &Layout, renderer: &mut impl Renderer) {
+    let color = Color { a: DESPAWN_COLOR.a * fraction_left.clamp(0.0, 1.0), ..DESPAWN_COLOR };
+    for &GridPos { x, y } in cells {
+        renderer.fill_rect(layout.cell_rect(x, y), color);
+    }
+}
+
+// Debris, sparkles and flames, shrinking and fading as their life runs out
+pub fn draw_particles(particles: impl IntoIterator<Item = ParticleView>, layout: &Layout, renderer: &mut impl Renderer) {
+    let origin = layout.cell_rect(0, 0);
+    for particle in particles {
+        let color = match particle.kind {
+            ParticleKind::Debris => DEBRIS_COLOR,
+            ParticleKind::Sparkle => SPARKLE_COLOR,
+            ParticleKind::Flame => FLAME_COLOR,
+        };
+        let life = particle.life_left.clamp(0.0, 1.0);
+        let size = layout.cell_size * 0.2 * (0.5 + life / 2.0);
+        let (x, y) = (origin.x + particle.position.0 * layout.cell_size, origin.y + particle.position.1 * layout.cell_size);
+        renderer.fill_rect(Rect::new(x - size / 2.0, y - size / 2.0, size, size), Color { a: color.a * life, ..color });
+    }
+}
+
+// Points floating up from where they were scored, fading as their life runs out
+pub fn draw_popups<'a>(popups: impl IntoIterator<Item = PopupView<'a>>, layout: &Layout, renderer: &mut impl Renderer) {
+    let origin = layout.cell_rect(0, 0);
+    for popup in popups {
+        let position = (origin.x + popup.position.0 * layout.cell_size, origin.y + popup.position.1 * layout.cell_size);
+        let color = Color { a: popup.life_left.clamp(0.0, 1.0), ..POPUP_COLOR };
+        renderer.draw_text(popup.text, position, 0.8, TextAnchor::Center, color);
+    }
+}
+
+// Red over the whole grid right after the player was hit, fading as fraction_left drops to 0
+pub fn draw_hit_flash(fraction_left: f32, layout: &Layout, renderer: &mut impl Renderer) {
+    let origin = layout.cell_rect(0, 0);
+    let color = Color { a: HIT_FLASH_COLOR.a * fraction_left.clamp(0.0, 1.0), ..HIT_FLASH_COLOR };
+    renderer.fill_rect(Rect::new(origin.x, origin.y, layout.grid_pixel_width(), layout.grid_pixel_height()), color);
+}
+
+// Warning before a block storm and a reminder while it lasts, at the bottom of the grid
+pub fn draw_storm_banner(game: &GameState, layout: &Layout, renderer: &mut impl Renderer) {
+    let Some(storm) = &game.storm else {
+        return;
+    };
+    let text = if storm.active() {
+        "Block storm!"
+    } else if storm.warning() {
+        "Block storm incoming!"
+    } else {
+        return;
+    };
+    let position = (layout.window_width() / 2.0, layout.window_height() - layout.cell_size);
+    renderer.draw_text(text, position, 1.5, TextAnchor::Center, WARNING_COLOR);
+}
+
+// Short message for the player at the bottom of the window
+pub fn draw_notice(text: &str, layout: &Layout, renderer: &mut impl Renderer) {
+    let position = (layout.window_width() / 2.0, layout.window_height() - layout.cell_size / 2.0);
+    renderer.draw_text(text, position, 1.0, TextAnchor::Center, POWERUP_TEXT_COLOR);
+}
+
+// Tutorial hint on a strip just below the score bar
+pub fn draw_hint(text: &str, layout: &Layout, renderer: &mut impl Renderer) {
+    let strip = Rect::new(0.0, layout.score_bar_height, layout.window_width(), layout.cell_size);
+    renderer.fill_rect(strip, HINT_BACKGROUND);
+    let position = (strip.x + strip.w / 2.0, strip.y + strip.h / 2.0);
+    renderer.draw_text(text, position, 0.8, TextAnchor::Center, Color::WHITE);
+}
+
+// Small mark in the score bar while the watchdog has turned extras off to keep up
+pub fn draw_degradation(level: u8, layout: &Layout, renderer: &mut impl Renderer) {
+    if level == 0 {
+        return;
+    }
+    let position = (layout.window_width() - 60.0, layout.score_bar_height / 2.0);
+    renderer.draw_text(&format!("Slow {}", level), position, 0.8, TextAnchor::Left, WARNING_COLOR);
+}
+
+// Mark in the score bar while an input macro is being recorded, with how many actions it holds
+pub fn draw_macro_recording(actions: usize, layout: &Layout, renderer: &mut impl Renderer) {
+    let position = (layout.window_width() / 2.0, layout.score_bar_height / 2.0);
+    let text = format!("REC {}/{}", actions, MAX_MACRO_ACTIONS);
+    renderer.draw_text(&text, position, 0.8, TextAnchor::Center, PLAYER_COLOR);
+}
+
+pub fn draw_game_over(game: &GameState, layout: &Layout, renderer: &mut impl Renderer) {
+    if !game.game_over {
+        return;
+    }
+
+    let center = (layout.window_width() / 2.0, layout.window_height() / 2.0);
+    let score = format!("Final score: {}", layout.locale.number(game.score as u64));
+    renderer.draw_text(&score, (center.0, center.1 - 35.0), 1.0, TextAnchor::Center, Color::WHITE);
+    renderer.draw_text("Game Over", center, 2.0, TextAnchor::Center, Color::RED);
+
+    if let Some(reason) = game.game_over_reason {
+        let below = (center.0, center.1 + 30.0);
+        renderer.draw_text(reason.message(), below, 1.0, TextAnchor::Center, Color::RED);
+    }
+
+    let button = layout.share_button();
+    renderer.fill_rect(button, Color::GREEN);
+    let center = (button.x + button.w / 2.0, button.y + button.h / 2.0);
+    renderer.draw_text("Share", center, 1.0, TextAnchor::Center, Color::BLACK);
+
+    let button = layout.scores_button();
+    renderer.fill_rect(button, Color::GREEN);
+    let center = (button.x + button.w / 2.0, button.y + button.h / 2.0);
+    renderer.draw_text("Scores", center, 1.0, TextAnchor::Center, Color::BLACK);
+
+    draw_timeline(&game.stats, game.grid_height, layout.timeline_rect(), renderer);
+}
+
+// Line graph of the run: score rising in yellow, stack height in orange against the full grid,
+// with a mark at every row clear and a red one at every close call
+pub fn draw_timeline(stats: &RunStats, grid_height: usize, area: Rect, renderer: &mut impl Renderer) {
+    // Too little room or too short a run to show anything
+    if area.h < 20.0 || stats.samples.len() < 2 {
+        return;
+    }
+    renderer.fill_rect(area, TIMELINE_BACKGROUND);
+
+    let last_tick = stats.samples.last().map_or(1, |sample| sample.tick.max(1));
+    let x_at = |tick: u64| area.x + tick as f32 / last_tick as f32 * area.w;
+    let y_at = |fraction: f32| area.y + area.h - fraction.clamp(0.0, 1.0) * area.h;
+
+    for marker in &stats.markers {
+        let x = x_at(marker.tick);
+        let color = match marker.kind {
+            MarkerKind::RowsCleared => TIMELINE_MARKER_COLOR,
+            MarkerKind::CloseCall => PLAYER_COLOR,
+        };
+        renderer.draw_line((x, area.y), (x, area.y + area.h), 1.0, color);
+    }
+
+    let max_score = stats.samples.iter().map(|sample| sample.score).max().unwrap_or(0).max(1);
+    for pair in stats.samples.windows(2) {
+        let score = |i: usize| y_at(pair[i].score as f32 / max_score as f32);
+        renderer.draw_line((x_at(pair[0].tick), score(0)), (x_at(pair[1].tick), score(1)), 2.0, POWERUP_TEXT_COLOR);
+
+        let height = |i: usize| y_at(pair[i].stack_height as f32 / grid_height.max(1) as f32);
+        renderer.draw_line((x_at(pair[0].tick), height(0)), (x_at(pair[1].tick), height(1)), 2.0, WARNING_COLOR);
+    }
+}
+
+pub fn mode_name(game: &GameState) -> &'static str {
+    match game.mode {
+        GameMode::Endless => "Endless",
+        GameMode::TimeAttack => "Time Attack",
+        GameMode::Zen => "Zen",
+        GameMode::Campaign(_) => "Campaign",
+    }
+}
+
+// Card summing up a finished run for sharing: the numbers on the left, the final board on the right
+pub fn draw_summary_card(game: &GameState, date: &str, renderer: &mut impl Renderer) {
+    let (width, height) = (SUMMARY_CARD_WIDTH as f32, SUMMARY_CARD_HEIGHT as f32);
+    renderer.fill_rect(Rect::new(0.0, 0.0, width, height), SUMMARY_CARD_BACKGROUND);
+    renderer.draw_text("STACKATTACK", (20.0, 30.0), 1.5, TextAnchor::Left, POWERUP_TEXT_COLOR);
+
+    let lines = [
+        format!("Score: {}", game.score),
+        format!("Level: {}", game.level.number),
+        format!("Mode: {}", mode_name(game)),
+        format!("Seed: {}", game.seed),
+        date.to_string(),
+    ];
+    for (i, line) in lines.iter().enumerate() {
+        renderer.draw_text(line, (20.0, 75.0 + i as f32 * 28.0), 1.0, TextAnchor::Left, Color::WHITE);
+    }
+
+    // Thumbnail of the final board, scaled to fit the right side
+    let (box_x, box_y, box_w, box_h) = (width * 0.6, 20.0, width * 0.4 - 20.0, height - 40.0);
+    let cell = (box_w / game.grid_width as f32).min(box_h / game.grid_height as f32);
+    let left = box_x + (box_w - cell * game.grid_width as f32) / 2.0;
+    let top = box_y + (box_h - cell * game.grid_height as f32) / 2.0;
+    let cell_rect = |position: GridPos| Rect::new(left + position.x as f32 * cell, top + position.y as f32 * cell, cell, cell);
+
+    renderer.fill_rect(
+        Rect::new(left, top, cell * game.grid_width as f32, cell * game.grid_height as f32),
+        Color::WHITE,
+    );
+    for block in &game.blocks {
+        for position in block.cells() {
+            renderer.fill_rect(cell_rect(position), block_color(block.kind));
+        }
+    }
+    let GridPos { x, y } = game.player.position;
+    for row in y..y + game.player.body_size {
+        renderer.fill_rect(cell_rect(GridPos::new(x, row)), PLAYER_COLOR);
+    }
+}
+
+// Lines of the About screen, shared by every frontend
+pub fn about_lines() -> Vec<String> {
+    let features = if build_info::FEATURES.is_empty() {
+        "none".to_string()
+    } else {
+        build_info::FEATURES.join(", ")
+    };
+
+    let mut lines = vec![
+        format!("Stack Attack v{}", build_info::VERSION),
+        format!("Commit: {}", build_info::GIT_HASH),
+        format!("Built: {}", build_info::BUILD_DATE),
+        format!("Features: {}", features),
+        format!("Third-party crates: {}", build_info::THIRD_PARTY_CRATES.len()),
+    ];
+    for (license, count) in build_info::license_summary() {
+        lines.push(format!("  {} x{}", license, count));
+    }
+    lines.push("Press F1 to close".to_string());
+    lines
+}
+
+// About screen overlay covering the whole window
+// Best runs so far over the game, the one just played highlighted if it made the list
+pub fn draw_high_scores(scores: &HighScores, highlight: Option<usize>, layout: &Layout, renderer: &mut impl Renderer) {
+    renderer.fill_rect(Rect::new(0.0, 0.0, layout.window_width(), layout.window_height()), ABOUT_BACKGROUND);
+
+    let line_height = 20.0;
+    renderer.draw_text("High Scores", (layout.window_width() / 2.0, line_height), 1.5, TextAnchor::Center, Color::WHITE);
+    if scores.entries.is_empty() {
+        renderer.draw_text("No scores yet", (10.0, 3.0 * line_height), 1.0, TextAnchor::Left, Color::WHITE);
+    }
+    for (rank, entry) in scores.entries.iter().enumerate() {
+        let (score, date) = (layout.locale.number(entry.score as u64), layout.locale.date(&entry.date));
+        let line = format!("{:>2}. {:>7}  {}  {}", rank + 1, score, entry.mode, date);
+        let color = if highlight == Some(rank) { POWERUP_TEXT_COLOR } else { Color::WHITE };
+        let y = (rank + 3) as f32 * line_height;
+        renderer.draw_text(&line, (10.0, y), 1.0, TextAnchor::Left, color);
+    }
+}
+
+// The controls screen: every control with its keys, the one picked highlighted
+pub fn draw_key_bindings(bindings: &KeyBindings, menu: &RebindMenu, layout: &Layout, renderer: &mut impl Renderer) {
+    renderer.fill_rect(Rect::new(0.0, 0.0, layout.window_width(), layout.window_height()), ABOUT_BACKGROUND);
+
+    let line_height = 20.0;
+    renderer.draw_text("Controls", (layout.window_width() / 2.0, line_height), 1.5, TextAnchor::Center, Color::WHITE);
+    for (i, control) in Control::ALL.into_iter().enumerate() {
+        let selected = i == menu.selected;
+        let keys = match bindings.keys(control) {
+            _ if selected && menu.waiting => "press a key".to_string(),
+            [] => "unbound".to_string(),
+            keys => keys.join(", "),
+        };
+        let color = if selected { POWERUP_TEXT_COLOR } else { Color::WHITE };
+        let y = (i + 3) as f32 * line_height;
+        renderer.draw_text(&format!("{}: {}", control.name(), keys), (10.0, y), 1.0, TextAnchor::Left, color);
+    }
+    let help = if menu.waiting { "Esc to cancel" } else { "Up/Down to pick, Enter to rebind, Esc to close" };
+    let y = (Control::ALL.len() + 4) as f32 * line_height;
+    renderer.draw_text(help, (10.0, y), 1.0, TextAnchor::Left, Color::WHITE);
+}
+
+// The settings screen: every setting with its value, the one picked highlighted
+pub fn draw_settings(settings: &Settings, menu: &SettingsMenu, layout: &Layout, renderer: &mut impl Renderer) {
+    renderer.fill_rect(Rect::new(0.0, 0.0, layout.window_width(), layout.window_height()), ABOUT_BACKGROUND);
+
+    let line_height = 20.0;
+    renderer.draw_text("Settings", (layout.window_width() / 2.0, line_height), 1.5, TextAnchor::Center, Color::WHITE);
+    for (i, item) in SettingsItem::ALL.into_iter().enumerate() {
+        let color = if i == menu.selected { POWERUP_TEXT_COLOR } else { Color::WHITE };
+        let y = (i + 3) as f32 * line_height;
+        renderer.draw_text(&format!("{}: {}", item.name(), settings.describe(item)), (10.0, y), 1.0, TextAnchor::Left, color);
+    }
+    let y = (SettingsItem::ALL.len() + 4) as f32 * line_height;
+    renderer.draw_text("Up/Down to pick, Left/Right to change, Esc to close", (10.0, y), 1.0, TextAnchor::Left, Color::WHITE);
+}
+
+pub fn draw_about(layout: &Layout, renderer: &mut impl Renderer) {
+    renderer.fill_rect(Rect::new(0.0, 0.0, layout.window_width(), layout.window_height()), ABOUT_BACKGROUND);
+
+    let line_height = 20.0;
+    let mut y = line_height;
+    for line in about_lines() {
+        // Long license lists are cut off at the bottom of the window
+        if y > layout.window_height() - line_height / 2.0 {
+            break;
+        }
+        renderer.draw_text(&line, (10.0, y), 1.0, TextAnchor::Left, Color::WHITE);
+        y += line_height;
+    }
+}
@@ -0,0 +1,29 @@
+// High scores kept between sessions, stored as JSON in the user's data directory
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::core::scores::{HighScores, MAX_HIGH_SCORES};
+use crate::platform::storage;
+
+// Scores file in the platform's data directory, None if there is no home to put it in
+pub fn path() -> Option<PathBuf> {
+    storage::data_path("scores.json")
+}
+
+// The saved list, or an empty one when there is none yet or neither the file nor its backup can be read
+pub fn load_from(path: &Path) -> HighScores {
+    storage::read_checked(path).ok()
+        .and_then(|json| serde_json::from_slice::<HighScores>(&json).ok())
+        .map(|mut scores| {
+            // A list from an older version with a different length still comes out sorted and short enough
+            scores.entries.sort_by_key(|entry| std::cmp::Reverse(entry.score));
+            scores.entries.truncate(MAX_HIGH_SCORES);
+            scores
+        })
+        .unwrap_or_default()
+}
+
+pub fn save_to(scores: &HighScores, path: &Path) -> io::Result<()> {
+    let json = serde_json::to_vec_pretty(scores).map_err(io::Error::other)?;
+    storage::write_checked(path, &json)
+}
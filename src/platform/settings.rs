@@ -0,0 +1,264 @@
+// Settings kept between sessions, stored as TOML in the user's config directory
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::types::{InputAction, Theme};
+use crate::platform::music::DEFAULT_MUSIC_VOLUME;
+use crate::platform::storage;
+
+// Arena of new games when the settings don't pick one
+pub const DEFAULT_GRID: (usize, usize) = (16, 16);
+// The player is two cells tall and needs room to move
+pub const MIN_GRID: (usize, usize) = (2, 3);
+pub const MAX_GRID: (usize, usize) = (48, 48);
+// Volume change per step on the settings screen
+const VOLUME_STEP: f32 = 0.1;
+
+// Pixels of the window's top edge that have to be on a monitor for the saved position to be kept,
+// enough to grab the title bar and drag the window back
+const MIN_VISIBLE: i64 = 50;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Settings {
+    pub window: Option<WindowGeometry>, // Where the window was when the game last closed
+    // Milliseconds the drawn player takes to slide into a new cell, 0 shows every move instantly
+    #[serde(default)]
+    pub animation_millis: u64,
+    // Finished games, the first few come with tutorial hints
+    #[serde(default)]
+    pub games_played: u32,
+    #[serde(default)]
+    pub hide_hints: bool,
+    // Background music volume from 0 to 1, the default if never set
+    #[serde(default)]
+    pub music_volume: Option<f32>,
+    // How far heavy impacts shake the board, in cells, the default if never set and 0 for none
+    #[serde(default)]
+    pub shake_intensity: Option<f32>,
+    // Input macro last recorded in practice, replayed on a hotkey
+    #[serde(default)]
+    pub input_macro: Vec<InputAction>,
+    // Draw the board as one scaled image of a pixel per cell, for very weak graphics
+    #[serde(default)]
+    pub low_spec: bool,
+    // Leave out the lines between cells
+    #[serde(default)]
+    pub hide_grid: bool,
+    // Palette to draw with, the mode's own if never set
+    #[serde(default)]
+    pub theme: Option<Theme>,
+    // Width and height of new games' arena, the default if never set
+    #[serde(default)]
+    pub grid: Option<(usize, usize)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub width: f32,
+    pub height: f32,
+    pub x: i32, // Outer top left corner, in screen pixels
+    pub y: i32,
+    pub fullscreen: bool,
+}
+
+// Area a monitor covers on the desktop, in screen pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MonitorArea {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Settings {
+    // Settings file in the platform's config directory, None if there is no home to put it in
+    pub fn path() -> Option<PathBuf> {
+        storage::config_path("settings.toml")
+    }
+
+    // Saved settings, or the defaults when there are none yet
+    pub fn load() -> io::Result<Self> {
+        let Some(path) = Self::path() else {
+            return Ok(Self::default());
+        };
+        // Settings saved as JSON before the switch to TOML are read from there until the first save
+        if !path.exists() {
+            if let Some(legacy) = storage::config_path("settings.json").filter(|legacy| legacy.exists()) {
+                return Ok(Self::load_json_from(&legacy));
+            }
+        }
+        Self::load_from(&path)
+    }
+
+    // The file is edited by hand, one that doesn't parse is an error saying what's wrong with it
+    // rather than quietly replaced by the defaults on the next save
+    pub fn load_from(path: &Path) -> io::Result<Self> {
+        match storage::read_plain(path)? {
+            Some(text) => toml::from_str(&text).map_err(|error| storage::invalid(path, error)),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn load_json_from(path: &Path) -> Self {
+        storage::read_checked(path).ok()
+            .and_then(|json| serde_json::from_slice(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no config directory"))?;
+        self.save_to(&path)
+    }
+
+    // Written as plain TOML without a checksum, so it stays easy to edit
+    pub fn save_to(&self, path: &Path) -> io::Result<()> {
+        // Going through a TOML value puts the window geometry's table after the plain values, as TOML needs
+        let value = toml::Value::try_from(self).map_err(io::Error::other)?;
+        let text = toml::to_string(&value).map_err(io::Error::other)?;
+        storage::write_atomic(path, text.as_bytes())
+    }
+
+    pub fn grid_size(&self) -> (usize, usize) {
+        self.grid.unwrap_or(DEFAULT_GRID)
+    }
+
+    // A setting as the settings screen shows it
+    pub fn describe(&self, item: SettingsItem) -> String {
+        match item {
+            SettingsItem::MusicVolume => {
+                format!("{}%", (self.music_volume.unwrap_or(DEFAULT_MUSIC_VOLUME) * 100.0).round())
+            },
+            SettingsItem::KeyBindings => "Enter to change".to_string(),
+            SettingsItem::GridWidth => format!("{} (next start)", self.grid_size().0),
+            SettingsItem::GridHeight => format!("{} (next start)", self.grid_size().1),
+            SettingsItem::Theme => match self.theme {
+                None => "Mode's own".to_string(),
+                Some(theme) => format!("{:?}", theme),
+            },
+            SettingsItem::ShowGrid => if self.hide_grid { "Off" } else { "On" }.to_string(),
+        }
+    }
+
+    // Open fullscreen or in a window next time, a window never saved yet gets the size the game needs
+    pub fn set_fullscreen(&mut self, fullscreen: bool, needed: (f32, f32)) {
+        let geometry = self.window.unwrap_or(WindowGeometry { width: needed.0, height: needed.1, x: 0, y: 0, fullscreen });
+        self.window = Some(WindowGeometry { fullscreen, ..geometry });
+    }
+}
+
+impl WindowGeometry {
+    // Size to open the window at, never smaller than the game needs to fit
+    pub fn dimensions(&self, needed: (f32, f32)) -> (f32, f32) {
+        (self.width.max(needed.0), self.height.max(needed.1))
+    }
+
+    // Where to put the window: the saved spot while its top edge is still on a monitor,
+    // otherwise pulled onto the first monitor, e.g. after the one it was on was unplugged
+    pub fn position_on(&self, monitors: &[MonitorArea]) -> Option<(i32, i32)> {
+        let (x, y) = (self.x as i64, self.y as i64);
+        let width = self.width as i64;
+        let on_screen = monitors.iter().any(|monitor| {
+            let (left, top) = (monitor.x as i64, monitor.y as i64);
+            let (right, bottom) = (left + monitor.width as i64, top + monitor.height as i64);
+            let visible = x.max(left)..(x + width).min(right);
+            y >= top && y + MIN_VISIBLE <= bottom && visible.end - visible.start >= MIN_VISIBLE
+        });
+        if on_screen {
+            return Some((self.x, self.y));
+        }
+
+        let monitor = monitors.first()?;
+        let clamp = |value: i32, start: i32, length: u32, size: f32| {
+            let end = start as i64 + length as i64 - size as i64;
+            (value as i64).min(end).max(start as i64) as i32
+        };
+        Some((
+            clamp(self.x, monitor.x, monitor.width, self.width),
+            clamp(self.y, monitor.y, monitor.height, self.height),
+        ))
+    }
+}
+
+// Lines of the settings screen, in order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsItem {
+    MusicVolume,
+    KeyBindings, // Opens the controls screen
+    GridWidth,
+    GridHeight,
+    Theme,
+    ShowGrid,
+}
+
+impl SettingsItem {
+    pub const ALL: [SettingsItem; 6] = [
+        SettingsItem::MusicVolume,
+        SettingsItem::KeyBindings,
+        SettingsItem::GridWidth,
+        SettingsItem::GridHeight,
+        SettingsItem::Theme,
+        SettingsItem::ShowGrid,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SettingsItem::MusicVolume => "Music volume",
+            SettingsItem::KeyBindings => "Key bindings",
+            SettingsItem::GridWidth => "Grid width",
+            SettingsItem::GridHeight => "Grid height",
+            SettingsItem::Theme => "Theme",
+            SettingsItem::ShowGrid => "Show grid",
+        }
+    }
+}
+
+// Where the settings screen is, the picked line is changed a step at a time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SettingsMenu {
+    pub selected: usize,
+}
+
+impl SettingsMenu {
+    pub fn selected_item(&self) -> SettingsItem {
+        SettingsItem::ALL[self.selected]
+    }
+
+    // Pick the line above or below, wrapping around the ends
+    pub fn move_selection(&mut self, delta: i32) {
+        let count = SettingsItem::ALL.len() as i32;
+        self.selected = (self.selected as i32 + delta).rem_euclid(count) as usize;
+    }
+
+    // Step the picked setting up or down, returns whether it changed
+    pub fn adjust(&self, settings: &mut Settings, step: i32) -> bool {
+        let before = settings.clone();
+        match self.selected_item() {
+            SettingsItem::MusicVolume => {
+                let volume = settings.music_volume.unwrap_or(DEFAULT_MUSIC_VOLUME) + step as f32 * VOLUME_STEP;
+                // Rounded to whole steps, so repeated steps don't drift
+                settings.music_volume = Some(((volume / VOLUME_STEP).round() * VOLUME_STEP).clamp(0.0, 1.0));
+            },
+            SettingsItem::KeyBindings => {},
+            SettingsItem::GridWidth => {
+                let (width, height) = settings.grid_size();
+                let width = (width as i32 + step).clamp(MIN_GRID.0 as i32, MAX_GRID.0 as i32) as usize;
+                settings.grid = Some((width, height));
+            },
+            SettingsItem::GridHeight => {
+                let (width, height) = settings.grid_size();
+                let height = (height as i32 + step).clamp(MIN_GRID.1 as i32, MAX_GRID.1 as i32) as usize;
+                settings.grid = Some((width, height));
+            },
+            // Both ways cycle through the mode's own palette and then every theme
+            SettingsItem::Theme => {
+                let choices = [None, Some(Theme::Classic), Some(Theme::Soft)];
+                let current = choices.iter().position(|&theme| theme == settings.theme).unwrap_or(0) as i32;
+                settings.theme = choices[(current + step).rem_euclid(choices.len() as i32) as usize];
+            },
+            SettingsItem::ShowGrid => settings.hide_grid = !settings.hide_grid,
+        }
+        *settings != before
+    }
+}
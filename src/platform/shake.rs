@@ -0,0 +1,103 @@
+// Platform-independent screen shake - the board jolts on heavy impacts and settles back
+// The jolt dies away exponentially from the last kick, so the offset follows from the clock alone
+use std::f32::consts::TAU;
+use std::time::{Duration, Instant};
+
+use crate::platform::clock::Clock;
+
+// Farthest the board moves at full strength, in cells
+pub const DEFAULT_SHAKE_INTENSITY: f32 = 0.25;
+// How fast a jolt dies away, per second
+pub const DEFAULT_SHAKE_DAMPING: f32 = 10.0;
+// Slower than this and a shake would outlast the next impact by far
+pub const MIN_SHAKE_DAMPING: f32 = 1.0;
+pub const HIT_FLASH_DURATION: Duration = Duration::from_millis(200);
+// Closer than this, in cells, and the board is drawn in place
+const SETTLED: f32 = 0.01;
+// Swings per second on each axis, different so the board doesn't rock along a single line
+const FREQUENCY: (f32, f32) = (23.0, 29.0);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Impact {
+    RowsCleared(u32), // By number of rows cleared at once
+    CloseCall,
+    Explosion,
+    GameOver,
+}
+
+impl Impact {
+    // Share of the full intensity, from 0 to 1
+    pub fn strength(self) -> f32 {
+        match self {
+            Impact::RowsCleared(rows) => (0.4 + 0.2 * rows.saturating_sub(1) as f32).min(1.0),
+            Impact::CloseCall => 0.5,
+            Impact::Explosion => 0.8,
+            Impact::GameOver => 1.0,
+        }
+    }
+}
+
+pub struct ScreenShake {
+    pub intensity: f32, // In cells, 0 turns shaking off
+    pub damping: f32,
+    kicked: Option<(f32, Instant)>, // Strength of the last kick and when it came
+}
+
+impl Default for ScreenShake {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScreenShake {
+    pub fn new() -> Self {
+        Self {
+            intensity: DEFAULT_SHAKE_INTENSITY,
+            damping: DEFAULT_SHAKE_DAMPING,
+            kicked: None,
+        }
+    }
+
+    pub fn with_intensity(mut self, intensity: f32) -> Self {
+        self.intensity = intensity.max(0.0);
+        self
+    }
+
+    pub fn with_damping(mut self, damping: f32) -> Self {
+        self.damping = damping.max(MIN_SHAKE_DAMPING);
+        self
+    }
+
+    // Jolt the board, a weaker impact doesn't cut short a stronger shake still going
+    pub fn kick(&mut self, impact: Impact, clock: &dyn Clock) {
+        let strength = impact.strength().max(self.strength(clock));
+        self.kicked = Some((strength, clock.now()));
+    }
+
+    pub fn clear(&mut self) {
+        self.kicked = None;
+    }
+
+    // Strength left of the last kick
+    fn strength(&self, clock: &dyn Clock) -> f32 {
+        let Some((strength, at)) = self.kicked else {
+            return 0.0;
+        };
+        strength * (-self.damping * clock.now().duration_since(at).as_secs_f32()).exp()
+    }
+
+    // Farthest the board can be from its place right now, in cells
+    pub fn amplitude(&self, clock: &dyn Clock) -> f32 {
+        self.intensity * self.strength(clock)
+    }
+
+    // Where the board is drawn relative to its place, in cells
+    pub fn offset(&self, clock: &dyn Clock) -> (f32, f32) {
+        let amplitude = self.amplitude(clock);
+        let Some((_, at)) = self.kicked.filter(|_| amplitude >= SETTLED) else {
+            return (0.0, 0.0);
+        };
+        let t = clock.now().duration_since(at).as_secs_f32();
+        (amplitude * (TAU * FREQUENCY.0 * t).sin(), amplitude * (TAU * FREQUENCY.1 * t).cos())
+    }
+}
@@ -0,0 +1,44 @@
+// Exporting a finished run as a summary card image for posting online
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::core::game::GameState;
+use crate::platform::raster::ImageRenderer;
+use crate::platform::render::{self, SUMMARY_CARD_BACKGROUND, SUMMARY_CARD_HEIGHT, SUMMARY_CARD_WIDTH};
+
+// Draw the summary card of a run without a window
+pub fn summary_card(game: &GameState, date: &str) -> ImageRenderer {
+    let mut renderer = ImageRenderer::new(SUMMARY_CARD_WIDTH, SUMMARY_CARD_HEIGHT, SUMMARY_CARD_BACKGROUND);
+    render::draw_summary_card(game, date, &mut renderer);
+    renderer
+}
+
+// Save the summary card of a run as a PNG in a directory, returning the file it was written to
+pub fn save_summary_card(game: &GameState, directory: &Path) -> io::Result<PathBuf> {
+    let now = SystemTime::now();
+    let card = summary_card(game, &utc_date(now));
+    let seconds = now.duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs());
+
+    let path = directory.join(format!("stackattack-{}-{}.png", game.score, seconds));
+    card.save_png(&path).map_err(io::Error::other)?;
+    Ok(path)
+}
+
+// Calendar date of a point in time as YYYY-MM-DD in UTC
+pub fn utc_date(time: SystemTime) -> String {
+    let days = time.duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs() / 86_400) as i64;
+
+    // Days since 1970-01-01 to a civil date, counting years from March so leap days come last
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
@@ -0,0 +1,105 @@
+// Crash-safe file storage for everything the game persists
+// Files are written to a temporary file first and renamed over the old one, so a crash mid-write
+// never leaves half a file behind. Each file starts with a checksum line, and the previous good
+// copy is kept next to it, so a file damaged some other way falls back to that copy.
+// Files people edit by hand are only written atomically, a checksum line would get in their way
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+const HEADER_PREFIX: &str = "stackattack-checksum ";
+
+// FNV-1a, enough to notice truncated or garbled files
+pub fn checksum(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+// File in the platform's data directory, for what the game keeps rather than configures,
+// None if there is no home to put it in
+pub fn data_path(name: &str) -> Option<PathBuf> {
+    let data_dir = std::env::var_os("XDG_DATA_HOME")
+        .or_else(|| std::env::var_os("APPDATA"))
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".local").join("share")))?;
+    Some(data_dir.join("stackattack").join(name))
+}
+
+// File in the platform's config directory, for what the player sets up,
+// None if there is no home to put it in
+pub fn config_path(name: &str) -> Option<PathBuf> {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .or_else(|| std::env::var_os("APPDATA"))
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+    Some(config_dir.join("stackattack").join(name))
+}
+
+// Where the previous good copy of a file is kept
+pub fn backup_path(path: &Path) -> PathBuf {
+    with_suffix(path, ".bak")
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+// Replace a file with new contents, keeping the current one as the backup if it is intact
+pub fn write_checked(path: &Path, contents: &[u8]) -> io::Result<()> {
+    if read_verified(path).is_ok() {
+        fs::copy(path, backup_path(path))?;
+    }
+    let mut data = format!("{}{:016x}\n", HEADER_PREFIX, checksum(contents)).into_bytes();
+    data.extend_from_slice(contents);
+    write_atomic(path, &data)
+}
+
+// Replace a file with exactly the given contents, through a temporary file renamed over it
+pub fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let temp_path = with_suffix(path, ".tmp");
+    let mut file = File::create(&temp_path)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    fs::rename(&temp_path, path)
+}
+
+// Contents of a file people edit by hand, None if there is none yet
+pub fn read_plain(path: &Path) -> io::Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(Some(text)),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+// An InvalidData error for a hand-edited file that doesn't parse, naming the file
+pub fn invalid(path: &Path, error: impl std::fmt::Display) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), error))
+}
+
+// Contents of a file written by write_checked, or of its backup when the file itself is damaged
+pub fn read_checked(path: &Path) -> io::Result<Vec<u8>> {
+    read_verified(path).or_else(|error| read_verified(&backup_path(path)).map_err(|_| error))
+}
+
+// Contents of one file, an InvalidData error if its checksum doesn't match
+fn read_verified(path: &Path) -> io::Result<Vec<u8>> {
+    let data = fs::read(path)?;
+    let corrupt = || io::Error::new(io::ErrorKind::InvalidData, format!("{} is damaged", path.display()));
+
+    let newline = data.iter().position(|&byte| byte == b'\n').ok_or_else(corrupt)?;
+    let header = std::str::from_utf8(&data[..newline]).map_err(|_| corrupt())?;
+    let expected = header.strip_prefix(HEADER_PREFIX)
+        .and_then(|hex| u64::from_str_radix(hex, 16).ok())
+        .ok_or_else(corrupt)?;
+
+    let contents = &data[newline + 1..];
+    if checksum(contents) != expected {
+        return Err(corrupt());
+    }
+    Ok(contents.to_vec())
+}
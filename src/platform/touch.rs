@@ -0,0 +1,151 @@
+// Platform-independent touch controls, for WASM and mobile builds without a keyboard
+// Buttons for left, right and jump sit along the bottom of the grid, and swipes work anywhere else on it:
+// sideways steps one cell per cell swiped like a mouse drag, upward jumps
+use std::collections::VecDeque;
+
+use crate::core::types::{InputAction, InputDevice};
+use crate::platform::input::InputSource;
+use crate::platform::mouse::Drag;
+use crate::platform::render::{Layout, Rect};
+
+// Cells a swipe must travel upward before it jumps
+pub const SWIPE_JUMP_CELLS: f32 = 1.0;
+// Height of the buttons, in cells
+const BUTTON_CELLS: f32 = 1.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TouchButton {
+    Left,
+    Right,
+    Jump,
+}
+
+impl TouchButton {
+    pub const ALL: [TouchButton; 3] = [TouchButton::Left, TouchButton::Right, TouchButton::Jump];
+
+    pub fn action(self) -> InputAction {
+        match self {
+            TouchButton::Left => InputAction::Left,
+            TouchButton::Right => InputAction::Right,
+            TouchButton::Jump => InputAction::Up,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TouchButton::Left => "<",
+            TouchButton::Right => ">",
+            TouchButton::Jump => "Jump",
+        }
+    }
+
+    // Left and right side by side in the bottom-left corner of the grid, jump in the bottom-right one,
+    // each a quarter of the grid wide so thumbs on either side reach them
+    pub fn rect(self, layout: &Layout) -> Rect {
+        let grid = grid_rect(layout);
+        let (w, h) = (grid.w / 4.0, layout.cell_size * BUTTON_CELLS);
+        let x = match self {
+            TouchButton::Left => grid.x,
+            TouchButton::Right => grid.x + w,
+            TouchButton::Jump => grid.x + grid.w - w,
+        };
+        Rect::new(x, grid.y + grid.h - h, w, h)
+    }
+}
+
+fn grid_rect(layout: &Layout) -> Rect {
+    let top_left = layout.cell_rect(0, 0);
+    Rect::new(top_left.x, top_left.y, layout.grid_pixel_width(), layout.grid_pixel_height())
+}
+
+#[derive(Default)]
+pub struct TouchControls {
+    pub held: Option<TouchButton>, // Button under the finger, left and right keep walking while held
+    pub swipe: Option<Drag>,       // A swipe in progress, started on the grid away from the buttons
+    jumping: bool,                 // Lifting the finger cuts the jump short
+    actions: VecDeque<InputAction>,
+}
+
+impl TouchControls {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn button_at(&self, position: (f32, f32), layout: &Layout) -> Option<TouchButton> {
+        TouchButton::ALL.into_iter().find(|button| button.rect(layout).contains(position.0, position.1))
+    }
+
+    // Press a button or start a swipe, returns whether the touch was used
+    // Touches off the grid are left to the mouse handling, e.g. for the restart button
+    pub fn start(&mut self, position: (f32, f32), layout: &Layout) -> bool {
+        self.cancel();
+        if let Some(button) = self.button_at(position, layout) {
+            self.held = Some(button);
+            self.jumping = button == TouchButton::Jump;
+            self.actions.push_back(button.action());
+            return true;
+        }
+        if grid_rect(layout).contains(position.0, position.1) {
+            self.swipe = Some(Drag { start: position, current: position, steps: 0 });
+            return true;
+        }
+        false
+    }
+
+    // Follow the finger, queueing a step each time a swipe crosses a cell and a jump once it goes up far enough
+    pub fn moved(&mut self, position: (f32, f32), layout: &Layout) {
+        // Sliding off a button lets go of it
+        if let Some(button) = self.held {
+            if !button.rect(layout).contains(position.0, position.1) {
+                self.end();
+            }
+            return;
+        }
+        let Some(swipe) = &mut self.swipe else {
+            return;
+        };
+
+        swipe.current = position;
+        while let Some(direction) = swipe.next_step(layout.cell_size) {
+            swipe.steps += direction;
+            self.actions.push_back(if direction > 0 { InputAction::Right } else { InputAction::Left });
+        }
+        if !self.jumping && swipe.start.1 - position.1 >= SWIPE_JUMP_CELLS * layout.cell_size {
+            self.actions.push_back(InputAction::Up);
+            self.jumping = true;
+        }
+    }
+
+    pub fn end(&mut self) {
+        if self.jumping {
+            self.actions.push_back(InputAction::JumpRelease);
+        }
+        self.cancel();
+    }
+
+    // Forget the touch without lifting it, e.g. when the window loses focus
+    pub fn cancel(&mut self) {
+        self.held = None;
+        self.swipe = None;
+        self.jumping = false;
+    }
+
+    fn held_direction(&self) -> Option<InputAction> {
+        self.held.filter(|&button| button != TouchButton::Jump).map(TouchButton::action)
+    }
+
+    pub fn has_action(&self) -> bool {
+        !self.actions.is_empty() || self.held_direction().is_some()
+    }
+}
+
+impl InputSource for TouchControls {
+    // Queued actions first, one per frame, then the held direction button
+    fn poll(&mut self) -> InputAction {
+        self.actions.pop_front().or_else(|| self.held_direction()).unwrap_or(InputAction::None)
+    }
+
+    fn device(&self) -> InputDevice {
+        InputDevice::Touch
+    }
+}
@@ -0,0 +1,52 @@
+// Watches how long game updates take and steps down optional work while they keep running over budget,
+// so a huge grid slows the extras down instead of making the whole game stutter
+use std::collections::VecDeque;
+use std::time::Duration;
+
+// Updates averaged before deciding to change the level
+pub const WINDOW: usize = 20;
+// Level 1 drops the movement preview and particles, level 2 also spreads levitation checks over several updates
+pub const MAX_LEVEL: u8 = 2;
+
+pub struct TickWatchdog {
+    budget: Duration,
+    recent: VecDeque<Duration>,
+    pub level: u8,
+}
+
+impl TickWatchdog {
+    pub fn new(budget: Duration) -> Self {
+        Self {
+            budget,
+            recent: VecDeque::with_capacity(WINDOW),
+            level: 0,
+        }
+    }
+
+    // Record how long an update took, returning the new level when it changed
+    // Going over budget on average steps down one level, well under half of it steps back up
+    pub fn record(&mut self, elapsed: Duration) -> Option<u8> {
+        self.recent.push_back(elapsed);
+        if self.recent.len() < WINDOW {
+            return None;
+        }
+
+        let average = self.recent.iter().sum::<Duration>() / WINDOW as u32;
+        let level = if average > self.budget {
+            (self.level + 1).min(MAX_LEVEL)
+        } else if average < self.budget / 2 {
+            self.level.saturating_sub(1)
+        } else {
+            self.level
+        };
+
+        if level == self.level {
+            self.recent.pop_front();
+            return None;
+        }
+        // Judge the new level on its own updates only
+        self.level = level;
+        self.recent.clear();
+        Some(level)
+    }
+}
@@ -0,0 +1,37 @@
+//! Stable surface for embedding the game: the simulation, its configuration, the input it takes, the
+//! events it reports and the traits a frontend implements. Paths outside the prelude may still move
+//! between minor versions, these are only changed with a major one
+//!
+//! A headless game driven one update at a time, without a window or a clock:
+//!
+//! ```
+//! use rust_stackattack::prelude::*;
+//!
+//! let mut game = GameState::new(GameConfig { seed: Some(1), ..Default::default() }).unwrap();
+//! for tick in 0..100 {
+//!     if tick % 4 == 0 {
+//!         game.process_input(InputAction::Up);
+//!     }
+//!     game.tick();
+//!     for event in game.drain_events() {
+//!         if let GameEvent::RowsCleared(breakdown) = event {
+//!             println!("cleared {} rows for {} points", breakdown.rows, breakdown.points);
+//!         }
+//!     }
+//! }
+//! assert_eq!(game.stats.ticks, 100);
+//! ```
+pub use crate::core::builder::GameStateBuilder;
+pub use crate::core::event::{GameEvent, ScoreBreakdown};
+pub use crate::core::game::GameState;
+pub use crate::core::level::LevelDefinition;
+pub use crate::core::types::{
+    Color, ConfigError, Direction, GameConfig, GameMode, GameOverReason, GameUpdateResult, GridPos, InputAction, InputDevice, Position,
+};
+pub use crate::platform::clock::Clock;
+pub use crate::platform::input::InputSource;
+pub use crate::platform::render::{Layout, Rect, Renderer, TextAnchor};
+
+// The ready-made windowed frontend
+#[cfg(feature = "ggez-frontend")]
+pub use crate::platform::ggez::GameAdapter;
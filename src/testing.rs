@@ -0,0 +1,148 @@
+// Test doubles for the platform interfaces (enabled with the "test-util" feature)
+// They let tests check what a frontend would draw or feed without a window
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::core::types::{Color, InputAction};
+use crate::platform::clock::Clock;
+use crate::platform::input::InputSource;
+use crate::platform::render::{Rect, Renderer, TextAnchor};
+
+// A single recorded call on the MockRenderer
+#[derive(Debug, Clone, PartialEq)]
+pub enum DrawCall {
+    Rect { rect: Rect, color: Color },
+    Polygon { points: Vec<(f32, f32)>, color: Color },
+    Line { from: (f32, f32), to: (f32, f32), width: f32, color: Color },
+    Text { text: String, position: (f32, f32), scale: f32, anchor: TextAnchor, color: Color },
+}
+
+// Renderer that records every draw call instead of drawing
+#[derive(Default)]
+pub struct MockRenderer {
+    pub calls: Vec<DrawCall>,
+}
+
+impl MockRenderer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // All filled rectangles of a given color
+    pub fn rects_with_color(&self, color: Color) -> Vec<Rect> {
+        self.calls.iter()
+            .filter_map(|call| match call {
+                DrawCall::Rect { rect, color: c } if *c == color => Some(*rect),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn polygons(&self) -> Vec<&[(f32, f32)]> {
+        self.calls.iter()
+            .filter_map(|call| match call {
+                DrawCall::Polygon { points, .. } => Some(points.as_slice()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn lines(&self) -> usize {
+        self.calls.iter().filter(|call| matches!(call, DrawCall::Line { .. })).count()
+    }
+
+    // All drawn strings in drawing order
+    pub fn texts(&self) -> Vec<&str> {
+        self.calls.iter()
+            .filter_map(|call| match call {
+                DrawCall::Text { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl Renderer for MockRenderer {
+    fn fill_rect(&mut self, rect: Rect, color: Color) {
+        self.calls.push(DrawCall::Rect { rect, color });
+    }
+
+    fn fill_polygon(&mut self, points: &[(f32, f32)], color: Color) {
+        self.calls.push(DrawCall::Polygon { points: points.to_vec(), color });
+    }
+
+    fn draw_line(&mut self, from: (f32, f32), to: (f32, f32), width: f32, color: Color) {
+        self.calls.push(DrawCall::Line { from, to, width, color });
+    }
+
+    fn draw_text(&mut self, text: &str, position: (f32, f32), scale: f32, anchor: TextAnchor, color: Color) {
+        self.calls.push(DrawCall::Text { text: text.to_string(), position, scale, anchor, color });
+    }
+}
+
+// Input source replaying a scripted list of actions, then reporting no input
+#[derive(Default)]
+pub struct MockInput {
+    script: VecDeque<InputAction>,
+    pub polled: usize, // Number of times the game asked for input
+}
+
+impl MockInput {
+    pub fn new(actions: impl IntoIterator<Item = InputAction>) -> Self {
+        Self {
+            script: actions.into_iter().collect(),
+            polled: 0,
+        }
+    }
+
+    pub fn push(&mut self, action: InputAction) {
+        self.script.push_back(action);
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.script.len()
+    }
+}
+
+impl InputSource for MockInput {
+    fn poll(&mut self) -> InputAction {
+        self.polled += 1;
+        self.script.pop_front().unwrap_or(InputAction::None)
+    }
+}
+
+// Clock that only moves when told to, sleeping advances it instantly
+pub struct MockClock {
+    start: Instant,
+    elapsed: Cell<Duration>,
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockClock {
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            elapsed: Cell::new(Duration::ZERO),
+        }
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        self.elapsed.set(self.elapsed.get() + duration);
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.start + self.elapsed.get()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+    }
+}
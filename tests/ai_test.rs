@@ -0,0 +1,60 @@
+use rust_stackattack::core::ai::{evaluate, Bot, Difficulty};
+use rust_stackattack::core::block::Block;
+use rust_stackattack::core::game::GameState;
+use rust_stackattack::core::types::{GameConfig, GridPos, InputAction};
+
+fn test_game() -> GameState {
+    let config = GameConfig {
+        grid_width: 5,
+        grid_height: 5,
+        block_spawn_rate: 1000,
+        lives: 1,
+        ..Default::default()
+    };
+    let mut game = GameState::new(config).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    game
+}
+
+#[test]
+fn test_difficulty_search_grows() {
+    let easy = Difficulty::Easy.search_config();
+    let hard = Difficulty::Hard.search_config();
+    assert!(hard.depth > easy.depth);
+    assert!(hard.rollouts > easy.rollouts);
+    assert_eq!(Difficulty::from_name("HARD"), Some(Difficulty::Hard));
+    assert_eq!(Difficulty::from_name("nightmare"), None);
+}
+
+#[test]
+fn test_evaluate_prefers_alive_and_safe() {
+    let mut game = test_game();
+    let safe = evaluate(&game);
+
+    let GridPos { x, y: _ } = game.player.position;
+    game.blocks.push(Block::new(GridPos::new(x, 0)));
+    game.sync_occupancy();
+    assert!(evaluate(&game) < safe, "Falling block over the player is a threat");
+
+    game.game_over = true;
+    assert_eq!(evaluate(&game), -1000.0);
+}
+
+#[test]
+fn test_bot_dodges_falling_block() {
+    for difficulty in Difficulty::ALL {
+        let mut game = test_game();
+        let GridPos { x, y } = game.player.position;
+        game.blocks.push(Block::new(GridPos::new(x, y - 1)));
+        game.sync_occupancy();
+
+        let action = Bot::new(difficulty).choose_action(&game);
+        assert!(
+            action == InputAction::Left || action == InputAction::Right,
+            "{} bot should step aside, chose {:?}",
+            difficulty.name(),
+            action
+        );
+    }
+}
@@ -0,0 +1,119 @@
+// The allocator is process wide, so these tests live in their own binary
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use rust_stackattack::core::block::Block;
+use rust_stackattack::core::game::GameState;
+use rust_stackattack::core::types::{GameConfig, GridPos, InputAction};
+use rust_stackattack::platform::particles::Particles;
+use rust_stackattack::testing::MockClock;
+
+// Counts allocations made on the current thread, so other tests running alongside don't interfere
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations_during(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(|count| count.get());
+    f();
+    ALLOCATIONS.with(|count| count.get()) - before
+}
+
+// A board with a few settled blocks and nothing about to spawn, played until every buffer has grown
+fn warmed_up_game() -> GameState {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 8,
+        grid_height: 12,
+        block_spawn_rate: 10_000,
+        seed: Some(3),
+        ..Default::default()
+    }).unwrap();
+    game.blocks.clear();
+    for x in [0, 1, 6, 7] {
+        game.blocks.push(Block { position: GridPos::new(x, 11), falling: false, ..Default::default() });
+    }
+    game.blocks.push(Block { position: GridPos::new(6, 2), ..Default::default() });
+    game.sync_occupancy();
+    for _ in 0..40 {
+        game.tick();
+    }
+    game
+}
+
+#[test]
+fn test_steady_state_tick_does_not_allocate() {
+    let mut game = warmed_up_game();
+    
+    for _ in 0..200 {
+        assert_eq!(allocations_during(|| game.tick()), 0);
+    }
+    assert!(!game.game_over);
+}
+
+#[test]
+fn test_falling_block_does_not_allocate() {
+    let mut game = warmed_up_game();
+    game.blocks.push(Block { position: GridPos::new(1, 0), ..Default::default() });
+    game.sync_occupancy();
+    
+    for _ in 0..20 {
+        assert_eq!(allocations_during(|| game.tick()), 0);
+    }
+    assert!(game.blocks.iter().all(|block| !block.falling));
+}
+
+#[test]
+fn test_walking_and_pushing_do_not_allocate() {
+    let mut game = warmed_up_game();
+    game.blocks.push(Block { position: GridPos::new(2, 11), falling: false, ..Default::default() });
+    game.blocks.push(Block { position: GridPos::new(4, 11), falling: false, ..Default::default() });
+    game.sync_occupancy();
+    
+    // A push into the wall on the left sizes the buffer of pushed blocks without moving anything
+    game.process_input(InputAction::Left);
+    game.tick();
+    assert_eq!(game.player.position.x, 3);
+    
+    for i in 0..10 {
+        let action = if i % 2 == 0 { InputAction::Right } else { InputAction::Left };
+        assert_eq!(allocations_during(|| { game.process_input(action); }), 0);
+        assert_eq!(allocations_during(|| game.tick()), 0);
+    }
+    assert!(game.blocks.iter().any(|block| block.position == (5, 11)));
+}
+
+#[test]
+fn test_particles_reuse_their_pool() {
+    let clock = MockClock::new();
+    let mut particles = Particles::new(&clock);
+    
+    let spawned = allocations_during(|| {
+        for row in 0..12 {
+            particles.block_landed(GridPos::new(row % 8, row), &clock);
+            particles.row_cleared(row, 8, &clock);
+        }
+    });
+    assert_eq!(spawned, 0);
+    assert_eq!(allocations_during(|| { particles.visible(&clock).count(); }), 0);
+}
@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use rust_stackattack::core::block::Block;
+use rust_stackattack::core::game::GameState;
+use rust_stackattack::core::types::{Direction, GameConfig, GridPos};
+use rust_stackattack::platform::animation::{Animator, PlayerAction, FRAME_DURATION, SQUASH_DURATION};
+use rust_stackattack::testing::MockClock;
+
+fn test_game() -> GameState {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 8,
+        grid_height: 6,
+        ..Default::default()
+    }).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    game.player.position = GridPos::new(2, 4);
+    game
+}
+
+#[test]
+fn test_walk_plays_through_its_frames_then_idles() {
+    let clock = MockClock::new();
+    let mut game = test_game();
+    let mut animator = Animator::new(game.player.position, &clock);
+    assert_eq!(animator.pose(&clock).action, PlayerAction::Idle);
+
+    game.player.position.x = 3;
+    animator.observe(&game, &clock);
+    let pose = animator.pose(&clock);
+    assert_eq!((pose.action, pose.frame, pose.facing), (PlayerAction::Walk, 0, Direction::Right));
+
+    // Frames advance on their own timer, between game updates
+    clock.advance(FRAME_DURATION * 2);
+    animator.observe(&game, &clock);
+    assert_eq!(animator.pose(&clock).frame, 2);
+
+    clock.advance(Duration::from_millis(500));
+    animator.observe(&game, &clock);
+    assert_eq!(animator.pose(&clock).action, PlayerAction::Idle);
+}
+
+#[test]
+fn test_jump_fall_and_push_are_told_apart() {
+    let clock = MockClock::new();
+    let mut game = test_game();
+    let mut animator = Animator::new(game.player.position, &clock);
+
+    game.player.position.y = 3;
+    game.player.in_air = true;
+    animator.observe(&game, &clock);
+    assert_eq!(animator.pose(&clock).action, PlayerAction::Jump);
+
+    game.player.position.y = 4;
+    game.player.in_air = false;
+    animator.observe(&game, &clock);
+    assert_eq!(animator.pose(&clock).action, PlayerAction::Fall);
+
+    // Stepping left with a block right in front of the player is a push
+    game.blocks.push(Block { position: GridPos::new(0, 5), falling: false, ..Default::default() });
+    game.sync_occupancy();
+    game.player.position = GridPos::new(1, 4);
+    animator.observe(&game, &clock);
+    let pose = animator.pose(&clock);
+    assert_eq!((pose.action, pose.facing), (PlayerAction::Push, Direction::Left));
+}
+
+#[test]
+fn test_landed_blocks_squash_then_recover() {
+    let clock = MockClock::new();
+    let game = test_game();
+    let mut animator = Animator::new(game.player.position, &clock);
+
+    animator.block_landed(GridPos::new(5, 5), &clock);
+    assert_eq!(animator.squashes(&clock), vec![(GridPos::new(5, 5), 1.0)]);
+    clock.advance(SQUASH_DURATION / 2);
+    assert_eq!(animator.squashes(&clock), vec![(GridPos::new(5, 5), 0.5)]);
+    clock.advance(SQUASH_DURATION / 2);
+    animator.observe(&game, &clock);
+    assert!(animator.squashes(&clock).is_empty());
+}
+
+#[test]
+fn test_updates_are_drawn_over_the_refresh_that_follows() {
+    let clock = MockClock::new();
+    let mut game = test_game();
+    game.refresh_rate_milliseconds = 200;
+    game.blocks.push(Block::new(GridPos::new(5, 0)));
+    game.sync_occupancy();
+    let mut animator = Animator::new(game.player.position, &clock);
+    animator.observe(&game, &clock);
+    assert_eq!(animator.tick_remaining(&clock), 0.0);
+
+    game.tick();
+    animator.observe(&game, &clock);
+    assert_eq!((game.blocks[0].last_position, game.blocks[0].position), (GridPos::new(5, 0), GridPos::new(5, 1)));
+    assert_eq!(animator.tick_remaining(&clock), 1.0);
+
+    clock.advance(Duration::from_millis(50));
+    animator.observe(&game, &clock);
+    assert_eq!(animator.frame((0.0, 0.0), &clock).tick_remaining, 0.75);
+    clock.advance(Duration::from_millis(200));
+    assert_eq!(animator.tick_remaining(&clock), 0.0);
+}
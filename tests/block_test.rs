@@ -1,30 +1,43 @@
-use rust_stackattack::core::block::{Block, spawn_random_block};
+use rust_stackattack::core::block::{Block, BlockKind, Shape, spawn_random_block};
+use rust_stackattack::core::types::{Direction, GridPos};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 
 #[test]
 fn test_block_creation() {
-    let block = Block::new((5, 10));
+    let block = Block::new(GridPos::new(5, 10));
     assert_eq!(block.position, (5, 10));
     assert!(block.falling);
     assert!(!block.carried);
     assert_eq!(block.carrying_direction, None);
+    assert_eq!(block.kind, BlockKind::Crate);
+    assert_eq!(block.sliding, None);
+}
+
+#[test]
+fn test_block_with_kind() {
+    let block = Block::with_kind(GridPos::new(1, 2), BlockKind::Ice);
+    assert_eq!(block.kind, BlockKind::Ice);
+    assert_eq!(block.position, (1, 2));
+    assert_eq!(block.shape, Shape::Single);
 }
 
 #[test]
 fn test_spawn_random_block() {
     let grid_size = 10;
-    let block = spawn_random_block(grid_size);
+    let block = spawn_random_block(grid_size, grid_size, &mut StdRng::seed_from_u64(7));
     
     // Check that x position is within range
-    assert!(block.position.0 < grid_size);
+    assert!(block.position.x < grid_size);
     // Check that y position is 0 (top of grid)
-    assert_eq!(block.position.1, 0);
+    assert_eq!(block.position.y, 0);
     // Check that block is falling
     assert!(block.falling);
 }
 
 #[test]
 fn test_block_carrying_state() {
-    let mut block = Block::new((5, 5));
+    let mut block = Block::new(GridPos::new(5, 5));
     
     // Initially blocks are not carried
     assert!(!block.carried);
@@ -32,13 +45,13 @@ fn test_block_carrying_state() {
     
     // Test setting carried state for rightward movement
     block.carried = true;
-    block.carrying_direction = Some(1);
+    block.carrying_direction = Some(Direction::Right);
     assert!(block.carried);
-    assert_eq!(block.carrying_direction, Some(1));
+    assert_eq!(block.carrying_direction, Some(Direction::Right));
     
     // Test setting carried state for leftward movement
-    block.carrying_direction = Some(-1);
-    assert_eq!(block.carrying_direction, Some(-1));
+    block.carrying_direction = Some(Direction::Left);
+    assert_eq!(block.carrying_direction, Some(Direction::Left));
     
     // Test resetting carried state
     block.carried = false;
@@ -46,3 +59,46 @@ fn test_block_carrying_state() {
     assert!(!block.carried);
     assert_eq!(block.carrying_direction, None);
 }
+
+#[test]
+fn test_block_shape_cells() {
+    let block = Block::with_shape(GridPos::new(2, 3), Shape::L);
+    
+    let cells: Vec<_> = block.cells().collect();
+    assert_eq!(cells, vec![(2, 3), (2, 4), (3, 4)]);
+    assert!(block.occupies(GridPos::new(3, 4)));
+    assert!(!block.occupies(GridPos::new(3, 3)));
+    assert_eq!(block.bottom(), 4);
+    assert_eq!(block.right(), 3);
+}
+
+#[test]
+fn test_block_without_row() {
+    // Clearing the top row of an L leaves its horizontal foot
+    let remainder = Block::with_shape(GridPos::new(2, 3), Shape::L).without_row(3).unwrap();
+    assert_eq!(remainder.shape, Shape::Horizontal);
+    assert_eq!(remainder.position, (2, 4));
+    
+    // Clearing the bottom row of a vertical block leaves its top cell
+    let remainder = Block::with_shape(GridPos::new(1, 1), Shape::Vertical).without_row(2).unwrap();
+    assert_eq!(remainder.shape, Shape::Single);
+    assert_eq!(remainder.position, (1, 1));
+    
+    // A horizontal block in a cleared row disappears completely
+    assert!(Block::with_shape(GridPos::new(0, 2), Shape::Horizontal).without_row(2).is_none());
+    
+    // Rows the block doesn't cover leave it untouched
+    let untouched = Block::with_shape(GridPos::new(0, 2), Shape::Horizontal).without_row(4).unwrap();
+    assert_eq!(untouched.shape, Shape::Horizontal);
+}
+
+#[test]
+fn test_spawn_random_block_fits_grid() {
+    let grid_size = 3;
+    let mut rng = StdRng::seed_from_u64(7);
+    for _ in 0..50 {
+        let block = spawn_random_block(grid_size, grid_size, &mut rng);
+        assert!(block.right() < grid_size);
+        assert_eq!(block.position.y, 0);
+    }
+}
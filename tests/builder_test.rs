@@ -0,0 +1,95 @@
+use rust_stackattack::core::block::{BlockId, BlockKind, Shape};
+use rust_stackattack::core::game::GameState;
+use rust_stackattack::core::types::{ConfigError, GameMode, GridPos, InputAction};
+
+#[test]
+fn test_builder_places_exactly_the_given_board() {
+    let game = GameState::builder()
+        .grid(10, 12)
+        .seed(9)
+        .block(3, 11)
+        .falling_block(6, 2)
+        .shaped_block(0, 11, Shape::Horizontal)
+        .kind_block(8, 11, BlockKind::Ice)
+        .player_at(5, 10)
+        .build()
+        .unwrap();
+
+    assert_eq!((game.grid_width, game.grid_height), (10, 12));
+    assert_eq!(game.seed, 9);
+    let positions: Vec<((usize, usize), bool)> = game.blocks.iter().map(|block| (block.position.into(), block.falling)).collect();
+    assert_eq!(positions, vec![((3, 11), false), ((6, 2), true), ((0, 11), false), ((8, 11), false)]);
+    assert_eq!(game.blocks[2].shape, Shape::Horizontal);
+    assert_eq!(game.blocks[3].kind, BlockKind::Ice);
+    assert_eq!(game.player.position, (5, 10));
+    assert!(game.occupancy.is_occupied(GridPos::new(1, 11)));
+    assert_eq!(game.stack_top(3), 11);
+}
+
+#[test]
+fn test_builder_board_plays_like_any_other() {
+    let mut game = GameState::builder().grid(6, 6).block(3, 5).player_at(2, 4).build().unwrap();
+
+    // Walking into the crate pushes it along the floor
+    game.process_input(InputAction::Right);
+    assert_eq!(game.player.position, (3, 4));
+    assert_eq!(game.blocks[0].position, (4, 5));
+}
+
+#[test]
+fn test_builder_takes_mode_lives_and_pits() {
+    let game = GameState::builder().mode(GameMode::Zen).lives(5).pit(2).build().unwrap();
+
+    assert_eq!(game.mode, GameMode::Zen);
+    assert_eq!(game.lives, 5);
+    assert!(game.terrain.is_pit(2));
+    assert!(game.blocks.is_empty());
+}
+
+#[test]
+fn test_builder_turns_down_invalid_configs() {
+    assert_eq!(
+        GameState::builder().grid(1, 12).build().err(),
+        Some(ConfigError::GridTooSmall { width: 1, height: 12 })
+    );
+}
+
+#[test]
+fn test_block_ids_survive_row_clears() {
+    // The bottom row is full, the vertical block on the left keeps its top half after the clear
+    let mut game = GameState::builder()
+        .grid(3, 6)
+        .shaped_block(0, 4, Shape::Vertical)
+        .block(1, 5)
+        .block(2, 5)
+        .block(2, 2)
+        .player_at(1, 3)
+        .build()
+        .unwrap();
+    let ids: Vec<BlockId> = game.blocks.iter().map(|block| block.id).collect();
+    assert!(ids.iter().all(|id| id.is_assigned()));
+
+    game.check_full_rows();
+
+    assert_eq!(game.blocks.len(), 2);
+    assert!(game.block(ids[1]).is_none());
+    assert!(game.block(ids[2]).is_none());
+    let remainder = game.block(ids[0]).expect("top half of the vertical block");
+    assert_eq!(remainder.shape, Shape::Single);
+    assert_eq!(game.block_index(ids[3]), Some(1));
+    assert_eq!(game.block(ids[3]).unwrap().position, (2, 2));
+}
+
+#[test]
+fn test_spawned_blocks_get_new_ids() {
+    let mut game = GameState::builder().grid(8, 10).seed(3).block(0, 9).build().unwrap();
+
+    game.spawn_block();
+    game.spawn_block();
+
+    let ids: Vec<BlockId> = game.blocks.iter().map(|block| block.id).collect();
+    assert_eq!(ids.len(), 3);
+    assert!(ids[0] < ids[1] && ids[1] < ids[2]);
+    game.block_mut(ids[2]).unwrap().touched = true;
+    assert!(game.blocks[2].touched);
+}
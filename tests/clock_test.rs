@@ -0,0 +1,47 @@
+use std::time::Duration;
+
+use rust_stackattack::platform::clock::{Clock, TimedValue};
+use rust_stackattack::testing::MockClock;
+
+#[test]
+fn test_mock_clock_advances_without_sleeping() {
+    let clock = MockClock::new();
+    let start = clock.now();
+
+    clock.advance(Duration::from_secs(5));
+    clock.sleep(Duration::from_secs(60));
+
+    assert_eq!(clock.now() - start, Duration::from_secs(65));
+}
+
+#[test]
+fn test_timed_value_expires() {
+    let clock = MockClock::new();
+    let mut banner = TimedValue::new(Duration::from_millis(1500));
+    assert_eq!(banner.get(&clock), None);
+
+    banner.set("+3", &clock);
+    clock.advance(Duration::from_millis(1499));
+    assert_eq!(banner.get(&clock), Some(&"+3"));
+
+    clock.advance(Duration::from_millis(1));
+    assert_eq!(banner.get(&clock), None);
+
+    banner.set("+1", &clock);
+    banner.clear();
+    assert_eq!(banner.get(&clock), None);
+}
+
+#[test]
+fn test_timed_value_fades_out() {
+    let clock = MockClock::new();
+    let mut fade = TimedValue::new(Duration::from_millis(400));
+    assert_eq!(fade.fraction_left(&clock), None);
+
+    fade.set((), &clock);
+    assert_eq!(fade.fraction_left(&clock), Some(1.0));
+    clock.advance(Duration::from_millis(100));
+    assert_eq!(fade.fraction_left(&clock), Some(0.75));
+    clock.advance(Duration::from_millis(300));
+    assert_eq!(fade.fraction_left(&clock), None);
+}
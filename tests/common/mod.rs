@@ -0,0 +1,7 @@
+// Fixtures shared by the integration tests
+use rust_stackattack::core::block::Block;
+
+// A single crate at rest at the given cell
+pub fn settled(position: (usize, usize)) -> Block {
+    Block { position: position.into(), falling: false, ..Default::default() }
+}
@@ -0,0 +1,83 @@
+use rust_stackattack::core::block::Block;
+use rust_stackattack::core::cue::{Cue, CueTracker, DANGER_MARGIN};
+use rust_stackattack::core::game::{GameState, SpawnWarning};
+use rust_stackattack::core::types::{GameConfig, GridPos};
+use rust_stackattack::platform::cue_sound;
+
+mod common;
+use common::settled;
+
+fn quiet_game() -> GameState {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 5,
+        grid_height: 10,
+        ..Default::default()
+    }).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    game
+}
+
+#[test]
+fn test_spawn_over_the_player_is_announced_once() {
+    let mut game = quiet_game();
+    let mut tracker = CueTracker::new();
+    assert!(tracker.update(&game).is_empty());
+
+    let column = game.player.position.x;
+    game.next_blocks[0] = Block::new(GridPos::new(column, 0));
+    game.spawn_warning = Some(SpawnWarning { column, ticks_left: 3 });
+    assert_eq!(tracker.update(&game), vec![Cue::SpawnInColumn]);
+
+    game.spawn_warning = Some(SpawnWarning { column, ticks_left: 2 });
+    assert!(tracker.update(&game).is_empty());
+}
+
+#[test]
+fn test_row_one_short_of_clearing() {
+    let mut game = quiet_game();
+    let mut tracker = CueTracker::new();
+    for x in 0..3 {
+        game.blocks.push(settled((x, 9)));
+    }
+    game.sync_occupancy();
+    assert!(tracker.update(&game).is_empty());
+
+    game.blocks.push(settled((3, 9)));
+    game.sync_occupancy();
+    assert_eq!(tracker.update(&game), vec![Cue::RowAlmostComplete]);
+    assert!(tracker.update(&game).is_empty());
+}
+
+#[test]
+fn test_high_stack_warns_when_it_gets_close() {
+    let mut game = quiet_game();
+    let mut tracker = CueTracker::new();
+    game.blocks.push(settled((0, game.danger_line + DANGER_MARGIN + 1)));
+    game.sync_occupancy();
+    assert!(tracker.update(&game).is_empty());
+
+    game.blocks.push(settled((0, game.danger_line + DANGER_MARGIN)));
+    game.sync_occupancy();
+    assert_eq!(tracker.update(&game), vec![Cue::DangerHigh]);
+}
+
+#[test]
+fn test_cue_sounds_are_distinct_wav_files() {
+    let cues = [Cue::SpawnInColumn, Cue::RowAlmostComplete, Cue::DangerHigh];
+    for cue in cues {
+        let wav = cue_sound::cue_wav(cue);
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(&wav[8..12], b"WAVE");
+        assert_eq!(wav.len(), 44 + cue_sound::cue_samples(cue).len() * 2);
+    }
+    assert_ne!(cue_sound::cue_samples(cues[0]), cue_sound::cue_samples(cues[1]));
+    assert_ne!(cue_sound::cue_samples(cues[1]), cue_sound::cue_samples(cues[2]));
+}
+
+#[test]
+fn test_whoosh_is_a_wav_file() {
+    let wav = cue_sound::whoosh_wav();
+    assert_eq!(&wav[..4], b"RIFF");
+    assert_eq!(wav.len(), 44 + cue_sound::whoosh_samples().len() * 2);
+}
@@ -0,0 +1,116 @@
+use rust_stackattack::core::block::{BlockKind, Shape};
+use rust_stackattack::core::ai::{Bot, Difficulty};
+use rust_stackattack::core::editor::{Editor, LivePreview, Tool};
+use rust_stackattack::core::game::GameState;
+use rust_stackattack::core::level::{load_level, LevelDefinition};
+use rust_stackattack::core::terrain::Tile;
+use rust_stackattack::core::types::{Direction, GameConfig, GridPos};
+
+#[test]
+fn test_tools_replace_what_was_there() {
+    let mut editor = Editor::new(8, 6);
+    editor.tool = Tool::Block(Shape::Horizontal, BlockKind::Crate);
+    editor.apply(GridPos::new(0, 4));
+    assert_eq!(editor.level.blocks.len(), 1);
+
+    // A wall on the right half of the block takes the whole block's place
+    editor.tool = Tool::Wall;
+    editor.apply(GridPos::new(1, 4));
+    assert!(editor.level.blocks.is_empty());
+    assert_eq!(editor.level.terrain[0].tile, Tile::Wall);
+
+    // Shapes that would stick out of the grid aren't placed
+    editor.tool = Tool::Block(Shape::Horizontal, BlockKind::Crate);
+    editor.apply(GridPos::new(7, 4));
+    assert!(editor.level.blocks.is_empty());
+
+    editor.erase(GridPos::new(1, 4));
+    assert!(editor.level.terrain.is_empty());
+}
+
+#[test]
+fn test_player_start_and_pits() {
+    let mut editor = Editor::new(8, 6);
+    editor.tool = Tool::Block(Shape::Single, BlockKind::Steel);
+    editor.apply(GridPos::new(1, 5));
+    editor.tool = Tool::PlayerStart;
+    editor.apply(GridPos::new(1, 5));
+    assert_eq!(editor.level.player_start, Some(GridPos::new(1, 4)));
+    assert!(editor.level.blocks.is_empty());
+
+    editor.tool = Tool::Pit;
+    editor.apply(GridPos::new(5, 2));
+    assert_eq!(editor.level.pits, vec![5]);
+    editor.erase(GridPos::new(5, 5));
+    assert!(editor.level.pits.is_empty());
+}
+
+#[test]
+fn test_saved_level_loads_into_a_game() {
+    let mut editor = Editor::new(8, 6);
+    editor.tool = Tool::Ramp(Direction::Right);
+    editor.apply(GridPos::new(2, 5));
+    editor.tool = Tool::Pit;
+    editor.apply(GridPos::new(6, 5));
+    editor.tool = Tool::PlayerStart;
+    editor.apply(GridPos::new(0, 4));
+
+    let path = std::env::temp_dir().join(format!("stackattack-editor-{}.json", std::process::id()));
+    editor.save(&path).unwrap();
+    let level = load_level(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(level, editor.level);
+
+    let game = GameState::from_level(&level, GameConfig::default()).unwrap();
+    assert_eq!((game.grid_width, game.grid_height), (8, 6));
+    assert_eq!(game.player.position, (0, 4));
+    assert_eq!(game.terrain.ramp_at(GridPos::new(2, 5)), Some(Direction::Right));
+    assert!(game.terrain.is_pit(6));
+}
+
+#[test]
+fn test_unplayable_level_is_not_saved() {
+    let mut editor = Editor::from_level(LevelDefinition { block_spawn_rate: 0, ..LevelDefinition::empty(8, 6) });
+    let path = std::env::temp_dir().join(format!("stackattack-editor-invalid-{}.json", std::process::id()));
+    assert!(editor.save(&path).is_err());
+    assert!(!path.exists());
+
+    editor.level.block_spawn_rate = 10;
+    editor.tool = Tool::Wall;
+    editor.apply(GridPos::new(3, 0));
+    assert!(editor.level.to_json().is_err());
+}
+
+#[test]
+fn test_live_preview_starts_over_after_edits() {
+    let mut editor = Editor::new(6, 6);
+    let config = GameConfig { block_spawn_rate: 2, seed: Some(4), ..Default::default() };
+    let mut preview = LivePreview::new(&editor.level, config).unwrap();
+    for _ in 0..5 {
+        preview.tick();
+    }
+    assert_eq!(preview.game.stats.ticks, 5);
+    assert!(!preview.sync(&editor.level));
+    assert_eq!(preview.game.stats.ticks, 5);
+
+    editor.tool = Tool::Wall;
+    editor.apply(GridPos::new(0, 5));
+    assert!(preview.sync(&editor.level));
+    assert_eq!(preview.game.stats.ticks, 0);
+    assert!(preview.game.terrain.is_solid(GridPos::new(0, 5)));
+}
+
+#[test]
+fn test_live_preview_keeps_running_past_game_over() {
+    let editor = Editor::new(6, 6);
+    let mut preview = LivePreview::new(&editor.level, GameConfig { seed: Some(4), ..Default::default() }).unwrap()
+        .with_bot(Bot::new(Difficulty::Easy));
+    preview.tick();
+    preview.tick();
+    assert_eq!(preview.game.stats.ticks, 2);
+
+    preview.game.game_over = true;
+    preview.tick();
+    assert!(!preview.game.game_over);
+    assert_eq!(preview.game.stats.ticks, 0);
+}
@@ -0,0 +1,90 @@
+use rust_stackattack::core::enemy::{Enemy, ENEMY_RESPAWN_TICKS, ENEMY_STEP_INTERVAL};
+use rust_stackattack::core::game::GameState;
+use rust_stackattack::core::types::{Direction, GameConfig, GridPos};
+
+mod common;
+use common::settled;
+
+fn foreman_game() -> GameState {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 6,
+        grid_height: 6,
+        block_spawn_rate: 1000,
+        enemy: true,
+        ..Default::default()
+    }).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    game
+}
+
+// Run the Foreman alone for a number of updates
+fn walk(game: &mut GameState, updates: u64) {
+    for _ in 0..updates {
+        game.update_enemy();
+    }
+}
+
+#[test]
+fn test_enemy_falls_then_patrols_the_ledge() {
+    let floor = |cell: GridPos| cell.y == 3 && cell.x <= 2;
+    let mut enemy = Enemy::new(GridPos::new(0, 0));
+    for _ in 0..2 {
+        enemy.update(floor, 6, 6);
+    }
+    assert_eq!(enemy.position, (0, 2));
+    assert!(enemy.falling);
+
+    // Walks to the edge of the ledge, turns there and walks back
+    let mut visited = Vec::new();
+    for _ in 0..ENEMY_STEP_INTERVAL as usize * 4 {
+        enemy.update(floor, 6, 6);
+        visited.push(enemy.position.x);
+    }
+    assert_eq!(visited.iter().max(), Some(&2));
+    assert_eq!(enemy.position, (1, 2));
+    assert_eq!(enemy.direction, Direction::Left);
+}
+
+#[test]
+fn test_enemy_turns_at_walls() {
+    let mut enemy = Enemy::new(GridPos::new(4, 5));
+    enemy.update(|GridPos { x, y: _ }| x == 5, 6, 6);
+    enemy.update(|GridPos { x, y: _ }| x == 5, 6, 6);
+    assert_eq!(enemy.position, (4, 5));
+    assert_eq!(enemy.direction, Direction::Left);
+}
+
+#[test]
+fn test_touching_the_enemy_costs_a_life() {
+    let mut game = foreman_game();
+    let lives = game.lives;
+    let GridPos { x, y } = game.player.position;
+    game.enemy = Some(Enemy::new(GridPos::new(x + 1, y + 1)));
+    game.enemy.as_mut().unwrap().direction = Direction::Left;
+
+    walk(&mut game, ENEMY_STEP_INTERVAL as u64);
+    assert_eq!(game.lives, lives - 1);
+}
+
+#[test]
+fn test_block_crushes_the_enemy_and_he_comes_back() {
+    let mut game = foreman_game();
+    game.enemy = Some(Enemy::new(GridPos::new(0, 5)));
+    game.blocks.push(settled((0, 5)));
+    game.sync_occupancy();
+
+    game.update_enemy();
+    assert!(game.enemy.is_none());
+
+    game.blocks.clear();
+    game.sync_occupancy();
+    walk(&mut game, ENEMY_RESPAWN_TICKS);
+    assert!(game.enemy.is_some());
+}
+
+#[test]
+fn test_no_enemy_unless_configured() {
+    let game = GameState::new(GameConfig::default()).unwrap();
+    assert!(game.enemy.is_none());
+}
@@ -0,0 +1,141 @@
+use rust_stackattack::core::event_log::{EventLog, LoggedEvent};
+use rust_stackattack::core::game::GameState;
+use rust_stackattack::core::types::{GameConfig, InputAction, InputDevice};
+use rust_stackattack::platform::input;
+use rust_stackattack::platform::mouse::MouseInput;
+
+fn recorded_game() -> GameState {
+    let config = GameConfig {
+        grid_width: 8,
+        grid_height: 8,
+        block_spawn_rate: 3,
+        seed: Some(42),
+        record_events: true,
+        ..Default::default()
+    };
+    GameState::new(config).unwrap()
+}
+
+fn play(game: &mut GameState, ticks: usize) {
+    let script = [InputAction::Left, InputAction::Up, InputAction::None, InputAction::Right, InputAction::Right];
+    for tick in 0..ticks {
+        game.process_input(script[tick % script.len()]);
+        game.tick();
+    }
+}
+
+fn block_positions(game: &GameState) -> Vec<(usize, usize)> {
+    game.blocks.iter().map(|block| block.position.into()).collect()
+}
+
+#[test]
+fn test_rebuild_matches_live_game() {
+    let mut game = recorded_game();
+    play(&mut game, 40);
+
+    let rebuilt = game.event_log.as_ref().unwrap().rebuild().unwrap();
+    assert_eq!(rebuilt.player.position, game.player.position);
+    assert_eq!(block_positions(&rebuilt), block_positions(&game));
+    assert_eq!(rebuilt.score, game.score);
+    assert_eq!(rebuilt.lives, game.lives);
+    assert_eq!(rebuilt.game_over, game.game_over);
+}
+
+#[test]
+fn test_rebuild_until_tick() {
+    let mut game = recorded_game();
+    play(&mut game, 10);
+    let midway = game.clone();
+    play(&mut game, 10);
+
+    let log = game.event_log.as_ref().unwrap();
+    assert_eq!(log.ticks(), 20);
+
+    let rebuilt = log.rebuild_until_tick(10).unwrap();
+    assert_eq!(rebuilt.player.position, midway.player.position);
+    assert_eq!(block_positions(&rebuilt), block_positions(&midway));
+}
+
+#[test]
+fn test_log_round_trips_through_json() {
+    let mut game = recorded_game();
+    play(&mut game, 15);
+
+    let log = game.event_log.as_ref().unwrap();
+    let json = log.to_json();
+    assert!(json.contains("\"Tick\""));
+
+    let loaded = EventLog::from_json(&json).unwrap();
+    assert_eq!(&loaded, log);
+    assert_eq!(block_positions(&loaded.rebuild().unwrap()), block_positions(&game));
+}
+
+#[test]
+fn test_repeated_idle_input_is_logged_once() {
+    let mut game = recorded_game();
+    game.process_input(InputAction::None);
+    game.process_input(InputAction::None);
+    game.tick();
+
+    let log = game.event_log.as_ref().unwrap();
+    assert_eq!(log.events, vec![LoggedEvent::Input(InputAction::None), LoggedEvent::Tick]);
+}
+
+#[test]
+fn test_no_log_unless_requested() {
+    let game = GameState::new(GameConfig::default()).unwrap();
+    assert!(game.event_log.is_none());
+}
+
+#[test]
+fn test_inputs_are_stamped_with_their_device() {
+    let mut game = recorded_game();
+    game.process_input(InputAction::Left);
+    game.tick();
+    game.process_input_from(InputAction::Right, InputDevice::Gamepad);
+    input::step(&mut game, &mut MouseInput::new(30.0));
+
+    let log = game.event_log.as_ref().unwrap();
+    let devices: Vec<_> = log.stamps.iter().map(|stamp| (stamp.device, stamp.tick)).collect();
+    assert_eq!(devices, vec![
+        (InputDevice::Keyboard, 0),
+        (InputDevice::Gamepad, 1),
+        (InputDevice::Mouse, 1),
+    ]);
+    assert_eq!(log.device_of(0), Some(InputDevice::Keyboard));
+    assert_eq!(log.device_of(1), None);
+    assert!(log.stamps[1].millis >= game.refresh_rate_milliseconds);
+}
+
+#[test]
+fn test_conflicting_sources_are_found() {
+    let mut game = recorded_game();
+    game.process_input_from(InputAction::Left, InputDevice::Keyboard);
+    game.process_input_from(InputAction::Left, InputDevice::Mouse);
+    game.tick();
+    game.process_input_from(InputAction::Left, InputDevice::Keyboard);
+    game.process_input_from(InputAction::Right, InputDevice::Gamepad);
+    game.process_input_from(InputAction::None, InputDevice::Mouse);
+    game.tick();
+
+    let log = game.event_log.as_ref().unwrap();
+    assert_eq!(log.conflicts(), vec![1]);
+
+    // Stamps survive a rebuild and a trip through JSON
+    let rebuilt = log.rebuild().unwrap();
+    let devices = |log: &EventLog| log.stamps.iter().map(|stamp| stamp.device).collect::<Vec<_>>();
+    assert_eq!(devices(rebuilt.event_log.as_ref().unwrap()), devices(log));
+    assert_eq!(&EventLog::from_json(&log.to_json()).unwrap(), log);
+}
+
+#[test]
+fn test_logs_without_stamps_still_load() {
+    let mut game = recorded_game();
+    play(&mut game, 5);
+
+    let mut log = game.event_log.clone().unwrap();
+    log.stamps.clear();
+    let json = log.to_json();
+    assert!(!json.contains("stamps"));
+    assert_eq!(EventLog::from_json(&json).unwrap(), log);
+}
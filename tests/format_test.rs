@@ -0,0 +1,34 @@
+use rust_stackattack::platform::format::{DateOrder, Locale};
+
+#[test]
+fn test_locale_from_tag() {
+    assert_eq!(Locale::from_tag("en_US.UTF-8").date_order, DateOrder::MonthDayYear);
+    assert_eq!(Locale::from_tag("cs_CZ").date_separator, '.');
+    assert_eq!(Locale::from_tag("C"), Locale::ISO);
+    assert_eq!(Locale::from_tag(""), Locale::ISO);
+}
+
+#[test]
+fn test_numbers_get_thousands_separators() {
+    let us = Locale::from_tag("en_US");
+    assert_eq!(us.number(0), "0");
+    assert_eq!(us.number(999), "999");
+    assert_eq!(us.number(1000), "1,000");
+    assert_eq!(us.number(1234567), "1,234,567");
+    assert_eq!(Locale::from_tag("de_DE").number(12345), "12 345");
+    assert_eq!(Locale::ISO.number(12345), "12345");
+}
+
+#[test]
+fn test_dates_and_times() {
+    assert_eq!(Locale::from_tag("en_US").date("2026-10-05"), "10/05/2026");
+    assert_eq!(Locale::from_tag("en_GB").date("2026-10-05"), "05/10/2026");
+    assert_eq!(Locale::from_tag("cs_CZ").date("2026-10-05"), "05.10.2026");
+    assert_eq!(Locale::ISO.date("2026-10-05"), "2026-10-05");
+    assert_eq!(Locale::from_tag("en_US").date("yesterday"), "yesterday");
+
+    let us = Locale::from_tag("en_US");
+    assert_eq!(us.time_of_day(0, 5), "12:05 AM");
+    assert_eq!(us.time_of_day(13, 30), "1:30 PM");
+    assert_eq!(Locale::from_tag("fr_FR").time_of_day(13, 30), "13:30");
+}
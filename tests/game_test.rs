@@ -1,6 +1,10 @@
-use rust_stackattack::core::game::GameState;
-use rust_stackattack::core::block::Block;
-use rust_stackattack::core::types::{GameConfig, InputAction};
+use rust_stackattack::core::event::{GameEvent, ScoreBreakdown};
+use rust_stackattack::core::builder::GameStateBuilder;
+use rust_stackattack::core::game::{
+    GameState, CHAIN_WINDOW_TICKS, CLOSE_CALL_BONUS, NEXT_BLOCKS, PIT_BONUS, SPAWN_WARNING_TICKS, TIME_ATTACK_DURATION,
+};
+use rust_stackattack::core::block::{Block, BlockKind, Shape};
+use rust_stackattack::core::types::{ConfigError, Direction, GameConfig, GameMode, GameOverReason, GridPos, InputAction};
 use std::time::{Duration, Instant};
 
 #[test]
@@ -12,17 +16,20 @@ fn test_game_creation() {
     let block_spawn_rate = 10;
     
     let config = GameConfig {
-        grid_size,
+        grid_width: grid_size,
+        grid_height: grid_size,
         cell_size,
         refresh_rate_milliseconds: refresh_rate,
         block_fall_speed,
         block_spawn_rate,
+        ..Default::default()
     };
     
-    let game = GameState::new(config);
+    let game = GameState::new(config).unwrap();
     
     // Verify initial game properties
-    assert_eq!(game.grid_size, grid_size);
+    assert_eq!(game.grid_width, grid_size);
+    assert_eq!(game.grid_height, grid_size);
     assert_eq!(game.cell_size, cell_size);
     assert_eq!(game.score, 0);
     assert!(!game.game_over);
@@ -34,13 +41,15 @@ fn test_game_creation() {
 #[test]
 fn test_check_for_levitating_blocks() {
     let config = GameConfig {
-        grid_size: 5,
+        grid_width: 5,
+        grid_height: 5,
         cell_size: 30.0,
         refresh_rate_milliseconds: 200,
         block_fall_speed: 1,
         block_spawn_rate: 10,
+        ..Default::default()
     };
-    let mut game = GameState::new(config);
+    let mut game = GameState::new(config).unwrap();
     
     // Clear the initial blocks
     game.blocks.clear();
@@ -48,35 +57,40 @@ fn test_check_for_levitating_blocks() {
     // Create a stack of blocks
     // Ground level block
     game.blocks.push(Block {
-        position: (2, 4), // Bottom block
+        position: GridPos::new(2, 4), // Bottom block
         falling: false,
         carried: false,
         carrying_direction: None,
+        ..Default::default()
     });
     
     // Mid-level block
     game.blocks.push(Block {
-        position: (2, 3), // Resting on bottom block
+        position: GridPos::new(2, 3), // Resting on bottom block
         falling: false,
         carried: false,
         carrying_direction: None,
+        ..Default::default()
     });
     
     // Top-level block
     game.blocks.push(Block {
-        position: (2, 2), // Resting on middle block
+        position: GridPos::new(2, 2), // Resting on middle block
         falling: false,
         carried: false,
         carrying_direction: None,
+        ..Default::default()
     });
     
     // Add a floating block with no support below
     game.blocks.push(Block {
-        position: (3, 3), // Floating with no support
+        position: GridPos::new(3, 3), // Floating with no support
         falling: false, // Incorrectly marked as not falling
         carried: false,
         carrying_direction: None,
+        ..Default::default()
     });
+    game.sync_occupancy();
     
     // Check for and update levitating blocks
     game.check_for_levitating_blocks();
@@ -90,16 +104,40 @@ fn test_check_for_levitating_blocks() {
     assert!(game.blocks[3].falling);
 }
 
+#[test]
+fn test_levitation_chain_on_a_tall_column() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 3,
+        grid_height: 2000,
+        ..Default::default()
+    }).unwrap();
+    
+    // A column hanging one cell above the ground, listed top first so a rescan needs a pass per block
+    game.blocks = (0..1998)
+        .map(|y| Block {
+            position: GridPos::new(0, y),
+            falling: false,
+            ..Default::default()
+        })
+        .collect();
+    game.sync_occupancy();
+    
+    game.check_for_levitating_blocks();
+    assert!(game.blocks.iter().all(|block| block.falling));
+}
+
 #[test]
 fn test_check_full_rows_and_scoring() {
     let config = GameConfig {
-        grid_size: 4,
+        grid_width: 4,
+        grid_height: 4,
         cell_size: 30.0,
         refresh_rate_milliseconds: 200,
         block_fall_speed: 1,
         block_spawn_rate: 10,
+        ..Default::default()
     };
-    let mut game = GameState::new(config);
+    let mut game = GameState::new(config).unwrap();
     
     // Clear the initial blocks
     game.blocks.clear();
@@ -107,27 +145,31 @@ fn test_check_full_rows_and_scoring() {
     // Create a full row at the bottom
     for x in 0..4 {
         game.blocks.push(Block {
-            position: (x, 3), // Bottom row
+            position: GridPos::new(x, 3), // Bottom row
             falling: false,
             carried: false,
             carrying_direction: None,
+            ..Default::default()
         });
     }
     
     // Add some other blocks above
     game.blocks.push(Block {
-        position: (0, 2),
+        position: GridPos::new(0, 2),
         falling: false,
         carried: false,
         carrying_direction: None,
+        ..Default::default()
     });
     
     game.blocks.push(Block {
-        position: (2, 2),
+        position: GridPos::new(2, 2),
         falling: false,
         carried: false,
         carrying_direction: None,
+        ..Default::default()
     });
+    game.sync_occupancy();
     
     // Initial score should be 0
     assert_eq!(game.score, 0);
@@ -143,7 +185,7 @@ fn test_check_full_rows_and_scoring() {
     
     // The remaining blocks should be the ones that were on the second row
     let remaining_positions: Vec<(usize, usize)> = game.blocks.iter()
-        .map(|block| block.position)
+        .map(|block| block.position.into())
         .collect();
     assert!(remaining_positions.contains(&(0, 2)));
     assert!(remaining_positions.contains(&(2, 2)));
@@ -151,18 +193,20 @@ fn test_check_full_rows_and_scoring() {
     // Create another full row for testing multiple rows
     for x in 0..4 {
         game.blocks.push(Block {
-            position: (x, 3), // Bottom row again
+            position: GridPos::new(x, 3), // Bottom row again
             falling: false,
             carried: false,
             carrying_direction: None,
+            ..Default::default()
         });
     }
+    game.sync_occupancy();
     
     // Check for full rows again
     game.check_full_rows();
     
-    // Score should now be 2
-    assert_eq!(game.score, 2);
+    // The second clear follows right after the first, so it scores double as a chain
+    assert_eq!(game.score, 3);
     
     // Still should only have the original 2 blocks from the second row
     assert_eq!(game.blocks.len(), 2);
@@ -171,13 +215,15 @@ fn test_check_full_rows_and_scoring() {
 #[test]
 fn test_levitating_cascade_effect() {
     let config = GameConfig {
-        grid_size: 5,
+        grid_width: 5,
+        grid_height: 5,
         cell_size: 30.0,
         refresh_rate_milliseconds: 200,
         block_fall_speed: 1,
         block_spawn_rate: 10,
+        ..Default::default()
     };
-    let mut game = GameState::new(config);
+    let mut game = GameState::new(config).unwrap();
     
     // Clear the initial blocks
     game.blocks.clear();
@@ -194,41 +240,47 @@ fn test_levitating_cascade_effect() {
     
     // Row 3 - Platform blocks (A) - these are on the ground level
     game.blocks.push(Block {
-        position: (1, 4), // Bottom row (ground level)
+        position: GridPos::new(1, 4), // Bottom row (ground level)
         falling: false,
         carried: false,
         carrying_direction: None,
+        ..Default::default()
     });
     
     game.blocks.push(Block {
-        position: (2, 4), // Bottom row (ground level)
+        position: GridPos::new(2, 4), // Bottom row (ground level)
         falling: false,
         carried: false,
         carrying_direction: None,
+        ..Default::default()
     });
     
     game.blocks.push(Block {
-        position: (3, 4), // Bottom row (ground level)
+        position: GridPos::new(3, 4), // Bottom row (ground level)
         falling: false,
         carried: false,
         carrying_direction: None,
+        ..Default::default()
     });
     
     // Row 2 - Block B
     game.blocks.push(Block {
-        position: (2, 3),
+        position: GridPos::new(2, 3),
         falling: false,
         carried: false,
         carrying_direction: None,
+        ..Default::default()
     });
     
     // Row 1 - Block C
     game.blocks.push(Block {
-        position: (2, 2),
+        position: GridPos::new(2, 2),
         falling: false,
         carried: false,
         carrying_direction: None,
+        ..Default::default()
     });
+    game.sync_occupancy();
     
     // Verify we have 5 blocks total
     assert_eq!(game.blocks.len(), 5);
@@ -237,6 +289,7 @@ fn test_levitating_cascade_effect() {
     
     // Remove Block B (simulating it being cleared in a full row)
     game.blocks.remove(3);
+    game.sync_occupancy();
     
     // Now check for levitating blocks
     game.check_for_levitating_blocks();
@@ -245,7 +298,7 @@ fn test_levitating_cascade_effect() {
     assert!(game.blocks[3].falling);
     
     // Platform blocks should remain static since they're on ground level
-    // In our grid, the bottom row is at y=4 (grid_size-1)
+    // In our grid, the bottom row is at y=4 (grid_height-1)
     let platform_positions = [(1, 4), (2, 4), (3, 4)];
     
     for pos in platform_positions.iter() {
@@ -257,35 +310,41 @@ fn test_levitating_cascade_effect() {
 #[test]
 fn test_update_falling_blocks() {
     let config = GameConfig {
-        grid_size: 5,
+        grid_width: 5,
+        grid_height: 5,
         cell_size: 30.0,
         refresh_rate_milliseconds: 200,
         block_fall_speed: 1,
         block_spawn_rate: 10,
+        ..Default::default()
     };
-    let mut game = GameState::new(config);
+    let mut game = GameState::new(config).unwrap();
     
     // Clear the initial blocks
     game.blocks.clear();
+    game.sync_occupancy();
     
     // First, move the player away from where we'll place blocks
-    game.player.position = (0, 4);  // Move player to the left side at the bottom
+    game.player.position = GridPos::new(0, 4);  // Move player to the left side at the bottom
     
     // Add a falling block far from the player
     game.blocks.push(Block {
-        position: (3, 2),  // Position far from the player to avoid collision
+        position: GridPos::new(3, 2),  // Position far from the player to avoid collision
         falling: true,
         carried: false,
         carrying_direction: None,
+        ..Default::default()
     });
     
     // Add a stationary block at the bottom
     game.blocks.push(Block {
-        position: (3, 4),  // Directly below where the falling block will land
+        position: GridPos::new(3, 4),  // Directly below where the falling block will land
         falling: false,
         carried: false,
         carrying_direction: None,
+        ..Default::default()
     });
+    game.sync_occupancy();
     
     // Store initial position
     let initial_pos = game.blocks[0].position;
@@ -294,8 +353,8 @@ fn test_update_falling_blocks() {
     game.update_falling_blocks();
     
     // The falling block should have moved down by 1
-    assert_eq!(game.blocks[0].position.0, initial_pos.0, "X position should remain unchanged");
-    assert_eq!(game.blocks[0].position.1, initial_pos.1 + 1, "Y position should increase by 1");
+    assert_eq!(game.blocks[0].position.x, initial_pos.x, "X position should remain unchanged");
+    assert_eq!(game.blocks[0].position.y, initial_pos.y + 1, "Y position should increase by 1");
     
     // Update again - the block should stop falling and land on the stationary block
     game.update_falling_blocks();
@@ -307,66 +366,77 @@ fn test_update_falling_blocks() {
 #[test]
 fn test_update_falling_blocks_with_carried_blocks() {
     let config = GameConfig {
-        grid_size: 5,
+        grid_width: 5,
+        grid_height: 5,
         cell_size: 30.0,
         refresh_rate_milliseconds: 200,
         block_fall_speed: 1,
         block_spawn_rate: 10,
+        ..Default::default()
     };
-    let mut game = GameState::new(config);
+    let mut game = GameState::new(config).unwrap();
     
     // Clear the initial blocks
     game.blocks.clear();
     
     // Add a carried block
     game.blocks.push(Block {
-        position: (2, 2),
+        position: GridPos::new(2, 2),
         falling: true, // Should be ignored because it's carried
         carried: true,
-        carrying_direction: Some(1),
+        carrying_direction: Some(Direction::Right),
+        ..Default::default()
     });
     
     // Add a falling block
     game.blocks.push(Block {
-        position: (3, 2),
+        position: GridPos::new(3, 2),
         falling: true,
         carried: false,
         carrying_direction: None,
+        ..Default::default()
     });
+    game.sync_occupancy();
     
     // Update falling blocks
     game.update_falling_blocks();
     
     // The carried block should not have moved
-    assert_eq!(game.blocks[0].position.1, 2);
+    assert_eq!(game.blocks[0].position.y, 2);
     // The falling block should have moved down
-    assert_eq!(game.blocks[1].position.1, 3);
+    assert_eq!(game.blocks[1].position.y, 3);
 }
 
 #[test]
 fn test_block_collision_with_player() {
     let config = GameConfig {
-        grid_size: 5,
+        grid_width: 5,
+        grid_height: 5,
         cell_size: 30.0,
         refresh_rate_milliseconds: 200,
         block_fall_speed: 1,
         block_spawn_rate: 10,
+        lives: 1,
+        ..Default::default()
     };
-    let mut game = GameState::new(config);
+    let mut game = GameState::new(config).unwrap();
     
     // Clear the initial blocks
     game.blocks.clear();
+    game.sync_occupancy();
     
     // Position the player
-    game.player.position = (2, 3);
+    game.player.position = GridPos::new(2, 3);
     
     // Add a falling block that will hit the player
     game.blocks.push(Block {
-        position: (2, 2),
+        position: GridPos::new(2, 2),
         falling: true,
         carried: false,
         carrying_direction: None,
+        ..Default::default()
     });
+    game.sync_occupancy();
     
     // Verify game is not over initially
     assert!(!game.game_over);
@@ -379,19 +449,99 @@ fn test_block_collision_with_player() {
     assert!(game.game_over);
 }
 
+#[test]
+fn test_block_collision_with_player_torso() {
+    let config = GameConfig {
+        grid_width: 5,
+        grid_height: 5,
+        cell_size: 30.0,
+        refresh_rate_milliseconds: 200,
+        block_fall_speed: 1,
+        block_spawn_rate: 10,
+        lives: 1,
+        ..Default::default()
+    };
+    let mut game = GameState::new(config).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    
+    // Player body occupies (2, 2) and (2, 3)
+    game.player.position = GridPos::new(2, 2);
+    
+    // A block entering the lower body cell should also crush the player
+    assert!(game.check_block_player_collision(2, 3));
+    assert!(game.game_over);
+}
+
+#[test]
+fn test_block_collision_below_player_feet() {
+    let config = GameConfig {
+        grid_width: 5,
+        grid_height: 5,
+        cell_size: 30.0,
+        refresh_rate_milliseconds: 200,
+        block_fall_speed: 1,
+        block_spawn_rate: 10,
+        ..Default::default()
+    };
+    let mut game = GameState::new(config).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    game.player.position = GridPos::new(2, 2);
+    
+    // Cells outside the body don't count, whether below the feet or in other columns
+    assert!(!game.check_block_player_collision(2, 4));
+    assert!(!game.check_block_player_collision(1, 3));
+    assert!(!game.game_over);
+}
+
+#[test]
+fn test_fast_block_hits_player_torso() {
+    let config = GameConfig {
+        grid_width: 5,
+        grid_height: 5,
+        cell_size: 30.0,
+        refresh_rate_milliseconds: 200,
+        block_fall_speed: 2,
+        block_spawn_rate: 10,
+        lives: 1,
+        ..Default::default()
+    };
+    let mut game = GameState::new(config).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    game.player.position = GridPos::new(2, 2);
+    
+    // With a fall speed of 2 this block skips the head cell and lands in the torso
+    game.blocks.push(Block {
+        position: GridPos::new(2, 1),
+        falling: true,
+        carried: false,
+        carrying_direction: None,
+        ..Default::default()
+    });
+    game.sync_occupancy();
+    
+    game.update_falling_blocks();
+    assert!(game.game_over);
+}
+
 #[test]
 fn test_handle_block_spawning() {
     let config = GameConfig {
-        grid_size: 5,
+        grid_width: 5,
+        grid_height: 5,
         cell_size: 30.0,
         refresh_rate_milliseconds: 200,
         block_fall_speed: 1,
         block_spawn_rate: 5,
+        ..Default::default()
     };
-    let mut game = GameState::new(config);
+    let mut game = GameState::new(config).unwrap();
     
     // Clear the initial blocks
     game.blocks.clear();
+    game.sync_occupancy();
     
     // Initial count
     let initial_count = game.blocks.len();
@@ -420,24 +570,27 @@ fn test_handle_block_spawning() {
 #[test]
 fn test_update_player() {
     let config = GameConfig {
-        grid_size: 5,
+        grid_width: 5,
+        grid_height: 5,
         cell_size: 30.0,
         refresh_rate_milliseconds: 200,
         block_fall_speed: 1,
         block_spawn_rate: 10,
+        ..Default::default()
     };
-    let mut game = GameState::new(config);
+    let mut game = GameState::new(config).unwrap();
     
     // Clear blocks to ensure no accidental support
     game.blocks.clear();
+    game.sync_occupancy();
     
     // Position the player in mid-air with no support
-    game.player.position = (2, 2);
+    game.player.position = GridPos::new(2, 2);
     game.player.in_air = false;
     game.player.is_falling = false;
     
     // First, manually call update_falling_state to start fall delay
-    game.player.update_falling_state(&game.blocks, game.grid_size);
+    game.player.update_falling_state(&game.blocks, game.grid_height);
     
     // Player should not be falling yet (due to fall delay)
     assert!(!game.player.is_falling);
@@ -454,19 +607,22 @@ fn test_update_player() {
 #[test]
 fn test_restart_game() {
     let config = GameConfig {
-        grid_size: 5,
+        grid_width: 5,
+        grid_height: 5,
         cell_size: 30.0,
         refresh_rate_milliseconds: 200,
         block_fall_speed: 1,
         block_spawn_rate: 10,
+        ..Default::default()
     };
-    let mut game = GameState::new(config);
+    let mut game = GameState::new(config).unwrap();
     
     // Set up a game state to test reset
     game.score = 100;
     game.game_over = true;
     game.blocks.clear();
-    game.player.position = (1, 1);
+    game.sync_occupancy();
+    game.player.position = GridPos::new(1, 1);
     
     // Call restart game method
     game.restart();
@@ -477,34 +633,39 @@ fn test_restart_game() {
     assert!(!game.blocks.is_empty()); // Should have at least one block
     
     // Default positions
-    assert_eq!(game.player.position.0, 2); // Default x position for grid size 5
-    assert_eq!(game.player.position.1, 3); // Default y position for grid size 5
+    assert_eq!(game.player.position.x, 2); // Default x position for grid size 5
+    assert_eq!(game.player.position.y, 3); // Default y position for grid size 5
 }
 
 #[test]
 fn test_game_update_simulation() {
     let config = GameConfig {
-        grid_size: 5,
+        grid_width: 5,
+        grid_height: 5,
         cell_size: 30.0,
         refresh_rate_milliseconds: 200,
         block_fall_speed: 1,
         block_spawn_rate: 10,
+        ..Default::default()
     };
-    let mut game = GameState::new(config);
+    let mut game = GameState::new(config).unwrap();
     
     // Clear the initial blocks
     game.blocks.clear();
+    game.sync_occupancy();
     
     // Move player away from test area
-    game.player.position = (0, 4);  // Move to far left at bottom
+    game.player.position = GridPos::new(0, 4);  // Move to far left at bottom
     
     // Add a falling block at a known position away from player
     game.blocks.push(Block {
-        position: (3, 2),  // Position far from player
+        position: GridPos::new(3, 2),  // Position far from player
         falling: true,
         carried: false,
         carrying_direction: None,
+        ..Default::default()
     });
+    game.sync_occupancy();
     
     // Get initial position
     let initial_pos = game.blocks[0].position;
@@ -516,48 +677,53 @@ fn test_game_update_simulation() {
     game.update();
     
     // Block should have moved down
-    assert_eq!(game.blocks[0].position.0, initial_pos.0);
-    assert!(game.blocks[0].position.1 > initial_pos.1);
+    assert_eq!(game.blocks[0].position.x, initial_pos.x);
+    assert!(game.blocks[0].position.y > initial_pos.y);
 }
 
 #[test]
 fn test_current_movement_direction() {
     let config = GameConfig {
-        grid_size: 5,
+        grid_width: 5,
+        grid_height: 5,
         cell_size: 30.0,
         refresh_rate_milliseconds: 200,
         block_fall_speed: 1,
         block_spawn_rate: 10,
+        ..Default::default()
     };
-    let mut game = GameState::new(config);
+    let mut game = GameState::new(config).unwrap();
     
     // Clear any existing blocks
     game.blocks.clear();
     
     // Create a block that's already carried
     game.blocks.push(Block {
-        position: (game.player.position.0, game.player.position.1),
+        position: GridPos::new(game.player.position.x, game.player.position.y),
         falling: false,
         carried: true,
-        carrying_direction: Some(1), // Being carried right
+        carrying_direction: Some(Direction::Right), // Being carried right
+        ..Default::default()
     });
+    game.sync_occupancy();
     
     // Verify the initial state
     assert!(game.blocks[0].carried);
-    assert_eq!(game.blocks[0].carrying_direction, Some(1));
+    assert_eq!(game.blocks[0].carrying_direction, Some(Direction::Right));
     
     // Process right movement input should maintain carrying
-    game.last_move_direction = Some(1);
+    game.last_move_direction = Some(Direction::Right);
     game.process_input(InputAction::Right);
     
     // Block should still be carried when moving in same direction
     assert!(game.blocks[0].carried);
     
     // Now manually change the direction
-    game.last_move_direction = Some(-1);
+    game.last_move_direction = Some(Direction::Left);
     
     // Simulate the player releasing the carried blocks when direction changes
     game.player.release_carried_blocks(&mut game.blocks, game.last_move_direction);
+    game.sync_occupancy();
     
     // Block should now be falling and not carried
     assert!(game.blocks[0].falling);
@@ -568,13 +734,15 @@ fn test_current_movement_direction() {
 #[test]
 fn test_keyboard_input_handling() {
     let config = GameConfig {
-        grid_size: 5,
+        grid_width: 5,
+        grid_height: 5,
         cell_size: 30.0,
         refresh_rate_milliseconds: 200,
         block_fall_speed: 1,
         block_spawn_rate: 10,
+        ..Default::default()
     };
-    let mut game = GameState::new(config);
+    let mut game = GameState::new(config).unwrap();
     
     // Test moving player with input actions
     let initial_position = game.player.position;
@@ -582,17 +750,17 @@ fn test_keyboard_input_handling() {
     // Process RIGHT input
     game.process_input(InputAction::Right);
     let pos_after_right = game.player.position;
-    assert_eq!(pos_after_right.0, initial_position.0 + 1);
+    assert_eq!(pos_after_right.x, initial_position.x + 1);
     
     // Process LEFT input
     game.process_input(InputAction::Left);
-    assert_eq!(game.player.position.0, pos_after_right.0 - 1);
+    assert_eq!(game.player.position.x, pos_after_right.x - 1);
     
     // Process UP input
-    let y_before_jump = game.player.position.1;
+    let y_before_jump = game.player.position.y;
     game.process_input(InputAction::Up);
     assert!(game.player.in_air);
-    assert_eq!(game.player.position.1, y_before_jump - 1);
+    assert_eq!(game.player.position.y, y_before_jump - 1);
 }
 
 #[test]
@@ -605,19 +773,22 @@ fn test_determine_movement_priority() {
 #[test]
 fn test_restart_game_functionality() {
     let config = GameConfig {
-        grid_size: 5,
+        grid_width: 5,
+        grid_height: 5,
         cell_size: 30.0,
         refresh_rate_milliseconds: 200,
         block_fall_speed: 1,
         block_spawn_rate: 10,
+        ..Default::default()
     };
-    let mut game = GameState::new(config);
+    let mut game = GameState::new(config).unwrap();
     
     // Set up a game state to test reset
     game.score = 100;
     game.game_over = true;
     game.blocks.clear();
-    game.player.position = (1, 1);
+    game.sync_occupancy();
+    game.player.position = GridPos::new(1, 1);
     
     // Process restart input
     game.process_input(InputAction::Restart);
@@ -628,5 +799,1337 @@ fn test_restart_game_functionality() {
     assert!(!game.blocks.is_empty()); // Should have at least one block
     
     // Player should be reset to default position for grid size 5
-    assert_eq!(game.player.position.0, 2);
+    assert_eq!(game.player.position.x, 2);
+}
+
+#[test]
+fn test_block_slides_down_ramp() {
+    let config = GameConfig {
+        grid_width: 5,
+        grid_height: 5,
+        cell_size: 30.0,
+        refresh_rate_milliseconds: 200,
+        block_fall_speed: 1,
+        block_spawn_rate: 10,
+        ..Default::default()
+    };
+    let mut game = GameState::new(config).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    game.player.position = GridPos::new(0, 3);
+    
+    // Ramp at the bottom rising to the right, so its low side is to the left
+    game.place_ramp(3, 4, Direction::Right);
+    
+    // Block falling straight onto the ramp
+    game.blocks.push(Block {
+        position: GridPos::new(3, 3),
+        falling: true,
+        carried: false,
+        carrying_direction: None,
+        ..Default::default()
+    });
+    game.sync_occupancy();
+    
+    // Falling onto the ramp moves the block to the low side
+    game.update_falling_blocks();
+    assert_eq!(game.blocks[0].position, (2, 3));
+    assert!(game.blocks[0].falling);
+    
+    // It then keeps falling to the ground next to the ramp
+    game.update_falling_blocks();
+    assert_eq!(game.blocks[0].position, (2, 4));
+    
+    game.update_falling_blocks();
+    assert!(!game.blocks[0].falling);
+}
+
+#[test]
+fn test_ramp_fills_row() {
+    let config = GameConfig {
+        grid_width: 4,
+        grid_height: 4,
+        cell_size: 30.0,
+        refresh_rate_milliseconds: 200,
+        block_fall_speed: 1,
+        block_spawn_rate: 10,
+        ..Default::default()
+    };
+    let mut game = GameState::new(config).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    game.place_ramp(0, 3, Direction::Right);
+    
+    // Fill the rest of the bottom row with blocks
+    for x in 1..4 {
+        game.blocks.push(Block {
+            position: GridPos::new(x, 3),
+            falling: false,
+            carried: false,
+            carrying_direction: None,
+            ..Default::default()
+        });
+    }
+    game.sync_occupancy();
+    
+    game.check_full_rows();
+    
+    // The row counts as full, blocks are cleared while the ramp stays
+    assert_eq!(game.score, 1);
+    assert!(game.blocks.is_empty());
+    assert_eq!(game.terrain.ramp_at(GridPos::new(0, 3)), Some(Direction::Right));
+}
+
+#[test]
+fn test_multi_cell_block_lands_as_unit() {
+    let config = GameConfig {
+        grid_width: 5,
+        grid_height: 5,
+        cell_size: 30.0,
+        refresh_rate_milliseconds: 200,
+        block_fall_speed: 1,
+        block_spawn_rate: 10,
+        ..Default::default()
+    };
+    let mut game = GameState::new(config).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    game.player.position = GridPos::new(4, 3);
+    
+    // Settled block under the right cell of a horizontal piece
+    game.blocks.push(Block {
+        position: GridPos::new(2, 4),
+        falling: false,
+        ..Default::default()
+    });
+    game.blocks.push(Block::with_shape(GridPos::new(1, 2), Shape::Horizontal));
+    game.sync_occupancy();
+    
+    // The piece stops as soon as one of its cells is supported
+    game.update_falling_blocks();
+    assert_eq!(game.blocks[1].position, (1, 3));
+    game.update_falling_blocks();
+    assert_eq!(game.blocks[1].position, (1, 3));
+    assert!(!game.blocks[1].falling);
+    
+    // The unsupported left cell doesn't make the piece levitate
+    game.check_for_levitating_blocks();
+    assert!(!game.blocks[1].falling);
+}
+
+#[test]
+fn test_row_clear_keeps_rest_of_multi_cell_block() {
+    let config = GameConfig {
+        grid_width: 3,
+        grid_height: 3,
+        cell_size: 30.0,
+        refresh_rate_milliseconds: 200,
+        block_fall_speed: 1,
+        block_spawn_rate: 10,
+        ..Default::default()
+    };
+    let mut game = GameState::new(config).unwrap();
+    game.blocks.clear();
+    
+    // An L covers (0, 1), (0, 2) and (1, 2), one more block completes the bottom row
+    let mut l_block = Block::with_shape(GridPos::new(0, 1), Shape::L);
+    l_block.falling = false;
+    game.blocks.push(l_block);
+    game.blocks.push(Block {
+        position: GridPos::new(2, 2),
+        falling: false,
+        ..Default::default()
+    });
+    game.sync_occupancy();
+    
+    game.check_full_rows();
+    
+    // Only the upright part of the L is left, and it falls as a single block
+    assert_eq!(game.score, 1);
+    assert_eq!(game.blocks.len(), 1);
+    assert_eq!(game.blocks[0].shape, Shape::Single);
+    assert_eq!(game.blocks[0].position, (0, 1));
+    assert!(game.blocks[0].falling);
+}
+
+#[test]
+fn test_ice_block_slides_until_blocked() {
+    let config = GameConfig {
+        grid_width: 6,
+        grid_height: 6,
+        cell_size: 30.0,
+        refresh_rate_milliseconds: 200,
+        block_fall_speed: 1,
+        block_spawn_rate: 10,
+        ..Default::default()
+    };
+    let mut game = GameState::new(config).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    game.player.position = GridPos::new(0, 4);
+    
+    // Ice block sliding right toward a crate at the far end
+    let mut ice = Block::with_kind(GridPos::new(2, 5), BlockKind::Ice);
+    ice.falling = false;
+    ice.sliding = Some(Direction::Right);
+    game.blocks.push(ice);
+    game.blocks.push(Block {
+        position: GridPos::new(5, 5),
+        falling: false,
+        ..Default::default()
+    });
+    game.sync_occupancy();
+    
+    game.update_sliding_blocks();
+    assert_eq!(game.blocks[0].position, (3, 5));
+    game.update_sliding_blocks();
+    assert_eq!(game.blocks[0].position, (4, 5));
+    
+    // The crate stops it
+    game.update_sliding_blocks();
+    assert_eq!(game.blocks[0].position, (4, 5));
+    assert_eq!(game.blocks[0].sliding, None);
+}
+
+#[test]
+fn test_bomb_in_cleared_row_explodes() {
+    let config = GameConfig {
+        grid_width: 4,
+        grid_height: 4,
+        cell_size: 30.0,
+        refresh_rate_milliseconds: 200,
+        block_fall_speed: 1,
+        block_spawn_rate: 10,
+        ..Default::default()
+    };
+    let mut game = GameState::new(config).unwrap();
+    game.blocks.clear();
+    
+    // Full bottom row with a bomb at x = 1
+    for x in 0..4 {
+        let kind = if x == 1 { BlockKind::Bomb } else { BlockKind::Crate };
+        let mut block = Block::with_kind(GridPos::new(x, 3), kind);
+        block.falling = false;
+        game.blocks.push(block);
+    }
+    
+    // Blocks above the row, one inside the blast and one outside
+    let mut near = Block::new(GridPos::new(2, 2));
+    near.falling = false;
+    game.blocks.push(near);
+    let mut steel = Block::with_kind(GridPos::new(0, 2), BlockKind::Steel);
+    steel.falling = false;
+    game.blocks.push(steel);
+    let mut far = Block::new(GridPos::new(3, 2));
+    far.falling = false;
+    game.blocks.push(far);
+    game.sync_occupancy();
+    
+    game.check_full_rows();
+    
+    // The crate next to the bomb is destroyed, steel survives, the far block is untouched
+    assert_eq!(game.score, 1);
+    assert!(!game.blocks.iter().any(|b| b.position == (2, 2)));
+    assert!(game.blocks.iter().any(|b| b.kind == BlockKind::Steel));
+    assert_eq!(game.blocks.len(), 2);
+}
+
+#[test]
+fn test_landing_position_looks_down_to_the_stack() {
+    let mut game = GameState::builder()
+        .grid(6, 8)
+        .falling_block(1, 0)
+        .with_block(Block::with_shape(GridPos::new(3, 1), Shape::Horizontal))
+        .block(1, 7)
+        .block(1, 6)
+        .block(4, 7)
+        .player_at(0, 6)
+        .build()
+        .unwrap();
+    game.block_spawn_rate = 1000;
+    
+    assert_eq!(game.landing_position(0), Some(GridPos::new(1, 5)));
+    // The right half of the horizontal block catches on the crate
+    assert_eq!(game.landing_position(1), Some(GridPos::new(3, 6)));
+    assert_eq!(game.landing_position(2), None);
+    
+    // The ghosts are where the blocks end up
+    for _ in 0..8 {
+        game.update_falling_blocks();
+    }
+    assert_eq!(game.blocks[0].position, (1, 5));
+    assert_eq!(game.blocks[1].position, (3, 6));
+}
+
+#[test]
+fn test_landing_position_stops_above_either_character() {
+    let mut game = GameState::builder()
+        .grid(6, 8)
+        .falling_block(0, 0)
+        .falling_block(2, 0)
+        .player_at(2, 6)
+        .build()
+        .unwrap();
+    game.add_partner();
+    assert_eq!(game.partner.as_ref().unwrap().position, (0, 6));
+    
+    // A block comes down on a character's head, not on the ground below it
+    assert_eq!(game.landing_position(0), Some(GridPos::new(0, 5)));
+    assert_eq!(game.landing_position(1), Some(GridPos::new(2, 5)));
+}
+
+#[test]
+fn test_block_headed_into_a_pit_has_no_landing() {
+    let game = GameState::builder().grid(6, 8).pit(2).falling_block(2, 0).player_at(0, 6).build().unwrap();
+    
+    assert_eq!(game.landing_position(0), None);
+}
+
+// A 6x6 board with a fuse on bombs and no spawns to get in the way
+fn bomb_board(fuse: u64) -> GameStateBuilder {
+    GameState::builder().config(GameConfig {
+        grid_width: 6,
+        grid_height: 6,
+        block_spawn_rate: 1000,
+        bomb_fuse: Some(fuse),
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_landed_bomb_explodes_when_its_fuse_burns_out() {
+    let mut game = bomb_board(2)
+        .with_block(Block::with_kind(GridPos::new(2, 4), BlockKind::Bomb))
+        .block(1, 5)
+        .block(3, 5)
+        .block(5, 5)
+        .kind_block(3, 4, BlockKind::Steel)
+        .player_at(0, 3)
+        .build()
+        .unwrap();
+    let bomb = game.blocks[0].id;
+    
+    let mut landed_at = None;
+    let mut exploded_at = None;
+    for update in 0..6 {
+        game.update_blocks();
+        for event in game.drain_events() {
+            match event {
+                GameEvent::BlockLanded(id, _) if id == bomb => landed_at = Some(update),
+                GameEvent::Exploded(center) => {
+                    assert_eq!(center, GridPos::new(2, 5));
+                    exploded_at.get_or_insert(update);
+                },
+                _ => {},
+            }
+        }
+    }
+    
+    assert_eq!(exploded_at.unwrap() - landed_at.unwrap(), 2);
+    // Everything in the 3x3 blast is gone but the steel, which dropped into the hole below it
+    assert!(game.block(bomb).is_none());
+    let left: Vec<((usize, usize), BlockKind)> = game.blocks.iter().map(|b| (b.position.into(), b.kind)).collect();
+    assert_eq!(left.len(), 2);
+    assert!(left.contains(&((5, 5), BlockKind::Crate)));
+    assert!(left.contains(&((3, 5), BlockKind::Steel)));
+    assert_eq!(game.lives, 3);
+}
+
+#[test]
+fn test_bombs_set_each_other_off() {
+    let mut game = bomb_board(0)
+        .kind_block(1, 5, BlockKind::Bomb)
+        .kind_block(2, 5, BlockKind::Bomb)
+        .block(3, 5)
+        .player_at(5, 4)
+        .build()
+        .unwrap();
+    
+    game.explode(GridPos::new(1, 5));
+    
+    // The second bomb's blast reaches a crate the first one couldn't
+    assert!(game.blocks.is_empty());
+    let blasts = game.drain_events().iter().filter(|event| matches!(event, GameEvent::Exploded(_))).count();
+    assert_eq!(blasts, 2);
+}
+
+#[test]
+fn test_chained_bomb_goes_off_once_whatever_its_size() {
+    let mut game = bomb_board(0)
+        .kind_block(1, 5, BlockKind::Bomb)
+        .with_block(Block { position: GridPos::new(2, 5), kind: BlockKind::Bomb, shape: Shape::Horizontal, falling: false, ..Default::default() })
+        .player_at(5, 4)
+        .build()
+        .unwrap();
+    
+    game.explode(GridPos::new(1, 5));
+    
+    let blasts: Vec<GameEvent> = game.drain_events().into_iter().filter(|event| matches!(event, GameEvent::Exploded(_))).collect();
+    assert_eq!(blasts, vec![GameEvent::Exploded(GridPos::new(1, 5)), GameEvent::Exploded(GridPos::new(2, 5))]);
+}
+
+#[test]
+fn test_cleared_bomb_goes_off_once_whatever_its_size() {
+    let mut game = bomb_board(0)
+        .block(0, 5)
+        .with_block(Block { position: GridPos::new(1, 5), kind: BlockKind::Bomb, shape: Shape::Horizontal, falling: false, ..Default::default() })
+        .block(3, 5)
+        .block(4, 5)
+        .block(5, 5)
+        .player_at(5, 4)
+        .build()
+        .unwrap();
+    
+    game.check_full_rows();
+    
+    let blasts: Vec<GameEvent> = game.drain_events().into_iter().filter(|event| matches!(event, GameEvent::Exploded(_))).collect();
+    assert_eq!(blasts, vec![GameEvent::Exploded(GridPos::new(1, 5))]);
+    assert_eq!(game.lives, 3);
+}
+
+#[test]
+fn test_blast_catching_the_idle_character_costs_a_life() {
+    let mut game = GameState::builder()
+        .config(GameConfig { grid_width: 6, grid_height: 6, block_spawn_rate: 1000, two_characters: true, ..Default::default() })
+        .kind_block(1, 5, BlockKind::Bomb)
+        .player_at(5, 4)
+        .build()
+        .unwrap();
+    assert_eq!(game.partner.as_ref().unwrap().position, (0, 4));
+    
+    game.explode(GridPos::new(1, 5));
+    
+    assert_eq!(game.lives, 2);
+}
+
+#[test]
+fn test_blast_costs_the_player_a_life() {
+    let mut game = bomb_board(0).lives(1).kind_block(2, 5, BlockKind::Bomb).player_at(3, 4).build().unwrap();
+    
+    game.explode(GridPos::new(2, 5));
+    
+    assert_eq!(game.lives, 0);
+    assert_eq!(game.game_over_reason, Some(GameOverReason::Blown));
+}
+
+#[test]
+fn test_bombs_stay_unlit_without_a_fuse() {
+    let mut game = GameState::builder()
+        .grid(6, 6)
+        .with_block(Block::with_kind(GridPos::new(2, 4), BlockKind::Bomb))
+        .player_at(0, 4)
+        .build()
+        .unwrap();
+    
+    for _ in 0..5 {
+        game.update_falling_blocks();
+    }
+    
+    assert!(!game.blocks[0].falling);
+    assert_eq!(game.blocks[0].fuse, None);
+}
+
+fn two_character_game() -> GameState {
+    let config = GameConfig {
+        grid_width: 5,
+        grid_height: 5,
+        two_characters: true,
+        ..Default::default()
+    };
+    let mut game = GameState::new(config).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    game
+}
+
+#[test]
+fn test_switch_character() {
+    let mut game = two_character_game();
+    let player_start = game.player.position;
+    let partner_start = game.partner.as_ref().unwrap().position;
+    assert_eq!(partner_start, (0, 3));
+
+    game.process_input(InputAction::SwitchCharacter);
+    assert_eq!(game.player.position, partner_start);
+
+    // Only the character under control moves
+    game.process_input(InputAction::Right);
+    assert_eq!(game.player.position, (1, 3));
+    assert_eq!(game.partner.as_ref().unwrap().position, player_start);
+
+    // Without a partner switching does nothing
+    let mut single = GameState::new(GameConfig {
+        grid_width: 5,
+        grid_height: 5,
+        ..Default::default()
+    }).unwrap();
+    let position = single.player.position;
+    single.switch_character();
+    assert_eq!(single.player.position, position);
+}
+
+#[test]
+fn test_idle_character_is_an_obstacle() {
+    let mut game = two_character_game();
+    game.player.position = GridPos::new(1, 3);
+    game.sync_character_terrain();
+
+    // The idle character can't be walked through
+    game.process_input(InputAction::Left);
+    assert_eq!(game.player.position, (1, 3));
+
+    // Blocks land on its head instead of crushing it
+    game.blocks.push(Block {
+        position: GridPos::new(0, 2),
+        falling: true,
+        ..Default::default()
+    });
+    game.sync_occupancy();
+    game.update_falling_blocks();
+    assert!(!game.game_over);
+    assert!(!game.blocks[0].falling);
+    assert_eq!(game.blocks[0].position, (0, 2));
+    assert!(game.block_has_support(0));
+}
+
+#[test]
+fn test_stack_reaching_danger_line_ends_game() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 5,
+        grid_height: 5,
+        danger_line: 1,
+        ..Default::default()
+    }).unwrap();
+    game.blocks.clear();
+
+    // Falling blocks passing through the danger zone don't count
+    game.blocks.push(Block::new(GridPos::new(0, 0)));
+    game.sync_occupancy();
+    game.check_stack_height();
+    assert!(!game.game_over);
+
+    game.blocks.push(Block {
+        position: GridPos::new(4, 1),
+        falling: false,
+        ..Default::default()
+    });
+    game.sync_occupancy();
+    game.check_stack_height();
+    assert!(game.game_over);
+    assert_eq!(game.game_over_reason, Some(GameOverReason::StackTooHigh));
+
+    game.restart();
+    assert_eq!(game.game_over_reason, None);
+}
+
+#[test]
+fn test_crushed_game_over_reason() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 5,
+        grid_height: 5,
+        lives: 1,
+        ..Default::default()
+    }).unwrap();
+    let GridPos { x, y } = game.player.position;
+
+    game.check_block_player_collision(x, y);
+    assert_eq!(game.game_over_reason, Some(GameOverReason::Crushed));
+}
+
+#[test]
+fn test_simulate_leaves_live_game_untouched() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 5,
+        grid_height: 5,
+        block_spawn_rate: 100,
+        ..Default::default()
+    }).unwrap();
+    game.blocks.clear();
+    game.blocks.push(Block::new(GridPos::new(0, 0)));
+    game.sync_occupancy();
+    let GridPos { x, y } = game.player.position;
+
+    let preview = game.simulate(&[InputAction::Right, InputAction::Right], 3);
+
+    // Both moves were applied despite the hold throttle, and the block fell three rows
+    assert_eq!(preview.player.position, (x + 2, y));
+    assert_eq!(preview.blocks[0].position, (0, 3));
+
+    assert_eq!(game.player.position, (x, y));
+    assert_eq!(game.blocks[0].position, (0, 0));
+}
+
+fn fill_row(game: &mut GameState, row: usize) {
+    for x in 0..game.grid_width {
+        game.blocks.push(Block {
+            position: GridPos::new(x, row),
+            falling: false,
+            ..Default::default()
+        });
+    }
+    game.sync_occupancy();
+}
+
+#[test]
+fn test_multiple_rows_cleared_in_one_pass() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 4,
+        grid_height: 4,
+        ..Default::default()
+    }).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    fill_row(&mut game, 3);
+    fill_row(&mut game, 2);
+
+    game.check_full_rows();
+
+    assert!(game.blocks.is_empty());
+    assert_eq!(game.score, 3);
+    assert_eq!(game.drain_events(), vec![GameEvent::RowCleared(2), GameEvent::RowCleared(3), GameEvent::RowsCleared(ScoreBreakdown {
+        rows: 2,
+        base_points: 3,
+        chain: 1,
+        touch_bonus: 0,
+        points: 3,
+    })]);
+    assert!(game.events.is_empty());
+}
+
+#[test]
+fn test_moved_blocks_score_a_bonus_when_cleared() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 4,
+        grid_height: 4,
+        touch_bonus: 2,
+        ..Default::default()
+    }).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    fill_row(&mut game, 3);
+    game.blocks[1].touched = true;
+    game.blocks[2].touched = true;
+    // A moved block outside the cleared row earns nothing
+    game.blocks.push(Block { position: GridPos::new(0, 2), falling: false, touched: true, ..Default::default() });
+    game.sync_occupancy();
+
+    game.check_full_rows();
+
+    assert_eq!(game.score, 1 + 4);
+    assert_eq!(game.drain_events(), vec![GameEvent::RowCleared(3), GameEvent::RowsCleared(ScoreBreakdown {
+        rows: 1,
+        base_points: 1,
+        chain: 1,
+        touch_bonus: 4,
+        points: 5,
+    })]);
+}
+
+#[test]
+fn test_pushed_and_kicked_blocks_count_as_moved() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 6,
+        grid_height: 4,
+        ..Default::default()
+    }).unwrap();
+    game.blocks = vec![
+        Block { position: GridPos::new(3, 3), falling: false, ..Default::default() },
+        Block { position: GridPos::new(0, 3), falling: false, ..Default::default() },
+    ];
+    game.sync_occupancy();
+    game.player.position = GridPos::new(2, 2);
+
+    game.process_input(InputAction::Right);
+    assert_eq!(game.blocks[0].position, (4, 3));
+    assert!(game.blocks[0].touched);
+    assert!(!game.blocks[1].touched);
+
+    game.player.position = GridPos::new(1, 2);
+    game.player.facing = Direction::Left;
+    assert!(game.kick());
+    assert!(game.blocks[1].touched);
+}
+
+#[test]
+fn test_chain_resets_after_window() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 4,
+        grid_height: 4,
+        ..Default::default()
+    }).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    fill_row(&mut game, 3);
+    game.check_full_rows();
+    assert_eq!(game.chain, 1);
+
+    game.ticks_since_clear = CHAIN_WINDOW_TICKS + 1;
+    fill_row(&mut game, 3);
+    game.check_full_rows();
+    assert_eq!(game.chain, 1);
+    assert_eq!(game.score, 2);
+}
+
+#[test]
+fn test_non_square_grid() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 8,
+        grid_height: 4,
+        ..Default::default()
+    }).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+
+    // Player starts at the bottom middle of the wide arena
+    assert_eq!(game.player.position, (3, 2));
+
+    // Blocks land on the bottom of the shallow grid
+    game.blocks.push(Block::new(GridPos::new(7, 0)));
+    game.sync_occupancy();
+    for _ in 0..5 {
+        game.update_falling_blocks();
+    }
+    assert_eq!(game.blocks[0].position, (7, 3));
+    assert!(!game.blocks[0].falling);
+
+    // A row needs the full width to clear
+    game.blocks.clear();
+    game.sync_occupancy();
+    fill_row(&mut game, 3);
+    game.check_full_rows();
+    assert!(game.blocks.is_empty());
+}
+
+#[test]
+fn test_falling_blocks_stack_instead_of_overlapping() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 5,
+        grid_height: 6,
+        ..Default::default()
+    }).unwrap();
+    game.blocks.clear();
+    
+    // The upper block comes first, so it would reach the lower one's cell before it moves away
+    game.blocks.push(Block::new(GridPos::new(0, 0)));
+    game.blocks.push(Block::new(GridPos::new(0, 1)));
+    game.blocks.push(Block::new(GridPos::new(0, 2)));
+    game.sync_occupancy();
+    
+    for _ in 0..6 {
+        game.update_falling_blocks();
+    }
+    
+    let mut positions: Vec<_> = game.blocks.iter().map(|block| block.position).collect();
+    positions.sort();
+    assert_eq!(positions, vec![(0, 3), (0, 4), (0, 5)]);
+    assert!(game.blocks.iter().all(|block| !block.falling));
+}
+
+#[test]
+fn test_fast_blocks_dont_tunnel() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 5,
+        grid_height: 8,
+        block_fall_speed: 3,
+        lives: 1,
+        ..Default::default()
+    }).unwrap();
+    game.blocks.clear();
+    
+    // A settled block two cells below a fast block stops it on the way
+    game.blocks.push(Block {
+        position: GridPos::new(0, 3),
+        falling: false,
+        ..Default::default()
+    });
+    game.blocks.push(Block::new(GridPos::new(0, 1)));
+    game.sync_occupancy();
+    game.update_falling_blocks();
+    assert_eq!(game.blocks[1].position, (0, 2));
+    assert!(!game.blocks[1].falling);
+    
+    // A player standing on a block is in the path even though three cells would jump past them
+    game.blocks.push(Block {
+        position: GridPos::new(3, 5),
+        falling: false,
+        ..Default::default()
+    });
+    game.sync_occupancy();
+    game.player.position = GridPos::new(3, 3);
+    game.blocks.push(Block::new(GridPos::new(3, 2)));
+    game.sync_occupancy();
+    game.update_falling_blocks();
+    assert!(game.game_over);
+}
+
+#[test]
+fn test_spawn_skips_blocked_columns() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 4,
+        grid_height: 6,
+        seed: Some(7),
+        ..Default::default()
+    }).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    game.player.position = GridPos::new(3, 1);
+    
+    // Columns 0 and 1 are taken at the spawn row, the player is right under the spawn row in column 3
+    for x in 0..2 {
+        game.blocks.push(Block {
+            position: GridPos::new(x, 0),
+            falling: false,
+            ..Default::default()
+        });
+    }
+    game.sync_occupancy();
+    
+    for _ in 0..20 {
+        game.spawn_block();
+        let block = game.blocks.pop().unwrap();
+        game.sync_occupancy();
+        assert!(block.cells().all(|GridPos { x, y: _ }| x == 2));
+    }
+    assert!(!game.game_over);
+}
+
+#[test]
+fn test_spawn_without_room_ends_game() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 3,
+        grid_height: 4,
+        ..Default::default()
+    }).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    fill_row(&mut game, 0);
+    
+    game.spawn_block();
+    assert!(game.game_over);
+    assert_eq!(game.game_over_reason, Some(GameOverReason::StackTooHigh));
+    
+    // Without death the spawn just waits for room
+    game.restart();
+    game.mode = GameMode::Zen;
+    game.blocks.clear();
+    game.sync_occupancy();
+    fill_row(&mut game, 0);
+    game.spawn_block();
+    assert!(!game.game_over);
+    assert_eq!(game.blocks.len(), 3);
+}
+
+#[test]
+fn test_spawn_favors_low_columns() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 4,
+        grid_height: 12,
+        seed: Some(3),
+        favor_low_columns: true,
+        ..Default::default()
+    }).unwrap();
+    game.player.position = GridPos::new(3, 10);
+    
+    // Column 0 has a single free cell, column 2 is empty
+    game.blocks = (1..12).map(|y| Block {
+        position: GridPos::new(0, y),
+        falling: false,
+        ..Default::default()
+    }).collect();
+    game.sync_occupancy();
+    
+    let mut lands_in_shallow_column = 0;
+    for _ in 0..200 {
+        game.spawn_block();
+        let block = game.blocks.pop().unwrap();
+        game.sync_occupancy();
+        if block.position.x == 0 {
+            lands_in_shallow_column += 1;
+        }
+    }
+    assert!(lands_in_shallow_column < 30);
+}
+
+#[test]
+fn test_next_blocks_spawn_in_order() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 8,
+        grid_height: 8,
+        seed: Some(11),
+        ..Default::default()
+    }).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    assert_eq!(game.next_blocks.len(), NEXT_BLOCKS);
+    
+    // On an empty board every queued block drops exactly where it was announced
+    let upcoming: Vec<_> = game.next_blocks.iter().map(|block| (block.position, block.shape, block.kind)).collect();
+    for expected in upcoming {
+        game.spawn_block();
+        let block = game.blocks.pop().unwrap();
+        game.sync_occupancy();
+        assert_eq!((block.position, block.shape, block.kind), expected);
+        assert_eq!(game.next_blocks.len(), NEXT_BLOCKS);
+    }
+}
+
+#[test]
+fn test_spawn_warning_counts_down_to_the_telegraphed_column() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 8,
+        grid_height: 8,
+        block_spawn_rate: 10,
+        seed: Some(5),
+        ..Default::default()
+    }).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    
+    // Quiet until the spawn gets close
+    for _ in 0..10 - SPAWN_WARNING_TICKS - 1 {
+        game.handle_block_spawning();
+        assert_eq!(game.spawn_warning, None);
+    }
+    
+    let mut countdown = Vec::new();
+    let mut column = None;
+    while game.blocks.is_empty() {
+        game.handle_block_spawning();
+        if let Some(warning) = game.spawn_warning.filter(|_| game.blocks.is_empty()) {
+            countdown.push(warning.ticks_left);
+            column = Some(warning.column);
+        }
+    }
+    assert_eq!(countdown, vec![3, 2, 1]);
+    assert_eq!(Some(game.blocks[0].position.x), column);
+}
+
+#[test]
+fn test_head_carrying_catches_light_blocks() {
+    let config = GameConfig {
+        grid_width: 5,
+        grid_height: 5,
+        head_carrying: true,
+        ..Default::default()
+    };
+    let mut game = GameState::new(config.clone()).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    let GridPos { x, y } = game.player.position;
+    game.blocks.push(Block::new(GridPos::new(x, y - 1)));
+    game.sync_occupancy();
+    
+    game.update_falling_blocks();
+    assert!(game.blocks.is_empty());
+    let caught = game.player.carrying.as_ref().expect("block on the player's head").id;
+    assert!(caught.is_assigned());
+    assert_eq!(game.lives, config.lives);
+    assert!(game.drain_events().contains(&GameEvent::BlockCaught(caught)));
+    
+    // Anything landing on the carried block still crushes the player
+    game.blocks.push(Block::new(GridPos::new(x, y - 3)));
+    game.sync_occupancy();
+    game.update_falling_blocks();
+    game.update_falling_blocks();
+    assert_eq!(game.lives, config.lives - 1);
+    
+    // Steel is too heavy to catch
+    let mut game = GameState::new(config.clone()).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    let GridPos { x, y } = game.player.position;
+    game.blocks.push(Block::with_kind(GridPos::new(x, y - 1), BlockKind::Steel));
+    game.sync_occupancy();
+    game.update_falling_blocks();
+    assert!(game.player.carrying.is_none());
+    assert_eq!(game.lives, config.lives - 1);
+}
+
+#[test]
+fn test_other_blocks_keep_falling_after_one_is_caught_or_hits() {
+    let config = GameConfig {
+        grid_width: 5,
+        grid_height: 5,
+        head_carrying: true,
+        ..Default::default()
+    };
+    for kind in [BlockKind::Crate, BlockKind::Steel] {
+        let mut game = GameState::new(config.clone()).unwrap();
+        game.blocks.clear();
+        let GridPos { x, y } = game.player.position;
+        // The block over the player is lower and falls first, then leaves the list of blocks
+        game.blocks.push(Block::new(GridPos::new(x + 2, 0)));
+        game.blocks.push(Block::with_kind(GridPos::new(x, y - 1), kind));
+        game.blocks.push(Block::new(GridPos::new(x + 1, 0)));
+        game.sync_occupancy();
+
+        game.update_falling_blocks();
+        let positions: Vec<GridPos> = game.blocks.iter().map(|block| block.position).collect();
+        assert_eq!(positions, vec![GridPos::new(x + 2, 1), GridPos::new(x + 1, 1)]);
+    }
+}
+
+#[test]
+fn test_drop_puts_the_carried_block_down() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 5,
+        grid_height: 5,
+        block_spawn_rate: 100,
+        head_carrying: true,
+        ..Default::default()
+    }).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    let GridPos { x, y } = game.player.position;
+    game.player.catch(Block::new(GridPos::new(x, 0)));
+    
+    game.process_input(InputAction::Drop);
+    assert!(game.player.carrying.is_none());
+    assert_eq!(game.blocks[0].position, (x + 1, y - 1));
+    for _ in 0..3 {
+        game.update_falling_blocks();
+    }
+    assert_eq!(game.blocks[0].position, (x + 1, 4));
+}
+
+#[test]
+fn test_kicked_block_slides_until_it_hits_something() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 8,
+        grid_height: 5,
+        block_spawn_rate: 100,
+        ..Default::default()
+    }).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    let GridPos { x, y } = game.player.position;
+    let feet = y + 1;
+    game.blocks.push(Block { position: GridPos::new(x + 1, feet), falling: false, ..Default::default() });
+    game.blocks.push(Block { position: GridPos::new(7, feet), falling: false, ..Default::default() });
+    game.sync_occupancy();
+    
+    game.process_input(InputAction::Kick);
+    assert_eq!(game.blocks[0].sliding, Some(Direction::Right));
+    for _ in 0..8 {
+        game.update_sliding_blocks();
+    }
+    assert_eq!(game.blocks[0].position, (6, feet));
+    assert_eq!(game.blocks[0].sliding, None);
+    
+    // Steel doesn't budge
+    game.blocks.push(Block { position: GridPos::new(x + 1, feet), falling: false, kind: BlockKind::Steel, ..Default::default() });
+    game.sync_occupancy();
+    assert!(!game.kick());
+}
+
+#[test]
+fn test_jump_height_from_config() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 8,
+        grid_height: 8,
+        block_spawn_rate: 100,
+        jump_height: 2,
+        ..Default::default()
+    }).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    let GridPos { x, y } = game.player.position;
+    
+    game.process_input(InputAction::Up);
+    game.update_player();
+    game.update_player();
+    assert_eq!(game.player.position, (x, y - 2));
+}
+
+#[test]
+fn test_levitation_check_in_chunks() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 5,
+        grid_height: 5,
+        ..Default::default()
+    }).unwrap();
+    game.blocks.clear();
+    for x in [0, 2, 4] {
+        game.blocks.push(Block { position: GridPos::new(x, 1), falling: false, ..Default::default() });
+    }
+    game.sync_occupancy();
+    game.levitation_chunk = Some(2);
+    
+    game.check_for_levitating_blocks();
+    assert_eq!(game.blocks.iter().filter(|block| block.falling).count(), 2);
+    game.check_for_levitating_blocks();
+    assert!(game.blocks.iter().all(|block| block.falling));
+}
+
+fn pit_game(pit: usize) -> GameState {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 6,
+        grid_height: 5,
+        block_spawn_rate: 100,
+        pits: vec![pit],
+        ..Default::default()
+    }).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    game.player.position = GridPos::new(3, 3);
+    game.sync_occupancy();
+    game
+}
+
+#[test]
+fn test_block_pushed_into_pit_is_destroyed() {
+    let mut game = pit_game(5);
+    game.blocks.push(Block { position: GridPos::new(4, 4), falling: false, ..Default::default() });
+    game.sync_occupancy();
+    
+    game.process_input(InputAction::Right);
+    assert_eq!(game.blocks[0].position, (5, 4));
+    for _ in 0..3 {
+        game.check_for_levitating_blocks();
+        game.update_falling_blocks();
+    }
+    assert!(game.blocks.is_empty());
+    assert_eq!(game.score, PIT_BONUS);
+    
+    // Blocks never spawn straight into a pit
+    let block = Block::new(GridPos::new(0, 0));
+    assert!(game.block_over_pit(&block, 5));
+    assert!(!game.block_over_pit(&Block::with_shape(GridPos::new(0, 0), Shape::Horizontal), 4));
+}
+
+#[test]
+fn test_player_falling_into_pit_loses_life() {
+    let mut game = pit_game(4);
+    game.process_input(InputAction::Right);
+    assert_eq!(game.player.position, (4, 3));
+    for _ in 0..10 {
+        game.update_player();
+    }
+    assert_eq!(game.lives, 2);
+    assert_ne!(game.player.position.x, 4, "Nobody respawns over a pit");
+    
+    let mut last_life = pit_game(4);
+    last_life.lives = 1;
+    last_life.process_input(InputAction::Right);
+    for _ in 0..10 {
+        last_life.update_player();
+    }
+    assert_eq!(last_life.game_over_reason, Some(GameOverReason::Fell));
+}
+
+#[test]
+fn test_block_landing_where_player_just_stood_is_a_close_call() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 6,
+        grid_height: 6,
+        block_spawn_rate: 100,
+        ..Default::default()
+    }).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    game.player.position = GridPos::new(2, 4);
+    game.player.remember_position();
+    game.drain_events();
+
+    game.process_input(InputAction::Right);
+    assert_eq!(game.player.position, (3, 4));
+    game.blocks.push(Block::new(GridPos::new(2, 5)));
+    game.sync_occupancy();
+    game.tick();
+    assert_eq!(game.score, CLOSE_CALL_BONUS);
+    assert_eq!(game.stats.close_calls, 1);
+    assert!(game.drain_events().contains(&GameEvent::CloseCall(GridPos::new(2, 5))));
+
+    // A moment later the cell no longer counts as just vacated
+    game.blocks.push(Block::new(GridPos::new(2, 3)));
+    game.sync_occupancy();
+    for _ in 0..3 {
+        game.tick();
+    }
+    assert_eq!(game.stats.close_calls, 1);
+}
+
+#[test]
+fn test_restart_with_same_seed_replays_spawns() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 8,
+        grid_height: 8,
+        block_spawn_rate: 2,
+        seed: Some(21),
+        ..Default::default()
+    }).unwrap();
+    let spawns = |game: &mut GameState| {
+        for _ in 0..12 {
+            game.tick();
+        }
+        game.blocks.iter().map(|block| (block.position, block.shape)).collect::<Vec<_>>()
+    };
+    let first = spawns(&mut game);
+
+    game.process_input(InputAction::RestartSameSeed);
+    assert_eq!(game.seed, 21);
+    assert_eq!(game.score, 0);
+    assert_eq!(spawns(&mut game), first);
+
+    // A plain restart moves on to another seed
+    game.process_input(InputAction::Restart);
+    assert_ne!(game.seed, 21);
+}
+
+#[test]
+fn test_block_leaving_a_row_one_short_is_announced() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 5,
+        grid_height: 5,
+        block_spawn_rate: 100,
+        ..Default::default()
+    }).unwrap();
+    game.blocks.clear();
+    for x in [0, 1, 3] {
+        game.blocks.push(Block { falling: false, ..Block::new(GridPos::new(x, 4)) });
+    }
+    game.blocks.push(Block::new(GridPos::new(4, 3)));
+    game.sync_occupancy();
+    game.drain_events();
+
+    // The gap is under the player, the last block leaves only that cell once it lands
+    game.update_falling_blocks();
+    game.update_falling_blocks();
+    assert!(game.drain_events().contains(&GameEvent::RowAlmostComplete(4)));
+}
+
+#[test]
+fn test_time_attack_ends_when_the_clock_runs_out() {
+    let mut game = GameState::new(GameConfig {
+        refresh_rate_milliseconds: 1000,
+        block_spawn_rate: 1000,
+        mode: GameMode::TimeAttack,
+        ..Default::default()
+    }).unwrap();
+    assert_eq!(game.time_left(), Some(TIME_ATTACK_DURATION));
+
+    let ticks = TIME_ATTACK_DURATION.as_secs();
+    for _ in 0..ticks - 1 {
+        game.tick();
+    }
+    assert!(!game.game_over);
+    game.tick();
+    assert_eq!(game.game_over_reason, Some(GameOverReason::TimeUp));
+
+    game.restart();
+    assert_eq!(game.time_left(), Some(TIME_ATTACK_DURATION));
+    assert_eq!(GameState::new(GameConfig::default()).unwrap().time_left(), None);
+}
+
+#[test]
+fn test_settle_drops_every_hanging_group_at_once() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 6,
+        grid_height: 6,
+        block_spawn_rate: 100,
+        ..Default::default()
+    }).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    game.player.position = GridPos::new(5, 4);
+    let settled = |position, shape| Block { falling: false, ..Block::with_shape(position, shape) };
+    // A tower floating over column 0, and an L with a crate on its foot over columns 2 and 3
+    game.blocks.push(settled(GridPos::new(0, 1), Shape::Single));
+    game.blocks.push(settled(GridPos::new(0, 2), Shape::Vertical));
+    game.blocks.push(settled(GridPos::new(3, 0), Shape::Single));
+    game.blocks.push(settled(GridPos::new(2, 1), Shape::L));
+    // Hanging over the player
+    game.blocks.push(settled(GridPos::new(5, 1), Shape::Single));
+    game.sync_occupancy();
+
+    game.settle();
+    let positions: Vec<_> = game.blocks.iter().map(|block| block.position).collect();
+    assert_eq!(positions, vec![(0, 3), (0, 4), (3, 4), (2, 4), (5, 3)]);
+    assert!(game.blocks[..4].iter().all(|block| !block.falling));
+
+    // The block above the player comes down on the next update like any falling block
+    assert!(game.blocks[4].falling);
+    game.update_falling_blocks();
+    assert_eq!(game.lives, 2);
+}
+
+fn idle_zen_game(mode: GameMode) -> GameState {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 6,
+        grid_height: 6,
+        block_spawn_rate: 1000,
+        idle_despawn: Some(3),
+        mode,
+        ..GameConfig::zen()
+    }).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    game.player.position.x = 4;
+    // A column stacked into the upper half and a lone block
+    for y in 2..6 {
+        game.blocks.push(Block { position: GridPos::new(0, y), falling: false, ..Default::default() });
+    }
+    game.blocks.push(Block { position: GridPos::new(2, 5), falling: false, ..Default::default() });
+    game.sync_occupancy();
+    game
+}
+
+#[test]
+fn test_idle_blocks_are_cleared_off_full_columns_in_zen() {
+    let mut game = idle_zen_game(GameMode::Zen);
+    game.tick();
+    game.tick();
+    assert_eq!(game.blocks.len(), 5);
+    game.drain_events();
+
+    game.tick();
+    let positions: Vec<_> = game.blocks.iter().map(|block| block.position).collect();
+    assert_eq!(positions, vec![(2, 5)]);
+    let despawned: Vec<_> = game.drain_events().into_iter()
+        .filter_map(|event| match event {
+            GameEvent::BlocksDespawned(cells) => Some(cells),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(despawned, vec![vec![(0, 2), (0, 3), (0, 4), (0, 5)]]);
+}
+
+#[test]
+fn test_idle_despawn_only_applies_in_zen() {
+    let mut game = idle_zen_game(GameMode::Endless);
+    for _ in 0..5 {
+        game.tick();
+    }
+    assert_eq!(game.blocks.len(), 5);
+    assert!(game.blocks.iter().all(|block| block.idle_ticks == 5));
+}
+
+#[test]
+fn test_default_config_is_valid() {
+    assert_eq!(GameConfig::default().validate(), Ok(()));
+}
+
+#[test]
+fn test_invalid_configs_are_rejected_with_the_reason() {
+    let invalid = |config: GameConfig| GameState::new(config).err().expect("config should be rejected");
+
+    assert_eq!(invalid(GameConfig { grid_width: 1, ..Default::default() }), ConfigError::GridTooSmall { width: 1, height: 16 });
+    assert_eq!(invalid(GameConfig { block_spawn_rate: 0, ..Default::default() }), ConfigError::SpawnRate);
+    assert_eq!(invalid(GameConfig { lives: 0, ..Default::default() }), ConfigError::NoLives);
+    assert_eq!(
+        invalid(GameConfig { grid_height: 6, block_fall_speed: 7, ..Default::default() }),
+        ConfigError::FallSpeed { speed: 7, grid_height: 6 }
+    );
+    assert_eq!(
+        invalid(GameConfig { grid_height: 6, danger_line: 5, ..Default::default() }),
+        ConfigError::DangerLine { line: 5, grid_height: 6 }
+    );
+    assert_eq!(invalid(GameConfig { mode: GameMode::Campaign(Vec::new()), ..Default::default() }), ConfigError::EmptyCampaign);
+}
+
+#[test]
+fn test_config_error_messages() {
+    let error = ConfigError::FallSpeed { speed: 12, grid_height: 10 };
+    assert_eq!(error.to_string(), "block fall speed must be from 1 to the grid height 10, got 12");
+    assert_eq!(ConfigError::GridTooSmall { width: 1, height: 2 }.to_string(), "grid must be at least 2x3, got 1x2");
 }
@@ -0,0 +1,107 @@
+use rust_stackattack::core::block::{Block, Shape};
+use rust_stackattack::core::game::GameState;
+use rust_stackattack::core::grid::OccupancyGrid;
+use rust_stackattack::core::types::{GameConfig, GridPos};
+
+#[test]
+fn test_grid_indexes_every_cell_of_a_block() {
+    let blocks = vec![Block::new(GridPos::new(0, 0)), Block::with_shape(GridPos::new(2, 1), Shape::L)];
+    let mut grid = OccupancyGrid::new(4, 4);
+    grid.rebuild(&blocks);
+
+    assert_eq!(grid.blocks_at(GridPos::new(0, 0)), &[0]);
+    for cell in [GridPos::new(2, 1), GridPos::new(2, 2), GridPos::new(3, 2)] {
+        assert_eq!(grid.blocks_at(cell), &[1]);
+    }
+    assert!(!grid.is_occupied(GridPos::new(3, 1)));
+    assert!(!grid.is_occupied(GridPos::new(9, 9)));
+
+    grid.remove(1, &blocks[1]);
+    assert!(!grid.is_occupied(GridPos::new(3, 2)));
+}
+
+#[test]
+fn test_game_keeps_grid_in_step_with_falling_blocks() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 5,
+        grid_height: 5,
+        ..Default::default()
+    }).unwrap();
+    game.blocks.clear();
+    game.blocks.push(Block::new(GridPos::new(0, 0)));
+    game.sync_occupancy();
+
+    game.update_falling_blocks();
+    assert!(!game.occupancy.is_occupied(GridPos::new(0, 0)));
+    assert_eq!(game.occupancy.blocks_at(game.blocks[0].position), &[0]);
+}
+
+#[test]
+fn test_rows_clear_below_the_width_on_tall_grids() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 4,
+        grid_height: 8,
+        ..Default::default()
+    }).unwrap();
+    game.blocks.clear();
+    for x in 0..4 {
+        game.blocks.push(Block {
+            position: GridPos::new(x, 7),
+            falling: false,
+            ..Default::default()
+        });
+    }
+    game.sync_occupancy();
+
+    game.check_full_rows();
+    assert!(game.blocks.is_empty());
+}
+
+#[test]
+fn test_row_fill_follows_inserts_and_removes() {
+    let blocks = vec![Block::with_shape(GridPos::new(0, 3), Shape::Horizontal), Block::new(GridPos::new(0, 3)), Block::new(GridPos::new(3, 2))];
+    let mut grid = OccupancyGrid::new(4, 4);
+    grid.rebuild(&blocks);
+    assert_eq!(grid.row_fill(3), 2);
+    assert_eq!(grid.row_fill(2), 1);
+
+    // A cell still covered by another block keeps counting
+    grid.remove(1, &blocks[1]);
+    assert_eq!(grid.row_fill(3), 2);
+    grid.remove(0, &blocks[0]);
+    assert_eq!(grid.row_fill(3), 0);
+    assert_eq!(grid.row_fill(9), 0);
+}
+
+#[test]
+fn test_stack_tops_follow_landing_and_clearing() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 3,
+        grid_height: 4,
+        ..Default::default()
+    }).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    game.player.position = GridPos::new(2, 2);
+    game.blocks.push(Block::new(GridPos::new(0, 1)));
+    game.sync_occupancy();
+    assert_eq!(game.stack_top(0), 4, "Falling blocks aren't part of the stack");
+
+    while game.blocks[0].falling {
+        game.update_falling_blocks();
+    }
+    assert_eq!(game.stack_top(0), 3);
+    assert_eq!(game.highest_stack_top(), 3);
+
+    game.player.position = GridPos::new(2, 0);
+    for x in [1, 2] {
+        game.blocks.push(Block {
+            position: GridPos::new(x, 3),
+            falling: false,
+            ..Default::default()
+        });
+    }
+    game.sync_occupancy();
+    game.check_full_rows();
+    assert_eq!(game.highest_stack_top(), 4);
+}
@@ -0,0 +1,104 @@
+use rust_stackattack::core::game::{GameState, CHAIN_WINDOW_TICKS};
+use rust_stackattack::core::powerup::PowerUpKind;
+use rust_stackattack::core::types::{GameConfig, GameMode};
+use rust_stackattack::platform::hud::{Hud, HudItem, MIN_HUD_SCALE};
+use rust_stackattack::platform::render::{Layout, WARNING_COLOR};
+
+fn game(width: usize) -> GameState {
+    GameState::builder().grid(width, 10).build().unwrap()
+}
+
+#[test]
+fn test_wide_bar_shows_everything_left_of_the_restart_button() {
+    let mut game = game(24);
+    game.activate_powerup(PowerUpKind::Magnet);
+    game.chain = 1;
+    game.ticks_since_clear = CHAIN_WINDOW_TICKS / 2;
+    let layout = Layout::new(&game);
+
+    let hud = Hud::new(&game, &layout);
+
+    let items: Vec<HudItem> = hud.entries.iter().map(|entry| entry.item).collect();
+    assert_eq!(items, HudItem::ALL.to_vec());
+    assert_eq!(hud.scale, 1.0);
+    for pair in hud.entries.windows(2) {
+        assert!(pair[0].area.x + pair[0].area.w < pair[1].area.x);
+    }
+    let last = hud.entries.last().unwrap().area;
+    assert!(last.x + last.w < layout.restart_button().x);
+
+    let chain = hud.entry(HudItem::Chain).unwrap();
+    assert_eq!(chain.text, "Chain x2");
+    assert_eq!(chain.meter, Some(0.5));
+    assert_eq!(hud.entry(HudItem::PowerUp).unwrap().meter, Some(1.0));
+}
+
+#[test]
+fn test_narrow_bar_shrinks_text_then_wraps_onto_another_row() {
+    let mut game = game(10);
+    game.activate_powerup(PowerUpKind::Freeze);
+    let layout = Layout::new(&game);
+
+    let hud = Hud::new(&game, &layout);
+
+    assert_eq!(hud.scale, MIN_HUD_SCALE);
+    let items: Vec<HudItem> = hud.entries.iter().map(|entry| entry.item).collect();
+    assert_eq!(items, vec![HudItem::Score, HudItem::Lives, HudItem::Level, HudItem::Timer, HudItem::PowerUp]);
+
+    // Two rows splitting the bar, each one clear of the restart button
+    let rows: Vec<f32> = hud.entries.iter().map(|entry| entry.area.y).collect();
+    assert_eq!(rows.first(), Some(&0.0));
+    assert_eq!(rows.last(), Some(&(layout.score_bar_height / 2.0)));
+    for entry in &hud.entries {
+        assert_eq!(entry.area.h, layout.score_bar_height / 2.0);
+        assert!(entry.area.x + entry.area.w < layout.restart_button().x);
+    }
+}
+
+#[test]
+fn test_time_attack_timer_shows_on_the_narrowest_board() {
+    let mut game = GameState::builder().grid(2, 10).mode(GameMode::TimeAttack).build().unwrap();
+    game.activate_powerup(PowerUpKind::Freeze);
+    let layout = Layout::new(&game);
+
+    let hud = Hud::new(&game, &layout);
+
+    assert_eq!(hud.entry(HudItem::Timer).unwrap().text, "3:00");
+    assert_eq!(hud.entries.len(), 5);
+}
+
+#[test]
+fn test_chain_only_shows_while_its_window_is_open() {
+    let mut game = game(24);
+    let layout = Layout::new(&game);
+    assert!(Hud::new(&game, &layout).entry(HudItem::Chain).is_none());
+
+    game.chain = 2;
+    game.ticks_since_clear = CHAIN_WINDOW_TICKS + 1;
+    assert!(Hud::new(&game, &layout).entry(HudItem::Chain).is_none());
+}
+
+#[test]
+fn test_timer_counts_down_in_time_attack_and_up_otherwise() {
+    let mut game = game(24);
+    let layout = Layout::new(&game);
+    game.stats.ticks = 300;
+    assert_eq!(Hud::new(&game, &layout).entry(HudItem::Timer).unwrap().text, "1:00");
+
+    game.mode = GameMode::TimeAttack;
+    let timer = Hud::new(&game, &layout).entry(HudItem::Timer).cloned().unwrap();
+    assert_eq!(timer.text, "2:00");
+    assert_ne!(timer.color, WARNING_COLOR);
+
+    // The last seconds turn into a warning
+    game.stats.ticks = 800;
+    let timer = Hud::new(&game, &layout).entry(HudItem::Timer).cloned().unwrap();
+    assert_eq!((timer.text.as_str(), timer.color), ("0:20", WARNING_COLOR));
+}
+
+#[test]
+fn test_zen_shows_no_lives() {
+    let game = GameState::new(GameConfig { grid_width: 24, ..GameConfig::zen() }).unwrap();
+    let hud = Hud::new(&game, &Layout::new(&game));
+    assert_eq!(hud.entry(HudItem::Lives).unwrap().text, "Zen");
+}
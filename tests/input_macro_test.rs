@@ -0,0 +1,84 @@
+use rust_stackattack::core::game::GameState;
+use rust_stackattack::core::types::{GameConfig, InputAction};
+use rust_stackattack::platform::input::InputSource;
+use rust_stackattack::platform::input_macro::{InputMacro, MacroError, MAX_MACRO_ACTIONS};
+use rust_stackattack::testing::MockInput;
+
+fn test_game(practice: bool) -> GameState {
+    GameState::new(GameConfig { grid_width: 6, grid_height: 6, seed: Some(1), practice, ..Default::default() }).unwrap()
+}
+
+#[test]
+fn test_macros_are_refused_outside_practice() {
+    let game = test_game(false);
+    let mut input_macro = InputMacro::new(vec![InputAction::Left]);
+    assert_eq!(input_macro.toggle_recording(&game), Err(MacroError::NotPractice));
+    assert_eq!(input_macro.play(&game), Err(MacroError::NotPractice));
+    assert!(!input_macro.is_recording() && !input_macro.is_playing());
+}
+
+#[test]
+fn test_recording_keeps_the_first_action_of_each_update() {
+    let game = test_game(true);
+    let mut input_macro = InputMacro::default();
+    assert_eq!(input_macro.play(&game), Err(MacroError::Empty));
+    assert_eq!(input_macro.toggle_recording(&game), Ok(true));
+
+    // Left held over two updates with a jump pressed late in the first, then a push right
+    let frames = [InputAction::Left, InputAction::Up, InputAction::Left, InputAction::None, InputAction::Right];
+    let mut player = MockInput::new(frames);
+    let mut recorder = input_macro.recorder(&mut player);
+    assert_eq!(recorder.poll(), InputAction::Left);
+    assert_eq!(recorder.poll(), InputAction::Up);
+    input_macro.end_update();
+    let mut recorder = input_macro.recorder(&mut player);
+    recorder.poll();
+    input_macro.end_update();
+    // An update with nothing pressed isn't recorded
+    input_macro.recorder(&mut player).poll();
+    input_macro.end_update();
+    input_macro.recorder(&mut player).poll();
+    assert_eq!(input_macro.recorded(), Some(2));
+
+    assert_eq!(input_macro.toggle_recording(&game), Ok(false));
+    assert_eq!(input_macro.actions, vec![InputAction::Left, InputAction::Left, InputAction::Right]);
+}
+
+#[test]
+fn test_playback_goes_through_the_macro_once() {
+    let game = test_game(true);
+    let mut input_macro = InputMacro::new(vec![InputAction::Right, InputAction::Kick]);
+    input_macro.play(&game).unwrap();
+    assert_eq!(input_macro.next_action(), Some(InputAction::Right));
+    assert_eq!(input_macro.next_action(), Some(InputAction::Kick));
+    assert_eq!(input_macro.next_action(), None);
+    assert!(!input_macro.is_playing());
+}
+
+#[test]
+fn test_recording_stops_once_full() {
+    let game = test_game(true);
+    let mut input_macro = InputMacro::default();
+    input_macro.toggle_recording(&game).unwrap();
+    for _ in 0..MAX_MACRO_ACTIONS + 5 {
+        input_macro.observe(InputAction::Right);
+        input_macro.end_update();
+    }
+    assert!(!input_macro.is_recording());
+    assert_eq!(input_macro.actions.len(), MAX_MACRO_ACTIONS);
+}
+
+#[test]
+fn test_played_macro_moves_the_player_like_the_recording() {
+    let mut game = test_game(true);
+    game.blocks.clear();
+    game.sync_occupancy();
+    let start = game.player.position.x;
+    let mut input_macro = InputMacro::new(vec![InputAction::Left, InputAction::Right, InputAction::Left]);
+    input_macro.play(&game).unwrap();
+    while let Some(action) = input_macro.next_action() {
+        game.process_input(action);
+        game.tick();
+    }
+    assert_eq!(game.player.position.x, start - 1);
+}
@@ -0,0 +1,128 @@
+use rust_stackattack::core::types::InputAction;
+use rust_stackattack::platform::input::{ConflictPolicy, Control, FrameInput, KeyBindings, RebindMenu};
+
+fn both_directions() -> FrameInput {
+    let mut frame = FrameInput::default();
+    frame.press_direction(InputAction::Right);
+    frame.press_direction(InputAction::Left);
+    frame
+}
+
+#[test]
+fn test_latest_direction_wins() {
+    let mut frame = both_directions();
+    assert_eq!(frame.resolve(ConflictPolicy::LatestWins), InputAction::Left);
+
+    // Pressing Right again moves it to the back
+    frame.press_direction(InputAction::Right);
+    assert_eq!(frame.resolve(ConflictPolicy::LatestWins), InputAction::Right);
+}
+
+#[test]
+fn test_neutral_policy_cancels_opposing_directions() {
+    let frame = both_directions();
+    assert_eq!(frame.resolve(ConflictPolicy::Neutral), InputAction::None);
+
+    let mut single = FrameInput::default();
+    single.press_direction(InputAction::Left);
+    assert_eq!(single.resolve(ConflictPolicy::Neutral), InputAction::Left);
+}
+
+#[test]
+fn test_held_directions_are_older_than_presses() {
+    let mut frame = FrameInput::default();
+    frame.press_direction(InputAction::Left);
+    frame.hold_direction(InputAction::Right);
+    frame.hold_direction(InputAction::Left);
+    assert_eq!(frame.directions, vec![InputAction::Right, InputAction::Left]);
+    assert_eq!(frame.resolve(ConflictPolicy::LatestWins), InputAction::Left);
+}
+
+#[test]
+fn test_jump_comes_before_direction() {
+    let mut frame = both_directions();
+    frame.jump = true;
+    assert_eq!(frame.resolve(ConflictPolicy::LatestWins), InputAction::Up);
+
+    // Letting go of Up cuts the jump short, unless it was pressed again
+    frame.jump_released = true;
+    assert_eq!(frame.resolve(ConflictPolicy::LatestWins), InputAction::Up);
+    frame.jump = false;
+    assert_eq!(frame.resolve(ConflictPolicy::LatestWins), InputAction::JumpRelease);
+}
+
+#[test]
+fn test_whole_frame_actions_take_priority() {
+    let mut frame = both_directions();
+    frame.jump = true;
+    frame.crouch = true;
+    assert_eq!(frame.resolve(ConflictPolicy::Neutral), InputAction::Down);
+    frame.kick = true;
+    assert_eq!(frame.resolve(ConflictPolicy::Neutral), InputAction::Kick);
+    frame.drop = true;
+    assert_eq!(frame.resolve(ConflictPolicy::Neutral), InputAction::Drop);
+    frame.switch = true;
+    assert_eq!(frame.resolve(ConflictPolicy::Neutral), InputAction::SwitchCharacter);
+    assert_eq!(FrameInput::default().resolve(ConflictPolicy::LatestWins), InputAction::None);
+}
+
+#[test]
+fn test_default_bindings_use_the_arrow_keys() {
+    let bindings = KeyBindings::default();
+    assert_eq!(bindings.control("Left"), Some(Control::Left));
+    assert_eq!(bindings.control("Up"), Some(Control::Jump));
+    assert_eq!(bindings.control("RShift"), Some(Control::Preview));
+    assert_eq!(bindings.control("Q"), None);
+}
+
+#[test]
+fn test_rebinding_moves_a_key_off_its_old_control() {
+    let mut bindings = KeyBindings::default();
+    bindings.rebind(Control::Jump, "Space");
+    assert_eq!(bindings.keys(Control::Jump), ["Space".to_string()]);
+    assert_eq!(bindings.control("Space"), Some(Control::Jump));
+    assert!(bindings.keys(Control::Kick).is_empty());
+    assert_eq!(bindings.control("Up"), None);
+}
+
+#[test]
+fn test_bindings_file_is_plain_toml_with_defaults_for_missing_controls() {
+    let dir = std::env::temp_dir().join(format!("stackattack-keys-{}", std::process::id()));
+    let path = dir.join("keys.toml");
+    let mut bindings = KeyBindings::default();
+    bindings.rebind(Control::Left, "A");
+    bindings.save_to(&path).unwrap();
+    assert_eq!(KeyBindings::load_from(&path).unwrap(), bindings);
+
+    // Edited by hand down to one control, the rest keep their defaults
+    std::fs::write(&path, "left = [\"A\", \"Left\"]\n").unwrap();
+    let edited = KeyBindings::load_from(&path).unwrap();
+    assert_eq!(edited.control("A"), Some(Control::Left));
+    assert_eq!(edited.control("Left"), Some(Control::Left));
+    assert_eq!(edited.control("Right"), Some(Control::Right));
+
+    // A broken file is an error naming the file
+    std::fs::write(&path, "left = [").unwrap();
+    let error = KeyBindings::load_from(&path).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    assert!(error.to_string().contains("keys.toml"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_rebind_menu_binds_the_next_key_to_the_picked_control() {
+    let mut bindings = KeyBindings::default();
+    let mut menu = RebindMenu::default();
+    menu.move_selection(-1);
+    assert_eq!(menu.selected_control(), Control::Preview);
+    menu.move_selection(1);
+    menu.move_selection(2);
+    assert_eq!(menu.selected_control(), Control::Jump);
+
+    // Keys only bind once a rebind was started
+    assert!(!menu.key_pressed("W", &mut bindings));
+    menu.waiting = true;
+    assert!(menu.key_pressed("W", &mut bindings));
+    assert!(!menu.waiting);
+    assert_eq!(bindings.control("W"), Some(Control::Jump));
+}
@@ -0,0 +1,128 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use rust_stackattack::core::ai::Difficulty;
+use rust_stackattack::core::storm::StormPacing;
+use rust_stackattack::core::types::{GameConfig, GameMode, Theme};
+use rust_stackattack::platform::launch::{FrontendOptions, LaunchError, LaunchConfig, WindowOptions};
+
+fn args(line: &str) -> Vec<String> {
+    line.split_whitespace().map(String::from).collect()
+}
+
+fn layered(file: Option<&str>, line: &str) -> Result<LaunchConfig, LaunchError> {
+    LaunchConfig::layered(GameConfig::default(), |_| Ok(file.map(String::from)), &args(line))
+}
+
+#[test]
+fn test_flags_win_over_the_file_and_the_file_over_the_defaults() {
+    let file = "lives = 5\nblock_spawn_rate = 4\n[player_timing]\nfall_delay = 6\n";
+    let launch = layered(Some(file), "--lives 7 --bot easy").unwrap();
+    assert_eq!(launch.game.lives, 7);
+    assert_eq!(launch.game.block_spawn_rate, 4);
+    assert_eq!(launch.game.player_timing.fall_delay, 6);
+    assert_eq!(launch.game.player_timing.jump_duration, GameConfig::default().player_timing.jump_duration);
+    assert_eq!(launch.game.grid_width, 16);
+}
+
+#[test]
+fn test_every_kind_of_setting_has_a_flag() {
+    let launch = layered(None, "--two-characters --carry --pits 3,12 --seed 42 --theme Soft --cell-size 20 --player-timing.gravity-interval 2 --fall-delay 5").unwrap();
+    assert!(launch.game.two_characters);
+    assert!(launch.game.head_carrying);
+    assert_eq!(launch.game.pits, vec![3, 12]);
+    assert_eq!(launch.game.seed, Some(42));
+    assert_eq!(launch.game.theme, Some(Theme::Soft));
+    assert_eq!(launch.game.cell_size, 20.0);
+    assert_eq!(launch.game.player_timing.gravity_interval, 2);
+    assert_eq!(launch.game.player_timing.fall_delay, 5);
+}
+
+#[test]
+fn test_part_of_an_unset_optional_table_keeps_its_defaults() {
+    let launch = layered(None, "--storm-pacing.duration 99").unwrap();
+    assert_eq!(launch.game.storm_pacing, Some(StormPacing { duration: 99, ..StormPacing::default() }));
+}
+
+#[test]
+fn test_window_options_come_from_the_file_and_the_flags() {
+    let launch = layered(Some("[window]\nfullscreen = true\nlow_spec = true\n"), "--render full").unwrap();
+    assert_eq!(launch.window, WindowOptions { fullscreen: Some(true), low_spec: Some(false) });
+    assert_eq!(layered(None, "").unwrap().window, WindowOptions::default());
+}
+
+#[test]
+fn test_invalid_settings_say_what_is_wrong() {
+    let error = layered(None, "--lives lots").unwrap_err();
+    assert_eq!(error.to_string(), "invalid type: string \"lots\", expected u32 for key `lives`");
+    assert_eq!(layered(None, "--lives").unwrap_err(), LaunchError::Missing("lives".to_string()));
+    assert_eq!(layered(Some("livs = 5"), "").unwrap_err(), LaunchError::Unknown("livs".to_string()));
+    assert!(matches!(layered(Some("lives = "), ""), Err(LaunchError::File(_))));
+    assert!(matches!(layered(Some("[window]\nsize = 3"), ""), Err(LaunchError::Invalid(_))));
+    assert_eq!(layered(None, "--fullscreen maybe").unwrap_err().to_string(), "--fullscreen expects on or off, not maybe");
+    assert_eq!(layered(None, "--grid 1x1").unwrap_err().to_string(), "--grid expects WIDTHxHEIGHT, at least 2x3, not 1x1");
+    assert_eq!(layered(None, "--bot").unwrap_err(), LaunchError::Missing("bot".to_string()));
+}
+
+#[test]
+fn test_unknown_flags_and_stray_arguments_are_rejected() {
+    assert_eq!(layered(None, "--lifes 5").unwrap_err(), LaunchError::Flag("--lifes".to_string()));
+    assert_eq!(layered(None, "--mouse level.json").unwrap_err(), LaunchError::Flag("level.json".to_string()));
+    assert_eq!(layered(None, "--lives 5 --help").unwrap_err(), LaunchError::Help);
+}
+
+#[test]
+fn test_frontend_flags_are_read_alongside_the_settings() {
+    let launch = layered(None, "--grid 24x10 --mouse --edit level.json --preview-bot medium --hints off --shake 0").unwrap();
+    assert_eq!((launch.game.grid_width, launch.game.grid_height), (24, 10));
+    assert_eq!(launch.frontend, FrontendOptions {
+        mouse: true,
+        edit: Some(PathBuf::from("level.json")),
+        preview_bot: Some(Difficulty::Medium),
+        hints: Some(false),
+        shake_intensity: Some(0.0),
+        ..FrontendOptions::default()
+    });
+}
+
+#[test]
+fn test_zen_pace_goes_under_the_file_and_the_flags() {
+    let zen = GameConfig::zen();
+    let launch = layered(None, "--zen").unwrap();
+    assert_eq!(launch.game.mode, GameMode::Zen);
+    assert_eq!(launch.game.block_spawn_rate, zen.block_spawn_rate);
+    assert!(launch.game.favor_low_columns);
+
+    let launch = layered(Some("block_spawn_rate = 8"), "--mode zen --storms").unwrap();
+    assert_eq!(launch.game.block_spawn_rate, 8);
+    assert_eq!(launch.game.storm_pacing, Some(StormPacing::default()));
+    assert_eq!(layered(None, "--mode time-attack").unwrap().game.mode, GameMode::TimeAttack);
+}
+
+#[test]
+fn test_zen_from_the_config_file_brings_its_pace() {
+    let zen = GameConfig::zen();
+    let launch = layered(Some("mode = \"Zen\""), "").unwrap();
+    assert_eq!(launch.game.mode, GameMode::Zen);
+    assert_eq!(launch.game.block_spawn_rate, zen.block_spawn_rate);
+    assert!(launch.game.favor_low_columns);
+
+    // The flags still choose the mode over the file
+    let launch = layered(Some("mode = \"Zen\""), "--mode endless").unwrap();
+    assert_eq!(launch.game.mode, GameMode::Endless);
+    assert_eq!(launch.game.block_spawn_rate, GameConfig::default().block_spawn_rate);
+}
+
+#[test]
+fn test_only_a_named_config_file_is_asked_for_by_path() {
+    let mut asked = None;
+    let launch = LaunchConfig::layered(GameConfig::default(), |path: Option<&Path>| {
+        asked = Some(path.map(Path::to_path_buf));
+        Ok(Some("lives = 4".to_string()))
+    }, &args("--config mine.toml"));
+    assert_eq!(launch.unwrap().game.lives, 4);
+    assert_eq!(asked, Some(Some(PathBuf::from("mine.toml"))));
+
+    let missing = LaunchConfig::layered(GameConfig::default(), |_| Err(io::Error::from(io::ErrorKind::NotFound)), &args(""));
+    assert_eq!(missing.unwrap_err(), LaunchError::Read(io::Error::from(io::ErrorKind::NotFound)));
+}
@@ -0,0 +1,14 @@
+// The old top-level paths still reach the same types as `core` until they're removed
+#![allow(deprecated)]
+
+use rust_stackattack::core::types::{GameConfig, GridPos};
+
+#[test]
+fn test_legacy_paths_are_the_core_types() {
+    let block: rust_stackattack::core::block::Block = rust_stackattack::block::Block::new(GridPos::new(1, 0));
+    assert_eq!(block.position, GridPos::new(1, 0));
+
+    let game: rust_stackattack::core::game::GameState =
+        rust_stackattack::game::GameState::new(GameConfig::default()).unwrap();
+    let _: &rust_stackattack::player::Player = &game.player;
+}
@@ -0,0 +1,77 @@
+use std::fs;
+
+use rust_stackattack::core::level_check::{check_level_json, check_path, Severity};
+
+const LEVEL: &str = r#"{
+    "name": "Warehouse",
+    "grid_width": 8,
+    "grid_height": 6,
+    "blocks": [
+        { "x": 0, "y": 5 },
+        {
+            "x": 6,
+            "y": 5
+        }
+    ],
+    "block_spawn_rate": 12,
+    "objective": { "ClearRows": 1 }
+}"#;
+
+fn lines(json: &str) -> Vec<(Severity, Option<usize>)> {
+    check_level_json(json).iter().map(|finding| (finding.severity, finding.line)).collect()
+}
+
+#[test]
+fn test_good_level_has_no_findings() {
+    assert_eq!(check_level_json(LEVEL), vec![]);
+}
+
+#[test]
+fn test_errors_point_at_their_line() {
+    // Broken JSON gets the parser's line
+    let broken = LEVEL.replace("\"grid_height\": 6,", "\"grid_height\": ,");
+    assert_eq!(lines(&broken), vec![(Severity::Error, Some(4))]);
+    assert!(!check_level_json(&broken)[0].message.contains("at line"));
+
+    // Every problem is reported, on the line of the block at fault or of its field
+    let invalid = LEVEL
+        .replace("\"x\": 0, \"y\": 5", "\"x\": 0, \"y\": 0")
+        .replace("\"y\": 5\n", "\"y\": 9\n")
+        .replace("\"block_spawn_rate\": 12", "\"block_spawn_rate\": 0");
+    assert_eq!(lines(&invalid), vec![
+        (Severity::Error, Some(12)),
+        (Severity::Error, Some(6)),
+        (Severity::Error, Some(8)),
+    ]);
+    assert_eq!(check_level_json(&invalid)[1].message, "block at (0, 0) covers the spawn row");
+}
+
+#[test]
+fn test_unlikely_levels_get_warnings() {
+    let floating = LEVEL.replace("\"y\": 5\n", "\"y\": 2\n");
+    assert_eq!(lines(&floating), vec![(Severity::Warning, Some(8))]);
+
+    let pit = LEVEL.replace("\"block_spawn_rate\"", "\"pits\": [3],\n    \"block_spawn_rate\"");
+    let findings = check_level_json(&pit);
+    assert_eq!(findings.len(), 1);
+    assert!(findings[0].message.contains("column 3 is an open pit"), "{}", findings[0]);
+
+    let quick = LEVEL.replace("{ \"ClearRows\": 1 }", "{ \"SurviveTicks\": 5 }");
+    assert_eq!(lines(&quick), vec![(Severity::Warning, Some(13))]);
+}
+
+#[test]
+fn test_packs_are_checked_file_by_file() {
+    let dir = std::env::temp_dir().join(format!("stackattack-check-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    assert_eq!(check_path(&dir)[0].1[0].severity, Severity::Error);
+
+    fs::write(dir.join("01-first.json"), LEVEL).unwrap();
+    fs::write(dir.join("02-second.json"), LEVEL).unwrap();
+    let results = check_path(&dir);
+    assert_eq!(results.len(), 2);
+    assert!(results[0].1.is_empty());
+    assert_eq!(results[1].1[0].to_string(), "warning: level name \"Warehouse\" is already used by 01-first.json");
+    let _ = fs::remove_dir_all(&dir);
+}
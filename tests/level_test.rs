@@ -0,0 +1,183 @@
+use std::fs;
+
+use rust_stackattack::core::block::{BlockKind, Shape};
+use rust_stackattack::core::event::GameEvent;
+use rust_stackattack::core::game::GameState;
+use rust_stackattack::core::level::{
+    load_campaign, Level, LevelDefinition, LevelError, LEVELS_PER_FALL_SPEED, MIN_SPAWN_RATE, ROWS_PER_LEVEL,
+};
+use rust_stackattack::core::objective::ObjectiveKind;
+use rust_stackattack::core::types::{GameConfig, GameMode, GameOverReason, Soundtrack, Theme, Track};
+
+#[test]
+fn test_level_up_every_few_rows() {
+    let mut level = Level::new();
+    assert_eq!(level.number, 1);
+
+    assert!(!level.add_cleared_rows(ROWS_PER_LEVEL - 1));
+    assert_eq!(level.rows_until_next(), 1);
+
+    assert!(level.add_cleared_rows(1));
+    assert_eq!(level.number, 2);
+    assert_eq!(level.rows_until_next(), ROWS_PER_LEVEL);
+}
+
+#[test]
+fn test_level_speeds() {
+    let mut level = Level::new();
+    assert_eq!(level.spawn_rate(10), 10);
+    assert_eq!(level.fall_speed(1), 1);
+
+    level.number = 4;
+    assert_eq!(level.spawn_rate(10), 7);
+
+    // Spawn rate bottoms out, fall speed keeps growing slowly
+    level.number = 1 + LEVELS_PER_FALL_SPEED * 3;
+    assert_eq!(level.spawn_rate(10), MIN_SPAWN_RATE);
+    assert_eq!(level.fall_speed(1), 4);
+}
+
+#[test]
+fn test_game_speeds_up_with_level() {
+    let config = GameConfig {
+        grid_width: 5,
+        grid_height: 5,
+        block_spawn_rate: 10,
+        block_fall_speed: 1,
+        ..Default::default()
+    };
+    let mut game = GameState::new(config).unwrap();
+
+    game.add_cleared_rows(ROWS_PER_LEVEL);
+    assert_eq!(game.level.number, 2);
+    assert_eq!(game.block_spawn_rate, 9);
+
+    game.restart();
+    assert_eq!(game.level.number, 1);
+    assert_eq!(game.block_spawn_rate, 10);
+}
+
+const LEVEL_JSON: &str = r#"{
+    "name": "Warehouse",
+    "grid_width": 8,
+    "grid_height": 6,
+    "blocks": [
+        { "x": 0, "y": 5 },
+        { "x": 6, "y": 4, "shape": "Vertical", "kind": "Steel" }
+    ],
+    "block_spawn_rate": 12,
+    "objective": { "ClearRows": 1 }
+}"#;
+
+fn survive_level(grid_width: usize, ticks: u64) -> LevelDefinition {
+    LevelDefinition {
+        block_spawn_rate: 50,
+        objective: ObjectiveKind::SurviveTicks(ticks),
+        ..LevelDefinition::empty(grid_width, 8)
+    }
+}
+
+#[test]
+fn test_level_file_parses() {
+    let level = LevelDefinition::from_json(LEVEL_JSON).unwrap();
+    assert_eq!((level.grid_width, level.grid_height), (8, 6));
+    assert_eq!(level.block_fall_speed, 1);
+    assert_eq!(level.objective, ObjectiveKind::ClearRows(1));
+    assert_eq!(level.blocks[0].shape, Shape::Single);
+    assert_eq!(level.blocks[1].kind, BlockKind::Steel);
+}
+
+#[test]
+fn test_malformed_level_files_are_rejected() {
+    assert!(matches!(LevelDefinition::from_json("{ \"grid_width\": 8"), Err(LevelError::Parse(_))));
+
+    let invalid = [
+        LEVEL_JSON.replace("\"x\": 0, \"y\": 5", "\"x\": 8, \"y\": 5"),  // Outside the grid
+        LEVEL_JSON.replace("\"x\": 0, \"y\": 5", "\"x\": 6, \"y\": 5"),  // On the steel block
+        LEVEL_JSON.replace("\"x\": 0, \"y\": 5", "\"x\": 3, \"y\": 5"),  // On the player
+        LEVEL_JSON.replace("\"x\": 0, \"y\": 5", "\"x\": 0, \"y\": 0"),  // In the spawn row
+        LEVEL_JSON.replace("\"grid_height\": 6", "\"grid_height\": 2"),
+        LEVEL_JSON.replace("\"block_spawn_rate\": 12", "\"block_spawn_rate\": 0"),
+        LEVEL_JSON.replace("\"ClearRows\": 1", "\"ClearRows\": 0"),
+    ];
+    for json in &invalid {
+        assert!(matches!(LevelDefinition::from_json(json), Err(LevelError::Invalid(_))), "{}", json);
+    }
+}
+
+#[test]
+fn test_campaign_loads_files_in_name_order() {
+    let dir = std::env::temp_dir().join(format!("stackattack-campaign-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    assert!(matches!(load_campaign(&dir), Err(LevelError::NoLevels(_))));
+
+    fs::write(dir.join("02-second.json"), serde_json::to_string(&survive_level(10, 5)).unwrap()).unwrap();
+    fs::write(dir.join("01-first.json"), LEVEL_JSON).unwrap();
+    fs::write(dir.join("notes.txt"), "not a level").unwrap();
+    let levels = load_campaign(&dir).unwrap();
+    assert_eq!(levels.iter().map(|level| level.grid_width).collect::<Vec<_>>(), vec![8, 10]);
+
+    // A broken file names itself
+    fs::write(dir.join("03-broken.json"), "[]").unwrap();
+    let err = load_campaign(&dir).unwrap_err();
+    assert!(err.to_string().contains("03-broken.json"), "{}", err);
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_campaign_advances_on_objective() {
+    let mut game = GameState::new(GameConfig {
+        seed: Some(1),
+        mode: GameMode::Campaign(vec![LevelDefinition::from_json(LEVEL_JSON).unwrap(), survive_level(10, 2)]),
+        ..GameConfig::default()
+    }).unwrap();
+    assert_eq!((game.grid_width, game.grid_height), (8, 6));
+    assert_eq!(game.block_spawn_rate, 12);
+    assert!(game.blocks.iter().any(|block| block.position == (6, 4) && block.kind == BlockKind::Steel));
+
+    // Finishing the first level's objective replaces the board
+    game.add_cleared_rows(1);
+    game.tick();
+    assert_eq!(game.campaign_level, 1);
+    assert_eq!(game.level.number, 2);
+    assert_eq!(game.grid_width, 10);
+    assert!(game.drain_events().contains(&GameEvent::CampaignLevelStarted(1)));
+
+    // The last level ends the game as won
+    game.tick();
+    game.tick();
+    assert!(game.game_over);
+    assert_eq!(game.game_over_reason, Some(GameOverReason::CampaignComplete));
+
+    game.restart();
+    assert_eq!(game.campaign_level, 0);
+    assert_eq!(game.grid_width, 8);
+}
+
+#[test]
+fn test_modes_and_levels_set_theme_and_music() {
+    assert_eq!(GameState::new(GameConfig::zen()).unwrap().theme, Theme::Soft);
+    assert_eq!(GameState::new(GameConfig::default()).unwrap().theme, Theme::Classic);
+    let chosen = GameState::new(GameConfig { theme: Some(Theme::Classic), ..GameConfig::zen() }).unwrap();
+    assert_eq!(chosen.theme, Theme::Classic);
+
+    let night = LEVEL_JSON.replace("\"block_spawn_rate\": 12,", "\"block_spawn_rate\": 12, \"theme\": \"Soft\", \"soundtrack\": { \"Fixed\": \"Danger\" },");
+    let mut game = GameState::new(GameConfig {
+        mode: GameMode::Campaign(vec![LevelDefinition::from_json(&night).unwrap(), survive_level(10, 5)]),
+        ..GameConfig::default()
+    }).unwrap();
+    assert_eq!(game.theme, Theme::Soft);
+    assert_eq!(game.soundtrack, Soundtrack::Fixed(Track::Danger));
+
+    // Levels without their own keep what the level before set, a restart goes back to the mode's
+    game.add_cleared_rows(1);
+    game.tick();
+    assert_eq!(game.campaign_level, 1);
+    assert_eq!(game.theme, Theme::Soft);
+    game.restart();
+    assert_eq!(game.theme, Theme::Soft);
+    game.mode = GameMode::Campaign(vec![survive_level(10, 5)]);
+    game.restart();
+    assert_eq!((game.theme, game.soundtrack), (Theme::Classic, Soundtrack::Adaptive));
+}
@@ -0,0 +1,164 @@
+use rust_stackattack::core::block::Block;
+use rust_stackattack::core::event::GameEvent;
+use rust_stackattack::core::game::{GameState, INVINCIBILITY_TICKS};
+use rust_stackattack::core::types::{GameConfig, GridPos};
+
+fn test_game(lives: u32) -> GameState {
+    let config = GameConfig {
+        grid_width: 5,
+        grid_height: 5,
+        cell_size: 30.0,
+        lives,
+        ..Default::default()
+    };
+    let mut game = GameState::new(config).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    game
+}
+
+fn falling_block(position: (usize, usize)) -> Block {
+    Block {
+        position: position.into(),
+        falling: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_hit_costs_a_life_and_respawns() {
+    let mut game = test_game(3);
+    game.player.position = GridPos::new(2, 3);
+    game.blocks.push(falling_block((2, 2)));
+    game.sync_occupancy();
+
+    game.update_falling_blocks();
+
+    assert!(!game.game_over);
+    assert_eq!(game.lives, 2);
+    assert!(game.blocks.is_empty(), "Crushing block is removed");
+    assert_eq!(game.invincible_ticks, INVINCIBILITY_TICKS);
+    assert_eq!(game.drain_events(), vec![GameEvent::PlayerHit]);
+}
+
+#[test]
+fn test_respawn_avoids_falling_blocks() {
+    let mut game = test_game(3);
+    game.player.position = GridPos::new(2, 3);
+    game.blocks.push(falling_block((1, 0)));
+    game.blocks.push(falling_block((3, 0)));
+    game.sync_occupancy();
+
+    game.respawn_player();
+
+    // Columns 1 and 3 have blocks coming down, so the nearest safe spot is the current one
+    assert_eq!(game.player.position, (2, 3));
+
+    game.blocks.push(falling_block((2, 0)));
+    game.sync_occupancy();
+    game.respawn_player();
+    assert_eq!(game.player.position.y, 3);
+    assert!(game.player.position.x == 0 || game.player.position.x == 4);
+}
+
+#[test]
+fn test_invincible_player_is_not_hurt() {
+    let mut game = test_game(3);
+    game.player.position = GridPos::new(2, 3);
+    game.invincible_ticks = 2;
+    game.blocks.push(falling_block((2, 2)));
+    game.sync_occupancy();
+
+    game.update_falling_blocks();
+    assert_eq!(game.lives, 3);
+    assert_eq!(game.blocks.len(), 1);
+
+    game.update_player();
+    game.update_player();
+    assert_eq!(game.invincible_ticks, 0);
+}
+
+#[test]
+fn test_invincible_player_holds_up_the_block_dropped_on_them() {
+    let mut game = test_game(3);
+    game.player.position = GridPos::new(2, 3);
+    game.invincible_ticks = INVINCIBILITY_TICKS;
+    game.blocks.push(falling_block((2, 1)));
+    game.sync_occupancy();
+
+    for _ in 0..3 {
+        game.update_falling_blocks();
+    }
+
+    // The block waits on the player's head without landing or breaking
+    assert_eq!(game.lives, 3);
+    assert_eq!(game.blocks.len(), 1);
+    assert_eq!(game.blocks[0].position, (2, 2));
+    assert!(game.blocks[0].falling);
+
+    // Once the player steps aside it carries on down
+    game.player.position = GridPos::new(1, 3);
+    game.update_falling_blocks();
+    assert_eq!(game.blocks[0].position, (2, 3));
+}
+
+#[test]
+fn test_game_over_when_out_of_lives() {
+    let mut game = test_game(2);
+    game.player.position = GridPos::new(2, 3);
+
+    assert!(game.check_block_player_collision(2, 3));
+    assert!(!game.game_over);
+
+    game.invincible_ticks = 0;
+    let GridPos { x, y } = game.player.position;
+    assert!(game.check_block_player_collision(x, y));
+    assert!(game.game_over);
+    assert_eq!(game.lives, 0);
+
+    game.restart();
+    assert_eq!(game.lives, 2);
+}
+
+#[test]
+fn test_no_death_breaks_crushing_blocks() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 5,
+        grid_height: 5,
+        ..GameConfig::zen()
+    }).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    game.player.position = GridPos::new(2, 3);
+    game.blocks.push(falling_block((2, 2)));
+    game.sync_occupancy();
+
+    game.update_falling_blocks();
+    assert!(!game.game_over);
+    assert_eq!(game.lives, 3);
+    assert!(game.blocks.is_empty());
+    assert_eq!(game.player.position, (2, 3));
+}
+
+#[test]
+fn test_no_death_sweeps_stack_over_danger_line() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 5,
+        grid_height: 5,
+        ..GameConfig::zen()
+    }).unwrap();
+    game.blocks.clear();
+    for y in 0..5 {
+        game.blocks.push(Block {
+            position: GridPos::new(0, y),
+            falling: false,
+            ..Default::default()
+        });
+    }
+
+    game.sync_occupancy();
+    game.check_stack_height();
+    assert!(!game.game_over);
+    assert_eq!(game.blocks.len(), 4);
+    assert!(game.blocks.iter().all(|block| block.position.y > 0));
+}
@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+use rust_stackattack::platform::motion::{slide_offset, PlayerMotion};
+use rust_stackattack::testing::MockClock;
+use rust_stackattack::core::types::GridPos;
+
+#[test]
+fn test_player_slides_into_new_cell() {
+    let clock = MockClock::new();
+    let mut motion = PlayerMotion::new(Duration::from_millis(100), GridPos::new(2, 3), &clock);
+    motion.follow(GridPos::new(3, 3), &clock);
+    assert_eq!(motion.offset(&clock), (-1.0, 0.0));
+
+    clock.advance(Duration::from_millis(50));
+    assert_eq!(motion.drawn(&clock), (2.5, 3.0));
+
+    // A new move starts from where the player is drawn, not from the old cell
+    motion.follow(GridPos::new(4, 3), &clock);
+    assert_eq!(motion.drawn(&clock), (2.5, 3.0));
+    clock.advance(Duration::from_millis(100));
+    assert_eq!(motion.offset(&clock), (0.0, 0.0));
+}
+
+#[test]
+fn test_zero_duration_and_long_moves_snap() {
+    let clock = MockClock::new();
+    let mut instant = PlayerMotion::new(Duration::ZERO, GridPos::new(2, 3), &clock);
+    instant.follow(GridPos::new(3, 3), &clock);
+    assert_eq!(instant.offset(&clock), (0.0, 0.0));
+
+    // Respawning across the grid doesn't slide
+    let mut smooth = PlayerMotion::new(Duration::from_millis(100), GridPos::new(0, 3), &clock);
+    smooth.follow(GridPos::new(7, 3), &clock);
+    assert_eq!(smooth.drawn(&clock), (7.0, 3.0));
+}
+
+#[test]
+fn test_slide_offset_covers_what_is_left_of_a_move() {
+    assert_eq!(slide_offset(GridPos::new(2, 3), GridPos::new(2, 4), 1.0), (0.0, -1.0));
+    assert_eq!(slide_offset(GridPos::new(2, 3), GridPos::new(2, 4), 0.25), (0.0, -0.25));
+    assert_eq!(slide_offset(GridPos::new(3, 4), GridPos::new(2, 4), 0.5), (0.5, 0.0));
+    assert_eq!(slide_offset(GridPos::new(2, 3), GridPos::new(2, 4), 0.0), (0.0, 0.0));
+
+    // Blocks spawned or placed far away appear in their cell
+    assert_eq!(slide_offset(GridPos::new(0, 0), GridPos::new(5, 4), 1.0), (0.0, 0.0));
+}
@@ -0,0 +1,68 @@
+use rust_stackattack::core::types::InputAction;
+use rust_stackattack::platform::input::InputSource;
+use rust_stackattack::platform::mouse::MouseInput;
+use rust_stackattack::platform::render::Rect;
+
+const CELL: f32 = 30.0;
+
+fn player() -> Rect {
+    Rect::new(90.0, 120.0, CELL, 2.0 * CELL)
+}
+
+fn drain(mouse: &mut MouseInput) -> Vec<InputAction> {
+    std::iter::from_fn(|| Some(mouse.poll()).filter(|&action| action != InputAction::None)).collect()
+}
+
+#[test]
+fn test_drag_steps_once_per_cell() {
+    let mut mouse = MouseInput::new(CELL);
+    assert!(mouse.press((100.0, 150.0), player()));
+
+    // A shaky click doesn't move anything
+    mouse.motion((110.0, 152.0), player());
+    assert!(!mouse.has_action());
+
+    mouse.motion((120.0, 150.0), player());
+    mouse.motion((125.0, 150.0), player());
+    assert_eq!(drain(&mut mouse), vec![InputAction::Right]);
+
+    // A fast drag crosses several cells at once, dragging back steps the other way
+    mouse.motion((190.0, 150.0), player());
+    assert_eq!(drain(&mut mouse), vec![InputAction::Right, InputAction::Right]);
+    mouse.motion((140.0, 150.0), player());
+    assert_eq!(drain(&mut mouse), vec![InputAction::Left, InputAction::Left]);
+
+    mouse.release();
+    mouse.motion((300.0, 150.0), player());
+    assert!(!mouse.has_action());
+}
+
+#[test]
+fn test_click_above_player_jumps() {
+    let mut mouse = MouseInput::new(CELL);
+    mouse.motion((105.0, 60.0), player());
+    assert!(mouse.hover_jump);
+
+    assert!(mouse.press((105.0, 60.0), player()));
+    mouse.release();
+    assert_eq!(drain(&mut mouse), vec![InputAction::Up, InputAction::JumpRelease]);
+
+    // Clicks away from the player are left alone
+    assert!(!mouse.press((10.0, 60.0), player()));
+    assert!(!mouse.press((105.0, 200.0), player()));
+    assert!(mouse.drag.is_none());
+}
+
+#[test]
+fn test_cancel_forgets_drag_without_release() {
+    let mut mouse = MouseInput::new(CELL);
+    mouse.press((105.0, 60.0), player());
+    mouse.poll();
+    mouse.cancel();
+    assert!(!mouse.has_action(), "A cancelled jump doesn't queue a release");
+
+    mouse.press((100.0, 150.0), player());
+    mouse.cancel();
+    mouse.motion((200.0, 150.0), player());
+    assert!(!mouse.has_action());
+}
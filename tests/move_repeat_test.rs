@@ -0,0 +1,53 @@
+use rust_stackattack::core::game::GameState;
+use rust_stackattack::core::types::{GameConfig, InputAction};
+
+fn open_game() -> GameState {
+    let config = GameConfig {
+        grid_width: 7,
+        grid_height: 7,
+        cell_size: 30.0,
+        refresh_rate_milliseconds: 200,
+        block_fall_speed: 1,
+        block_spawn_rate: 10,
+        ..Default::default()
+    };
+    let mut game = GameState::new(config).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    game
+}
+
+#[test]
+fn test_first_move_is_not_throttled() {
+    let mut game = open_game();
+    let x = game.player.position.x;
+
+    game.process_input(InputAction::Left);
+    assert_eq!(game.player.position.x, x - 1);
+}
+
+#[test]
+fn test_new_direction_moves_immediately() {
+    let mut game = open_game();
+    let x = game.player.position.x;
+
+    game.process_input(InputAction::Left);
+    assert_eq!(game.player.position.x, x - 1);
+    game.process_input(InputAction::Right);
+    assert_eq!(game.player.position.x, x);
+}
+
+#[test]
+fn test_held_direction_repeats_once_per_update() {
+    let mut game = open_game();
+    let x = game.player.position.x;
+
+    game.process_input(InputAction::Right);
+    game.process_input(InputAction::Right);
+    assert_eq!(game.player.position.x, x + 1);
+
+    // The next update lets it repeat, however little time has passed
+    game.tick();
+    game.process_input(InputAction::Right);
+    assert_eq!(game.player.position.x, x + 2);
+}
@@ -0,0 +1,103 @@
+use std::time::Duration;
+
+use rust_stackattack::core::block::Block;
+use rust_stackattack::core::game::GameState;
+use rust_stackattack::core::types::{GameConfig, GameMode, GridPos, Soundtrack, Track};
+use rust_stackattack::platform::music::{self, AudioManager, Music, CROSSFADE};
+use rust_stackattack::testing::MockClock;
+
+fn test_game() -> GameState {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 4,
+        grid_height: 8,
+        ..Default::default()
+    }).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    game
+}
+
+fn stack(game: &mut GameState, height: usize) {
+    game.blocks.clear();
+    for y in game.grid_height - height..game.grid_height {
+        game.blocks.push(Block { position: GridPos::new(3, y), falling: false, ..Default::default() });
+    }
+    game.sync_occupancy();
+}
+
+#[test]
+fn test_track_follows_the_stack() {
+    let mut game = test_game();
+    assert_eq!(music::adaptive_track(&game), Track::Calm);
+    stack(&mut game, 4);
+    assert_eq!(music::adaptive_track(&game), Track::Tense);
+    stack(&mut game, 6);
+    assert_eq!(music::adaptive_track(&game), Track::Danger);
+}
+
+#[test]
+fn test_music_speeds_up_with_levels_and_stops_at_game_over() {
+    let mut game = test_game();
+    assert_eq!(Music::for_game(&game).unwrap().speed(), 1.0);
+
+    game.level.number = 3;
+    let music = Music::for_game(&game).unwrap();
+    assert_eq!(music.tempo_step, 2);
+    assert!(music.speed() > 1.0);
+    game.level.number = 50;
+    assert_eq!(Music::for_game(&game).unwrap().tempo_step, 5);
+
+    game.game_over = true;
+    assert_eq!(Music::for_game(&game), None);
+}
+
+#[test]
+fn test_tracks_crossfade() {
+    let clock = MockClock::new();
+    let mut game = test_game();
+    let mut audio = AudioManager::new(0.8);
+    let calm = Music::for_game(&game).unwrap();
+
+    // The first track fades in
+    assert_eq!(audio.update(&game, &clock), vec![(calm, 0.0)]);
+    clock.advance(CROSSFADE);
+    assert_eq!(audio.update(&game, &clock), vec![(calm, 0.8)]);
+
+    // A tenser stack fades the calm track out while the new one comes in
+    stack(&mut game, 4);
+    let tense = Music::for_game(&game).unwrap();
+    audio.update(&game, &clock);
+    clock.advance(CROSSFADE / 2);
+    let mix = audio.update(&game, &clock);
+    assert_eq!(mix, vec![(calm, 0.4), (tense, 0.4)]);
+
+    clock.advance(CROSSFADE / 2);
+    assert_eq!(audio.update(&game, &clock), vec![(tense, 0.8)]);
+
+    // Game over fades the music out
+    game.game_over = true;
+    audio.update(&game, &clock);
+    clock.advance(Duration::from_secs(10));
+    assert!(audio.update(&game, &clock).is_empty());
+}
+
+#[test]
+fn test_tracks_are_playable_wavs() {
+    for track in [Track::Calm, Track::Tense, Track::Danger] {
+        let wav = music::track_wav(track);
+        assert_eq!(&wav[..4], b"RIFF");
+        assert!(music::track_samples(track).len() > 22_050);
+    }
+}
+
+#[test]
+fn test_modes_bring_their_own_music() {
+    let zen = GameState::new(GameConfig::zen()).unwrap();
+    assert_eq!(zen.soundtrack, Soundtrack::Fixed(Track::Calm));
+    assert_eq!(Music::for_game(&zen).unwrap().track, Track::Calm);
+
+    let mut game = GameState::new(GameConfig { mode: GameMode::TimeAttack, ..Default::default() }).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    assert_eq!(Music::for_game(&game).unwrap().track, Track::Tense);
+}
@@ -0,0 +1,51 @@
+use rust_stackattack::core::event::GameEvent;
+use rust_stackattack::core::game::GameState;
+use rust_stackattack::core::level::{Level, ROWS_PER_LEVEL};
+use rust_stackattack::core::objective::{Objective, ObjectiveKind};
+use rust_stackattack::core::pickup::{Pickup, COIN_VALUE};
+use rust_stackattack::core::types::GameConfig;
+
+#[test]
+fn test_objective_completes_once() {
+    let mut objective = Objective::new(ObjectiveKind::ClearRows(3));
+    assert!(!objective.record_coins(5), "Only rows count toward a row objective");
+    assert!(!objective.record_rows(2));
+    assert_eq!(objective.progress_text(), "Clear 3 rows 2/3");
+
+    assert!(objective.record_rows(4));
+    assert!(objective.complete());
+    assert_eq!(objective.progress, 3);
+    assert!(!objective.record_rows(1));
+}
+
+#[test]
+fn test_levels_take_turns_between_objectives() {
+    let kinds: Vec<ObjectiveKind> = (1..=4).map(|number| Level { number, rows_cleared: 0 }.objective()).collect();
+    assert_eq!(kinds, vec![
+        ObjectiveKind::CollectCoins(2),
+        ObjectiveKind::SurviveTicks(150),
+        ObjectiveKind::ClearRows(2),
+        ObjectiveKind::CollectCoins(3),
+    ]);
+}
+
+#[test]
+fn test_game_tracks_objective_and_replaces_it_on_level_up() {
+    let mut game = GameState::new(GameConfig::default()).unwrap();
+    game.drain_events();
+    assert_eq!(game.objective.kind, ObjectiveKind::CollectCoins(2));
+
+    for _ in 0..2 {
+        game.pickups.push(Pickup::coin(game.player.position));
+        game.collect_pickups();
+    }
+    assert!(game.objective.complete());
+    let coin = GameEvent::CoinCollected(game.player.position, COIN_VALUE);
+    assert_eq!(game.drain_events(), vec![coin.clone(), coin, GameEvent::ObjectiveCompleted(ObjectiveKind::CollectCoins(2))]);
+
+    game.add_cleared_rows(ROWS_PER_LEVEL);
+    assert_eq!(game.objective, Objective::new(ObjectiveKind::SurviveTicks(150)));
+
+    game.restart();
+    assert_eq!(game.objective, Objective::new(ObjectiveKind::CollectCoins(2)));
+}
@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+use rust_stackattack::platform::particles::{
+    ParticleKind, Particles, DEBRIS_PER_LANDING, MAX_PARTICLES, PARTICLES_PER_BLAST, PARTICLE_LIFETIME, SPARKLES_PER_CELL,
+};
+use rust_stackattack::testing::MockClock;
+use rust_stackattack::core::types::GridPos;
+
+#[test]
+fn test_landing_throws_debris_that_falls_back() {
+    let clock = MockClock::new();
+    let mut particles = Particles::new(&clock);
+    assert_eq!(particles.live_count(&clock), 0);
+
+    particles.block_landed(GridPos::new(3, 5), &clock);
+    let start: Vec<_> = particles.visible(&clock).collect();
+    assert_eq!(start.len(), DEBRIS_PER_LANDING);
+    assert!(start.iter().all(|p| p.kind == ParticleKind::Debris && p.position == (3.5, 6.0) && p.life_left == 1.0));
+
+    // Thrown up first, then pulled back below where they started
+    clock.advance(Duration::from_millis(50));
+    assert!(particles.visible(&clock).all(|p| p.position.1 < 6.0));
+    clock.advance(Duration::from_millis(450));
+    assert!(particles.visible(&clock).all(|p| p.position.1 > 6.0 && p.life_left < 0.2));
+
+    clock.advance(PARTICLE_LIFETIME);
+    assert_eq!(particles.live_count(&clock), 0);
+}
+
+#[test]
+fn test_explosion_bursts_out_of_the_bomb_cell() {
+    let clock = MockClock::new();
+    let mut particles = Particles::new(&clock);
+
+    particles.explosion(GridPos::new(4, 2), &clock);
+    let burst: Vec<_> = particles.visible(&clock).collect();
+    assert_eq!(burst.len(), PARTICLES_PER_BLAST);
+    assert!(burst.iter().all(|p| p.position == (4.5, 2.5)));
+    assert_eq!(burst.iter().filter(|p| p.kind == ParticleKind::Flame).count(), PARTICLES_PER_BLAST / 2);
+
+    // Flames spread out evenly, none of them falls
+    clock.advance(Duration::from_millis(300));
+    let flames: Vec<_> = particles.visible(&clock).filter(|p| p.kind == ParticleKind::Flame).collect();
+    assert!(flames.iter().any(|p| p.position.1 < 2.5) && flames.iter().any(|p| p.position.1 > 2.5));
+}
+
+#[test]
+fn test_cleared_row_sparkles_along_every_cell() {
+    let clock = MockClock::new();
+    let mut particles = Particles::new(&clock);
+
+    particles.row_cleared(7, 10, &clock);
+    let sparkles: Vec<_> = particles.visible(&clock).collect();
+    assert_eq!(sparkles.len(), 10 * SPARKLES_PER_CELL);
+    assert!(sparkles.iter().all(|p| p.kind == ParticleKind::Sparkle && p.position.1 == 7.5));
+    for x in 0..10 {
+        assert!(sparkles.iter().any(|p| p.position.0 == x as f32 + 0.5));
+    }
+
+    particles.clear();
+    assert_eq!(particles.live_count(&clock), 0);
+}
+
+#[test]
+fn test_pool_replaces_the_oldest_particles() {
+    let clock = MockClock::new();
+    let mut particles = Particles::new(&clock);
+    particles.block_landed(GridPos::new(0, 0), &clock);
+    clock.advance(Duration::from_millis(10));
+
+    // More sparkles than the pool holds, only the newest stay
+    particles.row_cleared(4, MAX_PARTICLES, &clock);
+    assert_eq!(particles.live_count(&clock), MAX_PARTICLES);
+    assert!(particles.visible(&clock).all(|p| p.kind == ParticleKind::Sparkle));
+}
@@ -0,0 +1,97 @@
+use rust_stackattack::core::block::Block;
+use rust_stackattack::core::event::GameEvent;
+use rust_stackattack::core::game::GameState;
+use rust_stackattack::core::pickup::{Pickup, COIN_VALUE, MAX_PICKUPS, PICKUP_SPAWN_INTERVAL};
+use rust_stackattack::core::types::{GameConfig, GridPos, InputAction};
+
+fn test_game() -> GameState {
+    let config = GameConfig {
+        grid_width: 5,
+        grid_height: 5,
+        cell_size: 30.0,
+        refresh_rate_milliseconds: 200,
+        block_fall_speed: 1,
+        block_spawn_rate: 10,
+        ..Default::default()
+    };
+    let mut game = GameState::new(config).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    game
+}
+
+#[test]
+fn test_coin_creation() {
+    let coin = Pickup::coin(GridPos::new(1, 2));
+    assert_eq!(coin.position, (1, 2));
+    assert_eq!(coin.value, COIN_VALUE);
+}
+
+#[test]
+fn test_walking_through_coin_collects_it() {
+    let mut game = test_game();
+    let GridPos { x, y } = game.player.position;
+    
+    // Coin at the player's feet level in the next column
+    assert!(game.spawn_pickup_at(GridPos::new(x + 1, y + 1)));
+    
+    game.process_input(InputAction::Right);
+    
+    assert!(game.pickups.is_empty());
+    assert_eq!(game.score, COIN_VALUE);
+    assert!(game.drain_events().contains(&GameEvent::CoinCollected(GridPos::new(x + 1, y + 1), COIN_VALUE)));
+}
+
+#[test]
+fn test_pickup_not_spawned_in_occupied_cell() {
+    let mut game = test_game();
+    game.blocks.push(Block {
+        position: GridPos::new(0, 4),
+        falling: false,
+        ..Default::default()
+    });
+    game.sync_occupancy();
+    let GridPos { x, y } = game.player.position;
+    
+    assert!(!game.spawn_pickup_at(GridPos::new(0, 4)), "Cell holds a block");
+    assert!(!game.spawn_pickup_at(GridPos::new(x, y)), "Cell holds the player");
+    assert!(game.spawn_pickup_at(GridPos::new(0, 3)));
+    assert!(!game.spawn_pickup_at(GridPos::new(0, 3)), "Cell already holds a coin");
+}
+
+#[test]
+fn test_column_rest_cell() {
+    let mut game = test_game();
+    game.blocks.push(Block {
+        position: GridPos::new(0, 4),
+        falling: false,
+        ..Default::default()
+    });
+    game.sync_occupancy();
+    
+    assert_eq!(game.column_rest_cell(0), Some(GridPos::new(0, 3)));
+    assert_eq!(game.column_rest_cell(1), Some(GridPos::new(1, 4)));
+}
+
+#[test]
+fn test_pickup_spawning_is_capped() {
+    let mut game = test_game();
+    
+    for _ in 0..(PICKUP_SPAWN_INTERVAL * 20) {
+        game.handle_pickup_spawning();
+    }
+    
+    assert!(!game.pickups.is_empty());
+    assert!(game.pickups.len() <= MAX_PICKUPS);
+}
+
+#[test]
+fn test_block_crushes_pickup() {
+    let mut game = test_game();
+    game.pickups.push(Pickup::coin(GridPos::new(0, 4)));
+    game.blocks.push(Block::new(GridPos::new(0, 4)));
+    game.sync_occupancy();
+    
+    game.crush_pickups();
+    assert!(game.pickups.is_empty());
+}
@@ -0,0 +1,56 @@
+use rust_stackattack::core::block::{Block, BlockKind};
+use rust_stackattack::core::game::GameState;
+use rust_stackattack::core::types::{GameConfig, GridPos};
+use rust_stackattack::platform::pixels::CellPixels;
+use rust_stackattack::platform::render::{block_color, PIT_COLOR, PLAYER_COLOR};
+
+fn test_game() -> GameState {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 5,
+        grid_height: 4,
+        seed: Some(3),
+        pits: vec![0],
+        ..Default::default()
+    }).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    game.pickups.clear();
+    game
+}
+
+fn rgba(color: rust_stackattack::core::types::Color) -> [u8; 4] {
+    let channel = |value: f32| (value * 255.0).round() as u8;
+    [channel(color.r), channel(color.g), channel(color.b), 255]
+}
+
+#[test]
+fn test_every_cell_is_one_pixel_under_the_score_bar() {
+    let mut game = test_game();
+    game.blocks.push(Block { position: GridPos::new(4, 3), kind: BlockKind::Ice, falling: false, ..Default::default() });
+    game.sync_occupancy();
+    let mut pixels = CellPixels::new();
+    pixels.paint(&game);
+
+    assert_eq!((pixels.width, pixels.height), (5, 5));
+    assert_eq!(pixels.rgba.len(), 5 * 5 * 4);
+    assert_eq!(pixels.pixel(1, 0), [0, 0, 255, 255]);
+    assert_eq!(pixels.pixel(4, 4), rgba(block_color(BlockKind::Ice)));
+    assert_eq!(pixels.pixel(0, 4), rgba(PIT_COLOR));
+    let GridPos { x, y } = game.player.position;
+    assert_eq!(pixels.pixel(x, y + 1), rgba(PLAYER_COLOR));
+    assert_eq!(pixels.pixel(2, 2), [255, 255, 255, 255]);
+}
+
+#[test]
+fn test_painting_again_follows_the_board() {
+    let mut game = test_game();
+    let mut pixels = CellPixels::new();
+    pixels.paint(&game);
+    let GridPos { x, y } = game.player.position;
+    game.player.position = GridPos::new(x - 1, y);
+    pixels.paint(&game);
+
+    assert_eq!(pixels.rgba.len(), 5 * 5 * 4);
+    assert_eq!(pixels.pixel(x - 1, y + 1), rgba(PLAYER_COLOR));
+    assert_ne!(pixels.pixel(x, y + 1), rgba(PLAYER_COLOR));
+}
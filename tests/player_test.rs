@@ -1,14 +1,19 @@
 use rust_stackattack::core::player::Player;
-use rust_stackattack::core::block::Block;
+use rust_stackattack::core::block::{Block, BlockKind, Shape};
+use rust_stackattack::core::terrain::{Terrain, Tile};
+use rust_stackattack::core::types::{ConfigError, Direction, GridPos, PlayerTiming};
+
+mod common;
+use common::settled;
 
 #[test]
 fn test_player_creation() {
     let grid_size = 10;
-    let player = Player::new(grid_size);
+    let player = Player::new(grid_size, grid_size);
     
     // Player should start at bottom middle of grid
-    assert_eq!(player.position.0, grid_size / 2 - 1); // For even grid size
-    assert_eq!(player.position.1, grid_size - player.body_size);
+    assert_eq!(player.position.x, grid_size / 2 - 1); // For even grid size
+    assert_eq!(player.position.y, grid_size - player.body_size);
     
     // Player should start on ground
     assert!(!player.in_air);
@@ -18,40 +23,41 @@ fn test_player_creation() {
 #[test]
 fn test_player_jump() {
     let grid_size = 10;
-    let mut player = Player::new(grid_size);
-    let initial_y = player.position.1;
+    let mut player = Player::new(grid_size, grid_size);
+    let initial_y = player.position.y;
     
     // Player should be able to jump
-    player.jump();
+    player.jump(&[]);
     assert!(player.in_air);
-    assert_eq!(player.position.1, initial_y - 1);
+    assert_eq!(player.position.y, initial_y - 1);
     
     // Player should not be able to jump again while in air
-    let air_y = player.position.1;
-    player.jump();
-    assert_eq!(player.position.1, air_y); // Position shouldn't change
+    let air_y = player.position.y;
+    player.jump(&[]);
+    assert_eq!(player.position.y, air_y); // Position shouldn't change
 }
 
 #[test]
 fn test_player_has_support() {
     let grid_size = 10;
-    let player = Player::new(grid_size);
+    let player = Player::new(grid_size, grid_size);
     let mut blocks = Vec::new();
     
     // Player should have support from the ground
     assert!(player.has_support(&blocks, grid_size));
     
     // Create a player in mid-air with no blocks below
-    let mut mid_air_player = Player::new(grid_size);
-    mid_air_player.position.1 = grid_size / 2;
+    let mut mid_air_player = Player::new(grid_size, grid_size);
+    mid_air_player.position.y = grid_size / 2;
     assert!(!mid_air_player.has_support(&blocks, grid_size));
     
     // Add a block below the player for support
     blocks.push(Block {
-        position: (mid_air_player.position.0, mid_air_player.position.1 + mid_air_player.body_size),
+        position: GridPos::new(mid_air_player.position.x, mid_air_player.position.y + mid_air_player.body_size),
         falling: false,
         carried: false,
         carrying_direction: None,
+        ..Default::default()
     });
     assert!(mid_air_player.has_support(&blocks, grid_size));
 }
@@ -59,61 +65,61 @@ fn test_player_has_support() {
 #[test]
 fn test_player_horizontal_movement() {
     let grid_size = 10;
-    let mut player = Player::new(grid_size);
+    let mut player = Player::new(grid_size, grid_size);
     let mut blocks = Vec::new();
     
     // Get initial position
-    let initial_position = player.position.0;
+    let initial_position = player.position.x;
     
     // Test moving left
     player.move_left(&mut blocks);
-    assert_eq!(player.position.0, initial_position - 1, "Player should move left by one block");
+    assert_eq!(player.position.x, initial_position - 1, "Player should move left by one block");
     
     // Test moving right from the new position
-    let position_after_left = player.position.0;
+    let position_after_left = player.position.x;
     player.move_right(&mut blocks);
-    assert_eq!(player.position.0, position_after_left + 1, "Player should move right by one block");
+    assert_eq!(player.position.x, position_after_left + 1, "Player should move right by one block");
 }
 
 // Test single left movement from starting position
 #[test]
 fn test_player_move_left_only() {
     let grid_size = 10;
-    let mut player = Player::new(grid_size);
+    let mut player = Player::new(grid_size, grid_size);
     let mut blocks = Vec::new();
     
     // Get initial position
-    let initial_position = player.position.0;
+    let initial_position = player.position.x;
     
     // Test moving left
     player.move_left(&mut blocks);
-    assert_eq!(player.position.0, initial_position - 1, "Player should move left by one block");
+    assert_eq!(player.position.x, initial_position - 1, "Player should move left by one block");
 }
 
 // Test single right movement from starting position
 #[test]
 fn test_player_move_right_only() {
     let grid_size = 10;
-    let mut player = Player::new(grid_size);
+    let mut player = Player::new(grid_size, grid_size);
     let mut blocks = Vec::new();
     
     // Get initial position
-    let initial_position = player.position.0;
+    let initial_position = player.position.x;
     
     // Test moving right
     player.move_right(&mut blocks);
-    assert_eq!(player.position.0, initial_position + 1, "Player should move right by one block");
+    assert_eq!(player.position.x, initial_position + 1, "Player should move right by one block");
 }
 
 // Test multiple left movements
 #[test]
 fn test_player_multiple_left_movements() {
     let grid_size = 10;
-    let mut player = Player::new(grid_size);
+    let mut player = Player::new(grid_size, grid_size);
     let mut blocks = Vec::new();
     
     // Get initial position
-    let initial_position = player.position.0;
+    let initial_position = player.position.x;
     
     // Ensure we don't go out of bounds
     let move_count = initial_position.min(3);
@@ -121,7 +127,7 @@ fn test_player_multiple_left_movements() {
     // Move left multiple times
     for i in 1..=move_count {
         player.move_left(&mut blocks);
-        assert_eq!(player.position.0, initial_position - i, 
+        assert_eq!(player.position.x, initial_position - i, 
                    "Player position incorrect after {} left moves", i);
     }
 }
@@ -130,11 +136,11 @@ fn test_player_multiple_left_movements() {
 #[test]
 fn test_player_multiple_right_movements() {
     let grid_size = 10;
-    let mut player = Player::new(grid_size);
+    let mut player = Player::new(grid_size, grid_size);
     let mut blocks = Vec::new();
     
     // Get initial position
-    let initial_position = player.position.0;
+    let initial_position = player.position.x;
     
     // Ensure we don't go out of bounds
     let move_count = 3.min(grid_size - 1 - initial_position);
@@ -142,7 +148,7 @@ fn test_player_multiple_right_movements() {
     // Move right multiple times
     for i in 1..=move_count {
         player.move_right(&mut blocks);
-        assert_eq!(player.position.0, initial_position + i, 
+        assert_eq!(player.position.x, initial_position + i, 
                    "Player position incorrect after {} right moves", i);
     }
 }
@@ -151,11 +157,11 @@ fn test_player_multiple_right_movements() {
 #[test]
 fn test_player_left_boundary() {
     let grid_size = 10;
-    let mut player = Player::new(grid_size);
+    let mut player = Player::new(grid_size, grid_size);
     let mut blocks = Vec::new();
     
     // Get initial position
-    let initial_position = player.position.0;
+    let initial_position = player.position.x;
     
     // Move player to the left edge (move exactly initial_position times)
     for _ in 0..initial_position {
@@ -163,22 +169,22 @@ fn test_player_left_boundary() {
     }
     
     // Now player should be at position 0
-    assert_eq!(player.position.0, 0, "Player should be at the left boundary");
+    assert_eq!(player.position.x, 0, "Player should be at the left boundary");
     
     // Try to move left once more (should be blocked by boundary)
     player.move_left(&mut blocks);
-    assert_eq!(player.position.0, 0, "Player should not move beyond the left boundary");
+    assert_eq!(player.position.x, 0, "Player should not move beyond the left boundary");
 }
 
 // Test boundary condition - right edge
 #[test]
 fn test_player_right_boundary() {
     let grid_size = 10;
-    let mut player = Player::new(grid_size);
+    let mut player = Player::new(grid_size, grid_size);
     let mut blocks = Vec::new();
     
     // Get initial position
-    let initial_position = player.position.0;
+    let initial_position = player.position.x;
     
     // Calculate how many moves needed to reach the right boundary
     let moves_needed = grid_size - 1 - initial_position;
@@ -189,22 +195,22 @@ fn test_player_right_boundary() {
     }
     
     // Now player should be at the right boundary
-    assert_eq!(player.position.0, grid_size - 1, "Player should be at the right boundary");
+    assert_eq!(player.position.x, grid_size - 1, "Player should be at the right boundary");
     
     // Try to move right once more (should be blocked by boundary)
     player.move_right(&mut blocks);
-    assert_eq!(player.position.0, grid_size - 1, "Player should not move beyond the right boundary");
+    assert_eq!(player.position.x, grid_size - 1, "Player should not move beyond the right boundary");
 }
 
 // Test movement during fall delay
 #[test]
 fn test_player_movement_during_fall_delay() {
     let grid_size = 10;
-    let mut player = Player::new(grid_size);
+    let mut player = Player::new(grid_size, grid_size);
     let mut blocks = Vec::new();
     
     // Put player in mid-air with no support below to trigger fall delay
-    player.position.1 = grid_size / 2;
+    player.position.y = grid_size / 2;
     
     // Make sure player is not already in "in_air" state from jumping
     player.in_air = false;
@@ -214,11 +220,11 @@ fn test_player_movement_during_fall_delay() {
     player.update_falling_state(&blocks, grid_size);
     
     // Try to move immediately after starting fall delay
-    let position_before_move = player.position.0;
+    let position_before_move = player.position.x;
     player.move_left(&mut blocks);
     
     // Player shouldn't be able to move horizontally during fall delay
-    assert_eq!(player.position.0, position_before_move, 
+    assert_eq!(player.position.x, position_before_move, 
                "Player should not move horizontally during fall delay");
 }
 
@@ -226,29 +232,29 @@ fn test_player_movement_during_fall_delay() {
 #[test]
 fn test_player_alternating_movement() {
     let grid_size = 10;
-    let mut player = Player::new(grid_size);
+    let mut player = Player::new(grid_size, grid_size);
     let mut blocks = Vec::new();
     
-    let initial_position = player.position.0;
+    let initial_position = player.position.x;
     
     // Move left
     player.move_left(&mut blocks);
-    let pos_after_left = player.position.0;
+    let pos_after_left = player.position.x;
     assert_eq!(pos_after_left, initial_position - 1, "Player should move left by one block");
     
     // Move right
     player.move_right(&mut blocks);
-    let pos_after_right = player.position.0;
+    let pos_after_right = player.position.x;
     assert_eq!(pos_after_right, pos_after_left + 1, "Player should move right by one block");
     
     // Move left again
     player.move_left(&mut blocks);
-    let pos_after_left_again = player.position.0;
+    let pos_after_left_again = player.position.x;
     assert_eq!(pos_after_left_again, pos_after_right - 1, "Player should move left by one block again");
     
     // Move right again
     player.move_right(&mut blocks);
-    let pos_after_right_again = player.position.0;
+    let pos_after_right_again = player.position.x;
     assert_eq!(pos_after_right_again, pos_after_left_again + 1, "Player should move right by one block again");
 }
 
@@ -256,41 +262,41 @@ fn test_player_alternating_movement() {
 #[test]
 fn test_basic_horizontal_movement() {
     let grid_size = 10;
-    let mut player = Player::new(grid_size);
+    let mut player = Player::new(grid_size, grid_size);
     let mut blocks = Vec::new();
     
     // Store initial position
-    let initial_x = player.position.0;
+    let initial_x = player.position.x;
     
     // Move left and verify
     player.move_left(&mut blocks);
-    let left_x = player.position.0;
+    let left_x = player.position.x;
     assert_eq!(left_x, initial_x - 1, "Player should move left by one");
     
     // Move right to return to initial position
     player.move_right(&mut blocks);
-    assert_eq!(player.position.0, initial_x, "Player should return to initial position");
+    assert_eq!(player.position.x, initial_x, "Player should return to initial position");
 }
 
 // Debug test with detailed position tracking
 #[test]
 fn test_debug_horizontal_movement() {
     let grid_size = 10;
-    let mut player = Player::new(grid_size);
+    let mut player = Player::new(grid_size, grid_size);
     let mut blocks = Vec::new();
     
     // Store initial position
-    let initial_x = player.position.0;
+    let initial_x = player.position.x;
     println!("Initial position: {}", initial_x);
     
     // Move left and verify
     player.move_left(&mut blocks);
-    let after_left = player.position.0;
+    let after_left = player.position.x;
     println!("After move_left: {}", after_left);
     
     // Move right and verify
     player.move_right(&mut blocks);
-    let after_right = player.position.0;
+    let after_right = player.position.x;
     println!("After move_right: {}", after_right);
     
     // Check if player returned to original position
@@ -300,11 +306,11 @@ fn test_debug_horizontal_movement() {
 #[test]
 fn test_player_falling_state() {
     let grid_size = 10;
-    let mut player = Player::new(grid_size);
+    let mut player = Player::new(grid_size, grid_size);
     let mut blocks = Vec::new();
     
     // Place player in mid-air
-    player.position.1 = 5;
+    player.position.y = 5;
     player.in_air = false; // Not in air due to jump
     player.is_falling = false; // Not yet falling
     
@@ -323,16 +329,17 @@ fn test_player_falling_state() {
     assert!(player.is_falling);
     
     // Test applying gravity
-    let initial_y = player.position.1;
+    let initial_y = player.position.y;
     player.apply_gravity();
-    assert_eq!(player.position.1, initial_y + 1);
+    assert_eq!(player.position.y, initial_y + 1);
     
     // Test landing when block appears beneath
     blocks.push(Block {
-        position: (player.position.0, player.position.1 + player.body_size),
+        position: GridPos::new(player.position.x, player.position.y + player.body_size),
         falling: false,
         carried: false,
         carrying_direction: None,
+        ..Default::default()
     });
     
     player.land(&blocks, grid_size);
@@ -342,21 +349,21 @@ fn test_player_falling_state() {
 #[test]
 fn test_player_fall_delay_prevents_movement() {
     let grid_size = 10;
-    let mut player = Player::new(grid_size);
+    let mut player = Player::new(grid_size, grid_size);
     let mut blocks = Vec::new();
     
     // Place player in mid-air
-    player.position = (5, 5);
+    player.position = GridPos::new(5, 5);
     
     // Update falling state should start fall delay
     player.update_falling_state(&blocks, grid_size);
     
     // Try to move during fall delay
-    let initial_x = player.position.0;
+    let initial_x = player.position.x;
     player.move_left(&mut blocks);
     
     // Position should remain unchanged
-    assert_eq!(player.position.0, initial_x);
+    assert_eq!(player.position.x, initial_x);
     
     // Complete the fall delay cycle
     for _ in 0..3 {
@@ -368,21 +375,22 @@ fn test_player_fall_delay_prevents_movement() {
     
     // Movement should now be possible
     player.move_left(&mut blocks);
-    assert_eq!(player.position.0, initial_x - 1);
+    assert_eq!(player.position.x, initial_x - 1);
 }
 
 #[test]
 fn test_player_release_carried_blocks() {
     let grid_size = 10;
-    let player = Player::new(grid_size);  // Removed 'mut' as it's not needed
+    let player = Player::new(grid_size, grid_size);  // Removed 'mut' as it's not needed
     
     // Create a carried block
     let mut blocks = vec![
         Block {
-            position: (player.position.0, player.position.1),
+            position: GridPos::new(player.position.x, player.position.y),
             falling: false,
             carried: true,
-            carrying_direction: Some(1),  // Being carried rightward
+            carrying_direction: Some(Direction::Right),  // Being carried rightward
+            ..Default::default()
         }
     ];
     
@@ -397,18 +405,18 @@ fn test_player_release_carried_blocks() {
     // Set up another carried block
     blocks[0].falling = false;
     blocks[0].carried = true;
-    blocks[0].carrying_direction = Some(1);
+    blocks[0].carrying_direction = Some(Direction::Right);
     
     // Player still moving in the carrying direction
-    player.release_carried_blocks(&mut blocks, Some(1));
+    player.release_carried_blocks(&mut blocks, Some(Direction::Right));
     
     // Block should still be carried
     assert!(!blocks[0].falling);
     assert!(blocks[0].carried);
-    assert_eq!(blocks[0].carrying_direction, Some(1));
+    assert_eq!(blocks[0].carrying_direction, Some(Direction::Right));
     
     // Player changes direction
-    player.release_carried_blocks(&mut blocks, Some(-1));
+    player.release_carried_blocks(&mut blocks, Some(Direction::Left));
     
     // Block should now be falling
     assert!(blocks[0].falling);
@@ -419,260 +427,271 @@ fn test_player_release_carried_blocks() {
 #[test]
 fn test_player_pushing_single_block() {
     let grid_size = 10;
-    let mut player = Player::new(grid_size);
+    let mut player = Player::new(grid_size, grid_size);
     let mut blocks = Vec::new();
     
     // Position player
-    player.position = (5, 8);
+    player.position = GridPos::new(5, 8);
     
     // Place a block to the right of the player
     blocks.push(Block {
-        position: (6, 8),
+        position: GridPos::new(6, 8),
         falling: false,
         carried: false,
         carrying_direction: None,
+        ..Default::default()
     });
     
     // Try to move right (should push the block)
     player.move_right(&mut blocks);
     
     // Player should have moved
-    assert_eq!(player.position.0, 6);
+    assert_eq!(player.position.x, 6);
     
     // Block should have been pushed
-    assert_eq!(blocks[0].position.0, 7);
+    assert_eq!(blocks[0].position.x, 7);
 }
 
 #[test]
 fn test_player_pushing_stack_of_blocks() {
     let grid_size = 10;
-    let mut player = Player::new(grid_size);
+    let mut player = Player::new(grid_size, grid_size);
     let mut blocks = Vec::new();
     
     // Position player
-    player.position = (5, 8);
+    player.position = GridPos::new(5, 8);
     
     // Create a stack of blocks to the right
     blocks.push(Block {
-        position: (6, 8), // Next to player
+        position: GridPos::new(6, 8), // Next to player
         falling: false,
         carried: false,
         carrying_direction: None,
+        ..Default::default()
     });
     
     blocks.push(Block {
-        position: (6, 7), // Above the first block
+        position: GridPos::new(6, 7), // Above the first block
         falling: false,
         carried: false,
         carrying_direction: None,
+        ..Default::default()
     });
     
     blocks.push(Block {
-        position: (6, 6), // Top of stack
+        position: GridPos::new(6, 6), // Top of stack
         falling: false,
         carried: false,
         carrying_direction: None,
+        ..Default::default()
     });
     
     // Try to move right (should push the entire stack)
     player.move_right(&mut blocks);
     
     // Player should have moved
-    assert_eq!(player.position.0, 6);
+    assert_eq!(player.position.x, 6);
     
     // All blocks should have been pushed
-    assert_eq!(blocks[0].position.0, 7); // Bottom block
-    assert_eq!(blocks[1].position.0, 7); // Middle block
-    assert_eq!(blocks[2].position.0, 7); // Top block
+    assert_eq!(blocks[0].position.x, 7); // Bottom block
+    assert_eq!(blocks[1].position.x, 7); // Middle block
+    assert_eq!(blocks[2].position.x, 7); // Top block
 }
 
 #[test]
 fn test_player_cannot_push_against_boundary() {
     let grid_size = 10;
-    let mut player = Player::new(grid_size);
+    let mut player = Player::new(grid_size, grid_size);
     let mut blocks = Vec::new();
     
     // Position player at right boundary
-    player.position = (grid_size - 2, 8);
+    player.position = GridPos::new(grid_size - 2, 8);
     
     // Place a block to the right of the player, against the boundary
     blocks.push(Block {
-        position: (grid_size - 1, 8),
+        position: GridPos::new(grid_size - 1, 8),
         falling: false,
         carried: false,
         carrying_direction: None,
+        ..Default::default()
     });
     
     // Try to move right (should fail as block is against boundary)
     player.move_right(&mut blocks);
     
     // Player should not have moved
-    assert_eq!(player.position.0, grid_size - 2);
+    assert_eq!(player.position.x, grid_size - 2);
     
     // Block should not have moved
-    assert_eq!(blocks[0].position.0, grid_size - 1);
+    assert_eq!(blocks[0].position.x, grid_size - 1);
 }
 
 #[test]
 fn test_player_cannot_push_against_another_block() {
     let grid_size = 10;
-    let mut player = Player::new(grid_size);
+    let mut player = Player::new(grid_size, grid_size);
     let mut blocks = Vec::new();
     
     // Position player
-    player.position = (5, 8);
+    player.position = GridPos::new(5, 8);
     
     // Place a block to the right of the player
     blocks.push(Block {
-        position: (6, 8),
+        position: GridPos::new(6, 8),
         falling: false,
         carried: false,
         carrying_direction: None,
+        ..Default::default()
     });
     
     // Place another block to the right of the first block (blocking movement)
     blocks.push(Block {
-        position: (7, 8),
+        position: GridPos::new(7, 8),
         falling: false,
         carried: false,
         carrying_direction: None,
+        ..Default::default()
     });
     
     // Try to move right (should fail as second block blocks the push)
     player.move_right(&mut blocks);
     
     // Player should not have moved
-    assert_eq!(player.position.0, 5);
+    assert_eq!(player.position.x, 5);
     
     // First block should not have moved
-    assert_eq!(blocks[0].position.0, 6);
+    assert_eq!(blocks[0].position.x, 6);
 }
 
 #[test]
 fn test_player_interaction_with_falling_block() {
     let grid_size = 10;
-    let mut player = Player::new(grid_size);
+    let mut player = Player::new(grid_size, grid_size);
     let mut blocks = Vec::new();
     
     // Position player
-    player.position = (5, 8);
+    player.position = GridPos::new(5, 8);
     
     // Place a falling block to the right of the player
     blocks.push(Block {
-        position: (6, 8),
+        position: GridPos::new(6, 8),
         falling: true,
         carried: false,
         carrying_direction: None,
+        ..Default::default()
     });
     
     // Try to move right (should move the block and mark it as carried)
     player.move_right(&mut blocks);
     
     // Player should have moved
-    assert_eq!(player.position.0, 6);
+    assert_eq!(player.position.x, 6);
     
     // Block should have been moved and marked as carried
-    assert_eq!(blocks[0].position.0, 7);
+    assert_eq!(blocks[0].position.x, 7);
     assert!(blocks[0].carried);
-    assert_eq!(blocks[0].carrying_direction, Some(1)); // Carried rightward
+    assert_eq!(blocks[0].carrying_direction, Some(Direction::Right)); // Carried rightward
 }
 
 #[test]
 fn test_find_pushable_blocks() {
     let grid_size = 10;
-    let mut player = Player::new(grid_size);
+    let mut player = Player::new(grid_size, grid_size);
     let mut blocks = Vec::new();
     
     // Position player
-    player.position = (5, 7); // Player occupies y=7,8
+    player.position = GridPos::new(5, 7); // Player occupies y=7,8
     
     // Create complex stack scenario:
     // A stack directly next to player and a disconnected block above
     blocks.push(Block {
-        position: (6, 7), // Next to player body (connected)
+        position: GridPos::new(6, 7), // Next to player body (connected)
         falling: false,
         carried: false,
         carrying_direction: None,
+        ..Default::default()
     });
     
     blocks.push(Block {
-        position: (6, 6), // Above the first block (connected)
+        position: GridPos::new(6, 6), // Above the first block (connected)
         falling: false,
         carried: false,
         carrying_direction: None,
+        ..Default::default()
     });
     
     blocks.push(Block {
-        position: (6, 4), // Floating above with gap (disconnected)
+        position: GridPos::new(6, 4), // Floating above with gap (disconnected)
         falling: false,
         carried: false,
         carrying_direction: None,
+        ..Default::default()
     });
     
     // Try to move right (should push only the connected blocks)
     player.move_right(&mut blocks);
     
     // Player should have moved
-    assert_eq!(player.position.0, 6);
+    assert_eq!(player.position.x, 6);
     
     // Connected blocks should have been pushed
-    assert_eq!(blocks[0].position.0, 7); // Was at player body level
-    assert_eq!(blocks[1].position.0, 7); // Was above first block
+    assert_eq!(blocks[0].position.x, 7); // Was at player body level
+    assert_eq!(blocks[1].position.x, 7); // Was above first block
     
     // Disconnected block should not have moved
-    assert_eq!(blocks[2].position.0, 6); // Disconnected block
+    assert_eq!(blocks[2].position.x, 6); // Disconnected block
 }
 
 #[test]
 fn test_player_moving_after_falling() {
     let grid_size = 10;
-    let mut player = Player::new(grid_size);
+    let mut player = Player::new(grid_size, grid_size);
     let mut blocks = Vec::new();
     
     // Position player in mid-air and set falling state
-    player.position = (5, 5);
+    player.position = GridPos::new(5, 5);
     player.is_falling = true;
     
     // Record initial position
-    let initial_x = player.position.0;
+    let initial_x = player.position.x;
     
     // Try horizontal movement while falling (should work)
     player.move_left(&mut blocks);
     
     // Player should have moved horizontally even while falling
-    assert_eq!(player.position.0, initial_x - 1);
+    assert_eq!(player.position.x, initial_x - 1);
     assert!(player.is_falling); // Still falling
 }
 
 #[test]
 fn test_player_cannot_move_offscreen() {
     let grid_size = 10;
-    let mut player = Player::new(grid_size);
+    let mut player = Player::new(grid_size, grid_size);
     let mut blocks = Vec::new();
     
     // Position player at left edge
-    player.position = (0, 8);
+    player.position = GridPos::new(0, 8);
     
     // Try to move left (should be blocked)
     player.move_left(&mut blocks);
     
     // Player should not have moved
-    assert_eq!(player.position.0, 0);
+    assert_eq!(player.position.x, 0);
     
     // Position player at right edge
-    player.position = (grid_size - 1, 8);
+    player.position = GridPos::new(grid_size - 1, 8);
     
     // Try to move right (should be blocked)
     player.move_right(&mut blocks);
     
     // Player should not have moved
-    assert_eq!(player.position.0, grid_size - 1);
+    assert_eq!(player.position.x, grid_size - 1);
 }
 
 #[test]
 fn test_player_new() {
     let grid_size = 10;
-    let player = Player::new(grid_size);
+    let player = Player::new(grid_size, grid_size);
     assert_eq!(player.position, (4, 8)); // grid_size/2 - 1 = 4, grid_size - body_size = 10 - 2 = 8
     assert!(!player.in_air);
     assert!(!player.is_falling);
@@ -682,27 +701,27 @@ fn test_player_new() {
 #[test]
 fn test_player_update_jump() {
     let grid_size = 10;
-    let mut player = Player::new(grid_size);
-    player.jump(); // Sets in_air=true, jump_counter=1, just_jumped=true
+    let mut player = Player::new(grid_size, grid_size);
+    player.jump(&[]); // Sets in_air=true, jump_counter=1, just_jumped=true
 
     // First update after jump: resets just_jumped, counter remains 1
-    player.update_jump();
+    player.update_jump(&[]);
     assert!(player.in_air); // Still in air
 
     // Second update: decrements counter to 0
-    player.update_jump();
+    player.update_jump(&[]);
     assert!(player.in_air); // Still in air until land() is called
 
     // Third update: counter stays 0
-    player.update_jump();
+    player.update_jump(&[]);
     assert!(player.in_air);
 }
 
 #[test]
 fn test_player_update_falling_state_and_delay() {
     let grid_size = 5;
-    let mut player = Player::new(grid_size);
-    player.position = (2, 1); // Move player up
+    let mut player = Player::new(grid_size, grid_size);
+    player.position = GridPos::new(2, 1); // Move player up
 
     let blocks = [];
 
@@ -716,7 +735,7 @@ fn test_player_update_falling_state_and_delay() {
     assert!(player.is_falling); // Should be falling now
 
     // Reset and test with support
-    player.position = (2, 3); // Back on ground
+    player.position = GridPos::new(2, 3); // Back on ground
     player.is_falling = false;
     player.update_falling_state(&blocks, grid_size);
     assert!(!player.is_falling); // Should not be falling
@@ -725,21 +744,70 @@ fn test_player_update_falling_state_and_delay() {
 #[test]
 fn test_player_apply_gravity() {
     let grid_size = 5;
-    let mut player = Player::new(grid_size);
-    player.position = (2, 1);
+    let mut player = Player::new(grid_size, grid_size);
+    player.position = GridPos::new(2, 1);
     player.is_falling = true;
 
-    let initial_y = player.position.1;
+    let initial_y = player.position.y;
     player.apply_gravity();
-    assert_eq!(player.position.1, initial_y + 1);
+    assert_eq!(player.position.y, initial_y + 1);
 
     // Test gravity stops at bottom
-    player.position = (2, 3); // At bottom (grid_size - body_size)
+    player.position = GridPos::new(2, 3); // At bottom (grid_size - body_size)
     player.is_falling = true;
-    println!("Before apply_gravity (at bottom): y={}, is_falling={}, grid_size={}, body_size={}", player.position.1, player.is_falling, grid_size, player.body_size);
+    println!("Before apply_gravity (at bottom): y={}, is_falling={}, grid_size={}, body_size={}", player.position.y, player.is_falling, grid_size, player.body_size);
     player.apply_gravity(); // Should not go below grid_size - body_size
-    println!("After apply_gravity (at bottom): y={}, is_falling={}", player.position.1, player.is_falling);
-    assert_eq!(player.position.1, 3, "Player moved below bottom boundary"); // Stays at 3
+    println!("After apply_gravity (at bottom): y={}, is_falling={}", player.position.y, player.is_falling);
+    assert_eq!(player.position.y, 3, "Player moved below bottom boundary"); // Stays at 3
+}
+
+#[test]
+fn test_player_custom_jump_duration() {
+    let grid_size = 5;
+    let timing = PlayerTiming { jump_duration: 3, ..Default::default() };
+    let mut player = Player::with_timing(grid_size, grid_size, timing);
+    let blocks = [];
+    player.jump(&blocks);
+
+    // The player hangs in the air for three updates after the jump one
+    for _ in 0..3 {
+        player.update_jump(&blocks);
+        player.land(&blocks, grid_size);
+        assert!(player.in_air);
+    }
+    player.update_jump(&blocks);
+    player.land(&blocks, grid_size);
+    assert!(!player.in_air);
+}
+
+#[test]
+fn test_player_custom_fall_delay_and_gravity() {
+    let grid_size = 8;
+    let timing = PlayerTiming { fall_delay: 1, gravity_interval: 2, ..Default::default() };
+    let mut player = Player::with_timing(grid_size, grid_size, timing);
+    player.position = GridPos::new(2, 1);
+    let blocks = [];
+
+    // A one update delay starts the fall on the next update
+    player.update_falling_state(&blocks, grid_size);
+    player.update_fall_delay();
+    assert!(player.is_falling);
+
+    // Gravity moves the player one cell every second update
+    player.apply_gravity();
+    assert_eq!(player.position.y, 1);
+    player.apply_gravity();
+    assert_eq!(player.position.y, 2);
+}
+
+#[test]
+fn test_player_timing_validation() {
+    assert!(PlayerTiming::default().validate().is_ok());
+    let stalled = PlayerTiming { gravity_interval: 0, ..Default::default() };
+    assert_eq!(stalled.validate(), Err(ConfigError::GravityInterval));
+    assert_eq!(ConfigError::GravityInterval.to_string(), "gravity interval must be at least 1 update");
+    let frozen = PlayerTiming { fall_delay: 0, ..Default::default() };
+    assert_eq!(frozen.validate(), Err(ConfigError::FallDelay));
 }
 
 // Removing the failing test_player_land
@@ -751,107 +819,107 @@ fn test_player_apply_gravity() {
 #[test]
 fn test_player_move_left_right_simple() {
     let grid_size = 5;
-    let mut player = Player::new(grid_size); // Starts at (2, 3)
+    let mut player = Player::new(grid_size, grid_size); // Starts at (2, 3)
     let mut blocks = [];
 
     // Move right
     player.move_right(&mut blocks);
-    assert_eq!(player.position.0, 3);
+    assert_eq!(player.position.x, 3);
 
     // Move left
     player.move_left(&mut blocks);
-    assert_eq!(player.position.0, 2);
+    assert_eq!(player.position.x, 2);
 
     // Test boundary left
-    player.position = (0, 3);
+    player.position = GridPos::new(0, 3);
     player.move_left(&mut blocks);
-    assert_eq!(player.position.0, 0);
+    assert_eq!(player.position.x, 0);
 
     // Test boundary right
-    player.position = (4, 3); // grid_size - 1
+    player.position = GridPos::new(4, 3); // grid_size - 1
     player.move_right(&mut blocks);
-    assert_eq!(player.position.0, 4);
+    assert_eq!(player.position.x, 4);
 }
 
 #[test]
 fn test_player_move_blocked_by_wall() {
     let grid_size = 5;
-    let mut player = Player::new(grid_size); // Starts at (2, 3)
+    let mut player = Player::new(grid_size, grid_size); // Starts at (2, 3)
     let mut blocks = [
-        Block { position: (3, 3), falling: false, carried: false, carrying_direction: None }, // Block to the right (bottom part of player)
-        Block { position: (3, 2), falling: false, carried: false, carrying_direction: None }, // Block to the right (top part of player)
-        Block { position: (4, 3), falling: false, carried: false, carrying_direction: None }, // ADDED: Block to block the push at x=4
+        Block { position: GridPos::new(3, 3), falling: false, carried: false, carrying_direction: None, ..Default::default() }, // Block to the right (bottom part of player)
+        Block { position: GridPos::new(3, 2), falling: false, carried: false, carrying_direction: None, ..Default::default() }, // Block to the right (top part of player)
+        Block { position: GridPos::new(4, 3), falling: false, carried: false, carrying_direction: None, ..Default::default() }, // ADDED: Block to block the push at x=4
     ];
 
     // Try move right - should be blocked by block at (4,3)
     player.move_right(&mut blocks);
-    assert_eq!(player.position.0, 2, "Player moved when push should be blocked"); // Position shouldn't change
+    assert_eq!(player.position.x, 2, "Player moved when push should be blocked"); // Position shouldn't change
 
     // Try move left - setup requires a block at (0,y) and a blocking block at (-1, y) which is impossible
     // Let's test blocking left properly
-    player.position = (1,3); // Player at (1,3)
+    player.position = GridPos::new(1, 3); // Player at (1,3)
     let mut blocks_left = [
-        Block { position: (0, 3), falling: false, carried: false, carrying_direction: None }, // Block to push left
+        Block { position: GridPos::new(0, 3), falling: false, carried: false, carrying_direction: None, ..Default::default() }, // Block to push left
         // No need for a block at (-1, 3) as the boundary blocks it
     ];
     player.move_left(&mut blocks_left); // Try push against left boundary
-    assert_eq!(player.position.0, 1, "Player moved when pushing against left boundary");
+    assert_eq!(player.position.x, 1, "Player moved when pushing against left boundary");
 
     // Test pushing left against another block
-    player.position = (2, 3); // Player at (2, 3)
+    player.position = GridPos::new(2, 3); // Player at (2, 3)
     let mut blocks_left_blocked = [
-        Block { position: (1, 3), falling: false, carried: false, carrying_direction: None }, // Block to push left
-        Block { position: (0, 3), falling: false, carried: false, carrying_direction: None }, // Blocking block at x=0
+        Block { position: GridPos::new(1, 3), falling: false, carried: false, carrying_direction: None, ..Default::default() }, // Block to push left
+        Block { position: GridPos::new(0, 3), falling: false, carried: false, carrying_direction: None, ..Default::default() }, // Blocking block at x=0
     ];
     player.move_left(&mut blocks_left_blocked);
-    assert_eq!(player.position.0, 2, "Player moved when push left was blocked by another block");
+    assert_eq!(player.position.x, 2, "Player moved when push left was blocked by another block");
 }
 
 #[test]
 fn test_player_push_single_block() {
     let grid_size = 5;
-    let mut player = Player::new(grid_size); // Starts at (2, 3)
+    let mut player = Player::new(grid_size, grid_size); // Starts at (2, 3)
     let mut blocks = [
-        Block { position: (3, 3), falling: false, carried: false, carrying_direction: None }, // Block to the right
+        Block { position: GridPos::new(3, 3), falling: false, carried: false, carrying_direction: None, ..Default::default() }, // Block to the right
     ];
 
     // Push right
     player.move_right(&mut blocks);
-    assert_eq!(player.position.0, 3); // Player moves
-    assert_eq!(blocks[0].position.0, 4); // Block moves
+    assert_eq!(player.position.x, 3); // Player moves
+    assert_eq!(blocks[0].position.x, 4); // Block moves
 }
 
 #[test]
 fn test_player_push_block_column() {
     let grid_size = 5;
-    let mut player = Player::new(grid_size); // Starts at (2, 3), body at y=3, y=2
+    let mut player = Player::new(grid_size, grid_size); // Starts at (2, 3), body at y=3, y=2
     let mut blocks = [
-        Block { position: (3, 3), falling: false, carried: false, carrying_direction: None }, // Block right-bottom
-        Block { position: (3, 2), falling: false, carried: false, carrying_direction: None }, // Block right-top
-        Block { position: (3, 1), falling: false, carried: false, carrying_direction: None }, // Block above pushable column
+        Block { position: GridPos::new(3, 3), falling: false, carried: false, carrying_direction: None, ..Default::default() }, // Block right-bottom
+        Block { position: GridPos::new(3, 2), falling: false, carried: false, carrying_direction: None, ..Default::default() }, // Block right-top
+        Block { position: GridPos::new(3, 1), falling: false, carried: false, carrying_direction: None, ..Default::default() }, // Block above pushable column
     ];
 
     // Push right
     player.move_right(&mut blocks);
-    assert_eq!(player.position.0, 3); // Player moves
-    assert_eq!(blocks[0].position.0, 4); // Block at (3,3) moves
-    assert_eq!(blocks[1].position.0, 4); // Block at (3,2) moves
-    assert_eq!(blocks[2].position.0, 4); // Block at (3,1) moves (connected)
+    assert_eq!(player.position.x, 3); // Player moves
+    assert_eq!(blocks[0].position.x, 4); // Block at (3,3) moves
+    assert_eq!(blocks[1].position.x, 4); // Block at (3,2) moves
+    assert_eq!(blocks[2].position.x, 4); // Block at (3,1) moves (connected)
 }
 
 #[test]
 fn test_player_push_blocked_column() {
     let grid_size = 5;
-    let mut player = Player::new(grid_size); // Starts at (2, 3)
+    let mut player = Player::new(grid_size, grid_size); // Starts at (2, 3)
     let mut blocks = [
-        Block { position: (3, 3), falling: false, carried: false, carrying_direction: None }, // Block to push
-        Block { position: (4, 3), falling: false, carried: false, carrying_direction: None }, // Blocking block
+        Block { position: GridPos::new(3, 3), falling: false, carried: false, carrying_direction: None, ..Default::default() }, // Block to push
+        Block { position: GridPos::new(4, 3), falling: false, carried: false, carrying_direction: None, ..Default::default() }, // Blocking block
     ];
 
     // Push right - should be blocked
     player.move_right(&mut blocks);
-    assert_eq!(player.position.0, 2); // Player doesn't move
-    assert_eq!(blocks[0].position.0, 3); // Pushed block doesn't move
+    assert_eq!(player.position.x, 2); // Player doesn't move
+    assert_eq!(blocks[0].position.x, 3); // Pushed block doesn't move
 }
 
 // Removing the failing test_player_carry_falling_block
@@ -863,9 +931,9 @@ fn test_player_push_blocked_column() {
 #[test]
 fn test_player_release_carried_block_when_stopped() {
     let grid_size = 5;
-    let player = Player::new(grid_size); // Removed 'mut'
+    let player = Player::new(grid_size, grid_size); // Removed 'mut'
     let mut blocks = [
-        Block { position: (3, 2), falling: false, carried: true, carrying_direction: Some(1) }, // Carried block
+        Block { position: GridPos::new(3, 2), falling: false, carried: true, carrying_direction: Some(Direction::Right), ..Default::default() }, // Carried block
     ];
 
     // Player stops moving (current_direction is None)
@@ -878,13 +946,13 @@ fn test_player_release_carried_block_when_stopped() {
 #[test]
 fn test_player_release_carried_block_when_direction_changes() {
     let grid_size = 5;
-    let player = Player::new(grid_size); // Removed 'mut'
+    let player = Player::new(grid_size, grid_size); // Removed 'mut'
     let mut blocks = [
-        Block { position: (3, 2), falling: false, carried: true, carrying_direction: Some(1) }, // Carried right
+        Block { position: GridPos::new(3, 2), falling: false, carried: true, carrying_direction: Some(Direction::Right), ..Default::default() }, // Carried right
     ];
 
     // Player starts moving left (current_direction is -1)
-    player.release_carried_blocks(&mut blocks, Some(-1));
+    player.release_carried_blocks(&mut blocks, Some(Direction::Left));
     assert!(!blocks[0].carried);
     assert!(blocks[0].falling);
     assert_eq!(blocks[0].carrying_direction, None);
@@ -893,33 +961,33 @@ fn test_player_release_carried_block_when_direction_changes() {
 #[test]
 fn test_player_keeps_carrying_block_when_direction_matches() {
     let grid_size = 5;
-    let player = Player::new(grid_size); // Removed 'mut'
+    let player = Player::new(grid_size, grid_size); // Removed 'mut'
     let mut blocks = [
-        Block { position: (3, 2), falling: false, carried: true, carrying_direction: Some(1) }, // Carried right
+        Block { position: GridPos::new(3, 2), falling: false, carried: true, carrying_direction: Some(Direction::Right), ..Default::default() }, // Carried right
     ];
 
     // Player continues moving right (current_direction is 1)
-    player.release_carried_blocks(&mut blocks, Some(1));
+    player.release_carried_blocks(&mut blocks, Some(Direction::Right));
     assert!(blocks[0].carried); // Should still be carried
     assert!(!blocks[0].falling);
-    assert_eq!(blocks[0].carrying_direction, Some(1));
+    assert_eq!(blocks[0].carrying_direction, Some(Direction::Right));
 }
 
 #[test]
 fn test_player_starts_falling_after_walking_off_ledge() {
     let grid_size = 5;
-    let mut player = Player::new(grid_size); // Starts at (2, 3)
+    let mut player = Player::new(grid_size, grid_size); // Starts at (2, 3)
     let mut blocks = [
-        Block { position: (1, 3), falling: false, carried: false, carrying_direction: None }, // Block to the left for support
+        Block { position: GridPos::new(1, 3), falling: false, carried: false, carrying_direction: None, ..Default::default() }, // Block to the left for support
     ];
-    player.position = (1, 1); // Place player on the block (body at y=1, y=2)
+    player.position = GridPos::new(1, 1); // Place player on the block (body at y=1, y=2)
 
     // Verify initial support
     assert!(player.has_support(&blocks, grid_size));
 
     // Move right off the ledge
     player.move_right(&mut blocks);
-    assert_eq!(player.position.0, 2); // Player moved
+    assert_eq!(player.position.x, 2); // Player moved
     assert!(!player.is_falling); // Should not be falling immediately due to delay
 
     // Simulate update cycles for fall delay
@@ -929,3 +997,300 @@ fn test_player_starts_falling_after_walking_off_ledge() {
     }
     assert!(player.is_falling); // Should be falling now
 }
+
+// Test walking up a ramp from its low side
+#[test]
+fn test_player_walks_up_ramp() {
+    let grid_size = 10;
+    let mut player = Player::new(grid_size, grid_size);
+    let mut blocks = Vec::new();
+    let GridPos { x: start_x, y: start_y } = player.position;
+    
+    // Ramp right next to the player's feet, rising to the right
+    let mut terrain = Terrain::new(grid_size, grid_size);
+    terrain.set_tile(GridPos::new(start_x + 1, grid_size - 1), Tile::Ramp(Direction::Right));
+    player.set_terrain(terrain);
+    
+    player.move_right(&mut blocks);
+    
+    // Player should step onto the ramp without jumping
+    assert_eq!(player.position, (start_x + 1, start_y - 1));
+    assert!(!player.in_air);
+    assert!(player.has_support(&blocks, grid_size), "Ramp should support the player");
+    
+    // Walking on toward the low side steps back down
+    player.move_left(&mut blocks);
+    assert_eq!(player.position, (start_x, start_y));
+}
+
+// Test that a ramp facing the other way acts as a wall
+#[test]
+fn test_player_blocked_by_ramp_high_side() {
+    let grid_size = 10;
+    let mut player = Player::new(grid_size, grid_size);
+    let mut blocks = Vec::new();
+    let GridPos { x: start_x, y: start_y } = player.position;
+    
+    // Ramp rising to the left, so its high side faces the player
+    let mut terrain = Terrain::new(grid_size, grid_size);
+    terrain.set_tile(GridPos::new(start_x + 1, grid_size - 1), Tile::Ramp(Direction::Left));
+    player.set_terrain(terrain);
+    
+    player.move_right(&mut blocks);
+    assert_eq!(player.position, (start_x, start_y), "Player should not walk into the high side of a ramp");
+}
+
+// Test pushing a horizontal block moves both of its cells
+#[test]
+fn test_push_horizontal_block_as_unit() {
+    let grid_size = 10;
+    let mut player = Player::new(grid_size, grid_size);
+    let GridPos { x: start_x, y: start_y } = player.position;
+    let feet_y = start_y + 1;
+    
+    let mut blocks = vec![Block::with_shape(GridPos::new(start_x + 1, feet_y), Shape::Horizontal)];
+    blocks[0].falling = false;
+    
+    player.move_right(&mut blocks);
+    
+    assert_eq!(player.position.x, start_x + 1);
+    assert_eq!(blocks[0].position, (start_x + 2, feet_y));
+}
+
+// Test a horizontal block can't be pushed when its far cell is blocked
+#[test]
+fn test_push_horizontal_block_blocked_at_far_end() {
+    let grid_size = 10;
+    let mut player = Player::new(grid_size, grid_size);
+    let GridPos { x: start_x, y: start_y } = player.position;
+    let feet_y = start_y + 1;
+    
+    let mut blocks = vec![
+        Block::with_shape(GridPos::new(start_x + 1, feet_y), Shape::Horizontal),
+        Block::new(GridPos::new(start_x + 3, feet_y)),
+    ];
+    blocks[0].falling = false;
+    blocks[1].falling = false;
+    
+    player.move_right(&mut blocks);
+    
+    assert_eq!(player.position.x, start_x, "Player should not move");
+    assert_eq!(blocks[0].position, (start_x + 1, feet_y), "Block should not move");
+}
+
+// Test steel blocks can't be pushed
+#[test]
+fn test_steel_block_not_pushable() {
+    let grid_size = 10;
+    let mut player = Player::new(grid_size, grid_size);
+    let GridPos { x: start_x, y: start_y } = player.position;
+    
+    let mut blocks = vec![Block::with_kind(GridPos::new(start_x + 1, start_y + 1), BlockKind::Steel)];
+    blocks[0].falling = false;
+    
+    player.move_right(&mut blocks);
+    
+    assert_eq!(player.position.x, start_x, "Player should be stopped by steel");
+    assert_eq!(blocks[0].position, (start_x + 1, start_y + 1), "Steel block should not move");
+}
+
+// Test pushing an ice block starts it sliding
+#[test]
+fn test_pushed_ice_block_starts_sliding() {
+    let grid_size = 10;
+    let mut player = Player::new(grid_size, grid_size);
+    let GridPos { x: start_x, y: start_y } = player.position;
+    
+    let mut blocks = vec![Block::with_kind(GridPos::new(start_x + 1, start_y + 1), BlockKind::Ice)];
+    blocks[0].falling = false;
+    
+    player.move_right(&mut blocks);
+    
+    assert_eq!(blocks[0].position.x, start_x + 2);
+    assert_eq!(blocks[0].sliding, Some(Direction::Right));
+}
+
+#[test]
+fn test_carried_block_rides_along_and_is_dropped_ahead() {
+    let mut player = Player::new(6, 6);
+    let GridPos { x, y } = player.position;
+    let mut blocks = Vec::new();
+    
+    assert!(!player.can_catch(&Block::with_kind(GridPos::new(x, 0), BlockKind::Steel)));
+    assert!(!player.can_catch(&Block::with_shape(GridPos::new(x, 0), Shape::Horizontal)));
+    let block = Block::new(GridPos::new(x, 0));
+    assert!(player.can_catch(&block));
+    player.catch(block);
+    assert!(player.occupies(GridPos::new(x, y - 1)));
+    
+    // The block moves with the player and is put down on the side it last moved toward
+    player.move_left(&mut blocks);
+    assert_eq!(player.carried_cell(), Some(GridPos::new(x - 1, y - 1)));
+    assert!(player.drop_carried_block(&mut blocks));
+    assert!(player.carrying.is_none());
+    assert_eq!(blocks[0].position, (x - 2, y - 1));
+    assert!(blocks[0].falling);
+}
+
+#[test]
+fn test_carried_block_blocks_moves_and_jumps_without_room() {
+    let mut player = Player::new(6, 6);
+    let GridPos { x, y } = player.position;
+    player.catch(Block::new(GridPos::new(x, 0)));
+    
+    // A block level with the carried one stops the player walking under it
+    let mut blocks = vec![Block { position: GridPos::new(x + 1, y - 1), falling: false, ..Default::default() }];
+    player.move_right(&mut blocks);
+    assert_eq!(player.position, (x, y));
+    
+    // A block above the carried one leaves no room to jump
+    assert!(player.has_jump_room(&blocks));
+    blocks.push(Block { position: GridPos::new(x, y - 2), falling: false, ..Default::default() });
+    assert!(!player.has_jump_room(&blocks));
+}
+
+#[test]
+fn test_auto_climb_steps_onto_a_single_block() {
+    let mut player = Player::new(6, 6).with_auto_climb(true);
+    let GridPos { x, y } = player.position;
+    let mut blocks = vec![settled((x + 1, y + 1))];
+    
+    player.move_right(&mut blocks);
+    assert_eq!(player.position, (x + 1, y - 1));
+    assert_eq!(blocks[0].position, (x + 1, y + 1));
+    assert!(player.has_support(&blocks, 6));
+    
+    // Without the flag the same block is pushed
+    let mut player = Player::new(6, 6);
+    player.move_right(&mut blocks);
+    assert_eq!(player.position, (x + 1, y));
+    assert_eq!(blocks[0].position, (x + 2, y + 1));
+}
+
+#[test]
+fn test_auto_climb_stops_at_two_high_walls() {
+    let mut player = Player::new(6, 6).with_auto_climb(true);
+    let GridPos { x, y } = player.position;
+    let mut blocks = vec![settled((x + 1, y + 1)), settled((x + 1, y)), settled((x + 2, y + 1)), settled((x + 2, y))];
+    
+    player.move_right(&mut blocks);
+    assert_eq!(player.position, (x, y));
+}
+
+#[test]
+fn test_auto_climb_respects_the_grid_edges() {
+    // No row above to climb into
+    let mut player = Player::new(3, 2).with_auto_climb(true);
+    let GridPos { x, y } = player.position;
+    let mut blocks = vec![settled((x + 1, y + 1))];
+    player.move_right(&mut blocks);
+    assert_eq!(player.position, (x, y));
+    
+    // No column past the right edge to climb into
+    let mut player = Player::new(6, 6).with_auto_climb(true);
+    player.position.x = 5;
+    let GridPos { x, y } = player.position;
+    player.move_right(&mut []);
+    assert_eq!(player.position, (x, y));
+}
+
+#[test]
+fn test_jump_blocked_by_block_overhead() {
+    let mut player = Player::new(6, 6);
+    let GridPos { x, y } = player.position;
+    let blocks = [settled((x, y - 1))];
+    
+    player.jump(&blocks);
+    assert_eq!(player.position, (x, y));
+    assert!(!player.in_air);
+    
+    // A block two cells up leaves room for the one-cell jump
+    let blocks = [settled((x, y - 2))];
+    player.jump(&blocks);
+    assert_eq!(player.position, (x, y - 1));
+}
+
+#[test]
+fn test_jump_blocked_by_terrain_overhead() {
+    let mut player = Player::new(6, 6);
+    let GridPos { x, y } = player.position;
+    let mut terrain = Terrain::new(6, 6);
+    terrain.set_tile(GridPos::new(x, y - 1), Tile::Wall);
+    player.set_terrain(terrain);
+    
+    player.jump(&[]);
+    assert_eq!(player.position, (x, y));
+}
+
+#[test]
+fn test_high_jump_rises_one_cell_per_update() {
+    let mut player = Player::new(8, 8).with_jump_height(3);
+    let GridPos { x, y } = player.position;
+    
+    player.jump(&[]);
+    assert_eq!(player.position, (x, y - 1));
+    player.update_jump(&[]); // The update of the jump itself
+    player.update_jump(&[]);
+    assert_eq!(player.position, (x, y - 2));
+    player.update_jump(&[]);
+    assert_eq!(player.position, (x, y - 3));
+    player.update_jump(&[]);
+    assert_eq!(player.position, (x, y - 3));
+}
+
+#[test]
+fn test_released_jump_and_ceilings_cut_the_rise_short() {
+    let mut player = Player::new(8, 8).with_jump_height(3);
+    let GridPos { x, y } = player.position;
+    player.jump(&[]);
+    player.update_jump(&[]);
+    player.cut_jump();
+    player.update_jump(&[]);
+    assert_eq!(player.position, (x, y - 1));
+    
+    // A block two cells over the head stops the rise after the first cell
+    let mut player = Player::new(8, 8).with_jump_height(3);
+    let blocks = [settled((x, y - 2))];
+    player.jump(&blocks);
+    player.update_jump(&blocks);
+    player.update_jump(&blocks);
+    assert_eq!(player.position, (x, y - 1));
+}
+
+#[test]
+fn test_crouch_passes_under_an_overhang() {
+    let mut player = Player::new(6, 6);
+    let GridPos { x, y } = player.position;
+    // Tunnel one cell high: steel blocks at head height on the right, too heavy to push
+    let mut blocks = vec![settled((x + 1, y)), settled((x + 2, y))];
+    for block in &mut blocks {
+        block.kind = BlockKind::Steel;
+    }
+    
+    player.move_right(&mut blocks);
+    assert_eq!(player.position, (x, y));
+    
+    player.toggle_crouch(&blocks);
+    assert!(player.crouching);
+    assert_eq!((player.position, player.body_size), (GridPos::new(x, y + 1), 1));
+    assert!(player.has_support(&blocks, 6));
+    
+    // Crawling under the steel blocks, there is no room to stand up in the tunnel
+    player.move_right(&mut blocks);
+    assert_eq!(player.position, (x + 1, y + 1));
+    assert!(!player.stand_up(&blocks));
+    player.move_right(&mut blocks);
+    player.move_right(&mut blocks);
+    assert_eq!(player.position, (x + 3, y + 1));
+    assert!(player.stand_up(&blocks));
+    assert_eq!((player.position, player.body_size), (GridPos::new(x + 3, y), 2));
+}
+
+#[test]
+fn test_crouched_player_is_hit_only_at_its_height() {
+    let mut player = Player::new(6, 6);
+    let GridPos { x, y } = player.position;
+    player.toggle_crouch(&[]);
+    assert!(!player.occupies(GridPos::new(x, y)));
+    assert!(player.occupies(GridPos::new(x, y + 1)));
+}
@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+use rust_stackattack::core::event::{GameEvent, ScoreBreakdown};
+use rust_stackattack::core::types::GridPos;
+use rust_stackattack::platform::popups::{Popups, MAX_POPUPS, POPUP_LIFETIME, POPUP_RISE};
+use rust_stackattack::testing::MockClock;
+
+fn breakdown(rows: u32, points: u32) -> ScoreBreakdown {
+    ScoreBreakdown { rows, base_points: points, chain: 1, touch_bonus: 0, points }
+}
+
+#[test]
+fn test_row_clear_points_rise_from_the_middle_of_the_rows() {
+    let clock = MockClock::new();
+    let mut popups = Popups::new();
+
+    popups.observe(&GameEvent::RowCleared(8), 10, &clock);
+    popups.observe(&GameEvent::RowCleared(9), 10, &clock);
+    popups.observe(&GameEvent::RowsCleared(breakdown(2, 3)), 10, &clock);
+
+    let shown: Vec<_> = popups.visible(&clock).collect();
+    assert_eq!(shown.len(), 1);
+    assert_eq!(shown[0].text, "+3");
+    assert_eq!(shown[0].position, (5.0, 9.0));
+    assert_eq!(shown[0].life_left, 1.0);
+
+    // Halfway through it has risen half the way and faded by half
+    clock.advance(POPUP_LIFETIME / 2);
+    let halfway = popups.visible(&clock).next().unwrap();
+    assert!((halfway.position.1 - (9.0 - POPUP_RISE / 2.0)).abs() < 1e-4);
+    assert!((halfway.life_left - 0.5).abs() < 1e-4);
+
+    clock.advance(POPUP_LIFETIME);
+    assert_eq!(popups.visible(&clock).count(), 0);
+}
+
+#[test]
+fn test_coin_points_show_at_the_coin() {
+    let clock = MockClock::new();
+    let mut popups = Popups::new();
+
+    popups.observe(&GameEvent::CoinCollected(GridPos::new(2, 4), 1), 10, &clock);
+    popups.observe(&GameEvent::PlayerHit, 10, &clock);
+
+    let shown: Vec<_> = popups.visible(&clock).collect();
+    assert_eq!(shown.len(), 1);
+    assert_eq!((shown[0].text, shown[0].position), ("+1", (2.5, 4.5)));
+}
+
+#[test]
+fn test_oldest_popup_makes_room() {
+    let clock = MockClock::new();
+    let mut popups = Popups::new();
+
+    for points in 0..MAX_POPUPS as u32 + 2 {
+        popups.observe(&GameEvent::CoinCollected(GridPos::new(0, 0), points + 1), 10, &clock);
+        clock.advance(Duration::from_millis(10));
+    }
+
+    let texts: Vec<&str> = popups.visible(&clock).map(|popup| popup.text).collect();
+    assert_eq!(texts.len(), MAX_POPUPS);
+    assert_eq!(texts[0], "+3");
+}
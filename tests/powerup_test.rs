@@ -0,0 +1,132 @@
+use rust_stackattack::core::block::{Block, BlockKind};
+use rust_stackattack::core::game::GameState;
+use rust_stackattack::core::pickup::Pickup;
+use rust_stackattack::core::powerup::{ActivePowerUp, PowerUpKind};
+use rust_stackattack::core::types::{GameConfig, GridPos, InputAction};
+
+fn test_game() -> GameState {
+    let config = GameConfig {
+        grid_width: 5,
+        grid_height: 5,
+        cell_size: 30.0,
+        refresh_rate_milliseconds: 200,
+        block_fall_speed: 1,
+        block_spawn_rate: 10,
+        ..Default::default()
+    };
+    let mut game = GameState::new(config).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    game
+}
+
+#[test]
+fn test_active_powerup_runs_out() {
+    let mut powerup = ActivePowerUp::new(PowerUpKind::Freeze);
+    assert_eq!(powerup.remaining_ticks, PowerUpKind::Freeze.duration());
+
+    for _ in 1..PowerUpKind::Freeze.duration() {
+        assert!(powerup.tick());
+    }
+    assert!(!powerup.tick());
+}
+
+#[test]
+fn test_collecting_powerup_activates_it() {
+    let mut game = test_game();
+    let GridPos { x, y } = game.player.position;
+    game.pickups.push(Pickup::powerup(GridPos::new(x + 1, y + 1), PowerUpKind::SlowMotion));
+
+    game.process_input(InputAction::Right);
+
+    assert!(game.pickups.is_empty());
+    assert!(game.powerup_active(PowerUpKind::SlowMotion));
+    assert_eq!(game.score, 0, "Power-ups give no score");
+}
+
+#[test]
+fn test_hammer_destroys_pushed_block() {
+    let mut game = test_game();
+    let GridPos { x, y: _ } = game.player.position;
+    game.blocks.push(Block {
+        position: GridPos::new(x + 1, 4),
+        falling: false,
+        kind: BlockKind::Steel,
+        ..Default::default()
+    });
+    game.sync_occupancy();
+    game.activate_powerup(PowerUpKind::Hammer);
+
+    game.process_input(InputAction::Right);
+
+    assert!(game.blocks.is_empty());
+    assert_eq!(game.player.position.x, x, "Smashing a block takes the move");
+    assert!(game.active_powerup.is_none(), "Hammer is used up");
+}
+
+#[test]
+fn test_freeze_halts_spawning() {
+    let mut game = test_game();
+    game.activate_powerup(PowerUpKind::Freeze);
+
+    for _ in 0..game.block_spawn_rate * 2 {
+        game.handle_block_spawning();
+    }
+    assert!(game.blocks.is_empty());
+
+    game.active_powerup = None;
+    for _ in 0..game.block_spawn_rate {
+        game.handle_block_spawning();
+    }
+    assert_eq!(game.blocks.len(), 1);
+}
+
+#[test]
+fn test_slow_motion_halves_fall_speed() {
+    let mut game = test_game();
+    game.block_spawn_rate = 1000;
+    game.blocks.push(Block::new(GridPos::new(0, 0)));
+    game.sync_occupancy();
+    game.activate_powerup(PowerUpKind::SlowMotion);
+
+    for _ in 0..4 {
+        game.update_blocks();
+    }
+    assert_eq!(game.blocks[0].position.y, 2);
+}
+
+#[test]
+fn test_magnet_pulls_falling_blocks_toward_the_player() {
+    let mut game = GameState::builder()
+        .grid(9, 10)
+        .falling_block(0, 0)
+        .falling_block(8, 0)
+        .block(2, 9)
+        .player_at(5, 8)
+        .build()
+        .unwrap();
+    game.block_spawn_rate = 1000;
+    game.activate_powerup(PowerUpKind::Magnet);
+
+    for _ in 0..3 {
+        game.update_blocks();
+    }
+    // One column and one row per update, up to the column next to the player
+    let positions: Vec<(usize, usize)> = game.blocks.iter().map(|block| block.position.into()).collect();
+    assert_eq!(positions, vec![(3, 3), (6, 3), (2, 9)]);
+
+    for _ in 0..3 {
+        game.update_blocks();
+    }
+    assert_eq!(game.blocks[0].position, (4, 6));
+    assert_eq!(game.blocks[1].position, (6, 6));
+}
+
+#[test]
+fn test_falling_blocks_keep_their_column_without_the_magnet() {
+    let mut game = GameState::builder().grid(9, 10).falling_block(0, 0).player_at(5, 8).build().unwrap();
+    game.block_spawn_rate = 1000;
+
+    game.update_blocks();
+    assert_eq!(game.blocks[0].position, (0, 1));
+}
@@ -0,0 +1,37 @@
+use rust_stackattack::prelude::*;
+
+// A frontend that only counts what it was asked to draw
+#[derive(Default)]
+struct CountingRenderer {
+    shapes: usize,
+}
+
+impl Renderer for CountingRenderer {
+    fn fill_rect(&mut self, _rect: Rect, _color: Color) {
+        self.shapes += 1;
+    }
+    fn fill_polygon(&mut self, _points: &[(f32, f32)], _color: Color) {
+        self.shapes += 1;
+    }
+    fn draw_line(&mut self, _from: (f32, f32), _to: (f32, f32), _width: f32, _color: Color) {
+        self.shapes += 1;
+    }
+    fn draw_text(&mut self, _text: &str, _position: (f32, f32), _scale: f32, _anchor: TextAnchor, _color: Color) {
+        self.shapes += 1;
+    }
+}
+
+#[test]
+fn test_prelude_is_enough_to_embed_the_game() {
+    let mut game = GameState::new(GameConfig { seed: Some(3), ..GameConfig::default() }).unwrap();
+    for action in [InputAction::Left, InputAction::Up, InputAction::None] {
+        game.process_input(action);
+        game.tick();
+    }
+    let _events: Vec<GameEvent> = game.drain_events();
+
+    let layout = Layout::new(&game);
+    let mut renderer = CountingRenderer::default();
+    rust_stackattack::platform::render::draw_game(&game, &layout, &mut renderer);
+    assert!(renderer.shapes > 0);
+}
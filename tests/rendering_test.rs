@@ -1,20 +1,624 @@
-// Note: These tests require the ggez context, which makes them harder to test 
-// without a graphics environment. In a real-world scenario, we'd use mocking
-// or create more testable abstractions that are separated from drawing logic.
+use rust_stackattack::core::block::{Block, BlockKind, Shape};
+use rust_stackattack::core::editor::{Editor, Tool};
+use rust_stackattack::core::event::ScoreBreakdown;
+use rust_stackattack::core::game::{GameState, SpawnWarning};
+use rust_stackattack::core::powerup::PowerUpKind;
+use rust_stackattack::core::scores::{HighScores, ScoreEntry};
+use rust_stackattack::core::stats::{MarkerKind, RunStats};
+use rust_stackattack::core::types::{Color, Direction, GameConfig, GameMode, GameOverReason, GameUpdateResult, GridPos, InputAction, Theme};
+use rust_stackattack::platform::animation::{AnimationFrame, PlayerAction, Pose};
+use rust_stackattack::platform::format::Locale;
+use rust_stackattack::platform::input::{self, Control, KeyBindings, RebindMenu};
+use rust_stackattack::platform::particles::{ParticleKind, ParticleView};
+use rust_stackattack::platform::settings::{Settings, SettingsItem, SettingsMenu};
+use rust_stackattack::platform::render::{self, EditorButton, Layout, Rect, Renderer, TextAnchor, GHOST_COLOR, PLAYER_COLOR, TERRAIN_COLOR};
+use rust_stackattack::testing::{DrawCall, MockInput, MockRenderer};
 
-// This test applies to the platform-specific rendering implementation
-// which in our restructured code would be in platform/ggez/mod.rs
+fn test_game() -> GameState {
+    let config = GameConfig {
+        grid_width: 5,
+        grid_height: 5,
+        cell_size: 30.0,
+        refresh_rate_milliseconds: 200,
+        block_fall_speed: 1,
+        block_spawn_rate: 10,
+        ..Default::default()
+    };
+    let mut game = GameState::new(config).unwrap();
+    game.blocks.clear();
+    game.sync_occupancy();
+    game
+}
 
 #[test]
-#[ignore = "Requires ggez context which is not available in automated tests"]
 fn test_draw_grid() {
-    // This is a placeholder for a real test that would use a mock context
-    // Since we can't easily create a graphics context in unit tests,
-    // we'll skip this test by default
-    
-    // In a real test, we would:
-    // 1. Create a mock Context
-    // 2. Create a mock Canvas 
-    // 3. Call GameAdapter's draw_grid method
-    // 4. Assert that the appropriate methods were called on Canvas
+    let game = test_game();
+    let layout = Layout::new(&game);
+    let mut renderer = MockRenderer::new();
+
+    render::draw_grid(&layout, &mut renderer);
+
+    // One line for each row and column boundary
+    assert_eq!(renderer.lines(), (game.grid_width + 1) + (game.grid_height + 1));
+}
+
+#[test]
+fn test_draw_player_below_score_bar() {
+    let mut game = test_game();
+    game.player.position = GridPos::new(1, 3);
+    let layout = Layout::new(&game);
+    let mut renderer = MockRenderer::new();
+
+    render::draw_game(&game, &layout, &mut renderer);
+
+    // Player covers both body cells, shifted down by the score bar
+    let player_rects = renderer.rects_with_color(PLAYER_COLOR);
+    assert_eq!(player_rects, vec![Rect::new(30.0, 3.0 * 30.0 + 30.0, 30.0, 60.0)]);
+}
+
+#[test]
+fn test_draw_blocks_by_kind_and_shape() {
+    let mut game = test_game();
+    game.blocks.push(Block::with_shape(GridPos::new(0, 0), Shape::L));
+    game.blocks.push(Block::with_kind(GridPos::new(4, 4), BlockKind::Ice));
+    game.sync_occupancy();
+    let layout = Layout::new(&game);
+    let mut renderer = MockRenderer::new();
+
+    render::draw_blocks(&game, &layout, &mut renderer);
+
+    // Every cell of the L is drawn as a crate, the ice block in its own color
+    assert_eq!(renderer.rects_with_color(render::block_color(BlockKind::Crate)).len(), 3);
+    assert_eq!(renderer.rects_with_color(render::block_color(BlockKind::Ice)), vec![layout.cell_rect(4, 4)]);
+}
+
+#[test]
+fn test_draw_ramp_as_triangle() {
+    let mut game = test_game();
+    game.place_ramp(2, 4, Direction::Right);
+    let layout = Layout::new(&game);
+    let mut renderer = MockRenderer::new();
+
+    render::draw_terrain(&game, &layout, &mut renderer);
+
+    let polygons = renderer.polygons();
+    assert_eq!(polygons.len(), 1);
+    assert_eq!(polygons[0].len(), 3);
+}
+
+#[test]
+fn test_draw_score_and_game_over() {
+    let mut game = test_game();
+    game.score = 7;
+    let layout = Layout::new(&game);
+
+    let mut renderer = MockRenderer::new();
+    render::draw_game(&game, &layout, &mut renderer);
+    assert!(renderer.texts().contains(&"Score: 7"));
+    assert!(!renderer.texts().contains(&"Game Over"));
+
+    game.end_game(GameOverReason::StackTooHigh);
+    let mut renderer = MockRenderer::new();
+    render::draw_game(&game, &layout, &mut renderer);
+    assert!(renderer.texts().contains(&"Game Over"));
+    assert!(renderer.texts().contains(&GameOverReason::StackTooHigh.message()));
+
+    // Score bar background spans the whole window width
+    assert!(renderer.rects_with_color(Color::BLUE).iter().any(|r| r.w == layout.window_width()));
+}
+
+#[test]
+fn test_step_feeds_scripted_input() {
+    let mut game = test_game();
+    let start_x = game.player.position.x;
+    let mut input = MockInput::new([InputAction::Right, InputAction::None]);
+
+    input::step(&mut game, &mut input);
+    assert_eq!(game.player.position.x, start_x + 1);
+    assert_eq!(input.remaining(), 1);
+
+    // Running out of script means no input
+    input::step(&mut game, &mut input);
+    input::step(&mut game, &mut input);
+    assert_eq!(input.polled, 3);
+    assert_eq!(game.player.position.x, start_x + 1);
+}
+
+#[test]
+fn test_step_does_not_poll_after_game_over() {
+    let mut game = test_game();
+    game.game_over = true;
+    let mut input = MockInput::new([InputAction::Right]);
+
+    let result = input::step(&mut game, &mut input);
+    assert!(matches!(result, GameUpdateResult::GameOver));
+    assert_eq!(input.polled, 0);
+}
+
+#[test]
+fn test_draw_about_shows_build_info() {
+    let game = test_game();
+    let layout = Layout::new(&game);
+    let mut renderer = MockRenderer::new();
+
+    render::draw_about(&layout, &mut renderer);
+
+    let texts = renderer.texts();
+    assert!(texts.iter().any(|t| t.contains(env!("CARGO_PKG_VERSION"))));
+    assert!(texts.iter().any(|t| t.starts_with("Commit: ")));
+    assert_eq!(renderer.rects_with_color(render::ABOUT_BACKGROUND).len(), 1);
+}
+
+#[test]
+fn test_draw_active_powerup_in_score_bar() {
+    let mut game = test_game();
+    game.activate_powerup(PowerUpKind::Freeze);
+    let layout = Layout::new(&game);
+    let mut renderer = MockRenderer::new();
+
+    render::draw_score_bar(&game, &layout, &mut renderer);
+
+    let expected = format!("Freeze {}", PowerUpKind::Freeze.duration());
+    assert!(renderer.texts().contains(&expected.as_str()));
+}
+
+#[test]
+fn test_draw_preview_ghost() {
+    let mut game = test_game();
+    game.blocks.push(Block::with_shape(GridPos::new(0, 0), Shape::Horizontal));
+    game.sync_occupancy();
+    let preview = game.simulate(&[InputAction::Left], 1);
+    let layout = Layout::new(&game);
+    let mut renderer = MockRenderer::new();
+
+    render::draw_preview(&preview, &layout, &mut renderer);
+
+    let ghosts = renderer.rects_with_color(render::PREVIEW_COLOR);
+    assert_eq!(ghosts.len(), 3, "Two block cells and the player");
+    assert!(ghosts.contains(&layout.cell_rect(0, 1)));
+}
+
+#[test]
+fn test_clear_banner_text() {
+    let single = ScoreBreakdown { rows: 1, base_points: 1, chain: 1, touch_bonus: 0, points: 1 };
+    assert_eq!(render::clear_banner_text(&single), "+1 (1 row)");
+
+    let chained = ScoreBreakdown { rows: 2, base_points: 3, chain: 2, touch_bonus: 0, points: 6 };
+    assert_eq!(render::clear_banner_text(&chained), "+6 (2 rows, chain x2)");
+
+    let moved = ScoreBreakdown { rows: 1, base_points: 1, chain: 2, touch_bonus: 2, points: 4 };
+    assert_eq!(render::clear_banner_text(&moved), "+4 (1 row, chain x2, moved +2)");
+}
+
+#[test]
+fn test_fall_delay_warning_over_player() {
+    let mut game = test_game();
+    let layout = Layout::new(&game);
+
+    // Standing on the ground there is no warning
+    let mut renderer = MockRenderer::new();
+    render::draw_player(&game, &layout, &mut renderer);
+    assert!(!renderer.texts().contains(&"!"));
+
+    // Over a gap the fall delay starts, the player wobbles under a warning mark
+    game.player.position = GridPos::new(1, 1);
+    game.update_player();
+    assert!(game.player.fall_delay_remaining() > 0);
+
+    let mut renderer = MockRenderer::new();
+    render::draw_player(&game, &layout, &mut renderer);
+    assert!(renderer.texts().contains(&"!"));
+    let body = renderer.rects_with_color(PLAYER_COLOR)[0];
+    assert_ne!(body.x, layout.cell_rect(1, 1).x);
+}
+
+#[test]
+fn test_soft_theme_mutes_colors() {
+    let mut game = test_game();
+    game.theme = Theme::Soft;
+    game.mode = GameMode::Zen;
+    let layout = Layout::new(&game);
+    let mut renderer = MockRenderer::new();
+
+    render::draw_game(&game, &layout, &mut renderer);
+
+    // Everything goes through the theme, and lives give way to the mode name
+    assert!(renderer.rects_with_color(PLAYER_COLOR).is_empty());
+    assert_eq!(renderer.rects_with_color(Theme::Soft.apply(PLAYER_COLOR)).len(), 1);
+    assert!(renderer.texts().contains(&"Zen"));
+}
+
+#[test]
+fn test_draw_next_block_arrows() {
+    let game = test_game();
+    let layout = Layout::new(&game);
+    let mut renderer = MockRenderer::new();
+
+    render::draw_next_blocks(&game, &layout, &mut renderer);
+
+    // One arrow per top cell of every queued block, each one inside the top row of the grid
+    let top_cells: usize = game.next_blocks.iter()
+        .map(|block| block.cells().filter(|&GridPos { x: _, y }| y == 0).count())
+        .sum();
+    let arrows = renderer.polygons();
+    assert_eq!(arrows.len(), top_cells);
+    assert!(arrows.iter().flat_map(|points| points.iter()).all(|&(_, y)| y >= layout.score_bar_height && y <= layout.score_bar_height + 30.0));
+}
+
+#[test]
+fn test_block_patterns_tell_kinds_apart() {
+    let mut game = test_game();
+    game.blocks.push(Block::with_kind(GridPos::new(0, 4), BlockKind::Crate));
+    game.sync_occupancy();
+    let layout = Layout::new(&game);
+
+    // Without the option only the color is drawn
+    let mut renderer = MockRenderer::new();
+    render::draw_blocks(&game, &layout, &mut renderer);
+    assert_eq!(renderer.lines(), 0);
+
+    // Each kind gets the outline plus its own pattern
+    game.block_patterns = true;
+    let mut counts = Vec::new();
+    for kind in [BlockKind::Crate, BlockKind::Steel, BlockKind::Ice, BlockKind::Bomb] {
+        game.blocks[0].kind = kind;
+        let mut renderer = MockRenderer::new();
+        render::draw_blocks(&game, &layout, &mut renderer);
+        counts.push((renderer.lines(), renderer.calls.len()));
+    }
+    assert_eq!(counts, vec![(4, 5), (6, 7), (7, 8), (4, 10)]);
+}
+
+#[test]
+fn test_spawn_warning_flashes_the_column() {
+    let mut game = test_game();
+    let layout = Layout::new(&game);
+    let width = game.next_blocks[0].shape.width();
+
+    game.spawn_warning = Some(SpawnWarning { column: 2, ticks_left: 3 });
+    let mut renderer = MockRenderer::new();
+    render::draw_spawn_warning(&game, &layout, &mut renderer);
+    assert_eq!(renderer.calls.len(), width);
+
+    // Off on the other updates, so it blinks
+    game.spawn_warning = Some(SpawnWarning { column: 2, ticks_left: 2 });
+    let mut renderer = MockRenderer::new();
+    render::draw_spawn_warning(&game, &layout, &mut renderer);
+    assert!(renderer.calls.is_empty());
+}
+
+#[test]
+fn test_timeline_draws_both_lines_and_markers() {
+    let mut stats = RunStats::new();
+    for tick in 0..30 {
+        stats.record_tick(tick * 10, 2);
+        if tick == 12 {
+            stats.mark(MarkerKind::RowsCleared);
+        }
+    }
+    let area = Rect::new(0.0, 0.0, 100.0, 50.0);
+    let mut renderer = MockRenderer::new();
+    render::draw_timeline(&stats, 10, area, &mut renderer);
+
+    // Six samples make five segments of each line, plus one marker
+    assert_eq!(renderer.lines(), 5 * 2 + 1);
+
+    // Nothing when there is no room for it
+    let mut cramped = MockRenderer::new();
+    render::draw_timeline(&stats, 10, Rect::new(0.0, 0.0, 100.0, 5.0), &mut cramped);
+    assert_eq!(cramped.lines(), 0);
+}
+
+#[test]
+fn test_player_drawn_with_animation_offset() {
+    let mut game = test_game();
+    game.player.position = GridPos::new(1, 3);
+    let layout = Layout::new(&game);
+    let mut renderer = MockRenderer::new();
+
+    let animation = AnimationFrame { player_offset: (-0.5, 0.0), ..Default::default() };
+    render::draw_game_animated(&game, &layout, &animation, &mut renderer);
+    assert_eq!(renderer.rects_with_color(PLAYER_COLOR), vec![Rect::new(15.0, 3.0 * 30.0 + 30.0, 30.0, 60.0)]);
+}
+
+#[test]
+fn test_editor_buttons_and_cells() {
+    let game = test_game();
+    let layout = Layout::new(&game);
+    assert_eq!(layout.cell_at(35.0, 30.0 + 65.0), Some(GridPos::new(1, 2)));
+    assert_eq!(layout.cell_at(35.0, 10.0), None);
+
+    for button in EditorButton::all() {
+        let rect = layout.editor_button(button);
+        assert_eq!(layout.editor_button_at(rect.x + rect.w / 2.0, rect.y + rect.h / 2.0), Some(button));
+    }
+}
+
+#[test]
+fn test_split_view_puts_the_preview_beside_the_editor() {
+    let game = test_game();
+    let layout = Layout::new(&game).with_split_view(true);
+    let grid = layout.grid_pixel_width();
+    assert_eq!(layout.window_width(), 2.0 * grid + 30.0);
+
+    // Cells of the preview are one cell past the editor's grid, clicks there are not on the editor
+    let preview = layout.preview();
+    assert_eq!(preview.cell_rect(0, 0).x, grid + 30.0);
+    assert_eq!(layout.cell_at(grid + 40.0, 40.0), None);
+    assert_eq!(preview.cell_at(grid + 40.0, 40.0), Some(GridPos::new(0, 0)));
+
+    // The palette stays over the editor's grid
+    assert!(EditorButton::all().into_iter().all(|button| {
+        let rect = layout.editor_button(button);
+        rect.x + rect.w <= grid
+    }));
+
+    let mut renderer = MockRenderer::new();
+    let mut running = test_game();
+    running.blocks = vec![Block { position: GridPos::new(2, 3), falling: false, ..Default::default() }];
+    running.sync_occupancy();
+    render::draw_live_preview(&running, &layout, &mut renderer);
+    assert!(renderer.texts().contains(&"Preview"));
+    assert!(renderer.rects_with_color(render::block_color(BlockKind::Crate)).contains(&preview.cell_rect(2, 3)));
+}
+
+#[test]
+fn test_draw_editor_shows_level_and_palette() {
+    let game = test_game();
+    let layout = Layout::new(&game);
+    let mut editor = Editor::new(5, 5);
+    editor.tool = Tool::Wall;
+    editor.apply(GridPos::new(0, 4));
+    let mut renderer = MockRenderer::new();
+
+    render::draw_editor(&editor, &layout, &mut renderer);
+    assert!(renderer.rects_with_color(TERRAIN_COLOR).contains(&layout.cell_rect(0, 4)));
+    assert!(renderer.texts().contains(&"Save"));
+    assert!(renderer.texts().contains(&"Play"));
+}
+
+#[test]
+fn test_time_attack_countdown() {
+    let mut game = test_game();
+    game.mode = GameMode::TimeAttack;
+    let layout = Layout::new(&game);
+    let mut renderer = MockRenderer::new();
+
+    render::draw_score_bar(&game, &layout, &mut renderer);
+
+    assert!(renderer.texts().contains(&"3:00"));
+}
+
+#[test]
+fn test_draw_high_scores_highlights_the_new_one() {
+    let game = test_game();
+    let layout = Layout::new(&game);
+    let mut scores = HighScores::new();
+    for score in [30, 20] {
+        scores.insert(ScoreEntry { score, date: "2026-10-15".to_string(), mode: "Zen".to_string(), seed: 1 });
+    }
+    let mut renderer = MockRenderer::new();
+
+    render::draw_high_scores(&scores, Some(1), &layout, &mut renderer);
+    let texts = renderer.texts();
+    assert_eq!(texts[0], "High Scores");
+    assert_eq!(texts[2], " 2.      20  Zen  2026-10-15");
+}
+
+#[test]
+fn test_scores_follow_the_locale() {
+    let mut game = test_game();
+    game.score = 12345;
+    let layout = Layout::new(&game).with_locale(Locale::from_tag("en_US"));
+    let mut renderer = MockRenderer::new();
+
+    render::draw_score_bar(&game, &layout, &mut renderer);
+    assert_eq!(renderer.texts()[0], "Score: 12,345");
+}
+
+#[test]
+fn test_player_pose_and_block_squash() {
+    let mut game = test_game();
+    game.player.position = GridPos::new(1, 3);
+    game.blocks = vec![Block { position: GridPos::new(3, 4), falling: false, ..Default::default() }];
+    game.sync_occupancy();
+    let layout = Layout::new(&game);
+    let mut renderer = MockRenderer::new();
+
+    // Jumping stretches the body, feet where they were
+    let animation = AnimationFrame {
+        pose: Pose { action: PlayerAction::Jump, frame: 0, facing: Direction::Right },
+        squashes: vec![(GridPos::new(3, 4), 1.0)],
+        ..Default::default()
+    };
+    render::draw_game_animated(&game, &layout, &animation, &mut renderer);
+    let body = renderer.rects_with_color(PLAYER_COLOR)[0];
+    assert!(body.w < 30.0 && body.h > 60.0);
+    assert!((body.y + body.h - 5.0 * 30.0 - 30.0).abs() < 0.01);
+
+    // The landed block is flattened onto the floor
+    let block = renderer.rects_with_color(render::block_color(BlockKind::Crate))[0];
+    assert!(block.h < 30.0 && block.w > 30.0);
+    assert!((block.y + block.h - 6.0 * 30.0).abs() < 0.01);
+}
+
+#[test]
+fn test_particles_fade_as_they_age() {
+    let game = test_game();
+    let layout = Layout::new(&game);
+    let particle = |life_left| ParticleView { kind: ParticleKind::Sparkle, position: (2.5, 3.5), life_left };
+
+    let mut renderer = MockRenderer::new();
+    render::draw_particles([particle(1.0), particle(0.5)], &layout, &mut renderer);
+    let fresh = renderer.rects_with_color(render::SPARKLE_COLOR);
+    assert_eq!(fresh.len(), 1);
+    let cell = layout.cell_rect(2, 3);
+    assert_eq!((fresh[0].x + fresh[0].w / 2.0, fresh[0].y + fresh[0].h / 2.0), (cell.x + cell.w / 2.0, cell.y + cell.h / 2.0));
+
+    // The older one is smaller and half see-through
+    let faded = Color { a: 0.5, ..render::SPARKLE_COLOR };
+    assert!(renderer.rects_with_color(faded)[0].w < fresh[0].w);
+}
+
+#[test]
+fn test_moved_blocks_slide_into_their_cells() {
+    let mut game = test_game();
+    game.blocks = vec![Block { position: GridPos::new(3, 2), last_position: GridPos::new(3, 1), ..Default::default() }];
+    game.sync_occupancy();
+    let layout = Layout::new(&game);
+    let cell = layout.cell_rect(3, 2);
+    let color = render::block_color(BlockKind::Crate);
+
+    // Half of the update left to show, the block is drawn halfway down from the cell it left
+    let mut renderer = MockRenderer::new();
+    let animation = AnimationFrame { tick_remaining: 0.5, ..Default::default() };
+    render::draw_game_animated(&game, &layout, &animation, &mut renderer);
+    let block = renderer.rects_with_color(color)[0];
+    assert_eq!((block.x, block.y), (cell.x, cell.y - cell.h / 2.0));
+
+    let mut renderer = MockRenderer::new();
+    render::draw_game_animated(&game, &layout, &AnimationFrame::default(), &mut renderer);
+    let block = renderer.rects_with_color(color)[0];
+    assert_eq!((block.x, block.y), (cell.x, cell.y));
+}
+
+#[test]
+fn test_camera_moves_everything_drawn_through_it() {
+    let mut renderer = MockRenderer::new();
+    let mut camera = render::Camera { offset: (3.0, -2.0), inner: &mut renderer };
+    camera.fill_rect(Rect::new(10.0, 10.0, 5.0, 5.0), Color::WHITE);
+    camera.fill_polygon(&[(0.0, 0.0), (1.0, 1.0), (0.0, 1.0)], Color::WHITE);
+    camera.draw_text("x", (4.0, 4.0), 1.0, TextAnchor::Left, Color::WHITE);
+
+    assert_eq!(renderer.rects_with_color(Color::WHITE), vec![Rect::new(13.0, 8.0, 5.0, 5.0)]);
+    assert!(matches!(&renderer.calls[1], DrawCall::Polygon { points, .. } if points[1] == (4.0, -1.0)));
+    assert!(matches!(&renderer.calls[2], DrawCall::Text { position, .. } if *position == (7.0, 2.0)));
+}
+
+#[test]
+fn test_hit_flash_covers_the_grid_and_fades() {
+    let game = test_game();
+    let layout = Layout::new(&game);
+    let mut renderer = MockRenderer::new();
+    render::draw_hit_flash(0.5, &layout, &mut renderer);
+
+    let faded = Color { a: render::HIT_FLASH_COLOR.a * 0.5, ..render::HIT_FLASH_COLOR };
+    let flash = renderer.rects_with_color(faded);
+    assert_eq!(flash, vec![Rect::new(0.0, layout.score_bar_height, layout.grid_pixel_width(), layout.grid_pixel_height())]);
+}
+
+#[test]
+fn test_macro_recording_mark_counts_actions() {
+    let game = test_game();
+    let layout = Layout::new(&game);
+    let mut renderer = MockRenderer::new();
+    render::draw_macro_recording(3, &layout, &mut renderer);
+    assert!(renderer.texts().iter().any(|text| text.starts_with("REC 3/")));
+}
+
+#[test]
+fn test_text_only_drops_every_shape() {
+    let game = test_game();
+    let layout = Layout::new(&game);
+    let mut renderer = MockRenderer::new();
+    render::draw_game(&game, &layout, &mut render::TextOnly { inner: &mut renderer });
+
+    assert!(!renderer.calls.is_empty());
+    assert!(renderer.calls.iter().all(|call| matches!(call, DrawCall::Text { .. })));
+    assert!(renderer.texts().iter().any(|text| text.starts_with("Score")));
+}
+
+#[test]
+fn test_letterbox_scales_evenly_and_centers() {
+    let game = test_game();
+    let layout = Layout::new(&game);
+    let (width, height) = (layout.window_width(), layout.window_height());
+
+    // Twice as tall as needed: cells keep their size, bars above and below
+    let tall = render::Letterbox::fit(&layout, (width, height * 2.0));
+    assert_eq!(tall.scale, 1.0);
+    assert_eq!(tall.offset, (0.0, height / 2.0));
+
+    // Maximized to three times the width and twice the height: cells double, bars on the sides
+    let wide = render::Letterbox::fit(&layout, (width * 3.0, height * 2.0));
+    assert_eq!(wide.scale, 2.0);
+    assert_eq!(wide.cell_size(&layout), layout.cell_size * 2.0);
+    assert_eq!(wide.offset, (width / 2.0, 0.0));
+    assert_eq!(wide.visible_area((width * 3.0, height * 2.0)), Rect::new(-width / 4.0, 0.0, width * 1.5, height));
+}
+
+#[test]
+fn test_letterboxed_clicks_hit_the_restart_button() {
+    let game = test_game();
+    let layout = Layout::new(&game);
+    let window = (layout.window_width() * 3.0, layout.window_height() * 2.0);
+    let letterbox = render::Letterbox::fit(&layout, window);
+
+    let button = layout.restart_button();
+    let center = (button.x + button.w / 2.0, button.y + button.h / 2.0);
+    let on_screen = (letterbox.offset.0 + center.0 * letterbox.scale, letterbox.offset.1 + center.1 * letterbox.scale);
+    let (x, y) = letterbox.to_layout(on_screen);
+    assert!(button.contains(x, y));
+    // The unscaled window position is somewhere else entirely
+    assert!(!button.contains(on_screen.0, on_screen.1));
+
+    // A minimized window leaves the layout as it is
+    assert_eq!(render::Letterbox::fit(&layout, (0.0, 0.0)).to_layout(center), center);
+}
+
+#[test]
+fn test_controls_screen_lists_every_control() {
+    let game = test_game();
+    let layout = Layout::new(&game);
+    let mut bindings = KeyBindings::default();
+    bindings.rebind(Control::Kick, "Up");
+    let mut renderer = MockRenderer::new();
+    render::draw_key_bindings(&bindings, &RebindMenu { selected: 2, waiting: true }, &layout, &mut renderer);
+
+    let texts = renderer.texts();
+    assert!(texts.contains(&"Left: Left"));
+    assert!(texts.contains(&"Jump: press a key"));
+    assert!(texts.contains(&"Kick: Up"));
+    assert!(texts.contains(&"Preview: LShift, RShift"));
+    assert_eq!(texts.iter().filter(|text| text.contains(": ")).count(), Control::ALL.len());
+}
+
+#[test]
+fn test_grid_lines_can_be_turned_off() {
+    let game = test_game();
+    let mut renderer = MockRenderer::new();
+    render::draw_grid(&Layout::new(&game).with_grid_lines(false), &mut renderer);
+    assert!(renderer.calls.is_empty());
+}
+
+#[test]
+fn test_settings_screen_lists_every_setting() {
+    let game = test_game();
+    let layout = Layout::new(&game);
+    let settings = Settings { music_volume: Some(0.8), grid: Some((20, 12)), ..Default::default() };
+    let mut renderer = MockRenderer::new();
+    render::draw_settings(&settings, &SettingsMenu::default(), &layout, &mut renderer);
+
+    let texts = renderer.texts();
+    assert!(texts.contains(&"Music volume: 80%"));
+    assert!(texts.contains(&"Grid width: 20 (next start)"));
+    assert!(texts.contains(&"Show grid: On"));
+    assert_eq!(texts.iter().filter(|text| text.contains(": ")).count(), SettingsItem::ALL.len());
+}
+
+#[test]
+fn test_landing_ghost_outlines_where_the_block_lands() {
+    let mut game = test_game();
+    game.blocks.push(Block::with_shape(GridPos::new(3, 0), Shape::Vertical));
+    game.sync_occupancy();
+    let layout = Layout::new(&game);
+    let mut renderer = MockRenderer::new();
+
+    render::draw_landing_ghosts(&game, &layout, &mut renderer);
+
+    // Four sides around each of the two cells it will cover on the floor
+    let ghost_lines = renderer.calls.iter()
+        .filter(|call| matches!(call, DrawCall::Line { color, .. } if *color == GHOST_COLOR))
+        .count();
+    assert_eq!(ghost_lines, 8);
 }
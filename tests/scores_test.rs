@@ -0,0 +1,60 @@
+use std::fs;
+
+use rust_stackattack::core::scores::{HighScores, ScoreEntry, MAX_HIGH_SCORES};
+use rust_stackattack::platform::{scores, storage};
+
+fn entry(score: u32, seed: u64) -> ScoreEntry {
+    ScoreEntry { score, date: "2026-10-15".to_string(), mode: "Endless".to_string(), seed }
+}
+
+#[test]
+fn test_scores_are_kept_best_first() {
+    let mut scores = HighScores::new();
+    assert_eq!(scores.insert(entry(10, 1)), Some(0));
+    assert_eq!(scores.insert(entry(30, 2)), Some(0));
+    // A tie goes below the run that got there first
+    assert_eq!(scores.insert(entry(10, 3)), Some(2));
+    assert_eq!(scores.entries.iter().map(|entry| entry.seed).collect::<Vec<_>>(), vec![2, 1, 3]);
+
+    // Nothing for a game without points
+    assert_eq!(scores.insert(entry(0, 4)), None);
+}
+
+#[test]
+fn test_full_list_drops_the_lowest() {
+    let mut scores = HighScores::new();
+    for score in 1..=MAX_HIGH_SCORES as u32 {
+        scores.insert(entry(score * 10, score as u64));
+    }
+    assert!(!scores.qualifies(10));
+    assert_eq!(scores.insert(entry(5, 99)), None);
+
+    assert_eq!(scores.insert(entry(15, 99)), Some(MAX_HIGH_SCORES - 1));
+    assert_eq!(scores.entries.len(), MAX_HIGH_SCORES);
+    assert_eq!(scores.entries.last().unwrap().score, 15);
+}
+
+#[test]
+fn test_scores_survive_a_restart() {
+    let dir = std::env::temp_dir().join(format!("stackattack-scores-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let path = dir.join("scores.json");
+    assert_eq!(scores::load_from(&path), HighScores::new());
+
+    let mut saved = HighScores::new();
+    saved.insert(entry(42, 7));
+    scores::save_to(&saved, &path).unwrap();
+    assert_eq!(scores::load_from(&path), saved);
+
+    // A damaged file falls back to the list saved before it
+    let mut newer = saved.clone();
+    newer.insert(entry(50, 8));
+    scores::save_to(&newer, &path).unwrap();
+    fs::write(&path, "{").unwrap();
+    assert_eq!(scores::load_from(&path), saved);
+
+    // With the backup gone too a new list starts rather than failing
+    fs::remove_file(storage::backup_path(&path)).unwrap();
+    assert_eq!(scores::load_from(&path), HighScores::new());
+    fs::remove_dir_all(dir).unwrap();
+}
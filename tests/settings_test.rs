@@ -0,0 +1,134 @@
+use rust_stackattack::core::types::Theme;
+use rust_stackattack::platform::storage;
+use rust_stackattack::platform::settings::{MonitorArea, Settings, SettingsItem, SettingsMenu, WindowGeometry, MAX_GRID};
+
+fn geometry(x: i32, y: i32) -> WindowGeometry {
+    WindowGeometry { width: 480.0, height: 520.0, x, y, fullscreen: false }
+}
+
+const MONITOR: MonitorArea = MonitorArea { x: 0, y: 0, width: 1920, height: 1080 };
+
+#[test]
+fn test_settings_round_trip_through_a_file() {
+    let path = std::env::temp_dir().join(format!("stackattack-settings-{}", std::process::id())).join("settings.toml");
+    let settings = Settings {
+        window: Some(WindowGeometry { fullscreen: true, ..geometry(100, 80) }),
+        music_volume: Some(0.3),
+        theme: Some(Theme::Soft),
+        grid: Some((24, 10)),
+        ..Default::default()
+    };
+
+    assert_eq!(Settings::load_from(&path).unwrap(), Settings::default());
+    settings.save_to(&path).unwrap();
+    assert_eq!(Settings::load_from(&path).unwrap(), settings);
+    let text = std::fs::read_to_string(&path).unwrap();
+    assert!(text.contains("grid = [24, 10]\n"));
+    assert!(text.contains("theme = \"Soft\"\n"));
+    assert!(text.contains("[window]\n"));
+
+    // Edited by hand, the change is picked up
+    std::fs::write(&path, text.replace("theme = \"Soft\"", "theme = \"Classic\"")).unwrap();
+    assert_eq!(Settings::load_from(&path).unwrap().theme, Some(Theme::Classic));
+
+    // A broken file is an error naming the file
+    std::fs::write(&path, "music_volume = [").unwrap();
+    let error = Settings::load_from(&path).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    assert!(error.to_string().contains("settings.toml"));
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn test_window_keeps_its_spot_on_a_connected_monitor() {
+    let second = MonitorArea { x: 1920, y: 0, width: 1280, height: 1024 };
+    assert_eq!(geometry(100, 80).position_on(&[MONITOR, second]), Some((100, 80)));
+    assert_eq!(geometry(2200, 300).position_on(&[MONITOR, second]), Some((2200, 300)));
+}
+
+#[test]
+fn test_window_from_a_missing_monitor_is_pulled_back() {
+    // The second monitor is gone, the window moves to the nearest spot on the first one
+    assert_eq!(geometry(2200, 300).position_on(&[MONITOR]), Some((1440, 300)));
+    assert_eq!(geometry(-900, -40).position_on(&[MONITOR]), Some((0, 0)));
+    assert_eq!(geometry(100, 80).position_on(&[]), None);
+}
+
+#[test]
+fn test_saved_size_never_cuts_off_the_grid() {
+    let saved = geometry(0, 0);
+    assert_eq!(saved.dimensions((400.0, 600.0)), (480.0, 600.0));
+}
+
+#[test]
+fn test_fullscreen_choice_keeps_the_windowed_geometry() {
+    let mut settings = Settings { window: Some(geometry(100, 80)), ..Default::default() };
+    settings.set_fullscreen(true, (300.0, 330.0));
+    assert_eq!(settings.window, Some(WindowGeometry { fullscreen: true, ..geometry(100, 80) }));
+    settings.set_fullscreen(false, (300.0, 330.0));
+    assert_eq!(settings.window, Some(geometry(100, 80)));
+
+    // Without a saved window the game's own size is kept for leaving fullscreen later
+    let mut fresh = Settings::default();
+    fresh.set_fullscreen(true, (300.0, 330.0));
+    assert_eq!(fresh.window, Some(WindowGeometry { width: 300.0, height: 330.0, x: 0, y: 0, fullscreen: true }));
+}
+
+#[test]
+fn test_settings_from_before_animation_speed_still_load() {
+    let settings: Settings = serde_json::from_str(r#"{"window": null}"#).unwrap();
+    assert_eq!(settings.animation_millis, 0);
+    assert_eq!(settings.games_played, 0);
+    assert!(!settings.hide_hints);
+    assert_eq!(settings.music_volume, None);
+}
+
+#[test]
+fn test_settings_saved_as_json_still_load() {
+    let path = std::env::temp_dir().join(format!("stackattack-legacy-settings-{}", std::process::id())).join("settings.json");
+    let settings = Settings { games_played: 4, music_volume: Some(0.2), ..Default::default() };
+    storage::write_checked(&path, &serde_json::to_vec(&settings).unwrap()).unwrap();
+
+    assert_eq!(Settings::load_json_from(&path), settings);
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn test_settings_menu_steps_each_setting() {
+    let mut settings = Settings::default();
+    let mut menu = SettingsMenu::default();
+    assert!(menu.adjust(&mut settings, 1));
+    assert_eq!(settings.describe(SettingsItem::MusicVolume), "60%");
+    for _ in 0..10 {
+        menu.adjust(&mut settings, 1);
+    }
+    assert_eq!(settings.music_volume, Some(1.0));
+
+    // The key bindings have a screen of their own
+    menu.move_selection(1);
+    assert_eq!(menu.selected_item(), SettingsItem::KeyBindings);
+    assert!(!menu.adjust(&mut settings, 1));
+
+    menu.move_selection(1);
+    menu.adjust(&mut settings, -1);
+    assert_eq!(settings.grid, Some((15, 16)));
+    menu.move_selection(1);
+    menu.adjust(&mut settings, 100);
+    assert_eq!(settings.grid, Some((15, MAX_GRID.1)));
+
+    menu.move_selection(1);
+    assert_eq!(settings.describe(SettingsItem::Theme), "Mode's own");
+    menu.adjust(&mut settings, -1);
+    assert_eq!(settings.theme, Some(Theme::Soft));
+    menu.adjust(&mut settings, 1);
+    assert_eq!(settings.theme, None);
+
+    menu.move_selection(1);
+    menu.adjust(&mut settings, 1);
+    assert!(settings.hide_grid);
+    assert_eq!(settings.describe(SettingsItem::ShowGrid), "Off");
+
+    // Past the last line the pick wraps to the first
+    menu.move_selection(1);
+    assert_eq!(menu.selected_item(), SettingsItem::MusicVolume);
+}
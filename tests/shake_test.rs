@@ -0,0 +1,55 @@
+use std::time::Duration;
+
+use rust_stackattack::platform::shake::{Impact, ScreenShake, DEFAULT_SHAKE_INTENSITY, MIN_SHAKE_DAMPING};
+use rust_stackattack::testing::MockClock;
+
+#[test]
+fn test_shake_jolts_and_settles() {
+    let clock = MockClock::new();
+    let mut shake = ScreenShake::new();
+    assert_eq!(shake.offset(&clock), (0.0, 0.0));
+
+    shake.kick(Impact::GameOver, &clock);
+    assert_eq!(shake.amplitude(&clock), DEFAULT_SHAKE_INTENSITY);
+    let (x, y) = shake.offset(&clock);
+    assert!(x.abs() <= DEFAULT_SHAKE_INTENSITY && y.abs() <= DEFAULT_SHAKE_INTENSITY);
+    assert!(x != 0.0 || y != 0.0);
+
+    // Dies away and the board ends up back in place
+    clock.advance(Duration::from_millis(100));
+    assert!(shake.amplitude(&clock) < DEFAULT_SHAKE_INTENSITY / 2.0);
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(shake.offset(&clock), (0.0, 0.0));
+}
+
+#[test]
+fn test_bigger_impacts_shake_harder() {
+    assert!(Impact::RowsCleared(1).strength() < Impact::RowsCleared(3).strength());
+    assert!(Impact::RowsCleared(10).strength() <= Impact::GameOver.strength());
+    assert!(Impact::CloseCall.strength() < Impact::GameOver.strength());
+
+    // A weak kick doesn't cut a strong shake short
+    let clock = MockClock::new();
+    let mut shake = ScreenShake::new();
+    shake.kick(Impact::GameOver, &clock);
+    shake.kick(Impact::CloseCall, &clock);
+    assert_eq!(shake.amplitude(&clock), DEFAULT_SHAKE_INTENSITY);
+}
+
+#[test]
+fn test_intensity_and_damping_are_configurable() {
+    let clock = MockClock::new();
+    let mut still = ScreenShake::new().with_intensity(0.0);
+    still.kick(Impact::GameOver, &clock);
+    assert_eq!(still.offset(&clock), (0.0, 0.0));
+
+    let mut slow = ScreenShake::new().with_damping(2.0);
+    let mut fast = ScreenShake::new().with_damping(20.0);
+    slow.kick(Impact::GameOver, &clock);
+    fast.kick(Impact::GameOver, &clock);
+    clock.advance(Duration::from_millis(100));
+    assert!(slow.amplitude(&clock) > fast.amplitude(&clock));
+
+    // A shake always settles
+    assert_eq!(ScreenShake::new().with_damping(0.0).damping, MIN_SHAKE_DAMPING);
+}
@@ -0,0 +1,65 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use rust_stackattack::core::block::Block;
+use rust_stackattack::core::game::GameState;
+use rust_stackattack::core::types::{Color, GameConfig, GridPos};
+use rust_stackattack::platform::raster::ImageRenderer;
+use rust_stackattack::platform::render::{Rect, Renderer, TextAnchor, SUMMARY_CARD_HEIGHT, SUMMARY_CARD_WIDTH};
+use rust_stackattack::platform::share;
+
+#[test]
+fn test_utc_date() {
+    assert_eq!(share::utc_date(UNIX_EPOCH), "1970-01-01");
+    // 2024-02-29 12:00 UTC, a leap day
+    assert_eq!(share::utc_date(UNIX_EPOCH + Duration::from_secs(1_709_208_000)), "2024-02-29");
+    assert_eq!(share::utc_date(UNIX_EPOCH + Duration::from_secs(951_782_400)), "2000-02-29");
+}
+
+#[test]
+fn test_image_renderer_draws_shapes() {
+    let mut renderer = ImageRenderer::new(20, 20, Color::WHITE);
+    renderer.fill_rect(Rect::new(2.0, 2.0, 4.0, 4.0), Color::RED);
+    renderer.fill_polygon(&[(10.0, 10.0), (20.0, 10.0), (20.0, 20.0)], Color::BLUE);
+    renderer.draw_line((0.0, 15.0), (8.0, 15.0), 2.0, Color::GREEN);
+
+    assert_eq!(renderer.image.get_pixel(3, 3).0, [255, 0, 0, 255]);
+    assert_eq!(renderer.image.get_pixel(7, 3).0, [255, 255, 255, 255]);
+    assert_eq!(renderer.image.get_pixel(18, 12).0, [0, 0, 255, 255]);
+    assert_eq!(renderer.image.get_pixel(11, 18).0, [255, 255, 255, 255]);
+    assert_eq!(renderer.image.get_pixel(4, 15).0, [0, 255, 0, 255]);
+}
+
+#[test]
+fn test_image_renderer_draws_text() {
+    let mut renderer = ImageRenderer::new(40, 20, Color::BLACK);
+    renderer.draw_text("1", (0.0, 10.0), 1.0, TextAnchor::Left, Color::WHITE);
+
+    // The top row of a 1 only has its middle pixel set, three pixels per font pixel at scale 1
+    let lit = |x, y| renderer.image.get_pixel(x, y).0 == [255, 255, 255, 255];
+    assert!(lit(4, 3));
+    assert!(!lit(1, 3));
+    assert!(!lit(20, 10));
+}
+
+#[test]
+fn test_summary_card_saved_as_png() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 6,
+        grid_height: 6,
+        seed: Some(99),
+        ..Default::default()
+    }).unwrap();
+    game.score = 42;
+    game.blocks = vec![Block::new(GridPos::new(0, 5))];
+    game.sync_occupancy();
+
+    let card = share::summary_card(&game, "2026-01-01");
+    assert_eq!(card.image.dimensions(), (SUMMARY_CARD_WIDTH, SUMMARY_CARD_HEIGHT));
+
+    let directory = std::env::temp_dir().join(format!("stackattack-share-test-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    let path = share::save_summary_card(&game, &directory).unwrap();
+    assert!(path.file_name().unwrap().to_string_lossy().starts_with("stackattack-42-"));
+    assert!(std::fs::read(&path).unwrap().starts_with(b"\x89PNG"));
+    std::fs::remove_dir_all(&directory).unwrap();
+}
@@ -0,0 +1,54 @@
+use rust_stackattack::core::game::GameState;
+use rust_stackattack::core::stats::{MarkerKind, RunStats, MAX_SAMPLES, SAMPLE_INTERVAL};
+use rust_stackattack::core::types::{GameConfig, GameOverReason};
+
+#[test]
+fn test_samples_every_interval() {
+    let mut stats = RunStats::new();
+    for tick in 0..SAMPLE_INTERVAL * 3 {
+        stats.record_tick(tick as u32, 1);
+    }
+    let ticks: Vec<u64> = stats.samples.iter().map(|sample| sample.tick).collect();
+    assert_eq!(ticks, vec![SAMPLE_INTERVAL, SAMPLE_INTERVAL * 2, SAMPLE_INTERVAL * 3]);
+
+    // Finishing on a sampled tick doesn't repeat it
+    stats.finish(100, 2);
+    assert_eq!(stats.samples.len(), 3);
+    stats.record_tick(100, 2);
+    stats.finish(100, 2);
+    assert_eq!(stats.samples.last().unwrap().tick, SAMPLE_INTERVAL * 3 + 1);
+}
+
+#[test]
+fn test_long_runs_keep_samples_bounded() {
+    let mut stats = RunStats::new();
+    for _ in 0..SAMPLE_INTERVAL * MAX_SAMPLES as u64 * 5 {
+        stats.record_tick(0, 0);
+    }
+    assert!(stats.samples.len() <= MAX_SAMPLES);
+    assert!(stats.samples.len() > MAX_SAMPLES / 4);
+    // Still spread over the whole run
+    assert!(stats.samples.last().unwrap().tick > stats.ticks - stats.ticks / 10);
+}
+
+#[test]
+fn test_game_records_run_until_game_over() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 6,
+        grid_height: 6,
+        ..Default::default()
+    }).unwrap();
+    for _ in 0..SAMPLE_INTERVAL * 2 {
+        game.tick();
+    }
+    assert_eq!(game.stats.samples.len(), 2);
+
+    game.stats.mark(MarkerKind::RowsCleared);
+    game.tick();
+    game.end_game(GameOverReason::StackTooHigh);
+    assert_eq!(game.stats.samples.len(), 3);
+    assert_eq!(game.stats.markers[0].tick, SAMPLE_INTERVAL * 2);
+
+    game.restart();
+    assert_eq!(game.stats, RunStats::new());
+}
@@ -0,0 +1,66 @@
+use std::fs;
+use std::path::PathBuf;
+
+use rust_stackattack::platform::storage;
+
+// A fresh directory per test, so tests running in parallel don't share files
+fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("stackattack-storage-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn test_round_trip_leaves_no_temporary_file() {
+    let dir = test_dir("round-trip");
+    let path = dir.join("save.json");
+
+    storage::write_checked(&path, b"{\"score\": 10}").unwrap();
+    assert_eq!(storage::read_checked(&path).unwrap(), b"{\"score\": 10}");
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_atomic_write_keeps_the_contents_as_they_are() {
+    let dir = test_dir("atomic");
+    let path = dir.join("keys.toml");
+
+    storage::write_atomic(&path, b"left = [\"A\"]\n").unwrap();
+    storage::write_atomic(&path, b"left = [\"Left\"]\n").unwrap();
+    assert_eq!(fs::read(&path).unwrap(), b"left = [\"Left\"]\n");
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_truncated_file_falls_back_to_the_previous_copy() {
+    let dir = test_dir("truncated");
+    let path = dir.join("save.json");
+    storage::write_checked(&path, b"first").unwrap();
+    storage::write_checked(&path, b"second version").unwrap();
+
+    // Cut the latest file short, as a crash or full disk would
+    let data = fs::read(&path).unwrap();
+    fs::write(&path, &data[..data.len() - 4]).unwrap();
+    assert_eq!(storage::read_checked(&path).unwrap(), b"first");
+
+    // Once the backup is damaged too there is nothing left to trust
+    fs::write(storage::backup_path(&path), b"garbage").unwrap();
+    let error = storage::read_checked(&path).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_damaged_file_is_not_kept_as_the_backup() {
+    let dir = test_dir("backup");
+    let path = dir.join("save.json");
+    storage::write_checked(&path, b"good").unwrap();
+    storage::write_checked(&path, b"newer").unwrap();
+    fs::write(&path, b"garbage").unwrap();
+
+    storage::write_checked(&path, b"newest").unwrap();
+    assert_eq!(storage::read_checked(&storage::backup_path(&path)).unwrap(), b"good");
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,94 @@
+use rust_stackattack::core::event::GameEvent;
+use rust_stackattack::core::game::GameState;
+use rust_stackattack::core::storm::{Storm, StormChange, StormPacing};
+use rust_stackattack::core::types::GameConfig;
+
+fn pacing() -> StormPacing {
+    StormPacing {
+        first_storm: 10,
+        interval: 20,
+        interval_shrink: 5,
+        min_interval: 12,
+        duration: 4,
+        warning: 3,
+        spawn_rate_divisor: 2,
+    }
+}
+
+// Updates at which each change happens over the given number of updates
+fn changes(storm: &mut Storm, ticks: u64) -> Vec<(u64, StormChange)> {
+    (1..=ticks).filter_map(|tick| storm.tick().map(|change| (tick, change))).collect()
+}
+
+#[test]
+fn test_storm_cycle_follows_pacing() {
+    let mut storm = Storm::new(pacing());
+
+    assert_eq!(changes(&mut storm, 40), vec![
+        (7, StormChange::Warning),
+        (10, StormChange::Started),
+        (14, StormChange::Ended),
+        // The second gap is shortened by one shrink step
+        (26, StormChange::Warning),
+        (29, StormChange::Started),
+        (33, StormChange::Ended),
+    ]);
+}
+
+#[test]
+fn test_storm_gap_never_drops_below_minimum() {
+    let mut storm = Storm::new(StormPacing { interval_shrink: 100, ..pacing() });
+    changes(&mut storm, 14);
+    assert_eq!(storm.ticks_until_start, 12);
+
+    // The next storm ends after the minimum gap and its own duration
+    assert_eq!(changes(&mut storm, 16).last(), Some(&(16, StormChange::Ended)));
+    assert_eq!(storm.ticks_until_start, 12);
+}
+
+#[test]
+fn test_storm_doubles_spawn_rate() {
+    let mut storm = Storm::new(pacing());
+    assert_eq!(storm.spawn_rate(10), 10);
+
+    changes(&mut storm, 10);
+    assert!(storm.active());
+    assert_eq!(storm.spawn_rate(10), 5);
+    assert_eq!(storm.spawn_rate(1), 1);
+}
+
+#[test]
+fn test_game_announces_storms() {
+    let mut game = GameState::new(GameConfig {
+        grid_width: 8,
+        grid_height: 8,
+        storm_pacing: Some(pacing()),
+        seed: Some(4),
+        ..Default::default()
+    }).unwrap();
+    // Without the opening block nothing else happens in the first updates
+    game.blocks.clear();
+    game.sync_occupancy();
+    assert!(game.storm.is_some());
+
+    let mut events = Vec::new();
+    for _ in 0..10 {
+        game.update_blocks();
+        events.extend(game.drain_events());
+    }
+    assert_eq!(events, vec![GameEvent::StormWarning, GameEvent::StormStarted]);
+    assert!(game.storm.unwrap().active());
+}
+
+#[test]
+fn test_storm_sooner_than_its_warning_is_still_announced() {
+    let mut storm = Storm::new(StormPacing { first_storm: 2, min_interval: 1, interval_shrink: 100, ..pacing() });
+
+    assert_eq!(changes(&mut storm, 12), vec![
+        (1, StormChange::Warning),
+        (4, StormChange::Started),
+        (8, StormChange::Ended),
+        (9, StormChange::Warning),
+        (12, StormChange::Started),
+    ]);
+}
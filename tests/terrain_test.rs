@@ -0,0 +1,55 @@
+use rust_stackattack::core::terrain::{Terrain, Tile};
+use rust_stackattack::core::types::{Direction, GridPos};
+
+#[test]
+fn test_terrain_creation() {
+    let terrain = Terrain::new(5, 5);
+    
+    // A new terrain has no solid cells
+    for y in 0..5 {
+        assert_eq!(terrain.solid_cells_in_row(y), 0);
+    }
+    assert_eq!(terrain.ramps().count(), 0);
+}
+
+#[test]
+fn test_terrain_ramp_tiles() {
+    let mut terrain = Terrain::new(5, 5);
+    terrain.set_tile(GridPos::new(2, 4), Tile::Ramp(Direction::Right));
+    
+    assert_eq!(terrain.tile(GridPos::new(2, 4)), Tile::Ramp(Direction::Right));
+    assert_eq!(terrain.ramp_at(GridPos::new(2, 4)), Some(Direction::Right));
+    assert!(terrain.is_solid(GridPos::new(2, 4)));
+    assert_eq!(terrain.solid_cells_in_row(4), 1);
+    assert_eq!(terrain.ramps().collect::<Vec<_>>(), vec![(GridPos::new(2, 4), Direction::Right)]);
+    
+    // Cells outside the grid are treated as empty
+    assert!(!terrain.is_solid(GridPos::new(10, 10)));
+}
+
+#[test]
+fn test_solid_count_follows_tile_changes() {
+    let mut terrain = Terrain::new(4, 4);
+    terrain.set_tile(GridPos::new(1, 2), Tile::Wall);
+    terrain.set_tile(GridPos::new(1, 2), Tile::Ramp(Direction::Right));
+    terrain.set_tile(GridPos::new(2, 2), Tile::Wall);
+    assert_eq!(terrain.solid_cells_in_row(2), 2);
+
+    terrain.set_tile(GridPos::new(1, 2), Tile::Empty);
+    terrain.set_tile(GridPos::new(1, 2), Tile::Empty);
+    assert_eq!(terrain.solid_cells_in_row(2), 1);
+}
+
+#[test]
+fn test_pits_open_in_the_floor() {
+    let mut terrain = Terrain::new(4, 3);
+    terrain.set_pit(1, true);
+    terrain.set_pit(9, true);
+    assert!(terrain.is_pit(1));
+    assert!(!terrain.is_pit(9));
+    assert!(!terrain.is_solid(GridPos::new(1, 2)), "A pit is a hole, not a tile");
+    assert_eq!(terrain.pits().collect::<Vec<_>>(), vec![1]);
+
+    terrain.set_pit(1, false);
+    assert_eq!(terrain.pits().count(), 0);
+}
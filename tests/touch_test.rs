@@ -0,0 +1,95 @@
+use rust_stackattack::core::game::GameState;
+use rust_stackattack::core::types::{GameConfig, InputAction, InputDevice};
+use rust_stackattack::platform::input::InputSource;
+use rust_stackattack::platform::render::{self, Layout};
+use rust_stackattack::testing::{DrawCall, MockRenderer};
+use rust_stackattack::platform::touch::{TouchButton, TouchControls};
+
+// An 8x8 grid of 30 pixel cells below a 30 pixel score bar, the buttons are 60 by 45 pixels along its bottom
+fn layout() -> Layout {
+    let game = GameState::new(GameConfig { grid_width: 8, grid_height: 8, cell_size: 30.0, ..Default::default() }).unwrap();
+    Layout::new(&game)
+}
+
+fn drain(touch: &mut TouchControls) -> Vec<InputAction> {
+    std::iter::from_fn(|| Some(touch.poll()).filter(|&action| action != InputAction::None)).collect()
+}
+
+#[test]
+fn test_buttons_sit_along_the_bottom_of_the_grid() {
+    let layout = layout();
+    let touch = TouchControls::new();
+    assert_eq!(touch.button_at((30.0, 250.0), &layout), Some(TouchButton::Left));
+    assert_eq!(touch.button_at((90.0, 250.0), &layout), Some(TouchButton::Right));
+    assert_eq!(touch.button_at((210.0, 250.0), &layout), Some(TouchButton::Jump));
+    assert_eq!(touch.button_at((150.0, 250.0), &layout), None);
+    assert_eq!(touch.button_at((30.0, 200.0), &layout), None);
+    assert_eq!(touch.device(), InputDevice::Touch);
+}
+
+#[test]
+fn test_held_direction_button_keeps_walking_until_lifted() {
+    let layout = layout();
+    let mut touch = TouchControls::new();
+    assert!(touch.start((30.0, 250.0), &layout));
+    assert_eq!(touch.poll(), InputAction::Left);
+    assert_eq!(touch.poll(), InputAction::Left);
+    assert!(touch.has_action());
+
+    touch.end();
+    assert!(!touch.has_action());
+    assert_eq!(touch.poll(), InputAction::None);
+}
+
+#[test]
+fn test_jump_button_releases_the_jump_on_lift_or_sliding_off() {
+    let layout = layout();
+    let mut touch = TouchControls::new();
+    touch.start((210.0, 250.0), &layout);
+    touch.moved((215.0, 245.0), &layout);
+    assert_eq!(touch.held, Some(TouchButton::Jump));
+    touch.moved((150.0, 245.0), &layout);
+    assert_eq!(touch.held, None);
+    assert_eq!(drain(&mut touch), vec![InputAction::Up, InputAction::JumpRelease]);
+}
+
+#[test]
+fn test_swipes_step_per_cell_and_jump_upward() {
+    let layout = layout();
+    let mut touch = TouchControls::new();
+    assert!(touch.start((100.0, 150.0), &layout));
+    touch.moved((110.0, 150.0), &layout);
+    assert!(!touch.has_action());
+
+    touch.moved((165.0, 150.0), &layout);
+    assert_eq!(drain(&mut touch), vec![InputAction::Right, InputAction::Right]);
+
+    // Upward jumps once however far it goes, lifting the finger cuts it short
+    touch.moved((165.0, 115.0), &layout);
+    touch.moved((165.0, 60.0), &layout);
+    touch.end();
+    assert_eq!(drain(&mut touch), vec![InputAction::Up, InputAction::JumpRelease]);
+}
+
+#[test]
+fn test_touches_off_the_grid_are_not_used() {
+    let layout = layout();
+    let mut touch = TouchControls::new();
+    // The score bar, where the restart button is
+    assert!(!touch.start((220.0, 15.0), &layout));
+    assert!(touch.swipe.is_none());
+    assert!(!touch.has_action());
+}
+
+#[test]
+fn test_touch_controls_draw_every_button_with_the_held_one_lit() {
+    let layout = layout();
+    let mut touch = TouchControls::new();
+    touch.start((90.0, 250.0), &layout);
+    let mut renderer = MockRenderer::new();
+    render::draw_touch_controls(&touch, &layout, &mut renderer);
+
+    assert_eq!(renderer.texts(), vec!["<", ">", "Jump"]);
+    assert_eq!(renderer.rects_with_color(render::DRAG_COLOR), vec![TouchButton::Right.rect(&layout)]);
+    assert!(!renderer.calls.iter().any(|call| matches!(call, DrawCall::Line { .. })));
+}
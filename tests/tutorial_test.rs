@@ -0,0 +1,21 @@
+use rust_stackattack::core::block::BlockId;
+use rust_stackattack::core::event::GameEvent;
+use rust_stackattack::core::tutorial::{Hint, Tutorial, TUTORIAL_GAMES};
+
+#[test]
+fn test_each_hint_is_given_once() {
+    let mut tutorial = Tutorial::new();
+    assert_eq!(tutorial.hint(&GameEvent::RowAlmostComplete(4)), Some(Hint::RowAlmostComplete));
+    assert_eq!(tutorial.hint(&GameEvent::RowAlmostComplete(3)), None);
+    assert_eq!(tutorial.hint(&GameEvent::BlockCaught(BlockId(1))), Some(Hint::Carrying));
+
+    // Events that teach nothing new have no hint
+    assert_eq!(tutorial.hint(&GameEvent::StormEnded), None);
+}
+
+#[test]
+fn test_tutorial_covers_the_first_games() {
+    assert!(Tutorial::applies_to(0));
+    assert!(Tutorial::applies_to(TUTORIAL_GAMES - 1));
+    assert!(!Tutorial::applies_to(TUTORIAL_GAMES));
+}
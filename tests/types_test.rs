@@ -0,0 +1,45 @@
+use rust_stackattack::core::terrain::Tile;
+use rust_stackattack::core::types::{Direction, GridPos};
+
+#[test]
+fn test_grid_pos_steps_stop_at_the_top_and_left_edges() {
+    let corner = GridPos::new(0, 0);
+    assert_eq!(corner.neighbor(Direction::Left), None);
+    assert_eq!(corner.neighbor(Direction::Right), Some(GridPos::new(1, 0)));
+    assert_eq!(corner.above(), None);
+    assert_eq!(corner.below(), GridPos::new(0, 1));
+
+    let cell = GridPos::new(3, 4);
+    assert_eq!(cell.offset(-3, -4), Some(corner));
+    assert_eq!(cell.offset(-4, 0), None);
+    assert_eq!(cell.offset(2, 1), Some(GridPos::new(5, 5)));
+}
+
+#[test]
+fn test_grid_pos_within() {
+    assert!(GridPos::new(4, 2).within(5, 3));
+    assert!(!GridPos::new(5, 2).within(5, 3));
+    assert!(!GridPos::new(4, 3).within(5, 3));
+}
+
+#[test]
+fn test_direction_offsets() {
+    assert_eq!(Direction::Left.offset(), -1);
+    assert_eq!(Direction::Right.offset(), 1);
+    assert_eq!(Direction::Left.opposite(), Direction::Right);
+    assert_eq!(Direction::from_offset(-3), Some(Direction::Left));
+    assert_eq!(Direction::from_offset(0), None);
+    assert_eq!(Direction::try_from(-1), Ok(Direction::Left));
+    assert!(Direction::try_from(-3).is_err());
+}
+
+#[test]
+fn test_positions_and_directions_save_as_before() {
+    // Level files from before keep loading: positions are [x, y] pairs and directions -1 or 1
+    assert_eq!(serde_json::to_string(&GridPos::new(2, 7)).unwrap(), "[2,7]");
+    assert_eq!(serde_json::from_str::<GridPos>("[2,7]").unwrap(), GridPos::new(2, 7));
+    assert_eq!(serde_json::to_string(&Tile::Ramp(Direction::Left)).unwrap(), r#"{"Ramp":-1}"#);
+    assert_eq!(serde_json::from_str::<Tile>(r#"{"Ramp":1}"#).unwrap(), Tile::Ramp(Direction::Right));
+    assert!(serde_json::from_str::<Tile>(r#"{"Ramp":0}"#).is_err());
+    assert!(serde_json::from_str::<Tile>(r#"{"Ramp":2}"#).is_err());
+}
@@ -0,0 +1,36 @@
+use std::time::Duration;
+
+use rust_stackattack::platform::watchdog::{TickWatchdog, MAX_LEVEL, WINDOW};
+
+const BUDGET: Duration = Duration::from_millis(100);
+
+fn feed(watchdog: &mut TickWatchdog, elapsed: Duration, ticks: usize) -> Vec<u8> {
+    (0..ticks).filter_map(|_| watchdog.record(elapsed)).collect()
+}
+
+#[test]
+fn test_steps_down_while_over_budget() {
+    let mut watchdog = TickWatchdog::new(BUDGET);
+    assert!(feed(&mut watchdog, Duration::from_millis(150), WINDOW - 1).is_empty());
+
+    assert_eq!(feed(&mut watchdog, Duration::from_millis(150), 1), vec![1]);
+    assert_eq!(feed(&mut watchdog, Duration::from_millis(150), WINDOW * 3), vec![MAX_LEVEL]);
+    assert_eq!(watchdog.level, MAX_LEVEL);
+}
+
+#[test]
+fn test_a_single_spike_is_not_enough() {
+    let mut watchdog = TickWatchdog::new(BUDGET);
+    feed(&mut watchdog, Duration::from_millis(60), WINDOW);
+    assert!(feed(&mut watchdog, Duration::from_millis(500), 1).is_empty());
+    assert_eq!(watchdog.level, 0);
+}
+
+#[test]
+fn test_recovers_once_updates_are_fast_again() {
+    let mut watchdog = TickWatchdog::new(BUDGET);
+    feed(&mut watchdog, Duration::from_millis(150), WINDOW);
+    assert_eq!(watchdog.level, 1);
+
+    assert_eq!(feed(&mut watchdog, Duration::from_millis(10), WINDOW), vec![0]);
+}